@@ -0,0 +1,5 @@
+-- Track consecutive failed logins so repeated password guessing can be throttled.
+-- This is independent of the admin-controlled is_locked flag.
+ALTER TABLE users ADD COLUMN failed_login_attempts INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE users ADD COLUMN first_failed_login_at DATETIME;
+ALTER TABLE users ADD COLUMN locked_until DATETIME;
@@ -0,0 +1,37 @@
+-- Allow fermentations to be archived. SQLite cannot alter a CHECK constraint,
+-- so the fermentations table is rebuilt with 'archived' added to the allowed statuses.
+-- archived_from_status remembers the status to restore when unarchiving.
+CREATE TABLE fermentations_new (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    user_id INTEGER NOT NULL,
+    profile_id INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    start_date DATETIME NOT NULL,
+    target_end_date DATETIME,
+    actual_end_date DATETIME,
+    status TEXT NOT NULL DEFAULT 'active' CHECK (status IN ('active', 'paused', 'completed', 'failed', 'archived')),
+    success_rating INTEGER CHECK (success_rating BETWEEN 1 AND 5),
+    notes TEXT,
+    ingredients_json TEXT, -- JSON formatted ingredients list
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    lessons_learned TEXT,
+    archived_from_status TEXT,
+    FOREIGN KEY (user_id) REFERENCES users (id) ON DELETE CASCADE,
+    FOREIGN KEY (profile_id) REFERENCES fermentation_profiles (id)
+);
+
+INSERT INTO fermentations_new (
+    id, user_id, profile_id, name, start_date, target_end_date, actual_end_date, status,
+    success_rating, notes, ingredients_json, created_at, updated_at, lessons_learned
+)
+SELECT
+    id, user_id, profile_id, name, start_date, target_end_date, actual_end_date, status,
+    success_rating, notes, ingredients_json, created_at, updated_at, lessons_learned
+FROM fermentations;
+
+DROP TABLE fermentations;
+ALTER TABLE fermentations_new RENAME TO fermentations;
+
+CREATE INDEX idx_fermentations_user_status ON fermentations(user_id, status);
+CREATE INDEX idx_fermentations_profile ON fermentations(profile_id);
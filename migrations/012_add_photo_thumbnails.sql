@@ -0,0 +1,2 @@
+-- Store a downscaled thumbnail alongside each uploaded photo
+ALTER TABLE fermentation_photos ADD COLUMN thumbnail_path TEXT;
@@ -0,0 +1,12 @@
+-- pH readings recorded over the course of a fermentation
+CREATE TABLE ph_logs (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    fermentation_id INTEGER NOT NULL,
+    recorded_at DATETIME NOT NULL,
+    ph REAL NOT NULL CHECK (ph >= 0 AND ph <= 14),
+    notes TEXT,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (fermentation_id) REFERENCES fermentations (id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_ph_logs_fermentation_time ON ph_logs(fermentation_id, recorded_at);
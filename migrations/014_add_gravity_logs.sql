@@ -0,0 +1,12 @@
+-- Specific gravity readings for brewing-style fermentations
+CREATE TABLE gravity_logs (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    fermentation_id INTEGER NOT NULL,
+    recorded_at DATETIME NOT NULL,
+    gravity REAL NOT NULL CHECK (gravity >= 0.98 AND gravity <= 1.2),
+    notes TEXT,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (fermentation_id) REFERENCES fermentations (id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_gravity_logs_fermentation_time ON gravity_logs(fermentation_id, recorded_at);
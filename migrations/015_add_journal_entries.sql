@@ -0,0 +1,11 @@
+-- Dated diary observations recorded over the course of a fermentation
+CREATE TABLE journal_entries (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    fermentation_id INTEGER NOT NULL,
+    entry_date DATETIME NOT NULL,
+    body TEXT NOT NULL,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (fermentation_id) REFERENCES fermentations (id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_journal_entries_fermentation_date ON journal_entries(fermentation_id, entry_date);
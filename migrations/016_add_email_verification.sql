@@ -0,0 +1,19 @@
+-- Track whether a user's email address has been confirmed
+ALTER TABLE users ADD COLUMN email_verified INTEGER NOT NULL DEFAULT 0;
+
+-- Accounts created before verification existed are treated as verified
+UPDATE users SET email_verified = 1;
+
+-- Single-use tokens for confirming an email address
+-- The address is recorded so a token can't confirm an email the user has since changed
+CREATE TABLE email_verification_tokens (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    user_id INTEGER NOT NULL,
+    email TEXT NOT NULL,
+    token_hash TEXT NOT NULL,
+    expires_at DATETIME NOT NULL,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (user_id) REFERENCES users (id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_email_verification_tokens_user ON email_verification_tokens(user_id);
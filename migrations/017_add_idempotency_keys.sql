@@ -0,0 +1,11 @@
+-- Client-supplied keys used to make fermentation creation safe to retry
+CREATE TABLE idempotency_keys (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    user_id INTEGER NOT NULL,
+    idempotency_key TEXT NOT NULL,
+    fermentation_id INTEGER NOT NULL,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (user_id) REFERENCES users (id) ON DELETE CASCADE,
+    FOREIGN KEY (fermentation_id) REFERENCES fermentations (id) ON DELETE CASCADE,
+    UNIQUE (user_id, idempotency_key)
+);
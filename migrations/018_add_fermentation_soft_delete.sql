@@ -0,0 +1,4 @@
+-- Deleted fermentations are kept for a restore window before being purged
+ALTER TABLE fermentations ADD COLUMN deleted_at DATETIME;
+
+CREATE INDEX idx_fermentations_deleted_at ON fermentations(deleted_at);
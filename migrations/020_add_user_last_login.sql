@@ -0,0 +1,2 @@
+-- Time of the most recent successful login
+ALTER TABLE users ADD COLUMN last_login_at DATETIME;
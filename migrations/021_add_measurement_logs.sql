@@ -0,0 +1,14 @@
+-- Generic numeric readings (weight, volume, pH, gravity) recorded over a fermentation
+CREATE TABLE measurement_logs (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    fermentation_id INTEGER NOT NULL,
+    kind TEXT NOT NULL CHECK (kind IN ('weight', 'volume', 'ph', 'gravity')),
+    value REAL NOT NULL CHECK (value > 0),
+    unit TEXT NOT NULL,
+    recorded_at DATETIME NOT NULL,
+    notes TEXT,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (fermentation_id) REFERENCES fermentations (id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_measurement_logs_fermentation_kind_time ON measurement_logs(fermentation_id, kind, recorded_at);
@@ -0,0 +1,11 @@
+-- Sequential, per-user batch numbers for labelling jars
+ALTER TABLE fermentations ADD COLUMN batch_number INTEGER;
+
+-- Number existing fermentations in creation order for each user
+UPDATE fermentations
+SET batch_number = (
+    SELECT COUNT(*) FROM fermentations earlier
+    WHERE earlier.user_id = fermentations.user_id AND earlier.id <= fermentations.id
+);
+
+CREATE UNIQUE INDEX idx_fermentations_user_batch_number ON fermentations(user_id, batch_number);
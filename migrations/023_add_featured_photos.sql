@@ -0,0 +1,4 @@
+-- Let users pick the photo shown as a fermentation's thumbnail
+ALTER TABLE fermentation_photos ADD COLUMN is_featured INTEGER NOT NULL DEFAULT 0;
+
+CREATE UNIQUE INDEX idx_fermentation_photos_featured ON fermentation_photos(fermentation_id) WHERE is_featured = 1;
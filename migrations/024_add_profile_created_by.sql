@@ -0,0 +1,2 @@
+-- Record which admin created each profile; seeded profiles have no creator
+ALTER TABLE fermentation_profiles ADD COLUMN created_by INTEGER REFERENCES users(id);
@@ -0,0 +1,2 @@
+-- Profile pre-selected on the new-fermentation form
+ALTER TABLE users ADD COLUMN default_profile_id INTEGER REFERENCES fermentation_profiles(id);
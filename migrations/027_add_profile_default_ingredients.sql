@@ -0,0 +1,33 @@
+-- Typical ingredients suggested when starting a fermentation with the profile,
+-- one ingredient per line
+ALTER TABLE fermentation_profiles ADD COLUMN default_ingredients TEXT;
+
+-- Suggestions for the built-in profiles
+UPDATE fermentation_profiles SET default_ingredients = '2 lbs cucumbers
+2 tbsp sea salt
+4 cups water
+dill
+garlic' WHERE name = 'Pickles' AND created_by IS NULL AND default_ingredients IS NULL;
+UPDATE fermentation_profiles SET default_ingredients = '1 gallon water
+1 cup sugar
+8 bags black tea
+1 SCOBY
+2 cups starter tea' WHERE name = 'Kombucha' AND created_by IS NULL AND default_ingredients IS NULL;
+UPDATE fermentation_profiles SET default_ingredients = '1 napa cabbage
+0.25 cup sea salt
+3 tbsp gochugaru
+garlic
+ginger
+scallions
+fish sauce' WHERE name = 'Kimchi' AND created_by IS NULL AND default_ingredients IS NULL;
+UPDATE fermentation_profiles SET default_ingredients = '5 lbs cabbage
+3 tbsp sea salt
+caraway seeds' WHERE name = 'Sauerkraut' AND created_by IS NULL AND default_ingredients IS NULL;
+UPDATE fermentation_profiles SET default_ingredients = '100 g whole wheat flour
+100 g water' WHERE name = 'Sourdough Starter' AND created_by IS NULL AND default_ingredients IS NULL;
+UPDATE fermentation_profiles SET default_ingredients = '2 cups whole milk
+1 tbsp milk kefir grains' WHERE name = 'Kefir (Milk)' AND created_by IS NULL AND default_ingredients IS NULL;
+UPDATE fermentation_profiles SET default_ingredients = '4 cups water
+0.25 cup sugar
+0.25 cup water kefir grains
+dried fruit' WHERE name = 'Water Kefir' AND created_by IS NULL AND default_ingredients IS NULL;
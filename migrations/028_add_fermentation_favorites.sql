@@ -0,0 +1,2 @@
+-- Let users star reference batches for quick access
+ALTER TABLE fermentations ADD COLUMN is_favorite INTEGER NOT NULL DEFAULT 0 CHECK (is_favorite IN (0, 1));
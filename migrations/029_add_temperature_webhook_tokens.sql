@@ -0,0 +1,3 @@
+-- Per-fermentation secret that lets a device post temperature readings without a session.
+-- Only an Argon2 hash of the token is stored; NULL means the webhook is disabled.
+ALTER TABLE fermentations ADD COLUMN webhook_token_hash TEXT;
@@ -0,0 +1,8 @@
+-- Public read-only links to a single fermentation; at most one link per fermentation
+CREATE TABLE fermentation_shares (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    fermentation_id INTEGER NOT NULL UNIQUE,
+    slug TEXT NOT NULL UNIQUE,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (fermentation_id) REFERENCES fermentations (id) ON DELETE CASCADE
+);
@@ -0,0 +1,3 @@
+-- Per-user secret for subscribing to the iCalendar feed of fermentation target dates.
+-- Only an Argon2 hash of the token is stored; NULL means no feed has been set up.
+ALTER TABLE users ADD COLUMN calendar_token_hash TEXT;
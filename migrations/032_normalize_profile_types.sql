@@ -0,0 +1,3 @@
+-- Profile types are now validated and stored lowercase; bring existing rows in line
+-- so filtering by type matches regardless of how they were originally entered.
+UPDATE fermentation_profiles SET type = LOWER(TRIM(type));
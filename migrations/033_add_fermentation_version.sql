@@ -0,0 +1,3 @@
+-- Revision counter for optimistic concurrency: bumped on every change to a fermentation,
+-- so an update based on a stale copy can be rejected instead of overwriting newer edits.
+ALTER TABLE fermentations ADD COLUMN version INTEGER NOT NULL DEFAULT 1;
@@ -0,0 +1,3 @@
+-- Size of each stored photo in bytes, summed per user to enforce the photo storage quota.
+-- Photos uploaded before this migration start at 0; startup fills them in from the files on disk.
+ALTER TABLE fermentation_photos ADD COLUMN file_size INTEGER NOT NULL DEFAULT 0;
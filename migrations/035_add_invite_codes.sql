@@ -0,0 +1,13 @@
+-- Single-use codes that admins hand out when registration requires an invite.
+-- used_by/used_at are set when a code is redeemed; expires_at NULL means it never expires.
+CREATE TABLE invite_codes (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    code TEXT NOT NULL UNIQUE,
+    created_by INTEGER,
+    used_by INTEGER,
+    used_at DATETIME,
+    expires_at DATETIME,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (created_by) REFERENCES users (id) ON DELETE SET NULL,
+    FOREIGN KEY (used_by) REFERENCES users (id) ON DELETE SET NULL
+);
@@ -0,0 +1,127 @@
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde_json::json;
+use tower_sessions::Session;
+
+use crate::admin::invites::models::{CreateInviteRequest, InviteCode, MAX_INVITE_EXPIRY_DAYS};
+use crate::admin::invites::repository::AdminInviteRepository;
+use crate::users::models::{UserRole, UserSession};
+use crate::AppState;
+
+#[derive(Debug)]
+pub enum AdminInviteApiError {
+    Unauthorized,
+    Forbidden,
+    ValidationError(String),
+    DatabaseError(String),
+    InternalError(String),
+}
+
+impl IntoResponse for AdminInviteApiError {
+    fn into_response(self) -> Response {
+        let (status, error_message) = match self {
+            AdminInviteApiError::Unauthorized => {
+                (StatusCode::UNAUTHORIZED, "Unauthorized".to_string())
+            }
+            AdminInviteApiError::Forbidden => {
+                (StatusCode::FORBIDDEN, "Admin access required".to_string())
+            }
+            AdminInviteApiError::ValidationError(msg) => (StatusCode::BAD_REQUEST, msg),
+            AdminInviteApiError::DatabaseError(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg),
+            AdminInviteApiError::InternalError(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg),
+        };
+
+        let body = Json(json!({
+            "error": error_message,
+        }));
+
+        (status, body).into_response()
+    }
+}
+
+/// Helper function to check if the current user is an admin
+async fn require_admin(session: &Session) -> Result<UserSession, AdminInviteApiError> {
+    let user_session: UserSession = session
+        .get("user")
+        .await
+        .map_err(|e| AdminInviteApiError::InternalError(format!("Failed to get session: {}", e)))?
+        .ok_or(AdminInviteApiError::Unauthorized)?;
+
+    match user_session.role {
+        UserRole::Admin => Ok(user_session),
+        _ => Err(AdminInviteApiError::Forbidden),
+    }
+}
+
+/// List all invite codes, used and unused (admin only)
+#[utoipa::path(
+    get,
+    path = "/api/admin/invites",
+    tag = "admin",
+    responses(
+        (status = 200, description = "Invite codes, newest first", body = [InviteCode]),
+        (status = 401, description = "Not logged in"),
+        (status = 403, description = "Not an admin")
+    )
+)]
+pub async fn list_invites(
+    session: Session,
+    State(state): State<AppState>,
+) -> Result<Json<Vec<InviteCode>>, AdminInviteApiError> {
+    // Check admin authorization
+    require_admin(&session).await?;
+
+    let repo = AdminInviteRepository::new(state.db.clone());
+    let invites = repo.list_invites().await.map_err(|e| {
+        AdminInviteApiError::DatabaseError(format!("Failed to list invites: {}", e))
+    })?;
+
+    Ok(Json(invites))
+}
+
+/// Mint a single-use invite code for registration (admin only)
+#[utoipa::path(
+    post,
+    path = "/api/admin/invites",
+    tag = "admin",
+    request_body = CreateInviteRequest,
+    responses(
+        (status = 201, description = "Invite code created", body = InviteCode),
+        (status = 400, description = "Invalid input"),
+        (status = 401, description = "Not logged in"),
+        (status = 403, description = "Not an admin")
+    )
+)]
+pub async fn create_invite(
+    session: Session,
+    State(state): State<AppState>,
+    Json(request): Json<CreateInviteRequest>,
+) -> Result<(StatusCode, Json<InviteCode>), AdminInviteApiError> {
+    // Check admin authorization
+    let admin = require_admin(&session).await?;
+
+    let expires_at = match request.expires_in_days {
+        Some(days) if !(1..=MAX_INVITE_EXPIRY_DAYS).contains(&days) => {
+            return Err(AdminInviteApiError::ValidationError(format!(
+                "expires_in_days must be between 1 and {}",
+                MAX_INVITE_EXPIRY_DAYS
+            )));
+        }
+        Some(days) => Some(chrono::Utc::now() + chrono::Duration::days(days)),
+        None => None,
+    };
+
+    let repo = AdminInviteRepository::new(state.db.clone());
+    let invite = repo
+        .create_invite(admin.user_id, expires_at)
+        .await
+        .map_err(|e| {
+            AdminInviteApiError::DatabaseError(format!("Failed to create invite: {}", e))
+        })?;
+
+    Ok((StatusCode::CREATED, Json(invite)))
+}
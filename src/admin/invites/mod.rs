@@ -0,0 +1,8 @@
+pub mod handlers;
+pub mod models;
+pub mod repository;
+
+// Re-export commonly used items
+pub use handlers::{create_invite, list_invites};
+pub use models::{CreateInviteRequest, InviteCode};
+pub use repository::AdminInviteRepository;
@@ -0,0 +1,28 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Longest validity an invite code can be given, in days
+pub const MAX_INVITE_EXPIRY_DAYS: i64 = 365;
+
+/// Request to mint an invite code
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateInviteRequest {
+    /// Days until the code expires; leave out for a code that never expires
+    #[serde(default)]
+    pub expires_in_days: Option<i64>,
+}
+
+/// A single-use registration code
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct InviteCode {
+    pub id: i64,
+    pub code: String,
+    /// Admin who minted the code; `None` if their account was deleted
+    pub created_by: Option<i64>,
+    /// User who registered with the code; `None` while it is unused
+    pub used_by: Option<i64>,
+    pub used_at: Option<DateTime<Utc>>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
@@ -0,0 +1,99 @@
+use crate::admin::invites::models::InviteCode;
+use crate::database::Database;
+use crate::users::auth::generate_token;
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+
+pub struct AdminInviteRepository {
+    db: Arc<Database>,
+}
+
+impl AdminInviteRepository {
+    pub fn new(db: Arc<Database>) -> Self {
+        Self { db }
+    }
+
+    /// Mint a new random invite code
+    pub async fn create_invite(
+        &self,
+        created_by: i64,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> Result<InviteCode, Box<dyn std::error::Error + Send + Sync>> {
+        let db = self.db.clone();
+        let code = generate_token();
+        let expires_at = expires_at.map(|at| at.format("%Y-%m-%d %H:%M:%S").to_string());
+
+        tokio::task::spawn_blocking(
+            move || -> Result<InviteCode, Box<dyn std::error::Error + Send + Sync>> {
+                let conn = db.get_connection().lock()?;
+
+                conn.execute(
+                    "INSERT INTO invite_codes (code, created_by, expires_at) VALUES (?1, ?2, ?3)",
+                    rusqlite::params![&code, created_by, &expires_at],
+                )?;
+
+                let invite = conn.query_row(
+                    "SELECT id, code, created_by, used_by, used_at, expires_at, created_at
+                     FROM invite_codes WHERE id = ?1",
+                    [conn.last_insert_rowid()],
+                    map_invite,
+                )?;
+
+                Ok(invite)
+            },
+        )
+        .await?
+    }
+
+    /// All invite codes, newest first
+    pub async fn list_invites(
+        &self,
+    ) -> Result<Vec<InviteCode>, Box<dyn std::error::Error + Send + Sync>> {
+        let db = self.db.clone();
+
+        tokio::task::spawn_blocking(
+            move || -> Result<Vec<InviteCode>, Box<dyn std::error::Error + Send + Sync>> {
+                let conn = db.get_connection().lock()?;
+
+                let mut stmt = conn.prepare(
+                    "SELECT id, code, created_by, used_by, used_at, expires_at, created_at
+                     FROM invite_codes ORDER BY created_at DESC, id DESC",
+                )?;
+
+                let invites = stmt
+                    .query_map([], map_invite)?
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                Ok(invites)
+            },
+        )
+        .await?
+    }
+}
+
+fn map_invite(row: &rusqlite::Row) -> rusqlite::Result<InviteCode> {
+    Ok(InviteCode {
+        id: row.get(0)?,
+        code: row.get(1)?,
+        created_by: row.get(2)?,
+        used_by: row.get(3)?,
+        used_at: row.get::<_, Option<String>>(4)?.map(parse_datetime),
+        expires_at: row.get::<_, Option<String>>(5)?.map(parse_datetime),
+        created_at: parse_datetime(row.get::<_, String>(6)?),
+    })
+}
+
+fn parse_datetime(s: String) -> DateTime<Utc> {
+    // SQLite stores timestamps as strings, parse them
+    // Format: YYYY-MM-DD HH:MM:SS
+    chrono::NaiveDateTime::parse_from_str(&s, "%Y-%m-%d %H:%M:%S")
+        .ok()
+        .map(|dt| dt.and_utc())
+        .unwrap_or_else(|| {
+            tracing::warn!(
+                "Failed to parse datetime '{}', falling back to current time",
+                s
+            );
+            Utc::now()
+        })
+}
@@ -1,15 +1,22 @@
+pub mod invites;
 pub mod profiles;
 pub mod users;
 
 // Re-export commonly used items
 pub use users::{
-    admin_users_list_handler, create_user, delete_user, list_users, lock_user, update_user,
-    AdminCreateUserRequest, AdminUpdateUserRequest, AdminUserRepository, AdminUserResponse,
+    admin_users_list_handler, create_user, delete_user, get_user, list_users, lock_user,
+    update_user, AdminCreateUserRequest, AdminUpdateUserRequest, AdminUserDetailResponse,
+    AdminUserListQuery, AdminUserListResponse, AdminUserRepository, AdminUserResponse,
     LockUserRequest,
 };
 
+pub use invites::{
+    create_invite, list_invites, AdminInviteRepository, CreateInviteRequest, InviteCode,
+};
+
 pub use profiles::{
-    admin_profiles_list_handler, copy_profile, create_profile, list_all_profiles,
-    set_profile_active_status, AdminProfileRepository, AdminProfileResponse, CopyProfileRequest,
-    CreateProfileRequest, DeactivateProfileRequest,
+    admin_profiles_list_handler, copy_profile, create_profile, delete_profile, list_all_profiles,
+    profile_usage, reassign_profile, set_profile_active_status, update_profile,
+    AdminProfileRepository, AdminProfileResponse, AdminUpdateProfileRequest, CopyProfileRequest,
+    CreateProfileRequest, DeactivateProfileRequest, ProfileUsageResponse, ReassignProfileRequest,
 };
@@ -8,10 +8,13 @@ use serde_json::json;
 use tower_sessions::Session;
 
 use crate::admin::profiles::models::{
-    AdminProfileResponse, CopyProfileRequest, CreateProfileRequest, DeactivateProfileRequest,
+    AdminProfileResponse, AdminUpdateProfileRequest, CopyProfileRequest, CreateProfileRequest,
+    DeactivateProfileRequest, ProfileUsageResponse, ReassignProfileRequest,
 };
 use crate::admin::profiles::repository::AdminProfileRepository;
+use crate::fermentation::models::{ProfileType, MAX_TEXT_FIELD_LENGTH};
 use crate::users::models::{UserRole, UserSession};
+use crate::users::UserRepository;
 use crate::AppState;
 
 #[derive(Debug)]
@@ -65,7 +68,90 @@ async fn require_admin(session: &Session) -> Result<UserSession, AdminProfileApi
     }
 }
 
+/// Validate the fields shared by profile creation and update. `profile_type` is expected to be
+/// normalized already.
+fn validate_profile_fields(
+    name: &str,
+    profile_type: &str,
+    min_days: i32,
+    max_days: i32,
+    temp_min: f64,
+    temp_max: f64,
+    allow_custom_types: bool,
+) -> Result<(), AdminProfileApiError> {
+    if name.trim().is_empty() {
+        return Err(AdminProfileApiError::ValidationError(
+            "Profile name cannot be empty".to_string(),
+        ));
+    }
+
+    if profile_type.trim().is_empty() {
+        return Err(AdminProfileApiError::ValidationError(
+            "Profile type cannot be empty".to_string(),
+        ));
+    }
+
+    if !allow_custom_types && ProfileType::parse(profile_type).is_none() {
+        let known: Vec<&str> = ProfileType::ALL.iter().map(|t| t.as_str()).collect();
+        return Err(AdminProfileApiError::ValidationError(format!(
+            "Unknown profile type '{}'; expected one of: {}",
+            profile_type,
+            known.join(", ")
+        )));
+    }
+
+    if min_days <= 0 || max_days <= 0 {
+        return Err(AdminProfileApiError::ValidationError(
+            "Days must be positive".to_string(),
+        ));
+    }
+
+    if min_days > max_days {
+        return Err(AdminProfileApiError::ValidationError(
+            "Minimum days cannot exceed maximum days".to_string(),
+        ));
+    }
+
+    if temp_min >= temp_max {
+        return Err(AdminProfileApiError::ValidationError(
+            "Minimum temperature must be less than maximum temperature".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Trim suggested ingredients, treating blank text as none, and enforce the usual text limit
+fn clean_default_ingredients(
+    default_ingredients: Option<String>,
+) -> Result<Option<String>, AdminProfileApiError> {
+    let cleaned = default_ingredients
+        .map(|text| text.trim().to_string())
+        .filter(|text| !text.is_empty());
+
+    if let Some(text) = &cleaned {
+        if text.chars().count() > MAX_TEXT_FIELD_LENGTH {
+            return Err(AdminProfileApiError::ValidationError(format!(
+                "Default ingredients must be at most {} characters",
+                MAX_TEXT_FIELD_LENGTH
+            )));
+        }
+    }
+
+    Ok(cleaned)
+}
+
 /// List all fermentation profiles (admin only, includes inactive)
+#[utoipa::path(
+    get,
+    path = "/api/admin/profiles",
+    tag = "admin",
+    responses(
+        (status = 200, description = "All profiles, including inactive ones", body = [AdminProfileResponse]),
+        (status = 401, description = "Not logged in"),
+        (status = 403, description = "Not an admin")
+    )
+)]
 pub async fn list_all_profiles(
     session: Session,
     State(state): State<AppState>,
@@ -86,45 +172,66 @@ pub async fn list_all_profiles(
     Ok(Json(responses))
 }
 
-/// Create a new fermentation profile (admin only)
-pub async fn create_profile(
+/// List every profile with how many fermentations use it (admin only)
+#[utoipa::path(
+    get,
+    path = "/api/admin/profiles/usage",
+    tag = "admin",
+    responses(
+        (status = 200, description = "Profiles with how many fermentations use them", body = [ProfileUsageResponse]),
+        (status = 401, description = "Not logged in"),
+        (status = 403, description = "Not an admin")
+    )
+)]
+pub async fn profile_usage(
     session: Session,
     State(state): State<AppState>,
-    Json(request): Json<CreateProfileRequest>,
-) -> Result<(StatusCode, Json<AdminProfileResponse>), AdminProfileApiError> {
+) -> Result<Json<Vec<ProfileUsageResponse>>, AdminProfileApiError> {
     // Check admin authorization
     require_admin(&session).await?;
 
-    // Validate input
-    if request.name.trim().is_empty() {
-        return Err(AdminProfileApiError::ValidationError(
-            "Profile name cannot be empty".to_string(),
-        ));
-    }
-
-    if request.r#type.trim().is_empty() {
-        return Err(AdminProfileApiError::ValidationError(
-            "Profile type cannot be empty".to_string(),
-        ));
-    }
+    let repo = AdminProfileRepository::new(state.db.clone());
+    let usage = repo.profile_usage().await.map_err(|e| {
+        AdminProfileApiError::DatabaseError(format!("Failed to load profile usage: {}", e))
+    })?;
 
-    if request.min_days <= 0 || request.max_days <= 0 {
-        return Err(AdminProfileApiError::ValidationError(
-            "Days must be positive".to_string(),
-        ));
-    }
+    Ok(Json(usage))
+}
 
-    if request.min_days > request.max_days {
-        return Err(AdminProfileApiError::ValidationError(
-            "Minimum days cannot exceed maximum days".to_string(),
-        ));
-    }
+/// Create a new fermentation profile (admin only)
+#[utoipa::path(
+    post,
+    path = "/api/admin/profiles",
+    tag = "admin",
+    request_body = CreateProfileRequest,
+    responses(
+        (status = 201, description = "Profile created", body = AdminProfileResponse),
+        (status = 400, description = "Invalid input"),
+        (status = 401, description = "Not logged in"),
+        (status = 403, description = "Not an admin"),
+        (status = 409, description = "Profile name already taken")
+    )
+)]
+pub async fn create_profile(
+    session: Session,
+    State(state): State<AppState>,
+    Json(mut request): Json<CreateProfileRequest>,
+) -> Result<(StatusCode, Json<AdminProfileResponse>), AdminProfileApiError> {
+    // Check admin authorization
+    let admin = require_admin(&session).await?;
 
-    if request.temp_min >= request.temp_max {
-        return Err(AdminProfileApiError::ValidationError(
-            "Minimum temperature must be less than maximum temperature".to_string(),
-        ));
-    }
+    // Validate input
+    request.r#type = ProfileType::normalize(&request.r#type);
+    validate_profile_fields(
+        &request.name,
+        &request.r#type,
+        request.min_days,
+        request.max_days,
+        request.temp_min,
+        request.temp_max,
+        state.config.allow_custom_types,
+    )?;
+    request.default_ingredients = clean_default_ingredients(request.default_ingredients)?;
 
     let repo = AdminProfileRepository::new(state.db.clone());
 
@@ -136,9 +243,12 @@ pub async fn create_profile(
     }
 
     // Create profile
-    let profile = repo.create_profile(request).await.map_err(|e| {
-        AdminProfileApiError::InternalError(format!("Failed to create profile: {}", e))
-    })?;
+    let profile = repo
+        .create_profile(request, admin.user_id)
+        .await
+        .map_err(|e| {
+            AdminProfileApiError::InternalError(format!("Failed to create profile: {}", e))
+        })?;
 
     Ok((
         StatusCode::CREATED,
@@ -146,7 +256,135 @@ pub async fn create_profile(
     ))
 }
 
+/// Update an existing profile in place (admin only)
+#[utoipa::path(
+    put,
+    path = "/api/admin/profiles/{id}",
+    tag = "admin",
+    params(("id" = i64, Path, description = "Profile id")),
+    request_body = AdminUpdateProfileRequest,
+    responses(
+        (status = 200, description = "Updated profile", body = AdminProfileResponse),
+        (status = 400, description = "Invalid input"),
+        (status = 401, description = "Not logged in"),
+        (status = 403, description = "Not an admin"),
+        (status = 404, description = "Profile not found")
+    )
+)]
+pub async fn update_profile(
+    session: Session,
+    State(state): State<AppState>,
+    Path(profile_id): Path<i64>,
+    Json(mut request): Json<AdminUpdateProfileRequest>,
+) -> Result<Json<AdminProfileResponse>, AdminProfileApiError> {
+    // Check admin authorization
+    require_admin(&session).await?;
+
+    // Validate input
+    request.r#type = ProfileType::normalize(&request.r#type);
+    validate_profile_fields(
+        &request.name,
+        &request.r#type,
+        request.min_days,
+        request.max_days,
+        request.temp_min,
+        request.temp_max,
+        state.config.allow_custom_types,
+    )?;
+    request.default_ingredients = clean_default_ingredients(request.default_ingredients)?;
+
+    let repo = AdminProfileRepository::new(state.db.clone());
+
+    // Check if another profile already uses the name
+    if repo
+        .name_exists_for_other(&request.name, profile_id)
+        .await
+        .unwrap_or(false)
+    {
+        return Err(AdminProfileApiError::Conflict(
+            "Profile with this name already exists".to_string(),
+        ));
+    }
+
+    // Update profile
+    let profile = repo
+        .update_profile(profile_id, request)
+        .await
+        .map_err(|e| {
+            if e.to_string().contains("not found") {
+                AdminProfileApiError::NotFound
+            } else {
+                AdminProfileApiError::DatabaseError(format!("Failed to update profile: {}", e))
+            }
+        })?;
+
+    Ok(Json(AdminProfileResponse::from(profile)))
+}
+
+/// Delete a profile that no fermentation references (admin only)
+#[utoipa::path(
+    delete,
+    path = "/api/admin/profiles/{id}",
+    tag = "admin",
+    params(("id" = i64, Path, description = "Profile id")),
+    responses(
+        (status = 204, description = "Profile deleted"),
+        (status = 401, description = "Not logged in"),
+        (status = 403, description = "Not an admin"),
+        (status = 404, description = "Profile not found")
+    )
+)]
+pub async fn delete_profile(
+    session: Session,
+    State(state): State<AppState>,
+    Path(profile_id): Path<i64>,
+) -> Result<StatusCode, AdminProfileApiError> {
+    // Check admin authorization
+    require_admin(&session).await?;
+
+    let repo = AdminProfileRepository::new(state.db.clone());
+
+    // Refuse to delete profiles that are still in use
+    let usage = repo
+        .count_fermentations_using(profile_id)
+        .await
+        .map_err(|e| {
+            AdminProfileApiError::DatabaseError(format!("Failed to check profile usage: {}", e))
+        })?;
+
+    if usage > 0 {
+        return Err(AdminProfileApiError::Conflict(format!(
+            "Profile is used by {} fermentation(s); deactivate it instead",
+            usage
+        )));
+    }
+
+    repo.delete_profile(profile_id).await.map_err(|e| {
+        if e.to_string().contains("not found") {
+            AdminProfileApiError::NotFound
+        } else {
+            AdminProfileApiError::DatabaseError(format!("Failed to delete profile: {}", e))
+        }
+    })?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
 /// Copy an existing profile (admin only)
+#[utoipa::path(
+    post,
+    path = "/api/admin/profiles/{id}/copy",
+    tag = "admin",
+    params(("id" = i64, Path, description = "Profile id")),
+    request_body = CopyProfileRequest,
+    responses(
+        (status = 201, description = "Copy created", body = AdminProfileResponse),
+        (status = 400, description = "Invalid input"),
+        (status = 401, description = "Not logged in"),
+        (status = 403, description = "Not an admin"),
+        (status = 404, description = "Profile not found")
+    )
+)]
 pub async fn copy_profile(
     session: Session,
     State(state): State<AppState>,
@@ -154,7 +392,7 @@ pub async fn copy_profile(
     Json(request): Json<CopyProfileRequest>,
 ) -> Result<(StatusCode, Json<AdminProfileResponse>), AdminProfileApiError> {
     // Check admin authorization
-    require_admin(&session).await?;
+    let admin = require_admin(&session).await?;
 
     // Validate input
     if request.new_name.trim().is_empty() {
@@ -174,7 +412,7 @@ pub async fn copy_profile(
 
     // Copy profile
     let profile = repo
-        .copy_profile(profile_id, request.new_name)
+        .copy_profile(profile_id, request.new_name, admin.user_id)
         .await
         .map_err(|e| {
             if e.to_string().contains("no rows") {
@@ -191,6 +429,19 @@ pub async fn copy_profile(
 }
 
 /// Deactivate or reactivate a profile (admin only)
+#[utoipa::path(
+    post,
+    path = "/api/admin/profiles/{id}/status",
+    tag = "admin",
+    params(("id" = i64, Path, description = "Profile id")),
+    request_body = DeactivateProfileRequest,
+    responses(
+        (status = 200, description = "Updated profile", body = AdminProfileResponse),
+        (status = 401, description = "Not logged in"),
+        (status = 403, description = "Not an admin"),
+        (status = 404, description = "Profile not found")
+    )
+)]
 pub async fn set_profile_active_status(
     session: Session,
     State(state): State<AppState>,
@@ -219,3 +470,53 @@ pub async fn set_profile_active_status(
 
     Ok(Json(AdminProfileResponse::from(profile)))
 }
+
+/// Reassign a profile to another admin (admin only)
+#[utoipa::path(
+    put,
+    path = "/api/admin/profiles/{id}/owner",
+    tag = "admin",
+    params(("id" = i64, Path, description = "Profile id")),
+    request_body = ReassignProfileRequest,
+    responses(
+        (status = 200, description = "Updated profile", body = AdminProfileResponse),
+        (status = 400, description = "Invalid input"),
+        (status = 401, description = "Not logged in"),
+        (status = 403, description = "Not an admin"),
+        (status = 404, description = "Profile not found")
+    )
+)]
+pub async fn reassign_profile(
+    session: Session,
+    State(state): State<AppState>,
+    Path(profile_id): Path<i64>,
+    Json(request): Json<ReassignProfileRequest>,
+) -> Result<Json<AdminProfileResponse>, AdminProfileApiError> {
+    // Check admin authorization
+    require_admin(&session).await?;
+
+    // Profiles can only be handed to another admin
+    let user_repo = UserRepository::new(state.db.clone());
+    match user_repo.find_by_id(request.created_by).await {
+        Ok(user) if matches!(user.role, UserRole::Admin) => {}
+        _ => {
+            return Err(AdminProfileApiError::ValidationError(
+                "New owner must be an existing admin".to_string(),
+            ))
+        }
+    }
+
+    let repo = AdminProfileRepository::new(state.db.clone());
+    let profile = repo
+        .set_profile_owner(profile_id, request.created_by)
+        .await
+        .map_err(|e| {
+            if e.to_string().contains("not found") {
+                AdminProfileApiError::NotFound
+            } else {
+                AdminProfileApiError::DatabaseError(format!("Failed to reassign profile: {}", e))
+            }
+        })?;
+
+    Ok(Json(AdminProfileResponse::from(profile)))
+}
@@ -4,9 +4,13 @@ pub mod repository;
 pub mod templates;
 
 // Re-export commonly used items
-pub use handlers::{copy_profile, create_profile, list_all_profiles, set_profile_active_status};
+pub use handlers::{
+    copy_profile, create_profile, delete_profile, list_all_profiles, profile_usage,
+    reassign_profile, set_profile_active_status, update_profile,
+};
 pub use models::{
-    AdminProfileResponse, CopyProfileRequest, CreateProfileRequest, DeactivateProfileRequest,
+    AdminProfileResponse, AdminUpdateProfileRequest, CopyProfileRequest, CreateProfileRequest,
+    DeactivateProfileRequest, ProfileUsageResponse, ReassignProfileRequest,
 };
 pub use repository::AdminProfileRepository;
 pub use templates::admin_profiles_list_handler;
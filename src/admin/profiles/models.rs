@@ -1,8 +1,9 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
 /// Request to create a new fermentation profile
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct CreateProfileRequest {
     pub name: String,
     pub r#type: String,
@@ -11,22 +12,46 @@ pub struct CreateProfileRequest {
     pub temp_min: f64,
     pub temp_max: f64,
     pub description: Option<String>,
+    /// Suggested ingredients, one per line
+    #[serde(default)]
+    pub default_ingredients: Option<String>,
+}
+
+/// Request to update an existing fermentation profile
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct AdminUpdateProfileRequest {
+    pub name: String,
+    pub r#type: String,
+    pub min_days: i32,
+    pub max_days: i32,
+    pub temp_min: f64,
+    pub temp_max: f64,
+    pub description: Option<String>,
+    /// Suggested ingredients, one per line
+    #[serde(default)]
+    pub default_ingredients: Option<String>,
 }
 
 /// Request to copy an existing profile
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct CopyProfileRequest {
     pub new_name: String,
 }
 
 /// Request to deactivate a profile
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct DeactivateProfileRequest {
     pub is_active: bool,
 }
 
+/// Request to hand a profile over to another admin
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ReassignProfileRequest {
+    pub created_by: i64,
+}
+
 /// Response for profile management (includes is_active status)
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct AdminProfileResponse {
     pub id: i64,
     pub name: String,
@@ -38,6 +63,8 @@ pub struct AdminProfileResponse {
     pub description: Option<String>,
     pub is_active: bool,
     pub created_at: DateTime<Utc>,
+    pub created_by: Option<i64>,
+    pub default_ingredients: Option<String>,
 }
 
 impl From<crate::fermentation::models::FermentationProfile> for AdminProfileResponse {
@@ -53,6 +80,18 @@ impl From<crate::fermentation::models::FermentationProfile> for AdminProfileResp
             description: profile.description,
             is_active: profile.is_active,
             created_at: profile.created_at,
+            created_by: profile.created_by,
+            default_ingredients: profile.default_ingredients,
         }
     }
 }
+
+/// A profile with the number of fermentations that use it
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ProfileUsageResponse {
+    #[serde(flatten)]
+    pub profile: AdminProfileResponse,
+    /// Fermentations of any user referencing the profile, excluding deleted ones
+    pub fermentation_count: i64,
+    pub active_fermentation_count: i64,
+}
@@ -1,3 +1,4 @@
+use crate::admin::profiles::models::{AdminProfileResponse, ProfileUsageResponse};
 use crate::database::Database;
 use crate::fermentation::models::FermentationProfile;
 use chrono::{DateTime, Utc};
@@ -21,10 +22,10 @@ impl AdminProfileRepository {
 
         tokio::task::spawn_blocking(
             move || -> Result<Vec<FermentationProfile>, Box<dyn std::error::Error + Send + Sync>> {
-                let conn = db.get_connection().lock().unwrap();
+                let conn = db.get_connection().lock()?;
 
                 let mut stmt = conn.prepare(
-                    "SELECT id, name, type, min_days, max_days, temp_min, temp_max, description, is_active, created_at
+                    "SELECT id, name, type, min_days, max_days, temp_min, temp_max, description, is_active, created_at, created_by, default_ingredients
                      FROM fermentation_profiles ORDER BY name",
                 )?;
 
@@ -41,6 +42,8 @@ impl AdminProfileRepository {
                             description: row.get(7)?,
                             is_active: row.get::<_, i32>(8)? != 0,
                             created_at: parse_datetime(row.get::<_, String>(9)?),
+                            created_by: row.get(10)?,
+                            default_ingredients: row.get(11)?,
                         })
                     })?
                     .collect::<Result<Vec<_>, _>>()?;
@@ -51,10 +54,11 @@ impl AdminProfileRepository {
         .await?
     }
 
-    /// Create a new profile
+    /// Create a new profile, recording the admin who created it
     pub async fn create_profile(
         &self,
         request: crate::admin::profiles::models::CreateProfileRequest,
+        created_by: i64,
     ) -> Result<FermentationProfile, Box<dyn std::error::Error + Send + Sync>> {
         let db = self.db.clone();
         let name = request.name;
@@ -64,15 +68,16 @@ impl AdminProfileRepository {
         let temp_min = request.temp_min;
         let temp_max = request.temp_max;
         let description = request.description;
+        let default_ingredients = request.default_ingredients;
 
         let profile_id = tokio::task::spawn_blocking(
             move || -> Result<i64, Box<dyn std::error::Error + Send + Sync>> {
-                let conn = db.get_connection().lock().unwrap();
+                let conn = db.get_connection().lock()?;
 
                 conn.execute(
-                    "INSERT INTO fermentation_profiles (name, type, min_days, max_days, temp_min, temp_max, description, is_active)
-                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, 1)",
-                    rusqlite::params![name, r#type, min_days, max_days, temp_min, temp_max, description],
+                    "INSERT INTO fermentation_profiles (name, type, min_days, max_days, temp_min, temp_max, description, is_active, created_by, default_ingredients)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, 1, ?8, ?9)",
+                    rusqlite::params![name, r#type, min_days, max_days, temp_min, temp_max, description, created_by, default_ingredients],
                 )?;
 
                 let profile_id = conn.last_insert_rowid();
@@ -86,59 +91,78 @@ impl AdminProfileRepository {
             .ok_or_else(|| "Failed to retrieve created profile".into())
     }
 
-    /// Copy an existing profile with a new name
+    /// Update an existing profile's details
+    pub async fn update_profile(
+        &self,
+        profile_id: i64,
+        request: crate::admin::profiles::models::AdminUpdateProfileRequest,
+    ) -> Result<FermentationProfile, Box<dyn std::error::Error + Send + Sync>> {
+        let db = self.db.clone();
+
+        let updated = tokio::task::spawn_blocking(
+            move || -> Result<usize, Box<dyn std::error::Error + Send + Sync>> {
+                let conn = db.get_connection().lock()?;
+
+                let updated = conn.execute(
+                    "UPDATE fermentation_profiles
+                     SET name = ?1, type = ?2, min_days = ?3, max_days = ?4, temp_min = ?5, temp_max = ?6, description = ?7,
+                         default_ingredients = ?8
+                     WHERE id = ?9",
+                    rusqlite::params![
+                        request.name,
+                        request.r#type,
+                        request.min_days,
+                        request.max_days,
+                        request.temp_min,
+                        request.temp_max,
+                        request.description,
+                        request.default_ingredients,
+                        profile_id
+                    ],
+                )?;
+
+                Ok(updated)
+            },
+        )
+        .await??;
+
+        if updated == 0 {
+            return Err("Profile not found".into());
+        }
+
+        self.get_profile_by_id(profile_id)
+            .await?
+            .ok_or_else(|| "Profile not found".into())
+    }
+
+    /// Copy an existing profile with a new name; the copy belongs to the admin making it.
+    /// Every editable column is carried over and the copy starts out active, even when the
+    /// source has been deactivated.
     pub async fn copy_profile(
         &self,
         profile_id: i64,
         new_name: String,
+        created_by: i64,
     ) -> Result<FermentationProfile, Box<dyn std::error::Error + Send + Sync>> {
         let db = self.db.clone();
 
         let new_profile_id = tokio::task::spawn_blocking(
             move || -> Result<i64, Box<dyn std::error::Error + Send + Sync>> {
-                let conn = db.get_connection().lock().unwrap();
-
-                // Get the source profile
-                let mut stmt = conn.prepare(
-                    "SELECT type, min_days, max_days, temp_min, temp_max, description
-                     FROM fermentation_profiles WHERE id = ?1",
+                let conn = db.get_connection().lock()?;
+
+                // Copy straight from the source row so the duplicate can't miss a column
+                let inserted = conn.execute(
+                    "INSERT INTO fermentation_profiles (name, type, min_days, max_days, temp_min, temp_max, description, default_ingredients, is_active, created_by)
+                     SELECT ?1, type, min_days, max_days, temp_min, temp_max, description, default_ingredients, 1, ?2
+                     FROM fermentation_profiles WHERE id = ?3",
+                    rusqlite::params![new_name, created_by, profile_id],
                 )?;
 
-                let (profile_type, min_days, max_days, temp_min, temp_max, description): (
-                    String,
-                    i32,
-                    i32,
-                    f64,
-                    f64,
-                    Option<String>,
-                ) = stmt.query_row([profile_id], |row| {
-                    Ok((
-                        row.get(0)?,
-                        row.get(1)?,
-                        row.get(2)?,
-                        row.get(3)?,
-                        row.get(4)?,
-                        row.get(5)?,
-                    ))
-                })?;
-
-                // Insert the copy with new name
-                conn.execute(
-                    "INSERT INTO fermentation_profiles (name, type, min_days, max_days, temp_min, temp_max, description, is_active)
-                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, 1)",
-                    rusqlite::params![
-                        new_name,
-                        profile_type,
-                        min_days,
-                        max_days,
-                        temp_min,
-                        temp_max,
-                        description
-                    ],
-                )?;
+                if inserted == 0 {
+                    return Err(rusqlite::Error::QueryReturnedNoRows.into());
+                }
 
-                let new_profile_id = conn.last_insert_rowid();
-                Ok(new_profile_id)
+                Ok(conn.last_insert_rowid())
             },
         )
         .await??;
@@ -158,7 +182,7 @@ impl AdminProfileRepository {
 
         tokio::task::spawn_blocking(
             move || -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-                let conn = db.get_connection().lock().unwrap();
+                let conn = db.get_connection().lock()?;
 
                 conn.execute(
                     "UPDATE fermentation_profiles SET is_active = ?1 WHERE id = ?2",
@@ -175,6 +199,142 @@ impl AdminProfileRepository {
             .ok_or_else(|| "Profile not found".into())
     }
 
+    /// Hand a profile over to another admin
+    pub async fn set_profile_owner(
+        &self,
+        profile_id: i64,
+        created_by: i64,
+    ) -> Result<FermentationProfile, Box<dyn std::error::Error + Send + Sync>> {
+        let db = self.db.clone();
+
+        let updated = tokio::task::spawn_blocking(
+            move || -> Result<usize, Box<dyn std::error::Error + Send + Sync>> {
+                let conn = db.get_connection().lock()?;
+
+                let updated = conn.execute(
+                    "UPDATE fermentation_profiles SET created_by = ?1 WHERE id = ?2",
+                    rusqlite::params![created_by, profile_id],
+                )?;
+
+                Ok(updated)
+            },
+        )
+        .await??;
+
+        if updated == 0 {
+            return Err("Profile not found".into());
+        }
+
+        self.get_profile_by_id(profile_id)
+            .await?
+            .ok_or_else(|| "Profile not found".into())
+    }
+
+    /// Every profile with how many fermentations use it and how many of those are active,
+    /// most used first
+    pub async fn profile_usage(
+        &self,
+    ) -> Result<Vec<ProfileUsageResponse>, Box<dyn std::error::Error + Send + Sync>> {
+        let db = self.db.clone();
+
+        tokio::task::spawn_blocking(
+            move || -> Result<Vec<ProfileUsageResponse>, Box<dyn std::error::Error + Send + Sync>> {
+                let conn = db.get_connection().lock()?;
+
+                let mut stmt = conn.prepare(
+                    "SELECT p.id, p.name, p.type, p.min_days, p.max_days, p.temp_min, p.temp_max, p.description, p.is_active, p.created_at, p.created_by, p.default_ingredients,
+                            COUNT(f.id), COALESCE(SUM(CASE WHEN f.status = 'active' THEN 1 ELSE 0 END), 0)
+                     FROM fermentation_profiles p
+                     LEFT JOIN fermentations f ON f.profile_id = p.id AND f.deleted_at IS NULL
+                     GROUP BY p.id
+                     ORDER BY COUNT(f.id) DESC, p.name",
+                )?;
+
+                let usage = stmt
+                    .query_map([], |row| {
+                        let profile = FermentationProfile {
+                            id: row.get(0)?,
+                            name: row.get(1)?,
+                            r#type: row.get(2)?,
+                            min_days: row.get(3)?,
+                            max_days: row.get(4)?,
+                            temp_min: row.get(5)?,
+                            temp_max: row.get(6)?,
+                            description: row.get(7)?,
+                            is_active: row.get::<_, i32>(8)? != 0,
+                            created_at: parse_datetime(row.get::<_, String>(9)?),
+                            created_by: row.get(10)?,
+                            default_ingredients: row.get(11)?,
+                        };
+
+                        Ok(ProfileUsageResponse {
+                            profile: AdminProfileResponse::from(profile),
+                            fermentation_count: row.get(12)?,
+                            active_fermentation_count: row.get(13)?,
+                        })
+                    })?
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                Ok(usage)
+            },
+        )
+        .await?
+    }
+
+    /// Count the fermentations (of any user) that reference a profile
+    pub async fn count_fermentations_using(
+        &self,
+        profile_id: i64,
+    ) -> Result<i64, Box<dyn std::error::Error + Send + Sync>> {
+        let db = self.db.clone();
+
+        tokio::task::spawn_blocking(
+            move || -> Result<i64, Box<dyn std::error::Error + Send + Sync>> {
+                let conn = db.get_connection().lock()?;
+
+                let count: i64 = conn.query_row(
+                    "SELECT COUNT(*) FROM fermentations WHERE profile_id = ?1",
+                    [profile_id],
+                    |row| row.get(0),
+                )?;
+
+                Ok(count)
+            },
+        )
+        .await?
+    }
+
+    /// Delete a profile, returning an error if it does not exist
+    pub async fn delete_profile(
+        &self,
+        profile_id: i64,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let db = self.db.clone();
+
+        tokio::task::spawn_blocking(
+            move || -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+                let conn = db.get_connection().lock()?;
+
+                conn.execute(
+                    "UPDATE users SET default_profile_id = NULL WHERE default_profile_id = ?1",
+                    [profile_id],
+                )?;
+
+                let deleted = conn.execute(
+                    "DELETE FROM fermentation_profiles WHERE id = ?1",
+                    [profile_id],
+                )?;
+
+                if deleted == 0 {
+                    return Err("Profile not found".into());
+                }
+
+                Ok(())
+            },
+        )
+        .await?
+    }
+
     /// Check if a profile name already exists
     pub async fn name_exists(
         &self,
@@ -185,7 +345,7 @@ impl AdminProfileRepository {
 
         tokio::task::spawn_blocking(
             move || -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
-                let conn = db.get_connection().lock().unwrap();
+                let conn = db.get_connection().lock()?;
 
                 let count: i64 = conn.query_row(
                     "SELECT COUNT(*) FROM fermentation_profiles WHERE name = ?1",
@@ -199,6 +359,31 @@ impl AdminProfileRepository {
         .await?
     }
 
+    /// Check if a profile name is used by any profile other than the given one
+    pub async fn name_exists_for_other(
+        &self,
+        name: &str,
+        profile_id: i64,
+    ) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+        let db = self.db.clone();
+        let name = name.to_string();
+
+        tokio::task::spawn_blocking(
+            move || -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+                let conn = db.get_connection().lock()?;
+
+                let count: i64 = conn.query_row(
+                    "SELECT COUNT(*) FROM fermentation_profiles WHERE name = ?1 AND id != ?2",
+                    rusqlite::params![name, profile_id],
+                    |row| row.get(0),
+                )?;
+
+                Ok(count > 0)
+            },
+        )
+        .await?
+    }
+
     /// Get a profile by ID (admin version, includes inactive profiles)
     async fn get_profile_by_id(
         &self,
@@ -208,10 +393,10 @@ impl AdminProfileRepository {
 
         tokio::task::spawn_blocking(
             move || -> Result<Option<FermentationProfile>, Box<dyn std::error::Error + Send + Sync>> {
-                let conn = db.get_connection().lock().unwrap();
+                let conn = db.get_connection().lock()?;
 
                 let mut stmt = conn.prepare(
-                    "SELECT id, name, type, min_days, max_days, temp_min, temp_max, description, is_active, created_at
+                    "SELECT id, name, type, min_days, max_days, temp_min, temp_max, description, is_active, created_at, created_by, default_ingredients
                      FROM fermentation_profiles WHERE id = ?1",
                 )?;
 
@@ -228,6 +413,8 @@ impl AdminProfileRepository {
                             description: row.get(7)?,
                             is_active: row.get::<_, i32>(8)? != 0,
                             created_at: parse_datetime(row.get::<_, String>(9)?),
+                            created_by: row.get(10)?,
+                            default_ingredients: row.get(11)?,
                         })
                     })
                     .optional()?;
@@ -1,5 +1,5 @@
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::StatusCode,
     response::{IntoResponse, Response},
     Json,
@@ -8,9 +8,11 @@ use serde_json::json;
 use tower_sessions::Session;
 
 use crate::admin::users::models::{
-    AdminCreateUserRequest, AdminUpdateUserRequest, AdminUserResponse, LockUserRequest,
+    AdminCreateUserRequest, AdminUpdateUserRequest, AdminUserDetailResponse, AdminUserListQuery,
+    AdminUserListResponse, AdminUserResponse, LockUserRequest,
 };
 use crate::admin::users::repository::AdminUserRepository;
+use crate::users::auth::password_policy_violation;
 use crate::users::models::{ExperienceLevel, UserRole, UserSession};
 use crate::AppState;
 
@@ -61,27 +63,77 @@ async fn require_admin(session: &Session) -> Result<UserSession, AdminApiError>
     }
 }
 
-/// List all users (admin only)
+/// List users with pagination, search and filters (admin only)
+#[utoipa::path(
+    get,
+    path = "/api/admin/users",
+    tag = "admin",
+    params(AdminUserListQuery),
+    responses(
+        (status = 200, description = "One page of users", body = AdminUserListResponse),
+        (status = 401, description = "Not logged in"),
+        (status = 403, description = "Not an admin")
+    )
+)]
 pub async fn list_users(
     session: Session,
     State(state): State<AppState>,
-) -> Result<Json<Vec<AdminUserResponse>>, AdminApiError> {
+    Query(query): Query<AdminUserListQuery>,
+) -> Result<Json<AdminUserListResponse>, AdminApiError> {
     // Check admin authorization
     require_admin(&session).await?;
 
+    if query.page == Some(0) {
+        return Err(AdminApiError::ValidationError(
+            "Page must be at least 1".to_string(),
+        ));
+    }
+
+    if query.per_page == Some(0) {
+        return Err(AdminApiError::ValidationError(
+            "per_page must be at least 1".to_string(),
+        ));
+    }
+
+    if let Some(role) = &query.role {
+        if role != "admin" && role != "user" {
+            return Err(AdminApiError::ValidationError(
+                "Invalid role. Must be 'user' or 'admin'".to_string(),
+            ));
+        }
+    }
+
+    let page = query.page();
+    let per_page = query.per_page();
+
     let repo = AdminUserRepository::new(state.db.clone());
-    let users = repo
-        .list_all_users()
+    let (users, total) = repo
+        .list_users(query)
         .await
         .map_err(|e| AdminApiError::DatabaseError(format!("Failed to list users: {}", e)))?;
 
-    let responses: Vec<AdminUserResponse> =
-        users.into_iter().map(AdminUserResponse::from).collect();
-
-    Ok(Json(responses))
+    Ok(Json(AdminUserListResponse {
+        users: users.into_iter().map(AdminUserResponse::from).collect(),
+        total,
+        page,
+        per_page,
+    }))
 }
 
 /// Create a new user (admin only)
+#[utoipa::path(
+    post,
+    path = "/api/admin/users",
+    tag = "admin",
+    request_body = AdminCreateUserRequest,
+    responses(
+        (status = 201, description = "User created", body = AdminUserResponse),
+        (status = 400, description = "Invalid input"),
+        (status = 401, description = "Not logged in"),
+        (status = 403, description = "Not an admin"),
+        (status = 409, description = "Email already registered")
+    )
+)]
 pub async fn create_user(
     session: Session,
     State(state): State<AppState>,
@@ -98,10 +150,11 @@ pub async fn create_user(
     }
 
     // Validate password strength
-    if request.password.len() < 8 {
-        return Err(AdminApiError::ValidationError(
-            "Password must be at least 8 characters long".to_string(),
-        ));
+    if let Some(requirement) = password_policy_violation(&request.password, &state.config) {
+        return Err(AdminApiError::ValidationError(format!(
+            "Password must {}",
+            requirement
+        )));
     }
 
     // Parse and validate role
@@ -146,7 +199,53 @@ pub async fn create_user(
     Ok((StatusCode::CREATED, Json(AdminUserResponse::from(user))))
 }
 
+/// Get a single user with fermentation counts and last login (admin only)
+#[utoipa::path(
+    get,
+    path = "/api/admin/users/{id}",
+    tag = "admin",
+    params(("id" = i64, Path, description = "User id")),
+    responses(
+        (status = 200, description = "The user with account totals", body = AdminUserDetailResponse),
+        (status = 401, description = "Not logged in"),
+        (status = 403, description = "Not an admin"),
+        (status = 404, description = "User not found")
+    )
+)]
+pub async fn get_user(
+    session: Session,
+    State(state): State<AppState>,
+    Path(user_id): Path<i64>,
+) -> Result<Json<AdminUserDetailResponse>, AdminApiError> {
+    // Check admin authorization
+    require_admin(&session).await?;
+
+    let repo = AdminUserRepository::new(state.db.clone());
+
+    let detail = repo
+        .find_detail(user_id)
+        .await
+        .map_err(|e| AdminApiError::DatabaseError(format!("Failed to fetch user: {}", e)))?
+        .ok_or(AdminApiError::NotFound)?;
+
+    Ok(Json(detail))
+}
+
 /// Update a user (admin only)
+#[utoipa::path(
+    put,
+    path = "/api/admin/users/{id}",
+    tag = "admin",
+    params(("id" = i64, Path, description = "User id")),
+    request_body = AdminUpdateUserRequest,
+    responses(
+        (status = 200, description = "Updated user", body = AdminUserResponse),
+        (status = 400, description = "Invalid input"),
+        (status = 401, description = "Not logged in"),
+        (status = 403, description = "Not an admin"),
+        (status = 404, description = "User not found")
+    )
+)]
 pub async fn update_user(
     session: Session,
     State(state): State<AppState>,
@@ -203,6 +302,19 @@ pub async fn update_user(
 }
 
 /// Lock or unlock a user account (admin only)
+#[utoipa::path(
+    post,
+    path = "/api/admin/users/{id}/lock",
+    tag = "admin",
+    params(("id" = i64, Path, description = "User id")),
+    request_body = LockUserRequest,
+    responses(
+        (status = 200, description = "Updated user", body = AdminUserResponse),
+        (status = 401, description = "Not logged in"),
+        (status = 403, description = "Not an admin"),
+        (status = 404, description = "User not found")
+    )
+)]
 pub async fn lock_user(
     session: Session,
     State(state): State<AppState>,
@@ -231,6 +343,18 @@ pub async fn lock_user(
 }
 
 /// Delete a user (admin only)
+#[utoipa::path(
+    delete,
+    path = "/api/admin/users/{id}",
+    tag = "admin",
+    params(("id" = i64, Path, description = "User id")),
+    responses(
+        (status = 204, description = "User deleted"),
+        (status = 401, description = "Not logged in"),
+        (status = 403, description = "Not an admin"),
+        (status = 404, description = "User not found")
+    )
+)]
 pub async fn delete_user(
     session: Session,
     State(state): State<AppState>,
@@ -249,10 +373,16 @@ pub async fn delete_user(
     let repo = AdminUserRepository::new(state.db.clone());
 
     // Delete user
-    repo.delete_user(user_id)
+    let fermentation_ids = repo
+        .delete_user(user_id)
         .await
         .map_err(|e| AdminApiError::DatabaseError(format!("Failed to delete user: {}", e)))?;
 
+    crate::photos::handlers::remove_fermentation_uploads(
+        &state.config.uploads_dir,
+        &fermentation_ids,
+    );
+
     Ok(StatusCode::NO_CONTENT)
 }
 
@@ -1,9 +1,10 @@
 use crate::users::{ExperienceLevel, User, UserRole};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
 
 /// Response for listing users in admin panel
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct AdminUserResponse {
     pub id: i64,
     pub email: String,
@@ -12,8 +13,10 @@ pub struct AdminUserResponse {
     pub first_name: Option<String>,
     pub last_name: Option<String>,
     pub is_locked: bool,
+    pub email_verified: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    pub last_login_at: Option<DateTime<Utc>>,
 }
 
 impl From<User> for AdminUserResponse {
@@ -26,14 +29,68 @@ impl From<User> for AdminUserResponse {
             first_name: user.first_name,
             last_name: user.last_name,
             is_locked: user.is_locked,
+            email_verified: user.email_verified,
             created_at: user.created_at,
             updated_at: user.updated_at,
+            last_login_at: user.last_login_at,
         }
     }
 }
 
+/// A single user with aggregate account information, for the admin user detail view
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AdminUserDetailResponse {
+    #[serde(flatten)]
+    pub user: AdminUserResponse,
+    /// Fermentations the user owns, excluding deleted ones
+    pub fermentation_count: i64,
+    pub active_fermentation_count: i64,
+    /// "locked", "unverified" or "active"
+    pub account_status: String,
+}
+
+/// Default number of users returned per page in the admin list
+pub const DEFAULT_USERS_PER_PAGE: u32 = 25;
+/// Upper bound on the page size the admin list will return
+pub const MAX_USERS_PER_PAGE: u32 = 100;
+
+/// Query parameters for the paginated admin user list
+#[derive(Debug, Clone, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct AdminUserListQuery {
+    pub page: Option<u32>,
+    pub per_page: Option<u32>,
+    /// Matches against email, first name and last name
+    pub search: Option<String>,
+    pub role: Option<String>,
+    pub is_locked: Option<bool>,
+}
+
+impl AdminUserListQuery {
+    /// Requested page, starting at 1
+    pub fn page(&self) -> u32 {
+        self.page.unwrap_or(1)
+    }
+
+    /// Requested page size, capped at `MAX_USERS_PER_PAGE`
+    pub fn per_page(&self) -> u32 {
+        self.per_page
+            .unwrap_or(DEFAULT_USERS_PER_PAGE)
+            .min(MAX_USERS_PER_PAGE)
+    }
+}
+
+/// A single page of users along with the total number of matches
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AdminUserListResponse {
+    pub users: Vec<AdminUserResponse>,
+    pub total: i64,
+    pub page: u32,
+    pub per_page: u32,
+}
+
 /// Request to create a new user by admin
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct AdminCreateUserRequest {
     pub email: String,
     pub password: String,
@@ -47,7 +104,7 @@ pub struct AdminCreateUserRequest {
 }
 
 /// Request to update a user by admin
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct AdminUpdateUserRequest {
     pub email: String,
     pub role: String,
@@ -59,7 +116,7 @@ pub struct AdminUpdateUserRequest {
 }
 
 /// Request to lock/unlock a user
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct LockUserRequest {
     pub locked: bool,
 }
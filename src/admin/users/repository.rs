@@ -1,6 +1,8 @@
+use crate::admin::users::models::{AdminUserDetailResponse, AdminUserListQuery, AdminUserResponse};
 use crate::database::Database;
 use crate::users::auth::hash_password;
 use crate::users::models::{ExperienceLevel, TemperatureUnit, User, UserRole};
+use crate::users::repository::delete_user_rows;
 use chrono::{DateTime, Utc};
 use rusqlite::OptionalExtension;
 use std::sync::Arc;
@@ -21,10 +23,10 @@ impl AdminUserRepository {
         let db = self.db.clone();
 
         tokio::task::spawn_blocking(move || -> Result<Vec<User>, Box<dyn std::error::Error + Send + Sync>> {
-            let conn = db.get_connection().lock().unwrap();
+            let conn = db.get_connection().lock()?;
 
             let mut stmt = conn.prepare(
-                "SELECT id, email, password_hash, role, experience_level, preferred_temp_unit, first_name, last_name, is_locked, created_at, updated_at
+                "SELECT id, email, password_hash, role, experience_level, preferred_temp_unit, first_name, last_name, is_locked, created_at, updated_at, email_verified, last_login_at, default_profile_id
                  FROM users ORDER BY created_at DESC"
             )?;
 
@@ -42,6 +44,9 @@ impl AdminUserRepository {
                         is_locked: row.get::<_, i64>(8)? != 0,
                         created_at: parse_datetime(row.get::<_, String>(9)?),
                         updated_at: parse_datetime(row.get::<_, String>(10)?),
+                        email_verified: row.get::<_, i64>(11)? != 0,
+                        last_login_at: row.get::<_, Option<String>>(12)?.map(parse_datetime),
+                        default_profile_id: row.get(13)?,
                     })
                 })?
                 .collect::<Result<Vec<_>, _>>()?;
@@ -51,6 +56,88 @@ impl AdminUserRepository {
         .await?
     }
 
+    /// List a page of users matching the given filters, along with the total match count
+    pub async fn list_users(
+        &self,
+        query: AdminUserListQuery,
+    ) -> Result<(Vec<User>, i64), Box<dyn std::error::Error + Send + Sync>> {
+        let db = self.db.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<(Vec<User>, i64), Box<dyn std::error::Error + Send + Sync>> {
+            let conn = db.get_connection().lock()?;
+
+            let mut where_clauses: Vec<String> = Vec::new();
+            let mut params: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
+
+            if let Some(search) = query.search.as_deref().map(str::trim).filter(|s| !s.is_empty()) {
+                where_clauses.push(
+                    "(email LIKE ? OR first_name LIKE ? OR last_name LIKE ?)".to_string(),
+                );
+                let pattern = format!("%{}%", search);
+                params.push(Box::new(pattern.clone()));
+                params.push(Box::new(pattern.clone()));
+                params.push(Box::new(pattern));
+            }
+
+            if let Some(role) = &query.role {
+                where_clauses.push("role = ?".to_string());
+                params.push(Box::new(role.clone()));
+            }
+
+            if let Some(is_locked) = query.is_locked {
+                where_clauses.push("is_locked = ?".to_string());
+                params.push(Box::new(is_locked as i64));
+            }
+
+            let where_sql = if where_clauses.is_empty() {
+                String::new()
+            } else {
+                format!(" WHERE {}", where_clauses.join(" AND "))
+            };
+
+            let params_refs: Vec<&dyn rusqlite::ToSql> = params.iter().map(|p| p.as_ref()).collect();
+
+            let total: i64 = conn.query_row(
+                &format!("SELECT COUNT(*) FROM users{}", where_sql),
+                params_refs.as_slice(),
+                |row| row.get(0),
+            )?;
+
+            let per_page = query.per_page() as i64;
+            let offset = (query.page() as i64 - 1) * per_page;
+
+            let mut stmt = conn.prepare(&format!(
+                "SELECT id, email, password_hash, role, experience_level, preferred_temp_unit, first_name, last_name, is_locked, created_at, updated_at, email_verified, last_login_at, default_profile_id
+                 FROM users{} ORDER BY created_at DESC, id DESC LIMIT {} OFFSET {}",
+                where_sql, per_page, offset
+            ))?;
+
+            let users = stmt
+                .query_map(params_refs.as_slice(), |row| {
+                    Ok(User {
+                        id: row.get(0)?,
+                        email: row.get(1)?,
+                        password_hash: row.get(2)?,
+                        role: UserRole::from(row.get::<_, String>(3)?),
+                        experience_level: ExperienceLevel::from(row.get::<_, String>(4)?),
+                        preferred_temp_unit: TemperatureUnit::from(row.get::<_, String>(5)?),
+                        first_name: row.get(6)?,
+                        last_name: row.get(7)?,
+                        is_locked: row.get::<_, i64>(8)? != 0,
+                        created_at: parse_datetime(row.get::<_, String>(9)?),
+                        updated_at: parse_datetime(row.get::<_, String>(10)?),
+                        email_verified: row.get::<_, i64>(11)? != 0,
+                        last_login_at: row.get::<_, Option<String>>(12)?.map(parse_datetime),
+                        default_profile_id: row.get(13)?,
+                    })
+                })?
+                .collect::<Result<Vec<_>, _>>()?;
+
+            Ok((users, total))
+        })
+        .await?
+    }
+
     /// Create a new user as admin (can set role)
     pub async fn create_user_as_admin(
         &self,
@@ -67,11 +154,12 @@ impl AdminUserRepository {
         let experience_level_str = experience_level.as_str().to_string();
 
         let user_id = tokio::task::spawn_blocking(move || -> Result<i64, Box<dyn std::error::Error + Send + Sync>> {
-            let conn = db.get_connection().lock().unwrap();
+            let conn = db.get_connection().lock()?;
 
+            // Accounts created by an administrator have no verification flow, so treat them as verified
             conn.execute(
-                "INSERT INTO users (email, password_hash, role, experience_level, first_name, last_name)
-                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                "INSERT INTO users (email, password_hash, role, experience_level, first_name, last_name, email_verified)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, 1)",
                 rusqlite::params![&email, &password_hash, &role_str, &experience_level_str, &first_name, &last_name],
             )?;
 
@@ -98,7 +186,7 @@ impl AdminUserRepository {
         let experience_level_str = experience_level.as_str().to_string();
 
         tokio::task::spawn_blocking(move || -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-            let conn = db.get_connection().lock().unwrap();
+            let conn = db.get_connection().lock()?;
 
             conn.execute(
                 "UPDATE users SET email = ?1, role = ?2, experience_level = ?3, first_name = ?4, last_name = ?5, updated_at = CURRENT_TIMESTAMP
@@ -124,7 +212,7 @@ impl AdminUserRepository {
 
         tokio::task::spawn_blocking(
             move || -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-                let conn = db.get_connection().lock().unwrap();
+                let conn = db.get_connection().lock()?;
 
                 conn.execute(
                     "UPDATE users SET is_locked = ?1, updated_at = CURRENT_TIMESTAMP WHERE id = ?2",
@@ -139,37 +227,103 @@ impl AdminUserRepository {
         self.find_by_id(user_id).await
     }
 
-    /// Delete a user (admin only)
+    /// Delete a user (admin only). Returns the IDs of the deleted fermentations so their
+    /// uploads can be cleaned up.
     pub async fn delete_user(
         &self,
         user_id: i64,
-    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    ) -> Result<Vec<i64>, Box<dyn std::error::Error + Send + Sync>> {
         let db = self.db.clone();
 
         tokio::task::spawn_blocking(
-            move || -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-                let conn = db.get_connection().lock().unwrap();
+            move || -> Result<Vec<i64>, Box<dyn std::error::Error + Send + Sync>> {
+                let mut conn = db.get_connection().lock()?;
+                let tx =
+                    conn.transaction_with_behavior(rusqlite::TransactionBehavior::Immediate)?;
 
-                conn.execute(
-                    "DELETE FROM users WHERE id = ?1",
-                    rusqlite::params![user_id],
-                )?;
+                let fermentation_ids = delete_user_rows(&tx, user_id)?;
 
-                Ok(())
+                tx.commit()?;
+                Ok(fermentation_ids)
             },
         )
         .await?
     }
 
+    /// Find a user together with their fermentation counts
+    pub async fn find_detail(
+        &self,
+        id: i64,
+    ) -> Result<Option<AdminUserDetailResponse>, Box<dyn std::error::Error + Send + Sync>> {
+        let db = self.db.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<Option<AdminUserDetailResponse>, Box<dyn std::error::Error + Send + Sync>> {
+            let conn = db.get_connection().lock()?;
+
+            let user = conn
+                .query_row(
+                    "SELECT id, email, password_hash, role, experience_level, preferred_temp_unit, first_name, last_name, is_locked, created_at, updated_at, email_verified, last_login_at, default_profile_id
+                     FROM users WHERE id = ?1",
+                    [id],
+                    |row| {
+                        Ok(User {
+                            id: row.get(0)?,
+                            email: row.get(1)?,
+                            password_hash: row.get(2)?,
+                            role: UserRole::from(row.get::<_, String>(3)?),
+                            experience_level: ExperienceLevel::from(row.get::<_, String>(4)?),
+                            preferred_temp_unit: TemperatureUnit::from(row.get::<_, String>(5)?),
+                            first_name: row.get(6)?,
+                            last_name: row.get(7)?,
+                            is_locked: row.get::<_, i64>(8)? != 0,
+                            created_at: parse_datetime(row.get::<_, String>(9)?),
+                            updated_at: parse_datetime(row.get::<_, String>(10)?),
+                            email_verified: row.get::<_, i64>(11)? != 0,
+                            last_login_at: row.get::<_, Option<String>>(12)?.map(parse_datetime),
+                            default_profile_id: row.get(13)?,
+                        })
+                    },
+                )
+                .optional()?;
+
+            let Some(user) = user else {
+                return Ok(None);
+            };
+
+            let (fermentation_count, active_fermentation_count): (i64, i64) = conn.query_row(
+                "SELECT COUNT(*), COALESCE(SUM(CASE WHEN status = 'active' THEN 1 ELSE 0 END), 0)
+                 FROM fermentations WHERE user_id = ?1 AND deleted_at IS NULL",
+                [id],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )?;
+
+            let account_status = if user.is_locked {
+                "locked"
+            } else if !user.email_verified {
+                "unverified"
+            } else {
+                "active"
+            };
+
+            Ok(Some(AdminUserDetailResponse {
+                user: AdminUserResponse::from(user),
+                fermentation_count,
+                active_fermentation_count,
+                account_status: account_status.to_string(),
+            }))
+        })
+        .await?
+    }
+
     /// Find user by ID (helper method)
     async fn find_by_id(&self, id: i64) -> Result<User, Box<dyn std::error::Error + Send + Sync>> {
         let db = self.db.clone();
 
         tokio::task::spawn_blocking(move || -> Result<User, Box<dyn std::error::Error + Send + Sync>> {
-            let conn = db.get_connection().lock().unwrap();
+            let conn = db.get_connection().lock()?;
 
             let mut stmt = conn.prepare(
-                "SELECT id, email, password_hash, role, experience_level, preferred_temp_unit, first_name, last_name, is_locked, created_at, updated_at
+                "SELECT id, email, password_hash, role, experience_level, preferred_temp_unit, first_name, last_name, is_locked, created_at, updated_at, email_verified, last_login_at, default_profile_id
                  FROM users WHERE id = ?1"
             )?;
 
@@ -186,6 +340,9 @@ impl AdminUserRepository {
                     is_locked: row.get::<_, i64>(8)? != 0,
                     created_at: parse_datetime(row.get::<_, String>(9)?),
                     updated_at: parse_datetime(row.get::<_, String>(10)?),
+                    email_verified: row.get::<_, i64>(11)? != 0,
+                    last_login_at: row.get::<_, Option<String>>(12)?.map(parse_datetime),
+                    default_profile_id: row.get(13)?,
                 })
             })?;
 
@@ -204,7 +361,7 @@ impl AdminUserRepository {
 
         tokio::task::spawn_blocking(
             move || -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
-                let conn = db.get_connection().lock().unwrap();
+                let conn = db.get_connection().lock()?;
 
                 let mut stmt = conn.prepare("SELECT id FROM users WHERE email = ?1")?;
                 let result = stmt.query_row([&email], |_row| Ok(())).optional()?;
@@ -0,0 +1,186 @@
+use axum::{
+    extract::{Query, State},
+    http::header,
+    response::IntoResponse,
+};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+use crate::fermentation::FermentationRepository;
+use crate::users::UserRepository;
+use crate::{ApiError, AppState};
+
+/// Longest content line allowed by RFC 5545 before it has to be folded, in octets
+const MAX_LINE_OCTETS: usize = 75;
+
+/// One entry in an iCalendar feed
+#[derive(Debug, Clone)]
+pub struct CalendarEvent {
+    /// Stable across feed refreshes so calendar apps update events instead of duplicating them
+    pub uid: String,
+    pub start: DateTime<Utc>,
+    pub summary: String,
+    pub description: Option<String>,
+}
+
+/// Serialize events as an iCalendar (RFC 5545) document
+pub fn render_calendar(name: &str, events: &[CalendarEvent], now: DateTime<Utc>) -> String {
+    let mut out = String::new();
+
+    write_line(&mut out, "BEGIN:VCALENDAR");
+    write_line(&mut out, "VERSION:2.0");
+    write_line(&mut out, "PRODID:-//Raugupatis Log//Fermentations//EN");
+    write_line(&mut out, "CALSCALE:GREGORIAN");
+    write_line(&mut out, &format!("X-WR-CALNAME:{}", escape_text(name)));
+
+    for event in events {
+        write_line(&mut out, "BEGIN:VEVENT");
+        write_line(&mut out, &format!("UID:{}", event.uid));
+        write_line(&mut out, &format!("DTSTAMP:{}", format_timestamp(now)));
+        write_line(
+            &mut out,
+            &format!("DTSTART:{}", format_timestamp(event.start)),
+        );
+        write_line(
+            &mut out,
+            &format!("SUMMARY:{}", escape_text(&event.summary)),
+        );
+        if let Some(description) = &event.description {
+            write_line(
+                &mut out,
+                &format!("DESCRIPTION:{}", escape_text(description)),
+            );
+        }
+        write_line(&mut out, "END:VEVENT");
+    }
+
+    write_line(&mut out, "END:VCALENDAR");
+    out
+}
+
+fn format_timestamp(at: DateTime<Utc>) -> String {
+    at.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+/// Escape a TEXT value: backslashes, separators and line breaks
+fn escape_text(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+/// Write a content line ending in CRLF, folding it onto continuation lines (which start with
+/// a space) so no line exceeds 75 octets, without splitting a UTF-8 character
+fn write_line(out: &mut String, line: &str) {
+    let mut octets = 0;
+    for c in line.chars() {
+        if octets + c.len_utf8() > MAX_LINE_OCTETS {
+            out.push_str("\r\n ");
+            // The leading space counts towards the continuation line's length
+            octets = 1;
+        }
+        out.push(c);
+        octets += c.len_utf8();
+    }
+    out.push_str("\r\n");
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CalendarFeedQuery {
+    pub token: Option<String>,
+}
+
+/// The user's active fermentations as "Check <name>" events on their target end dates.
+/// Calendar apps can't log in, so the feed is authenticated by the token from
+/// `POST /api/users/calendar-token` instead of the session cookie.
+pub async fn calendar_feed_handler(
+    State(state): State<AppState>,
+    Query(query): Query<CalendarFeedQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    let token = query.token.ok_or(ApiError::Unauthorized)?;
+
+    let user_repo = UserRepository::new(state.db.clone());
+    let user_id = user_repo
+        .verify_calendar_token(&token)
+        .await
+        .map_err(|e| ApiError::DatabaseError(format!("Error checking calendar token: {}", e)))?
+        .ok_or(ApiError::Unauthorized)?;
+
+    let fermentation_repo = FermentationRepository::new(state.db.clone());
+    let fermentations = fermentation_repo
+        .find_scheduled(user_id)
+        .await
+        .map_err(|e| ApiError::DatabaseError(format!("Error fetching fermentations: {}", e)))?;
+
+    let events: Vec<CalendarEvent> = fermentations
+        .into_iter()
+        .filter_map(|fermentation| {
+            let start = fermentation.target_end_date?;
+            Some(CalendarEvent {
+                uid: format!("fermentation-{}@raugupatis-log", fermentation.id),
+                start,
+                summary: format!("Check {}", fermentation.name),
+                description: Some(match fermentation.profile_name {
+                    Some(profile_name) => {
+                        format!("Batch #{} ({})", fermentation.batch_number, profile_name)
+                    }
+                    None => format!("Batch #{}", fermentation.batch_number),
+                }),
+            })
+        })
+        .collect();
+
+    Ok((
+        [(header::CONTENT_TYPE, "text/calendar; charset=utf-8")],
+        render_calendar("Raugupatis Log", &events, Utc::now()),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_render_calendar() {
+        let now = Utc.with_ymd_and_hms(2024, 1, 10, 8, 0, 0).unwrap();
+        let events = vec![CalendarEvent {
+            uid: "fermentation-7@raugupatis-log".to_string(),
+            start: Utc.with_ymd_and_hms(2024, 1, 22, 10, 30, 0).unwrap(),
+            summary: "Check Kimchi, spicy; batch\\2".to_string(),
+            description: Some("Line one\nLine two".to_string()),
+        }];
+
+        let ics = render_calendar("Raugupatis Log", &events, now);
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+        assert!(ics.ends_with("END:VEVENT\r\nEND:VCALENDAR\r\n"));
+        assert!(ics.contains("\r\nUID:fermentation-7@raugupatis-log\r\n"));
+        assert!(ics.contains("\r\nDTSTAMP:20240110T080000Z\r\n"));
+        assert!(ics.contains("\r\nDTSTART:20240122T103000Z\r\n"));
+        assert!(ics.contains("\r\nSUMMARY:Check Kimchi\\, spicy\\; batch\\\\2\r\n"));
+        assert!(ics.contains("\r\nDESCRIPTION:Line one\\nLine two\r\n"));
+    }
+
+    #[test]
+    fn test_long_lines_are_folded() {
+        let mut out = String::new();
+        let line = format!("SUMMARY:{}", "ä".repeat(60));
+        write_line(&mut out, &line);
+
+        let lines: Vec<&str> = out.trim_end_matches("\r\n").split("\r\n").collect();
+        assert!(lines.len() > 1);
+        assert!(lines.iter().all(|l| l.len() <= MAX_LINE_OCTETS));
+        assert!(lines[1..].iter().all(|l| l.starts_with(' ')));
+
+        // Unfolding gives back the original line
+        let unfolded: String = lines
+            .iter()
+            .enumerate()
+            .map(|(i, l)| if i == 0 { *l } else { &l[1..] })
+            .collect();
+        assert_eq!(unfolded, line);
+    }
+}
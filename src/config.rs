@@ -9,6 +9,94 @@ pub struct AppConfig {
     pub environment: String,
     pub session_secret: String,
     pub uploads_dir: String,
+    /// Session lifetime after the last request, in hours
+    #[serde(default = "default_session_ttl_hours")]
+    pub session_ttl_hours: i64,
+    /// Session lifetime when logging in with "remember me", in days
+    #[serde(default = "default_remember_me_days")]
+    pub remember_me_days: i64,
+    /// Whether users must verify their email address before creating fermentations
+    #[serde(default)]
+    pub require_email_verification: bool,
+    /// Maximum size of a request body in bytes; photo uploads have their own, higher limit
+    #[serde(default = "default_max_request_body_bytes")]
+    pub max_request_body_bytes: usize,
+    /// Origins allowed to make credentialed cross-origin requests outside development and test
+    #[serde(default)]
+    pub allowed_origins: Vec<String>,
+    /// SameSite policy of the session cookie, either "lax" or "strict"
+    #[serde(default = "default_session_same_site")]
+    pub session_same_site: String,
+    /// Minimum password length for registration and password changes
+    #[serde(default = "default_password_min_length")]
+    pub password_min_length: usize,
+    /// Whether passwords must contain at least one digit
+    #[serde(default)]
+    pub password_require_digit: bool,
+    /// Whether passwords must contain at least one character that is not a letter or digit
+    #[serde(default)]
+    pub password_require_non_alphanumeric: bool,
+    /// Whether a newly created database starts with the built-in fermentation profiles
+    #[serde(default = "default_seed_default_profiles")]
+    pub seed_default_profiles: bool,
+    /// Lowest temperature reading accepted, in Fahrenheit; Celsius readings are converted first
+    #[serde(default = "default_min_temperature_fahrenheit")]
+    pub min_temperature_fahrenheit: f64,
+    /// Highest temperature reading accepted, in Fahrenheit
+    #[serde(default = "default_max_temperature_fahrenheit")]
+    pub max_temperature_fahrenheit: f64,
+    /// Whether admins may give profiles a type outside the built-in set (vegetable, beverage, ...)
+    #[serde(default)]
+    pub allow_custom_types: bool,
+    /// Total size of the photos a single user may store, in bytes
+    #[serde(default = "default_max_photo_bytes_per_user")]
+    pub max_photo_bytes_per_user: u64,
+    /// Whether new accounts can be registered; turn off once a private instance has its users
+    #[serde(default = "default_registration_open")]
+    pub registration_open: bool,
+    /// Whether registering needs a single-use invite code minted by an admin
+    #[serde(default)]
+    pub require_invite: bool,
+}
+
+fn default_session_ttl_hours() -> i64 {
+    24
+}
+
+fn default_remember_me_days() -> i64 {
+    5
+}
+
+fn default_max_request_body_bytes() -> usize {
+    1024 * 1024
+}
+
+fn default_session_same_site() -> String {
+    "lax".to_string()
+}
+
+fn default_password_min_length() -> usize {
+    8
+}
+
+fn default_seed_default_profiles() -> bool {
+    true
+}
+
+fn default_min_temperature_fahrenheit() -> f64 {
+    0.0
+}
+
+fn default_max_temperature_fahrenheit() -> f64 {
+    150.0
+}
+
+fn default_max_photo_bytes_per_user() -> u64 {
+    500 * 1024 * 1024
+}
+
+fn default_registration_open() -> bool {
+    true
 }
 
 impl AppConfig {
@@ -22,13 +110,38 @@ impl AppConfig {
             .add_source(File::with_name(&format!("config/{}", env)).required(false))
             // Add local configuration (for development overrides)
             .add_source(File::with_name("config/local").required(false))
-            // Add environment variables with prefix "RAUGUPATIS_"
-            .add_source(Environment::with_prefix("RAUGUPATIS"))
+            // Add environment variables with prefix "RAUGUPATIS_"; allowed origins are comma-separated
+            .add_source(
+                Environment::with_prefix("RAUGUPATIS")
+                    .try_parsing(true)
+                    .list_separator(",")
+                    .with_list_parse_key("allowed_origins"),
+            )
             .build()
             .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
 
         Ok(s.try_deserialize()?)
     }
+
+    /// Create `uploads_dir` if it is missing and check that files can be written to it, so a
+    /// misconfigured host fails at startup rather than on the first photo upload
+    pub fn prepare_uploads_dir(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let dir = std::path::Path::new(&self.uploads_dir);
+        std::fs::create_dir_all(dir)
+            .map_err(|e| format!("Cannot create uploads_dir '{}': {}", self.uploads_dir, e))?;
+
+        let probe = dir.join(".write-check");
+        std::fs::write(&probe, b"")
+            .and_then(|_| std::fs::remove_file(&probe))
+            .map_err(|e| format!("uploads_dir '{}' is not writable: {}", self.uploads_dir, e))?;
+
+        Ok(())
+    }
+
+    /// Development and test run locally over plain HTTP
+    pub fn is_local(&self) -> bool {
+        matches!(self.environment.as_str(), "development" | "test")
+    }
 }
 
 impl Default for AppConfig {
@@ -39,6 +152,22 @@ impl Default for AppConfig {
             environment: "development".to_string(),
             session_secret: "your-secret-key-change-in-production".to_string(),
             uploads_dir: "data/uploads".to_string(),
+            session_ttl_hours: default_session_ttl_hours(),
+            remember_me_days: default_remember_me_days(),
+            require_email_verification: false,
+            max_request_body_bytes: default_max_request_body_bytes(),
+            allowed_origins: Vec::new(),
+            session_same_site: default_session_same_site(),
+            password_min_length: default_password_min_length(),
+            password_require_digit: false,
+            password_require_non_alphanumeric: false,
+            seed_default_profiles: default_seed_default_profiles(),
+            min_temperature_fahrenheit: default_min_temperature_fahrenheit(),
+            max_temperature_fahrenheit: default_max_temperature_fahrenheit(),
+            allow_custom_types: false,
+            max_photo_bytes_per_user: default_max_photo_bytes_per_user(),
+            registration_open: default_registration_open(),
+            require_invite: false,
         }
     }
 }
@@ -1,13 +1,180 @@
-use rusqlite::Connection;
+use chrono::{DateTime, Utc};
+use r2d2::{Pool, PooledConnection};
+use r2d2_sqlite::SqliteConnectionManager;
 use rusqlite_migration::{Migrations, M};
-use std::sync::Mutex;
+use std::time::Duration;
 use tokio::task;
 
+/// How long a statement waits for a lock held by another connection (such as the session
+/// store's) before failing with "database is locked"
+pub const BUSY_TIMEOUT: Duration = Duration::from_millis(5000);
+
+/// Number of SQLite connections kept open for request handlers
+pub const POOL_SIZE: u32 = 8;
+
+pub type PooledSqliteConnection = PooledConnection<SqliteConnectionManager>;
+
+/// All migrations in the order they apply; a migration's version is its position in this
+/// list (starting at 1), which must match the number its file name starts with
+const MIGRATIONS: &[(&str, &str)] = &[
+    (
+        "001_initial_schema",
+        include_str!("../migrations/001_initial_schema.sql"),
+    ),
+    (
+        "002_add_sessions_table",
+        include_str!("../migrations/002_add_sessions_table.sql"),
+    ),
+    (
+        "003_add_user_names",
+        include_str!("../migrations/003_add_user_names.sql"),
+    ),
+    (
+        "004_add_user_locked_field",
+        include_str!("../migrations/004_add_user_locked_field.sql"),
+    ),
+    (
+        "005_add_profile_active_field",
+        include_str!("../migrations/005_add_profile_active_field.sql"),
+    ),
+    (
+        "006_add_preferred_temp_unit",
+        include_str!("../migrations/006_add_preferred_temp_unit.sql"),
+    ),
+    (
+        "007_add_taste_profiles_and_lessons",
+        include_str!("../migrations/007_add_taste_profiles_and_lessons.sql"),
+    ),
+    (
+        "008_add_password_reset_tokens",
+        include_str!("../migrations/008_add_password_reset_tokens.sql"),
+    ),
+    (
+        "009_add_login_throttling",
+        include_str!("../migrations/009_add_login_throttling.sql"),
+    ),
+    (
+        "010_add_tags",
+        include_str!("../migrations/010_add_tags.sql"),
+    ),
+    (
+        "011_add_archived_status",
+        include_str!("../migrations/011_add_archived_status.sql"),
+    ),
+    (
+        "012_add_photo_thumbnails",
+        include_str!("../migrations/012_add_photo_thumbnails.sql"),
+    ),
+    (
+        "013_add_ph_logs",
+        include_str!("../migrations/013_add_ph_logs.sql"),
+    ),
+    (
+        "014_add_gravity_logs",
+        include_str!("../migrations/014_add_gravity_logs.sql"),
+    ),
+    (
+        "015_add_journal_entries",
+        include_str!("../migrations/015_add_journal_entries.sql"),
+    ),
+    (
+        "016_add_email_verification",
+        include_str!("../migrations/016_add_email_verification.sql"),
+    ),
+    (
+        "017_add_idempotency_keys",
+        include_str!("../migrations/017_add_idempotency_keys.sql"),
+    ),
+    (
+        "018_add_fermentation_soft_delete",
+        include_str!("../migrations/018_add_fermentation_soft_delete.sql"),
+    ),
+    (
+        "019_add_user_sessions",
+        include_str!("../migrations/019_add_user_sessions.sql"),
+    ),
+    (
+        "020_add_user_last_login",
+        include_str!("../migrations/020_add_user_last_login.sql"),
+    ),
+    (
+        "021_add_measurement_logs",
+        include_str!("../migrations/021_add_measurement_logs.sql"),
+    ),
+    (
+        "022_add_fermentation_batch_numbers",
+        include_str!("../migrations/022_add_fermentation_batch_numbers.sql"),
+    ),
+    (
+        "023_add_featured_photos",
+        include_str!("../migrations/023_add_featured_photos.sql"),
+    ),
+    (
+        "024_add_profile_created_by",
+        include_str!("../migrations/024_add_profile_created_by.sql"),
+    ),
+    (
+        "025_add_user_default_profile",
+        include_str!("../migrations/025_add_user_default_profile.sql"),
+    ),
+    (
+        "026_add_schema_migrations",
+        include_str!("../migrations/026_add_schema_migrations.sql"),
+    ),
+    (
+        "027_add_profile_default_ingredients",
+        include_str!("../migrations/027_add_profile_default_ingredients.sql"),
+    ),
+    (
+        "028_add_fermentation_favorites",
+        include_str!("../migrations/028_add_fermentation_favorites.sql"),
+    ),
+    (
+        "029_add_temperature_webhook_tokens",
+        include_str!("../migrations/029_add_temperature_webhook_tokens.sql"),
+    ),
+    (
+        "030_add_fermentation_shares",
+        include_str!("../migrations/030_add_fermentation_shares.sql"),
+    ),
+    (
+        "031_add_calendar_feed_tokens",
+        include_str!("../migrations/031_add_calendar_feed_tokens.sql"),
+    ),
+    (
+        "032_normalize_profile_types",
+        include_str!("../migrations/032_normalize_profile_types.sql"),
+    ),
+    (
+        "033_add_fermentation_version",
+        include_str!("../migrations/033_add_fermentation_version.sql"),
+    ),
+    (
+        "034_add_photo_file_size",
+        include_str!("../migrations/034_add_photo_file_size.sql"),
+    ),
+    (
+        "035_add_invite_codes",
+        include_str!("../migrations/035_add_invite_codes.sql"),
+    ),
+];
+
 pub struct Database {
-    connection: Mutex<Connection>,
+    pool: ConnectionPool,
     db_path: String,
 }
 
+/// Pool of SQLite connections shared by the repositories
+pub struct ConnectionPool(Pool<SqliteConnectionManager>);
+
+impl ConnectionPool {
+    /// Check a connection out of the pool, waiting for one to be returned if all are in use.
+    /// The connection goes back to the pool when the guard is dropped, like a mutex guard.
+    pub fn lock(&self) -> Result<PooledSqliteConnection, r2d2::Error> {
+        self.0.get()
+    }
+}
+
 impl Database {
     pub async fn new(database_url: &str) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
         let db_path = database_url
@@ -20,60 +187,214 @@ impl Database {
             tokio::fs::create_dir_all(parent).await?;
         }
 
-        let db_path_clone = db_path.clone();
-        let connection = task::spawn_blocking(
-            move || -> Result<Connection, Box<dyn std::error::Error + Send + Sync>> {
-                let conn = Connection::open(&db_path_clone)?;
-                Ok(conn)
-            },
-        )
-        .await??;
+        let manager = SqliteConnectionManager::file(&db_path).with_init(|conn| {
+            // Set the timeout first so connections opening together wait on each other
+            // while switching the journal mode
+            conn.busy_timeout(BUSY_TIMEOUT)?;
+            // WAL lets readers proceed while a write is in progress
+            conn.pragma_update_and_check(None, "journal_mode", "WAL", |row| {
+                row.get::<_, String>(0)
+            })?;
+            // Deleting a fermentation or user relies on ON DELETE CASCADE for dependent rows
+            conn.pragma_update(None, "foreign_keys", true)?;
+            Ok(())
+        });
+
+        // Building the pool opens its connections, which is blocking I/O
+        let pool = task::spawn_blocking(move || Pool::builder().max_size(POOL_SIZE).build(manager))
+            .await??;
 
         Ok(Database {
-            connection: Mutex::new(connection),
+            pool: ConnectionPool(pool),
             db_path,
         })
     }
 
-    pub async fn migrate(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        // Read migration files
-        let migration_sql_001 = include_str!("../migrations/001_initial_schema.sql");
-        let migration_sql_002 = include_str!("../migrations/002_add_sessions_table.sql");
-        let migration_sql_003 = include_str!("../migrations/003_add_user_names.sql");
-        let migration_sql_004 = include_str!("../migrations/004_add_user_locked_field.sql");
-        let migration_sql_005 = include_str!("../migrations/005_add_profile_active_field.sql");
-        let migration_sql_006 = include_str!("../migrations/006_add_preferred_temp_unit.sql");
-        let migration_sql_007 = include_str!("../migrations/007_add_taste_profiles_and_lessons.sql");
-
-        let migrations = Migrations::new(vec![
-            M::up(migration_sql_001),
-            M::up(migration_sql_002),
-            M::up(migration_sql_003),
-            M::up(migration_sql_004),
-            M::up(migration_sql_005),
-            M::up(migration_sql_006),
-            M::up(migration_sql_007),
-        ]);
-
-        // Apply migrations - need to move the migrations into the closure
-        let mut conn = self.connection.lock().unwrap();
-        migrations.to_latest(&mut conn)?;
+    /// Bring the schema up to date. Safe to run on every startup: migrations that are already
+    /// applied are skipped, and each one applied is recorded in `schema_migrations`. A database
+    /// created by this run keeps the built-in profiles only with `seed_default_profiles`.
+    pub async fn migrate(
+        &self,
+        seed_default_profiles: bool,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        check_migration_order()?;
+
+        let migrations = Migrations::new(MIGRATIONS.iter().map(|(_, sql)| M::up(sql)).collect());
+
+        let mut conn = self.pool.lock()?;
+        check_recorded_migrations(&conn)?;
+        let previous: i64 = conn.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+        // A migration that rebuilds a table (011 recreates fermentations) drops the old one,
+        // which with foreign keys enforced deletes every child row through ON DELETE CASCADE.
+        // The pragma is ignored inside the migration transaction, so it's switched off around
+        // the whole run and the references are checked once the rebuilt tables are in place.
+        conn.execute_batch("PRAGMA foreign_keys = OFF")?;
+        let migrated = migrations.to_latest(&mut conn);
+        conn.execute_batch("PRAGMA foreign_keys = ON")?;
+        migrated?;
+
+        // Migrations applied before the tracking table existed are recorded on the first run
+        // after upgrading, so their applied_at is when tracking started
+        let applied: i64 = conn.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+        if applied > previous {
+            check_foreign_keys(&conn)?;
+        }
+        let tx = conn.transaction_with_behavior(rusqlite::TransactionBehavior::Immediate)?;
+        for (version, (name, _)) in (1..=applied).zip(MIGRATIONS.iter()) {
+            tx.execute(
+                "INSERT OR IGNORE INTO schema_migrations (version, name) VALUES (?1, ?2)",
+                rusqlite::params![version, name],
+            )?;
+        }
+        // The initial schema inserts the built-in profiles. Nothing else can exist yet in a
+        // brand-new database, so opting out removes every profile; existing ones are left alone.
+        if previous == 0 && !seed_default_profiles {
+            tx.execute("DELETE FROM fermentation_profiles", [])?;
+        }
+        tx.commit()?;
 
         Ok(())
     }
 
     pub async fn health_check(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        let conn = self.connection.lock().unwrap();
+        let conn = self.pool.lock()?;
         // Simple health check - verify we can query the database
         let _result: i32 = conn.query_row("SELECT 1", [], |row| row.get(0))?;
         Ok(())
     }
 
-    pub fn get_connection(&self) -> &Mutex<Connection> {
-        &self.connection
+    /// Returns the version of the latest applied migration, or 0 for an empty database
+    pub async fn migration_version(&self) -> Result<i64, Box<dyn std::error::Error + Send + Sync>> {
+        let conn = self.pool.lock()?;
+        let version: i64 = conn.query_row(
+            "SELECT COALESCE(MAX(version), 0) FROM schema_migrations",
+            [],
+            |row| row.get(0),
+        )?;
+        Ok(version)
+    }
+
+    /// Every applied migration, oldest first
+    pub async fn applied_migrations(
+        &self,
+    ) -> Result<Vec<AppliedMigration>, Box<dyn std::error::Error + Send + Sync>> {
+        let conn = self.pool.lock()?;
+        let mut stmt = conn
+            .prepare("SELECT version, name, applied_at FROM schema_migrations ORDER BY version")?;
+        let migrations = stmt
+            .query_map([], |row| {
+                Ok(AppliedMigration {
+                    version: row.get(0)?,
+                    name: row.get(1)?,
+                    applied_at: parse_datetime(row.get::<_, String>(2)?),
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(migrations)
+    }
+
+    /// Write any pending write-ahead log pages back into the database file, so a stopped
+    /// server leaves a self-contained database behind
+    pub async fn checkpoint(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let conn = self.pool.lock()?;
+        conn.query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |_| Ok(()))?;
+        Ok(())
+    }
+
+    pub fn get_connection(&self) -> &ConnectionPool {
+        &self.pool
     }
 
     pub fn get_db_path(&self) -> &str {
         &self.db_path
     }
 }
+
+#[derive(Debug, Clone)]
+pub struct AppliedMigration {
+    pub version: i64,
+    pub name: String,
+    pub applied_at: DateTime<Utc>,
+}
+
+/// Catch a migration added out of sequence before anything touches the database
+fn check_migration_order() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    for (index, (name, _)) in MIGRATIONS.iter().enumerate() {
+        if !name.starts_with(&format!("{:03}_", index + 1)) {
+            return Err(format!(
+                "Migration {} is listed as version {}; migrations must be listed in order",
+                name,
+                index + 1
+            )
+            .into());
+        }
+    }
+    Ok(())
+}
+
+/// Refuse to migrate a database whose recorded history doesn't match this build's
+/// migrations, e.g. after a migration was renamed, reordered or came from another branch
+fn check_recorded_migrations(
+    conn: &rusqlite::Connection,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let tracking_table_exists: bool = conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'schema_migrations')",
+        [],
+        |row| row.get(0),
+    )?;
+    if !tracking_table_exists {
+        return Ok(());
+    }
+
+    let mut stmt = conn.prepare("SELECT version, name FROM schema_migrations ORDER BY version")?;
+    let recorded = stmt
+        .query_map([], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    for (version, name) in recorded {
+        let known = usize::try_from(version - 1)
+            .ok()
+            .and_then(|index| MIGRATIONS.get(index))
+            .map(|(known_name, _)| *known_name);
+        if known != Some(name.as_str()) {
+            return Err(format!(
+                "Database records migration {} as {}, which this build doesn't know as version {}",
+                version, name, version
+            )
+            .into());
+        }
+    }
+    Ok(())
+}
+
+/// Fail if any row references a parent that doesn't exist, e.g. after a table rebuild that
+/// changed ids while foreign keys were off
+fn check_foreign_keys(
+    conn: &rusqlite::Connection,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let mut stmt = conn.prepare("PRAGMA foreign_key_check")?;
+    let violations = stmt
+        .query_map([], |row| row.get::<_, String>(0))?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if let Some(table) = violations.first() {
+        return Err(format!(
+            "Migrations left {} rows with broken foreign keys, starting with table {}",
+            violations.len(),
+            table
+        )
+        .into());
+    }
+    Ok(())
+}
+
+fn parse_datetime(s: String) -> DateTime<Utc> {
+    // SQLite stores timestamps as strings, parse them
+    // Format: YYYY-MM-DD HH:MM:SS
+    chrono::NaiveDateTime::parse_from_str(&s, "%Y-%m-%d %H:%M:%S")
+        .ok()
+        .map(|dt| dt.and_utc())
+        .unwrap_or_else(Utc::now)
+}
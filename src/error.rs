@@ -0,0 +1,85 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Serialize;
+
+/// Error returned by the JSON API handlers.
+/// Serialized as `{ "error": "...", "field": "..." }`, where `field` names the offending
+/// request field for validation errors and is omitted otherwise.
+#[derive(Debug)]
+pub enum ApiError {
+    UserAlreadyExists,
+    ValidationError(String),
+    InvalidField {
+        field: &'static str,
+        message: String,
+    },
+    Unauthorized,
+    Forbidden(String),
+    NotFound(String),
+    Conflict(String),
+    DatabaseError(String),
+    InternalError(String),
+}
+
+#[derive(Serialize)]
+struct ErrorBody {
+    error: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    field: Option<&'static str>,
+}
+
+impl ApiError {
+    /// Validation error tied to a specific request field
+    pub fn invalid_field(field: &'static str, message: impl Into<String>) -> Self {
+        ApiError::InvalidField {
+            field,
+            message: message.into(),
+        }
+    }
+
+    pub fn not_found(message: impl Into<String>) -> Self {
+        ApiError::NotFound(message.into())
+    }
+}
+
+impl From<Box<dyn std::error::Error + Send + Sync>> for ApiError {
+    fn from(e: Box<dyn std::error::Error + Send + Sync>) -> Self {
+        ApiError::DatabaseError(e.to_string())
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let (status, error_message, field) = match self {
+            ApiError::UserAlreadyExists => (
+                StatusCode::CONFLICT,
+                "User with this email already exists".to_string(),
+                None,
+            ),
+            ApiError::ValidationError(msg) => (StatusCode::BAD_REQUEST, msg, None),
+            ApiError::InvalidField { field, message } => {
+                (StatusCode::BAD_REQUEST, message, Some(field))
+            }
+            ApiError::Unauthorized => (StatusCode::UNAUTHORIZED, "Unauthorized".to_string(), None),
+            ApiError::Forbidden(msg) => (StatusCode::FORBIDDEN, msg, None),
+            ApiError::NotFound(msg) => (StatusCode::NOT_FOUND, msg, None),
+            ApiError::Conflict(msg) => (StatusCode::CONFLICT, msg, None),
+            ApiError::DatabaseError(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg, None),
+            ApiError::InternalError(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg, None),
+        };
+
+        if status.is_server_error() {
+            tracing::error!("{}", error_message);
+        }
+
+        let body = Json(ErrorBody {
+            error: error_message,
+            field,
+        });
+
+        (status, body).into_response()
+    }
+}
@@ -1,31 +1,109 @@
 use axum::{
     extract::{Path, Query, State},
-    http::StatusCode,
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
     Json,
 };
 use tower_sessions::Session;
 
+use crate::error::ApiError;
 use crate::fermentation::models::{
-    CreateFermentationRequest, CreateTasteProfileRequest, CreateTemperatureLogRequest, Fermentation,
-    FermentationListQuery, FermentationResponse, FinishFermentationRequest, TasteProfile,
-    TemperatureLog, UpdateFermentationRequest,
+    BulkStatusRequest, BulkStatusResponse, CreateFermentationRequest, CreateGravityLogRequest,
+    CreateJournalEntryRequest, CreateMeasurementLogRequest, CreatePhLogRequest,
+    CreateTasteProfileRequest, CreateTemperatureLogRequest, DashboardStats,
+    DeleteTemperatureLogsQuery, DeletedTemperatureLogs, DueFermentation, DueSoonQuery,
+    Fermentation, FermentationExport, FermentationListQuery, FermentationResponse,
+    FermentationShare, FermentationStatus, FinishFermentationRequest, GravityLog, GravityStats,
+    IngredientQuery, JournalEntry, MeasurementKind, MeasurementLog, MeasurementLogQuery, PhLog,
+    ProfileSuccess, RatingDistribution, RecentFermentationsQuery, SearchQuery, SearchResults, Tag,
+    TagRequest, TasteProfile, TasteProfileListQuery, TasteProfileWithFermentation, TemperatureGaps,
+    TemperatureImportRowError, TemperatureLog, TemperatureLogResponse, TemperatureStats,
+    TimelineEvent, UpdateFermentationRequest, UpdateTemperatureLogRequest, WebhookTokenResponse,
+    DEFAULT_DUE_SOON_DAYS, DEFAULT_RECENT_LIMIT, MAX_BULK_STATUS_IDS, MAX_DUE_SOON_DAYS,
+    MAX_IDEMPOTENCY_KEY_LENGTH, MAX_JOURNAL_ENTRY_LENGTH, MAX_RECENT_LIMIT,
+    MAX_START_DATE_LEAD_HOURS, MAX_TEXT_FIELD_LENGTH,
 };
 use crate::fermentation::repository::FermentationRepository;
 use crate::users::UserSession;
 use crate::AppState;
 
+/// Look up the user's preferred temperature unit, falling back to Fahrenheit
+async fn preferred_temp_unit(state: &AppState, user_id: i64) -> crate::users::TemperatureUnit {
+    let user_repo = crate::users::UserRepository::new(state.db.clone());
+    user_repo
+        .find_by_id(user_id)
+        .await
+        .map(|u| u.preferred_temp_unit)
+        .unwrap_or_else(|e| {
+            tracing::warn!("Could not fetch user temperature preference: {}", e);
+            crate::users::TemperatureUnit::Fahrenheit
+        })
+}
+
+/// Trim surrounding whitespace from an optional free-text field and enforce its length limit
+fn normalize_text_field(value: &mut Option<String>, field: &'static str) -> Result<(), ApiError> {
+    if let Some(text) = value {
+        *text = text.trim().to_string();
+        if text.chars().count() > MAX_TEXT_FIELD_LENGTH {
+            return Err(ApiError::invalid_field(
+                field,
+                format!("Must be at most {} characters", MAX_TEXT_FIELD_LENGTH),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Reject the request if email verification is required and the user hasn't verified yet
+async fn ensure_email_verified(state: &AppState, user_id: i64) -> Result<(), ApiError> {
+    if !state.config.require_email_verification {
+        return Ok(());
+    }
+
+    let user_repo = crate::users::UserRepository::new(state.db.clone());
+    let user = user_repo.find_by_id(user_id).await.map_err(|e| {
+        ApiError::DatabaseError(format!(
+            "Failed to fetch user for verification check: {}",
+            e
+        ))
+    })?;
+
+    if user.email_verified {
+        Ok(())
+    } else {
+        Err(ApiError::Forbidden(
+            "Please verify your email address before creating fermentations".to_string(),
+        ))
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/fermentations",
+    tag = "fermentations",
+    params(FermentationListQuery),
+    responses(
+        (status = 200, description = "The user's fermentations", body = [Fermentation]),
+        (status = 400, description = "Invalid input"),
+        (status = 401, description = "Not logged in")
+    )
+)]
 pub async fn list_fermentations(
     State(state): State<AppState>,
     session: Session,
     Query(query): Query<FermentationListQuery>,
-) -> Result<Json<Vec<Fermentation>>, StatusCode> {
+) -> Result<Json<Vec<Fermentation>>, ApiError> {
     // Get user from session
-    let user_session: Option<UserSession> = session
+    let user: UserSession = session
         .get("user")
         .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        .map_err(|e| ApiError::InternalError(format!("Failed to get session: {}", e)))?
+        .ok_or(ApiError::Unauthorized)?;
 
-    let user = user_session.ok_or(StatusCode::UNAUTHORIZED)?;
+    // Validate the start date range
+    query
+        .start_date_range()
+        .map_err(ApiError::ValidationError)?;
 
     let repo = FermentationRepository::new(state.db.clone());
     let photo_repo = crate::photos::PhotoRepository::new(state.db.clone());
@@ -48,202 +126,2380 @@ pub async fn list_fermentations(
             }
             Ok(Json(fermentations))
         }
-        Err(e) => {
-            tracing::error!("Error fetching fermentations: {}", e);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
-        }
+        Err(e) => Err(ApiError::DatabaseError(format!(
+            "Error fetching fermentations: {}",
+            e
+        ))),
+    }
+}
+
+/// Active fermentations reaching their target end date within `days` days, overdue ones included
+#[utoipa::path(
+    get,
+    path = "/api/fermentations/due-soon",
+    tag = "fermentations",
+    params(DueSoonQuery),
+    responses(
+        (status = 200, description = "Active fermentations due within the window, overdue ones included", body = [DueFermentation]),
+        (status = 400, description = "Invalid input"),
+        (status = 401, description = "Not logged in")
+    )
+)]
+pub async fn list_due_soon(
+    session: Session,
+    State(state): State<AppState>,
+    Query(query): Query<DueSoonQuery>,
+) -> Result<Json<Vec<DueFermentation>>, ApiError> {
+    // Get user from session
+    let user: UserSession = session
+        .get("user")
+        .await
+        .map_err(|e| ApiError::InternalError(format!("Failed to get session: {}", e)))?
+        .ok_or(ApiError::Unauthorized)?;
+
+    let days = query.days.unwrap_or(DEFAULT_DUE_SOON_DAYS);
+    if !(0..=MAX_DUE_SOON_DAYS).contains(&days) {
+        return Err(ApiError::invalid_field(
+            "days",
+            format!("Days must be between 0 and {}", MAX_DUE_SOON_DAYS),
+        ));
+    }
+
+    let repo = FermentationRepository::new(state.db.clone());
+
+    let fermentations = repo
+        .find_due_before(
+            user.user_id,
+            chrono::Utc::now() + chrono::Duration::days(days),
+        )
+        .await
+        .map_err(|e| ApiError::DatabaseError(format!("Error fetching due fermentations: {}", e)))?;
+
+    Ok(Json(
+        fermentations
+            .into_iter()
+            .map(DueFermentation::from)
+            .collect(),
+    ))
+}
+
+/// The user's most recently changed fermentations in any status, for a "jump back in" list
+#[utoipa::path(
+    get,
+    path = "/api/fermentations/recent",
+    tag = "fermentations",
+    params(RecentFermentationsQuery),
+    responses(
+        (status = 200, description = "Most recently changed fermentations first", body = [Fermentation]),
+        (status = 400, description = "Invalid input"),
+        (status = 401, description = "Not logged in")
+    )
+)]
+pub async fn list_recent(
+    session: Session,
+    State(state): State<AppState>,
+    Query(query): Query<RecentFermentationsQuery>,
+) -> Result<Json<Vec<Fermentation>>, ApiError> {
+    // Get user from session
+    let user: UserSession = session
+        .get("user")
+        .await
+        .map_err(|e| ApiError::InternalError(format!("Failed to get session: {}", e)))?
+        .ok_or(ApiError::Unauthorized)?;
+
+    let limit = query.limit.unwrap_or(DEFAULT_RECENT_LIMIT);
+    if !(1..=MAX_RECENT_LIMIT).contains(&limit) {
+        return Err(ApiError::invalid_field(
+            "limit",
+            format!("Limit must be between 1 and {}", MAX_RECENT_LIMIT),
+        ));
+    }
+
+    let repo = FermentationRepository::new(state.db.clone());
+
+    let fermentations = repo
+        .find_recently_updated(user.user_id, limit)
+        .await
+        .map_err(|e| {
+            ApiError::DatabaseError(format!("Error fetching recent fermentations: {}", e))
+        })?;
+
+    Ok(Json(fermentations))
+}
+
+/// The user's fermentations that list an ingredient, e.g. every batch made with dill
+#[utoipa::path(
+    get,
+    path = "/api/fermentations/by-ingredient",
+    tag = "fermentations",
+    params(IngredientQuery),
+    responses(
+        (status = 200, description = "Fermentations listing the ingredient", body = [Fermentation]),
+        (status = 400, description = "Missing ingredient name"),
+        (status = 401, description = "Not logged in")
+    )
+)]
+pub async fn list_by_ingredient(
+    session: Session,
+    State(state): State<AppState>,
+    Query(query): Query<IngredientQuery>,
+) -> Result<Json<Vec<Fermentation>>, ApiError> {
+    // Get user from session
+    let user: UserSession = session
+        .get("user")
+        .await
+        .map_err(|e| ApiError::InternalError(format!("Failed to get session: {}", e)))?
+        .ok_or(ApiError::Unauthorized)?;
+
+    let name = query.name.as_deref().map(str::trim).unwrap_or_default();
+    if name.is_empty() {
+        return Err(ApiError::invalid_field(
+            "name",
+            "Ingredient name is required",
+        ));
+    }
+
+    let repo = FermentationRepository::new(state.db.clone());
+
+    let fermentations = repo
+        .find_by_ingredient(user.user_id, name)
+        .await
+        .map_err(|e| ApiError::DatabaseError(format!("Error searching ingredients: {}", e)))?;
+
+    Ok(Json(fermentations))
+}
+
+/// Change the status of several fermentations at once, e.g. to archive finished batches
+#[utoipa::path(
+    post,
+    path = "/api/fermentations/bulk-status",
+    tag = "fermentations",
+    request_body = BulkStatusRequest,
+    responses(
+        (status = 200, description = "How many fermentations changed status", body = BulkStatusResponse),
+        (status = 400, description = "Invalid input"),
+        (status = 401, description = "Not logged in")
+    )
+)]
+pub async fn bulk_update_status(
+    session: Session,
+    State(state): State<AppState>,
+    Json(mut request): Json<BulkStatusRequest>,
+) -> Result<Json<BulkStatusResponse>, ApiError> {
+    // Get user from session
+    let user: UserSession = session
+        .get("user")
+        .await
+        .map_err(|e| ApiError::InternalError(format!("Failed to get session: {}", e)))?
+        .ok_or(ApiError::Unauthorized)?;
+
+    let status = FermentationStatus::parse(&request.status).ok_or_else(|| {
+        ApiError::invalid_field(
+            "status",
+            "Status must be 'active', 'paused', 'completed', 'failed' or 'archived'",
+        )
+    })?;
+
+    request.ids.sort_unstable();
+    request.ids.dedup();
+
+    if request.ids.is_empty() {
+        return Err(ApiError::invalid_field(
+            "ids",
+            "At least one fermentation id is required",
+        ));
+    }
+
+    if request.ids.len() > MAX_BULK_STATUS_IDS {
+        return Err(ApiError::invalid_field(
+            "ids",
+            format!(
+                "At most {} fermentations can be updated at once",
+                MAX_BULK_STATUS_IDS
+            ),
+        ));
     }
+
+    let requested = request.ids.len();
+    let fermentation_repo = FermentationRepository::new(state.db.clone());
+
+    let updated = fermentation_repo
+        .bulk_update_status(request.ids, user.user_id, status)
+        .await
+        .map_err(|e| ApiError::DatabaseError(format!("Error updating statuses: {}", e)))?;
+
+    Ok(Json(BulkStatusResponse {
+        updated,
+        skipped: requested - updated,
+    }))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/fermentation",
+    tag = "fermentations",
+    params(
+        ("Idempotency-Key" = Option<String>, Header, description = "Retries with the same key replay the first response")
+    ),
+    request_body = CreateFermentationRequest,
+    responses(
+        (status = 201, description = "Fermentation created", body = FermentationResponse),
+        (status = 400, description = "Invalid input"),
+        (status = 401, description = "Not logged in")
+    )
+)]
 pub async fn create_fermentation(
     session: Session,
     State(state): State<AppState>,
-    Json(request): Json<CreateFermentationRequest>,
-) -> Result<(StatusCode, Json<FermentationResponse>), StatusCode> {
+    headers: HeaderMap,
+    Json(mut request): Json<CreateFermentationRequest>,
+) -> Result<(StatusCode, Json<FermentationResponse>), ApiError> {
     // Get user from session
-    let user_session: Option<UserSession> = session
+    let user: UserSession = session
         .get("user")
         .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        .map_err(|e| ApiError::InternalError(format!("Failed to get session: {}", e)))?
+        .ok_or(ApiError::Unauthorized)?;
+
+    ensure_email_verified(&state, user.user_id).await?;
+
+    let idempotency_key = match headers.get("Idempotency-Key") {
+        Some(value) => {
+            let key = value
+                .to_str()
+                .map_err(|_| {
+                    ApiError::ValidationError("Invalid Idempotency-Key header".to_string())
+                })?
+                .trim();
+            if key.is_empty() || key.len() > MAX_IDEMPOTENCY_KEY_LENGTH {
+                return Err(ApiError::ValidationError(format!(
+                    "Idempotency-Key must be between 1 and {} characters",
+                    MAX_IDEMPOTENCY_KEY_LENGTH
+                )));
+            }
+            Some(key.to_string())
+        }
+        None => None,
+    };
 
-    let user = user_session.ok_or(StatusCode::UNAUTHORIZED)?;
+    let fermentation_repo = FermentationRepository::new(state.db.clone());
+
+    // A retry carries the same body, so a replayed response reports the suggestion the same way
+    let target_end_date_suggested = request.target_end_date.is_none();
+
+    // A replayed key returns the fermentation created by the original request
+    if let Some(ref key) = idempotency_key {
+        let existing = fermentation_repo
+            .find_by_idempotency_key(user.user_id, key)
+            .await
+            .map_err(|e| {
+                ApiError::DatabaseError(format!("Error looking up idempotency key: {}", e))
+            })?;
+
+        if let Some(fermentation) = existing {
+            let profile = fermentation_repo
+                .get_profile_by_id(fermentation.profile_id)
+                .await?
+                .ok_or_else(|| {
+                    ApiError::InternalError("Fermentation profile not found".to_string())
+                })?;
+
+            let mut response =
+                FermentationResponse::from_fermentation_and_profile(fermentation, profile);
+            response.target_end_date_suggested = target_end_date_suggested;
+
+            return Ok((StatusCode::CREATED, Json(response)));
+        }
+    }
 
     // Validate request
     if request.name.trim().is_empty() {
-        return Err(StatusCode::BAD_REQUEST);
+        return Err(ApiError::invalid_field("name", "Name is required"));
     }
 
     if request.name.len() > 255 {
-        return Err(StatusCode::BAD_REQUEST);
+        return Err(ApiError::invalid_field(
+            "name",
+            "Name must be at most 255 characters",
+        ));
     }
 
+    normalize_text_field(&mut request.notes, "notes")?;
+
     // Validate date format
-    if chrono::DateTime::parse_from_rfc3339(&request.start_date).is_err() {
-        return Err(StatusCode::BAD_REQUEST);
+    let start_date = chrono::DateTime::parse_from_rfc3339(&request.start_date).map_err(|_| {
+        ApiError::invalid_field("start_date", "Start date must be an RFC 3339 date")
+    })?;
+
+    if start_date > chrono::Utc::now() + chrono::Duration::hours(MAX_START_DATE_LEAD_HOURS) {
+        return Err(ApiError::invalid_field(
+            "start_date",
+            "Start date cannot be more than a day in the future",
+        ));
     }
 
     if let Some(ref target_date) = request.target_end_date {
-        if chrono::DateTime::parse_from_rfc3339(target_date).is_err() {
-            return Err(StatusCode::BAD_REQUEST);
+        let target_end_date = chrono::DateTime::parse_from_rfc3339(target_date).map_err(|_| {
+            ApiError::invalid_field(
+                "target_end_date",
+                "Target end date must be an RFC 3339 date",
+            )
+        })?;
+
+        if target_end_date <= start_date {
+            return Err(ApiError::invalid_field(
+                "target_end_date",
+                "Target end date must be after the start date",
+            ));
         }
     }
 
+    // Verify profile exists
+    let profile = fermentation_repo
+        .get_profile_by_id(request.profile_id)
+        .await?
+        .ok_or_else(|| ApiError::not_found("Fermentation profile not found"))?;
+
+    // Suggest a target end date from the profile's longest duration when none was given
+    if target_end_date_suggested {
+        let suggested = start_date + chrono::Duration::days(profile.max_days as i64);
+        request.target_end_date = Some(suggested.to_rfc3339());
+    }
+
+    // Create the fermentation, claiming the idempotency key in the same transaction
+    let (fermentation, created) = fermentation_repo
+        .create_fermentation_with_idempotency_key(user.user_id, request, idempotency_key.as_deref())
+        .await?;
+
+    // A concurrent request with the same key got there first; return its fermentation
+    if !created {
+        let profile = fermentation_repo
+            .get_profile_by_id(fermentation.profile_id)
+            .await?
+            .ok_or_else(|| ApiError::InternalError("Fermentation profile not found".to_string()))?;
+
+        let mut response =
+            FermentationResponse::from_fermentation_and_profile(fermentation, profile);
+        response.target_end_date_suggested = target_end_date_suggested;
+
+        return Ok((StatusCode::CREATED, Json(response)));
+    }
+    state.metrics.record_fermentation_created();
+
+    let mut response = FermentationResponse::from_fermentation_and_profile(fermentation, profile);
+    response.target_end_date_suggested = target_end_date_suggested;
+
+    Ok((StatusCode::CREATED, Json(response)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/fermentation/{id}",
+    tag = "fermentations",
+    params(("id" = i64, Path, description = "Fermentation id")),
+    responses(
+        (status = 200, description = "The fermentation, with an ETag header", body = FermentationResponse),
+        (status = 304, description = "Unchanged since the If-None-Match ETag"),
+        (status = 401, description = "Not logged in"),
+        (status = 404, description = "Fermentation not found")
+    )
+)]
+pub async fn get_fermentation(
+    session: Session,
+    State(state): State<AppState>,
+    Path(id): Path<i64>,
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
+    // Get user from session
+    let user: UserSession = session
+        .get("user")
+        .await
+        .map_err(|e| ApiError::InternalError(format!("Failed to get session: {}", e)))?
+        .ok_or(ApiError::Unauthorized)?;
+
+    let fermentation_repo = FermentationRepository::new(state.db.clone());
+
+    let fermentation = fermentation_repo
+        .find_by_id(id, user.user_id)
+        .await?
+        .ok_or_else(|| ApiError::not_found("Fermentation not found"))?;
+
+    let profile = fermentation_repo
+        .get_profile_by_id(fermentation.profile_id)
+        .await?
+        .ok_or_else(|| ApiError::InternalError("Fermentation profile not found".to_string()))?;
+    let temp_unit = preferred_temp_unit(&state, user.user_id).await;
+
+    let etag = fermentation.etag(&profile, &temp_unit);
+
+    // Let polling clients skip the body when nothing has changed
+    let not_modified = headers
+        .get(header::IF_NONE_MATCH)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| {
+            value
+                .split(',')
+                .map(|tag| tag.trim().trim_start_matches("W/"))
+                .any(|tag| tag == "*" || tag == etag)
+        });
+
+    if not_modified {
+        return Ok((StatusCode::NOT_MODIFIED, [(header::ETAG, etag)]).into_response());
+    }
+
+    Ok((
+        [(header::ETAG, etag)],
+        Json(FermentationResponse::from_fermentation_and_profile(
+            fermentation,
+            profile,
+        )),
+    )
+        .into_response())
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/fermentation/{id}/clone",
+    tag = "fermentations",
+    params(("id" = i64, Path, description = "Fermentation id")),
+    responses(
+        (status = 201, description = "New active fermentation copied from this one", body = FermentationResponse),
+        (status = 401, description = "Not logged in"),
+        (status = 404, description = "Fermentation not found")
+    )
+)]
+pub async fn clone_fermentation(
+    session: Session,
+    State(state): State<AppState>,
+    Path(id): Path<i64>,
+) -> Result<(StatusCode, Json<FermentationResponse>), ApiError> {
+    // Get user from session
+    let user: UserSession = session
+        .get("user")
+        .await
+        .map_err(|e| ApiError::InternalError(format!("Failed to get session: {}", e)))?
+        .ok_or(ApiError::Unauthorized)?;
+
+    let fermentation_repo = FermentationRepository::new(state.db.clone());
+
+    let fermentation = fermentation_repo
+        .clone_fermentation(id, user.user_id)
+        .await?
+        .ok_or_else(|| ApiError::not_found("Fermentation not found"))?;
+    state.metrics.record_fermentation_created();
+
+    let profile = fermentation_repo
+        .get_profile_by_id(fermentation.profile_id)
+        .await?
+        .ok_or_else(|| ApiError::InternalError("Fermentation profile not found".to_string()))?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(FermentationResponse::from_fermentation_and_profile(
+            fermentation,
+            profile,
+        )),
+    ))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/fermentation/{id}/share",
+    tag = "fermentations",
+    params(("id" = i64, Path, description = "Fermentation id")),
+    responses(
+        (status = 200, description = "Public read-only link; the existing one if already shared", body = FermentationShare),
+        (status = 401, description = "Not logged in"),
+        (status = 404, description = "Fermentation not found")
+    )
+)]
+pub async fn share_fermentation(
+    session: Session,
+    State(state): State<AppState>,
+    Path(id): Path<i64>,
+) -> Result<Json<FermentationShare>, ApiError> {
+    // Get user from session
+    let user: UserSession = session
+        .get("user")
+        .await
+        .map_err(|e| ApiError::InternalError(format!("Failed to get session: {}", e)))?
+        .ok_or(ApiError::Unauthorized)?;
+
+    let fermentation_repo = FermentationRepository::new(state.db.clone());
+
+    let share = fermentation_repo
+        .create_share(id, user.user_id)
+        .await
+        .map_err(|e| ApiError::DatabaseError(format!("Error sharing fermentation: {}", e)))?
+        .ok_or_else(|| ApiError::not_found("Fermentation not found"))?;
+
+    Ok(Json(share))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/fermentation/{id}/share",
+    tag = "fermentations",
+    params(("id" = i64, Path, description = "Fermentation id")),
+    responses(
+        (status = 204, description = "Link revoked"),
+        (status = 401, description = "Not logged in"),
+        (status = 404, description = "Fermentation not found or not shared")
+    )
+)]
+pub async fn unshare_fermentation(
+    session: Session,
+    State(state): State<AppState>,
+    Path(id): Path<i64>,
+) -> Result<StatusCode, ApiError> {
+    // Get user from session
+    let user: UserSession = session
+        .get("user")
+        .await
+        .map_err(|e| ApiError::InternalError(format!("Failed to get session: {}", e)))?
+        .ok_or(ApiError::Unauthorized)?;
+
+    let fermentation_repo = FermentationRepository::new(state.db.clone());
+
+    let deleted = fermentation_repo
+        .delete_share(id, user.user_id)
+        .await
+        .map_err(|e| ApiError::DatabaseError(format!("Error revoking share link: {}", e)))?;
+
+    if !deleted {
+        return Err(ApiError::not_found("Fermentation is not shared"));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/fermentation/{id}/favorite",
+    tag = "fermentations",
+    params(("id" = i64, Path, description = "Fermentation id")),
+    responses(
+        (status = 200, description = "Starred fermentation", body = FermentationResponse),
+        (status = 401, description = "Not logged in"),
+        (status = 404, description = "Fermentation not found")
+    )
+)]
+pub async fn favorite_fermentation(
+    session: Session,
+    State(state): State<AppState>,
+    Path(id): Path<i64>,
+) -> Result<Json<FermentationResponse>, ApiError> {
+    set_favorite(session, state, id, true).await
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/fermentation/{id}/unfavorite",
+    tag = "fermentations",
+    params(("id" = i64, Path, description = "Fermentation id")),
+    responses(
+        (status = 200, description = "Unstarred fermentation", body = FermentationResponse),
+        (status = 401, description = "Not logged in"),
+        (status = 404, description = "Fermentation not found")
+    )
+)]
+pub async fn unfavorite_fermentation(
+    session: Session,
+    State(state): State<AppState>,
+    Path(id): Path<i64>,
+) -> Result<Json<FermentationResponse>, ApiError> {
+    set_favorite(session, state, id, false).await
+}
+
+/// Star or unstar one of the user's fermentations; doing it twice is harmless
+async fn set_favorite(
+    session: Session,
+    state: AppState,
+    id: i64,
+    is_favorite: bool,
+) -> Result<Json<FermentationResponse>, ApiError> {
+    // Get user from session
+    let user: UserSession = session
+        .get("user")
+        .await
+        .map_err(|e| ApiError::InternalError(format!("Failed to get session: {}", e)))?
+        .ok_or(ApiError::Unauthorized)?;
+
+    let fermentation_repo = FermentationRepository::new(state.db.clone());
+
+    let fermentation = fermentation_repo
+        .set_favorite(id, user.user_id, is_favorite)
+        .await
+        .map_err(|e| ApiError::DatabaseError(format!("Error updating favorite: {}", e)))?
+        .ok_or_else(|| ApiError::not_found("Fermentation not found"))?;
+
+    // Get the profile for the response
+    let profile = fermentation_repo
+        .get_profile_by_id(fermentation.profile_id)
+        .await?
+        .ok_or_else(|| ApiError::InternalError("Fermentation profile not found".to_string()))?;
+
+    Ok(Json(FermentationResponse::from_fermentation_and_profile(
+        fermentation,
+        profile,
+    )))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/fermentation/{id}/archive",
+    tag = "fermentations",
+    params(("id" = i64, Path, description = "Fermentation id")),
+    responses(
+        (status = 200, description = "Archived fermentation", body = FermentationResponse),
+        (status = 401, description = "Not logged in"),
+        (status = 404, description = "Fermentation not found")
+    )
+)]
+pub async fn archive_fermentation(
+    session: Session,
+    State(state): State<AppState>,
+    Path(id): Path<i64>,
+) -> Result<Json<FermentationResponse>, ApiError> {
+    // Get user from session
+    let user: UserSession = session
+        .get("user")
+        .await
+        .map_err(|e| ApiError::InternalError(format!("Failed to get session: {}", e)))?
+        .ok_or(ApiError::Unauthorized)?;
+
+    let fermentation_repo = FermentationRepository::new(state.db.clone());
+
+    let fermentation = fermentation_repo
+        .archive_fermentation(id, user.user_id)
+        .await
+        .map_err(|e| ApiError::DatabaseError(format!("Error archiving fermentation: {}", e)))?
+        .ok_or_else(|| ApiError::not_found("Fermentation not found"))?;
+
+    // Get the profile for the response
+    let profile = fermentation_repo
+        .get_profile_by_id(fermentation.profile_id)
+        .await?
+        .ok_or_else(|| ApiError::InternalError("Fermentation profile not found".to_string()))?;
+
+    Ok(Json(FermentationResponse::from_fermentation_and_profile(
+        fermentation,
+        profile,
+    )))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/fermentation/{id}/unarchive",
+    tag = "fermentations",
+    params(("id" = i64, Path, description = "Fermentation id")),
+    responses(
+        (status = 200, description = "Fermentation back in the list", body = FermentationResponse),
+        (status = 401, description = "Not logged in"),
+        (status = 404, description = "Fermentation not found")
+    )
+)]
+pub async fn unarchive_fermentation(
+    session: Session,
+    State(state): State<AppState>,
+    Path(id): Path<i64>,
+) -> Result<Json<FermentationResponse>, ApiError> {
+    // Get user from session
+    let user: UserSession = session
+        .get("user")
+        .await
+        .map_err(|e| ApiError::InternalError(format!("Failed to get session: {}", e)))?
+        .ok_or(ApiError::Unauthorized)?;
+
+    let fermentation_repo = FermentationRepository::new(state.db.clone());
+
+    let fermentation = fermentation_repo
+        .unarchive_fermentation(id, user.user_id)
+        .await
+        .map_err(|e| ApiError::DatabaseError(format!("Error unarchiving fermentation: {}", e)))?
+        .ok_or_else(|| ApiError::not_found("Fermentation not found"))?;
+
+    // Get the profile for the response
+    let profile = fermentation_repo
+        .get_profile_by_id(fermentation.profile_id)
+        .await?
+        .ok_or_else(|| ApiError::InternalError("Fermentation profile not found".to_string()))?;
+
+    Ok(Json(FermentationResponse::from_fermentation_and_profile(
+        fermentation,
+        profile,
+    )))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/fermentation/{id}/reopen",
+    tag = "fermentations",
+    params(("id" = i64, Path, description = "Fermentation id")),
+    responses(
+        (status = 200, description = "Fermentation active again", body = FermentationResponse),
+        (status = 401, description = "Not logged in"),
+        (status = 404, description = "Fermentation not found"),
+        (status = 409, description = "Only completed or failed fermentations can be reopened")
+    )
+)]
+pub async fn reopen_fermentation(
+    session: Session,
+    State(state): State<AppState>,
+    Path(id): Path<i64>,
+) -> Result<Json<FermentationResponse>, ApiError> {
+    // Get user from session
+    let user: UserSession = session
+        .get("user")
+        .await
+        .map_err(|e| ApiError::InternalError(format!("Failed to get session: {}", e)))?
+        .ok_or(ApiError::Unauthorized)?;
+
+    let fermentation_repo = FermentationRepository::new(state.db.clone());
+
+    let existing = fermentation_repo
+        .find_by_id(id, user.user_id)
+        .await?
+        .ok_or_else(|| ApiError::not_found("Fermentation not found"))?;
+
+    // Only finished fermentations can be reopened; archived ones must be unarchived first
+    if !matches!(
+        existing.status,
+        FermentationStatus::Completed | FermentationStatus::Failed
+    ) {
+        return Err(ApiError::Conflict(
+            "Only completed or failed fermentations can be reopened".to_string(),
+        ));
+    }
+
+    let fermentation = fermentation_repo
+        .reopen_fermentation(id, user.user_id)
+        .await
+        .map_err(|e| ApiError::DatabaseError(format!("Error reopening fermentation: {}", e)))?
+        .ok_or_else(|| ApiError::not_found("Fermentation not found"))?;
+
+    // Get the profile for the response
+    let profile = fermentation_repo
+        .get_profile_by_id(fermentation.profile_id)
+        .await?
+        .ok_or_else(|| ApiError::InternalError("Fermentation profile not found".to_string()))?;
+
+    Ok(Json(FermentationResponse::from_fermentation_and_profile(
+        fermentation,
+        profile,
+    )))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/fermentation/{id}",
+    tag = "fermentations",
+    params(("id" = i64, Path, description = "Fermentation id")),
+    responses(
+        (status = 204, description = "Fermentation deleted"),
+        (status = 401, description = "Not logged in"),
+        (status = 404, description = "Fermentation not found")
+    )
+)]
+pub async fn delete_fermentation(
+    session: Session,
+    State(state): State<AppState>,
+    Path(id): Path<i64>,
+) -> Result<StatusCode, ApiError> {
+    // Get user from session
+    let user: UserSession = session
+        .get("user")
+        .await
+        .map_err(|e| ApiError::InternalError(format!("Failed to get session: {}", e)))?
+        .ok_or(ApiError::Unauthorized)?;
+
+    let fermentation_repo = FermentationRepository::new(state.db.clone());
+
+    // Deleted fermentations are kept for a restore window and purged later
+    let deleted = fermentation_repo
+        .soft_delete_fermentation(id, user.user_id)
+        .await
+        .map_err(|e| ApiError::DatabaseError(format!("Error deleting fermentation: {}", e)))?;
+
+    if deleted {
+        Ok(StatusCode::NO_CONTENT)
+    } else {
+        Err(ApiError::not_found("Fermentation not found"))
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/fermentation/{id}/restore",
+    tag = "fermentations",
+    params(("id" = i64, Path, description = "Fermentation id")),
+    responses(
+        (status = 200, description = "Restored fermentation", body = FermentationResponse),
+        (status = 401, description = "Not logged in"),
+        (status = 404, description = "Fermentation not found or past the restore window")
+    )
+)]
+pub async fn restore_fermentation(
+    session: Session,
+    State(state): State<AppState>,
+    Path(id): Path<i64>,
+) -> Result<Json<FermentationResponse>, ApiError> {
+    // Get user from session
+    let user: UserSession = session
+        .get("user")
+        .await
+        .map_err(|e| ApiError::InternalError(format!("Failed to get session: {}", e)))?
+        .ok_or(ApiError::Unauthorized)?;
+
+    let fermentation_repo = FermentationRepository::new(state.db.clone());
+
+    let fermentation = fermentation_repo
+        .restore_fermentation(id, user.user_id)
+        .await
+        .map_err(|e| ApiError::DatabaseError(format!("Error restoring fermentation: {}", e)))?
+        .ok_or_else(|| ApiError::not_found("Fermentation not found"))?;
+
+    // Get the profile for the response
+    let profile = fermentation_repo
+        .get_profile_by_id(fermentation.profile_id)
+        .await?
+        .ok_or_else(|| ApiError::InternalError("Fermentation profile not found".to_string()))?;
+
+    Ok(Json(FermentationResponse::from_fermentation_and_profile(
+        fermentation,
+        profile,
+    )))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/fermentation/{id}/tags",
+    tag = "fermentations",
+    params(("id" = i64, Path, description = "Fermentation id")),
+    responses(
+        (status = 200, description = "The fermentation's tags", body = [Tag]),
+        (status = 401, description = "Not logged in"),
+        (status = 404, description = "Fermentation not found")
+    )
+)]
+pub async fn list_tags(
+    session: Session,
+    State(state): State<AppState>,
+    Path(fermentation_id): Path<i64>,
+) -> Result<Json<Vec<Tag>>, ApiError> {
+    // Get user from session
+    let user: UserSession = session
+        .get("user")
+        .await
+        .map_err(|e| ApiError::InternalError(format!("Failed to get session: {}", e)))?
+        .ok_or(ApiError::Unauthorized)?;
+
+    let fermentation_repo = FermentationRepository::new(state.db.clone());
+
+    // Verify fermentation exists and belongs to user
+    fermentation_repo
+        .find_by_id(fermentation_id, user.user_id)
+        .await?
+        .ok_or_else(|| ApiError::not_found("Fermentation not found"))?;
+
+    let tags = fermentation_repo
+        .tags_for_fermentation(fermentation_id, user.user_id)
+        .await?;
+
+    Ok(Json(tags))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/fermentation/{id}/tags",
+    tag = "fermentations",
+    params(("id" = i64, Path, description = "Fermentation id")),
+    request_body = TagRequest,
+    responses(
+        (status = 200, description = "Tags after adding this one", body = [Tag]),
+        (status = 400, description = "Invalid input"),
+        (status = 401, description = "Not logged in"),
+        (status = 404, description = "Fermentation not found")
+    )
+)]
+pub async fn add_tag(
+    session: Session,
+    State(state): State<AppState>,
+    Path(fermentation_id): Path<i64>,
+    Json(request): Json<TagRequest>,
+) -> Result<Json<Vec<Tag>>, ApiError> {
+    // Get user from session
+    let user: UserSession = session
+        .get("user")
+        .await
+        .map_err(|e| ApiError::InternalError(format!("Failed to get session: {}", e)))?
+        .ok_or(ApiError::Unauthorized)?;
+
+    let name = normalize_tag_name(&request.tag)
+        .ok_or_else(|| ApiError::invalid_field("tag", "Tag must be between 1 and 50 characters"))?;
+
+    let fermentation_repo = FermentationRepository::new(state.db.clone());
+
+    let tags = fermentation_repo
+        .add_tag(fermentation_id, user.user_id, &name)
+        .await
+        .map_err(|e| {
+            let error_msg = e.to_string();
+            if error_msg.contains("not found") {
+                ApiError::NotFound(error_msg)
+            } else {
+                ApiError::DatabaseError(format!("Error adding tag: {}", error_msg))
+            }
+        })?;
+
+    Ok(Json(tags))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/fermentation/{id}/tags",
+    tag = "fermentations",
+    params(("id" = i64, Path, description = "Fermentation id")),
+    request_body = TagRequest,
+    responses(
+        (status = 200, description = "Tags after removing this one", body = [Tag]),
+        (status = 400, description = "Invalid input"),
+        (status = 401, description = "Not logged in"),
+        (status = 404, description = "Fermentation not found")
+    )
+)]
+pub async fn remove_tag(
+    session: Session,
+    State(state): State<AppState>,
+    Path(fermentation_id): Path<i64>,
+    Json(request): Json<TagRequest>,
+) -> Result<Json<Vec<Tag>>, ApiError> {
+    // Get user from session
+    let user: UserSession = session
+        .get("user")
+        .await
+        .map_err(|e| ApiError::InternalError(format!("Failed to get session: {}", e)))?
+        .ok_or(ApiError::Unauthorized)?;
+
+    let name = normalize_tag_name(&request.tag)
+        .ok_or_else(|| ApiError::invalid_field("tag", "Tag must be between 1 and 50 characters"))?;
+
+    let fermentation_repo = FermentationRepository::new(state.db.clone());
+
+    let tags = fermentation_repo
+        .remove_tag(fermentation_id, user.user_id, &name)
+        .await
+        .map_err(|e| {
+            let error_msg = e.to_string();
+            if error_msg.contains("not found") {
+                ApiError::NotFound(error_msg)
+            } else {
+                ApiError::DatabaseError(format!("Error removing tag: {}", error_msg))
+            }
+        })?;
+
+    Ok(Json(tags))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/fermentation/profiles",
+    tag = "fermentations",
+    responses(
+        (status = 200, description = "Active fermentation profiles, with the temperature range also in the viewer's unit", body = [crate::fermentation::models::FermentationProfileResponse])
+    ),
+    security(())
+)]
+pub async fn get_profiles(
+    session: Session,
+    State(state): State<AppState>,
+) -> Result<Json<Vec<crate::fermentation::models::FermentationProfileResponse>>, ApiError> {
+    // Anonymous visitors see Fahrenheit, logged-in users their preferred unit
+    let user: Option<UserSession> = session
+        .get("user")
+        .await
+        .map_err(|e| ApiError::InternalError(format!("Failed to get session: {}", e)))?;
+    let temp_unit = match user {
+        Some(user) => preferred_temp_unit(&state, user.user_id).await,
+        None => crate::users::TemperatureUnit::Fahrenheit,
+    };
+
+    let fermentation_repo = FermentationRepository::new(state.db.clone());
+
+    let profiles = fermentation_repo.get_all_profiles().await?;
+
+    Ok(Json(
+        profiles
+            .into_iter()
+            .map(|profile| profile.with_display_unit(&temp_unit))
+            .collect(),
+    ))
+}
+
+#[utoipa::path(
+    patch,
+    path = "/api/fermentation/{id}",
+    tag = "fermentations",
+    params(("id" = i64, Path, description = "Fermentation id")),
+    request_body = UpdateFermentationRequest,
+    responses(
+        (status = 200, description = "Updated fermentation", body = FermentationResponse),
+        (status = 400, description = "Invalid input"),
+        (status = 401, description = "Not logged in"),
+        (status = 404, description = "Fermentation not found"),
+        (status = 409, description = "Changed since the given version")
+    )
+)]
+pub async fn patch_fermentation(
+    session: Session,
+    state: State<AppState>,
+    id: Path<i64>,
+    request: Json<UpdateFermentationRequest>,
+) -> Result<Json<FermentationResponse>, ApiError> {
+    update_fermentation(session, state, id, request).await
+}
+
+/// Kept for existing clients; behaves exactly like `PATCH`, so fields left out are not
+/// replaced
+#[utoipa::path(
+    put,
+    path = "/api/fermentation/{id}",
+    tag = "fermentations",
+    params(("id" = i64, Path, description = "Fermentation id")),
+    request_body = UpdateFermentationRequest,
+    responses(
+        (status = 200, description = "Updated fermentation", body = FermentationResponse),
+        (status = 400, description = "Invalid input"),
+        (status = 401, description = "Not logged in"),
+        (status = 404, description = "Fermentation not found"),
+        (status = 409, description = "Changed since the given version")
+    )
+)]
+pub async fn update_fermentation(
+    session: Session,
+    State(state): State<AppState>,
+    Path(id): Path<i64>,
+    Json(mut request): Json<UpdateFermentationRequest>,
+) -> Result<Json<FermentationResponse>, ApiError> {
+    // Get user from session
+    let user: UserSession = session
+        .get("user")
+        .await
+        .map_err(|e| ApiError::InternalError(format!("Failed to get session: {}", e)))?
+        .ok_or(ApiError::Unauthorized)?;
+
+    // Validate request fields
+    if let Some(ref name) = request.name {
+        if name.trim().is_empty() || name.len() > 255 {
+            return Err(ApiError::invalid_field(
+                "name",
+                "Name must be between 1 and 255 characters",
+            ));
+        }
+    }
+
+    if let Some(notes) = request.notes.as_mut() {
+        normalize_text_field(notes, "notes")?;
+    }
+
+    // Validate date formats
+    if let Some(ref start_date) = request.start_date {
+        if chrono::DateTime::parse_from_rfc3339(start_date).is_err() {
+            return Err(ApiError::invalid_field(
+                "start_date",
+                "Start date must be an RFC 3339 date",
+            ));
+        }
+    }
+
+    if let Some(Some(ref target_date)) = request.target_end_date {
+        if !target_date.is_empty() && chrono::DateTime::parse_from_rfc3339(target_date).is_err() {
+            return Err(ApiError::invalid_field(
+                "target_end_date",
+                "Target end date must be an RFC 3339 date",
+            ));
+        }
+    }
+
+    if let Some(Some(ref actual_date)) = request.actual_end_date {
+        if !actual_date.is_empty() && chrono::DateTime::parse_from_rfc3339(actual_date).is_err() {
+            return Err(ApiError::invalid_field(
+                "actual_end_date",
+                "Actual end date must be an RFC 3339 date",
+            ));
+        }
+    }
+
+    // Validate status
+    if let Some(ref status) = request.status {
+        if !matches!(
+            status.as_str(),
+            "active" | "paused" | "completed" | "failed"
+        ) {
+            return Err(ApiError::invalid_field(
+                "status",
+                "Status must be 'active', 'paused', 'completed' or 'failed'",
+            ));
+        }
+    }
+
+    // Validate success rating
+    if let Some(Some(rating)) = request.success_rating {
+        if !(1..=5).contains(&rating) {
+            return Err(ApiError::invalid_field(
+                "success_rating",
+                "Success rating must be between 1 and 5",
+            ));
+        }
+    }
+
+    let fermentation_repo = FermentationRepository::new(state.db.clone());
+
+    // Update the fermentation
+    let fermentation = fermentation_repo
+        .update_fermentation(id, user.user_id, request)
+        .await
+        .map_err(|e| {
+            if e.to_string().contains("has been changed") {
+                ApiError::Conflict(e.to_string())
+            } else {
+                ApiError::DatabaseError(format!("Error updating fermentation: {}", e))
+            }
+        })?
+        .ok_or_else(|| ApiError::not_found("Fermentation not found"))?;
+
+    // Get the profile for the response
+    let profile = fermentation_repo
+        .get_profile_by_id(fermentation.profile_id)
+        .await?
+        .ok_or_else(|| ApiError::InternalError("Fermentation profile not found".to_string()))?;
+
+    Ok(Json(FermentationResponse::from_fermentation_and_profile(
+        fermentation,
+        profile,
+    )))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/fermentation/{id}/temperature",
+    tag = "temperature",
+    params(("id" = i64, Path, description = "Fermentation id")),
+    request_body = CreateTemperatureLogRequest,
+    responses(
+        (status = 201, description = "Reading recorded, stored in Fahrenheit", body = TemperatureLog),
+        (status = 400, description = "Invalid input"),
+        (status = 401, description = "Not logged in"),
+        (status = 404, description = "Fermentation not found")
+    )
+)]
+pub async fn create_temperature_log(
+    session: Session,
+    State(state): State<AppState>,
+    Path(fermentation_id): Path<i64>,
+    Json(request): Json<CreateTemperatureLogRequest>,
+) -> Result<(StatusCode, Json<TemperatureLog>), ApiError> {
+    // Get user from session
+    let user: UserSession = session
+        .get("user")
+        .await
+        .map_err(|e| ApiError::InternalError(format!("Failed to get session: {}", e)))?
+        .ok_or(ApiError::Unauthorized)?;
+
+    let temperature_log =
+        record_temperature_log(&state, fermentation_id, user.user_id, request).await?;
+
+    Ok((StatusCode::CREATED, Json(temperature_log)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/fermentation/{id}/temperature/webhook",
+    tag = "temperature",
+    params(("id" = i64, Path, description = "Fermentation id")),
+    request_body = CreateTemperatureLogRequest,
+    responses(
+        (status = 201, description = "Reading recorded, stored in Fahrenheit", body = TemperatureLog),
+        (status = 400, description = "Invalid input"),
+        (status = 401, description = "Missing or wrong webhook token")
+    ),
+    security(("webhook_token" = []))
+)]
+pub async fn temperature_webhook(
+    State(state): State<AppState>,
+    Path(fermentation_id): Path<i64>,
+    headers: HeaderMap,
+    Json(request): Json<CreateTemperatureLogRequest>,
+) -> Result<(StatusCode, Json<TemperatureLog>), ApiError> {
+    let token = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(str::trim)
+        .filter(|token| !token.is_empty())
+        .ok_or(ApiError::Unauthorized)?;
+
+    let fermentation_repo = FermentationRepository::new(state.db.clone());
+
+    // A wrong token and an unknown fermentation look the same, so ids can't be probed
+    let user_id = fermentation_repo
+        .verify_webhook_token(fermentation_id, token)
+        .await
+        .map_err(|e| ApiError::DatabaseError(format!("Error checking webhook token: {}", e)))?
+        .ok_or(ApiError::Unauthorized)?;
+
+    let temperature_log = record_temperature_log(&state, fermentation_id, user_id, request).await?;
+
+    Ok((StatusCode::CREATED, Json(temperature_log)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/fermentation/{id}/temperature/webhook-token",
+    tag = "temperature",
+    params(("id" = i64, Path, description = "Fermentation id")),
+    responses(
+        (status = 201, description = "New webhook token; any previous token stops working", body = WebhookTokenResponse),
+        (status = 401, description = "Not logged in"),
+        (status = 404, description = "Fermentation not found")
+    )
+)]
+pub async fn create_webhook_token(
+    session: Session,
+    State(state): State<AppState>,
+    Path(fermentation_id): Path<i64>,
+) -> Result<(StatusCode, Json<WebhookTokenResponse>), ApiError> {
+    // Get user from session
+    let user: UserSession = session
+        .get("user")
+        .await
+        .map_err(|e| ApiError::InternalError(format!("Failed to get session: {}", e)))?
+        .ok_or(ApiError::Unauthorized)?;
+
+    let fermentation_repo = FermentationRepository::new(state.db.clone());
+
+    let token = fermentation_repo
+        .create_webhook_token(fermentation_id, user.user_id)
+        .await
+        .map_err(|e| ApiError::DatabaseError(format!("Error creating webhook token: {}", e)))?
+        .ok_or_else(|| ApiError::not_found("Fermentation not found"))?;
+
+    Ok((StatusCode::CREATED, Json(WebhookTokenResponse { token })))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/fermentation/{id}/temperature/webhook-token",
+    tag = "temperature",
+    params(("id" = i64, Path, description = "Fermentation id")),
+    responses(
+        (status = 204, description = "Webhook disabled"),
+        (status = 401, description = "Not logged in"),
+        (status = 404, description = "Fermentation not found")
+    )
+)]
+pub async fn revoke_webhook_token(
+    session: Session,
+    State(state): State<AppState>,
+    Path(fermentation_id): Path<i64>,
+) -> Result<StatusCode, ApiError> {
+    // Get user from session
+    let user: UserSession = session
+        .get("user")
+        .await
+        .map_err(|e| ApiError::InternalError(format!("Failed to get session: {}", e)))?
+        .ok_or(ApiError::Unauthorized)?;
+
+    let fermentation_repo = FermentationRepository::new(state.db.clone());
+
+    let revoked = fermentation_repo
+        .revoke_webhook_token(fermentation_id, user.user_id)
+        .await
+        .map_err(|e| ApiError::DatabaseError(format!("Error revoking webhook token: {}", e)))?;
+
+    if !revoked {
+        return Err(ApiError::not_found("Fermentation not found"));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Validate a reading and store it for one of `user_id`'s fermentations, converted to
+/// Fahrenheit
+async fn record_temperature_log(
+    state: &AppState,
+    fermentation_id: i64,
+    user_id: i64,
+    mut request: CreateTemperatureLogRequest,
+) -> Result<TemperatureLog, ApiError> {
+    // Determine the temperature unit from request or default to Fahrenheit
+    let temp_unit = parse_temp_unit(request.temp_unit.as_deref());
+
+    if !is_valid_temperature(request.temperature, &temp_unit, &state.config) {
+        return Err(ApiError::invalid_field(
+            "temperature",
+            "Temperature is outside the plausible range",
+        ));
+    }
+
+    // Convert temperature to Fahrenheit for storage
+    request.temperature =
+        crate::users::temperature::convert_temp_for_storage(request.temperature, &temp_unit);
+
+    // Validate recorded_at format if provided
+    if let Some(ref recorded_at) = request.recorded_at {
+        if chrono::DateTime::parse_from_rfc3339(recorded_at).is_err() {
+            return Err(ApiError::invalid_field(
+                "recorded_at",
+                "Recorded at must be an RFC 3339 date",
+            ));
+        }
+    }
+
+    let fermentation_repo = FermentationRepository::new(state.db.clone());
+
+    // Create the temperature log
+    fermentation_repo
+        .create_temperature_log(fermentation_id, user_id, request)
+        .await
+        .map_err(|e| {
+            let error_msg = e.to_string();
+            if error_msg.contains("not found") {
+                ApiError::NotFound(error_msg)
+            } else {
+                ApiError::DatabaseError(format!("Error creating temperature log: {}", error_msg))
+            }
+        })
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/fermentation/{id}/ph",
+    tag = "measurements",
+    params(("id" = i64, Path, description = "Fermentation id")),
+    request_body = CreatePhLogRequest,
+    responses(
+        (status = 201, description = "pH reading recorded", body = PhLog),
+        (status = 400, description = "Invalid input"),
+        (status = 401, description = "Not logged in"),
+        (status = 404, description = "Fermentation not found")
+    )
+)]
+pub async fn create_ph_log(
+    session: Session,
+    State(state): State<AppState>,
+    Path(fermentation_id): Path<i64>,
+    Json(request): Json<CreatePhLogRequest>,
+) -> Result<(StatusCode, Json<PhLog>), ApiError> {
+    // Get user from session
+    let user: UserSession = session
+        .get("user")
+        .await
+        .map_err(|e| ApiError::InternalError(format!("Failed to get session: {}", e)))?
+        .ok_or(ApiError::Unauthorized)?;
+
+    if !is_valid_ph(request.ph) {
+        return Err(ApiError::invalid_field("ph", "pH must be between 0 and 14"));
+    }
+
+    // Validate recorded_at format if provided
+    if let Some(ref recorded_at) = request.recorded_at {
+        if chrono::DateTime::parse_from_rfc3339(recorded_at).is_err() {
+            return Err(ApiError::invalid_field(
+                "recorded_at",
+                "Recorded at must be an RFC 3339 date",
+            ));
+        }
+    }
+
+    let fermentation_repo = FermentationRepository::new(state.db.clone());
+
+    // Create the pH log
+    let ph_log = fermentation_repo
+        .create_ph_log(fermentation_id, user.user_id, request)
+        .await
+        .map_err(|e| {
+            let error_msg = e.to_string();
+            if error_msg.contains("not found") {
+                ApiError::NotFound(error_msg)
+            } else {
+                ApiError::DatabaseError(format!("Error creating pH log: {}", error_msg))
+            }
+        })?;
+
+    Ok((StatusCode::CREATED, Json(ph_log)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/fermentation/{id}/ph",
+    tag = "measurements",
+    params(("id" = i64, Path, description = "Fermentation id")),
+    responses(
+        (status = 200, description = "pH readings, newest first", body = [PhLog]),
+        (status = 401, description = "Not logged in"),
+        (status = 404, description = "Fermentation not found")
+    )
+)]
+pub async fn list_ph_logs(
+    session: Session,
+    State(state): State<AppState>,
+    Path(fermentation_id): Path<i64>,
+) -> Result<Json<Vec<PhLog>>, ApiError> {
+    // Get user from session
+    let user: UserSession = session
+        .get("user")
+        .await
+        .map_err(|e| ApiError::InternalError(format!("Failed to get session: {}", e)))?
+        .ok_or(ApiError::Unauthorized)?;
+
+    let fermentation_repo = FermentationRepository::new(state.db.clone());
+
+    let logs = fermentation_repo
+        .find_ph_logs_by_fermentation(fermentation_id, user.user_id)
+        .await
+        .map_err(|e| {
+            let error_msg = e.to_string();
+            if error_msg.contains("not found") {
+                ApiError::NotFound(error_msg)
+            } else {
+                ApiError::DatabaseError(format!("Error fetching pH logs: {}", error_msg))
+            }
+        })?;
+
+    Ok(Json(logs))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/fermentation/{id}/measurements",
+    tag = "measurements",
+    params(("id" = i64, Path, description = "Fermentation id")),
+    request_body = CreateMeasurementLogRequest,
+    responses(
+        (status = 201, description = "Measurement recorded", body = MeasurementLog),
+        (status = 400, description = "Invalid input"),
+        (status = 401, description = "Not logged in"),
+        (status = 404, description = "Fermentation not found")
+    )
+)]
+pub async fn create_measurement_log(
+    session: Session,
+    State(state): State<AppState>,
+    Path(fermentation_id): Path<i64>,
+    Json(mut request): Json<CreateMeasurementLogRequest>,
+) -> Result<(StatusCode, Json<MeasurementLog>), ApiError> {
+    // Get user from session
+    let user: UserSession = session
+        .get("user")
+        .await
+        .map_err(|e| ApiError::InternalError(format!("Failed to get session: {}", e)))?
+        .ok_or(ApiError::Unauthorized)?;
+
+    let kind = parse_measurement_kind(&request.kind)?;
+
+    if !request.value.is_finite() || request.value <= 0.0 {
+        return Err(ApiError::invalid_field("value", "Value must be positive"));
+    }
+    if kind == MeasurementKind::Ph && !is_valid_ph(request.value) {
+        return Err(ApiError::invalid_field(
+            "value",
+            "pH must be between 0 and 14",
+        ));
+    }
+    if kind == MeasurementKind::Gravity && !is_valid_gravity(request.value) {
+        return Err(ApiError::invalid_field(
+            "value",
+            "Specific gravity must be between 0.980 and 1.200",
+        ));
+    }
+
+    if let Some(ref mut unit) = request.unit {
+        *unit = unit.trim().to_lowercase();
+        if !kind.units().contains(&unit.as_str()) {
+            return Err(ApiError::invalid_field(
+                "unit",
+                format!(
+                    "Unit for {} must be one of: {}",
+                    kind.as_str(),
+                    kind.units().join(", ")
+                ),
+            ));
+        }
+    }
+
+    // Validate recorded_at format if provided
+    if let Some(ref recorded_at) = request.recorded_at {
+        if chrono::DateTime::parse_from_rfc3339(recorded_at).is_err() {
+            return Err(ApiError::invalid_field(
+                "recorded_at",
+                "Recorded at must be an RFC 3339 date",
+            ));
+        }
+    }
+
+    normalize_text_field(&mut request.notes, "notes")?;
+
+    let fermentation_repo = FermentationRepository::new(state.db.clone());
+
+    let log = fermentation_repo
+        .create_measurement_log(fermentation_id, user.user_id, kind, request)
+        .await
+        .map_err(|e| {
+            let error_msg = e.to_string();
+            if error_msg.contains("not found") {
+                ApiError::NotFound(error_msg)
+            } else {
+                ApiError::DatabaseError(format!("Error creating measurement log: {}", error_msg))
+            }
+        })?;
+
+    Ok((StatusCode::CREATED, Json(log)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/fermentation/{id}/measurements",
+    tag = "measurements",
+    params(("id" = i64, Path, description = "Fermentation id"), MeasurementLogQuery),
+    responses(
+        (status = 200, description = "Measurements, newest first", body = [MeasurementLog]),
+        (status = 400, description = "Invalid input"),
+        (status = 401, description = "Not logged in"),
+        (status = 404, description = "Fermentation not found")
+    )
+)]
+pub async fn list_measurement_logs(
+    session: Session,
+    State(state): State<AppState>,
+    Path(fermentation_id): Path<i64>,
+    Query(query): Query<MeasurementLogQuery>,
+) -> Result<Json<Vec<MeasurementLog>>, ApiError> {
+    // Get user from session
+    let user: UserSession = session
+        .get("user")
+        .await
+        .map_err(|e| ApiError::InternalError(format!("Failed to get session: {}", e)))?
+        .ok_or(ApiError::Unauthorized)?;
+
+    let kind = query
+        .kind
+        .as_deref()
+        .map(parse_measurement_kind)
+        .transpose()?;
+
+    let fermentation_repo = FermentationRepository::new(state.db.clone());
+
+    let logs = fermentation_repo
+        .find_measurement_logs_by_fermentation(fermentation_id, user.user_id, kind)
+        .await
+        .map_err(|e| {
+            let error_msg = e.to_string();
+            if error_msg.contains("not found") {
+                ApiError::NotFound(error_msg)
+            } else {
+                ApiError::DatabaseError(format!("Error fetching measurement logs: {}", error_msg))
+            }
+        })?;
+
+    Ok(Json(logs))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/fermentation/{id}/gravity",
+    tag = "measurements",
+    params(("id" = i64, Path, description = "Fermentation id")),
+    request_body = CreateGravityLogRequest,
+    responses(
+        (status = 201, description = "Gravity reading recorded", body = GravityLog),
+        (status = 400, description = "Invalid input"),
+        (status = 401, description = "Not logged in"),
+        (status = 404, description = "Fermentation not found")
+    )
+)]
+pub async fn create_gravity_log(
+    session: Session,
+    State(state): State<AppState>,
+    Path(fermentation_id): Path<i64>,
+    Json(request): Json<CreateGravityLogRequest>,
+) -> Result<(StatusCode, Json<GravityLog>), ApiError> {
+    // Get user from session
+    let user: UserSession = session
+        .get("user")
+        .await
+        .map_err(|e| ApiError::InternalError(format!("Failed to get session: {}", e)))?
+        .ok_or(ApiError::Unauthorized)?;
+
+    if !is_valid_gravity(request.gravity) {
+        return Err(ApiError::invalid_field(
+            "gravity",
+            "Gravity must be between 0.980 and 1.200",
+        ));
+    }
+
+    // Validate recorded_at format if provided
+    if let Some(ref recorded_at) = request.recorded_at {
+        if chrono::DateTime::parse_from_rfc3339(recorded_at).is_err() {
+            return Err(ApiError::invalid_field(
+                "recorded_at",
+                "Recorded at must be an RFC 3339 date",
+            ));
+        }
+    }
+
+    let fermentation_repo = FermentationRepository::new(state.db.clone());
+
+    // Create the gravity log
+    let gravity_log = fermentation_repo
+        .create_gravity_log(fermentation_id, user.user_id, request)
+        .await
+        .map_err(|e| {
+            let error_msg = e.to_string();
+            if error_msg.contains("not found") {
+                ApiError::NotFound(error_msg)
+            } else {
+                ApiError::DatabaseError(format!("Error creating gravity log: {}", error_msg))
+            }
+        })?;
+
+    Ok((StatusCode::CREATED, Json(gravity_log)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/fermentation/{id}/gravity",
+    tag = "measurements",
+    params(("id" = i64, Path, description = "Fermentation id")),
+    responses(
+        (status = 200, description = "Gravity readings, newest first", body = [GravityLog]),
+        (status = 401, description = "Not logged in"),
+        (status = 404, description = "Fermentation not found")
+    )
+)]
+pub async fn list_gravity_logs(
+    session: Session,
+    State(state): State<AppState>,
+    Path(fermentation_id): Path<i64>,
+) -> Result<Json<Vec<GravityLog>>, ApiError> {
+    // Get user from session
+    let user: UserSession = session
+        .get("user")
+        .await
+        .map_err(|e| ApiError::InternalError(format!("Failed to get session: {}", e)))?
+        .ok_or(ApiError::Unauthorized)?;
+
+    let fermentation_repo = FermentationRepository::new(state.db.clone());
+
+    let logs = fermentation_repo
+        .find_gravity_logs_by_fermentation(fermentation_id, user.user_id)
+        .await
+        .map_err(|e| {
+            let error_msg = e.to_string();
+            if error_msg.contains("not found") {
+                ApiError::NotFound(error_msg)
+            } else {
+                ApiError::DatabaseError(format!("Error fetching gravity logs: {}", error_msg))
+            }
+        })?;
+
+    Ok(Json(logs))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/fermentation/{id}/gravity/stats",
+    tag = "measurements",
+    params(("id" = i64, Path, description = "Fermentation id")),
+    responses(
+        (status = 200, description = "Original and latest gravity with an ABV estimate", body = GravityStats),
+        (status = 401, description = "Not logged in"),
+        (status = 404, description = "Fermentation not found")
+    )
+)]
+pub async fn gravity_stats(
+    session: Session,
+    State(state): State<AppState>,
+    Path(fermentation_id): Path<i64>,
+) -> Result<Json<GravityStats>, ApiError> {
+    // Get user from session
+    let user: UserSession = session
+        .get("user")
+        .await
+        .map_err(|e| ApiError::InternalError(format!("Failed to get session: {}", e)))?
+        .ok_or(ApiError::Unauthorized)?;
+
+    let fermentation_repo = FermentationRepository::new(state.db.clone());
+
+    let stats = fermentation_repo
+        .gravity_stats(fermentation_id, user.user_id)
+        .await
+        .map_err(|e| {
+            let error_msg = e.to_string();
+            if error_msg.contains("not found") {
+                ApiError::NotFound(error_msg)
+            } else {
+                ApiError::DatabaseError(format!("Error computing gravity stats: {}", error_msg))
+            }
+        })?;
+
+    Ok(Json(stats))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/dashboard/profile-breakdown",
+    tag = "dashboard",
+    responses(
+        (status = 200, description = "Fermentations per profile type", body = std::collections::BTreeMap<String, i64>),
+        (status = 401, description = "Not logged in")
+    )
+)]
+pub async fn profile_breakdown(
+    session: Session,
+    State(state): State<AppState>,
+) -> Result<Json<std::collections::BTreeMap<String, i64>>, ApiError> {
+    // Get user from session
+    let user: UserSession = session
+        .get("user")
+        .await
+        .map_err(|e| ApiError::InternalError(format!("Failed to get session: {}", e)))?
+        .ok_or(ApiError::Unauthorized)?;
+
+    let fermentation_repo = FermentationRepository::new(state.db.clone());
+
+    let breakdown = fermentation_repo
+        .profile_type_breakdown(user.user_id)
+        .await
+        .map_err(|e| {
+            ApiError::DatabaseError(format!("Error computing profile breakdown: {}", e))
+        })?;
+
+    Ok(Json(breakdown))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/dashboard/ratings",
+    tag = "dashboard",
+    responses(
+        (status = 200, description = "Completed fermentations per success rating", body = RatingDistribution),
+        (status = 401, description = "Not logged in")
+    )
+)]
+pub async fn rating_distribution(
+    session: Session,
+    State(state): State<AppState>,
+) -> Result<Json<RatingDistribution>, ApiError> {
+    // Get user from session
+    let user: UserSession = session
+        .get("user")
+        .await
+        .map_err(|e| ApiError::InternalError(format!("Failed to get session: {}", e)))?
+        .ok_or(ApiError::Unauthorized)?;
+
+    let fermentation_repo = FermentationRepository::new(state.db.clone());
+
+    let distribution = fermentation_repo
+        .rating_distribution(user.user_id)
+        .await
+        .map_err(|e| {
+            ApiError::DatabaseError(format!("Error computing rating distribution: {}", e))
+        })?;
+
+    Ok(Json(distribution))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/dashboard/profile-success",
+    tag = "dashboard",
+    responses(
+        (status = 200, description = "Completed fermentations and their average rating per profile", body = [ProfileSuccess]),
+        (status = 401, description = "Not logged in")
+    )
+)]
+pub async fn profile_success(
+    session: Session,
+    State(state): State<AppState>,
+) -> Result<Json<Vec<ProfileSuccess>>, ApiError> {
+    // Get user from session
+    let user: UserSession = session
+        .get("user")
+        .await
+        .map_err(|e| ApiError::InternalError(format!("Failed to get session: {}", e)))?
+        .ok_or(ApiError::Unauthorized)?;
+
+    let fermentation_repo = FermentationRepository::new(state.db.clone());
+
+    let profiles = fermentation_repo
+        .profile_success(user.user_id)
+        .await
+        .map_err(|e| ApiError::DatabaseError(format!("Error computing profile success: {}", e)))?;
+
+    Ok(Json(profiles))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/fermentation/{id}/journal",
+    tag = "journal",
+    params(("id" = i64, Path, description = "Fermentation id")),
+    request_body = CreateJournalEntryRequest,
+    responses(
+        (status = 201, description = "Journal entry added", body = JournalEntry),
+        (status = 400, description = "Invalid input"),
+        (status = 401, description = "Not logged in"),
+        (status = 404, description = "Fermentation not found")
+    )
+)]
+pub async fn create_journal_entry(
+    session: Session,
+    State(state): State<AppState>,
+    Path(fermentation_id): Path<i64>,
+    Json(mut request): Json<CreateJournalEntryRequest>,
+) -> Result<(StatusCode, Json<JournalEntry>), ApiError> {
+    // Get user from session
+    let user: UserSession = session
+        .get("user")
+        .await
+        .map_err(|e| ApiError::InternalError(format!("Failed to get session: {}", e)))?
+        .ok_or(ApiError::Unauthorized)?;
+
+    request.body = request.body.trim().to_string();
+    if request.body.is_empty() || request.body.chars().count() > MAX_JOURNAL_ENTRY_LENGTH {
+        return Err(ApiError::invalid_field(
+            "body",
+            format!(
+                "Entry must be between 1 and {} characters",
+                MAX_JOURNAL_ENTRY_LENGTH
+            ),
+        ));
+    }
+
+    // Validate entry_date format if provided
+    if let Some(ref entry_date) = request.entry_date {
+        if chrono::DateTime::parse_from_rfc3339(entry_date).is_err() {
+            return Err(ApiError::invalid_field(
+                "entry_date",
+                "Entry date must be an RFC 3339 date",
+            ));
+        }
+    }
+
+    let fermentation_repo = FermentationRepository::new(state.db.clone());
+
+    let entry = fermentation_repo
+        .create_journal_entry(fermentation_id, user.user_id, request)
+        .await
+        .map_err(|e| {
+            let error_msg = e.to_string();
+            if error_msg.contains("not found") {
+                ApiError::NotFound(error_msg)
+            } else {
+                ApiError::DatabaseError(format!("Error creating journal entry: {}", error_msg))
+            }
+        })?;
+
+    Ok((StatusCode::CREATED, Json(entry)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/fermentation/{id}/journal",
+    tag = "journal",
+    params(("id" = i64, Path, description = "Fermentation id")),
+    responses(
+        (status = 200, description = "Journal entries, newest first", body = [JournalEntry]),
+        (status = 401, description = "Not logged in"),
+        (status = 404, description = "Fermentation not found")
+    )
+)]
+pub async fn list_journal_entries(
+    session: Session,
+    State(state): State<AppState>,
+    Path(fermentation_id): Path<i64>,
+) -> Result<Json<Vec<JournalEntry>>, ApiError> {
+    // Get user from session
+    let user: UserSession = session
+        .get("user")
+        .await
+        .map_err(|e| ApiError::InternalError(format!("Failed to get session: {}", e)))?
+        .ok_or(ApiError::Unauthorized)?;
+
+    let fermentation_repo = FermentationRepository::new(state.db.clone());
+
+    let entries = fermentation_repo
+        .find_journal_entries_by_fermentation(fermentation_id, user.user_id)
+        .await
+        .map_err(|e| {
+            let error_msg = e.to_string();
+            if error_msg.contains("not found") {
+                ApiError::NotFound(error_msg)
+            } else {
+                ApiError::DatabaseError(format!("Error fetching journal entries: {}", error_msg))
+            }
+        })?;
+
+    Ok(Json(entries))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/fermentation/{id}/journal/{entry_id}",
+    tag = "journal",
+    params(
+        ("id" = i64, Path, description = "Fermentation id"),
+        ("entry_id" = i64, Path, description = "Journal entry id")
+    ),
+    responses(
+        (status = 204, description = "Journal entry deleted"),
+        (status = 401, description = "Not logged in"),
+        (status = 404, description = "Journal entry not found")
+    )
+)]
+pub async fn delete_journal_entry(
+    session: Session,
+    State(state): State<AppState>,
+    Path((fermentation_id, entry_id)): Path<(i64, i64)>,
+) -> Result<StatusCode, ApiError> {
+    // Get user from session
+    let user: UserSession = session
+        .get("user")
+        .await
+        .map_err(|e| ApiError::InternalError(format!("Failed to get session: {}", e)))?
+        .ok_or(ApiError::Unauthorized)?;
+
+    let fermentation_repo = FermentationRepository::new(state.db.clone());
+
+    let deleted = fermentation_repo
+        .delete_journal_entry(fermentation_id, entry_id, user.user_id)
+        .await
+        .map_err(|e| ApiError::DatabaseError(format!("Error deleting journal entry: {}", e)))?;
+
+    if deleted {
+        Ok(StatusCode::NO_CONTENT)
+    } else {
+        Err(ApiError::not_found("Journal entry not found"))
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/fermentation/{id}/timeline",
+    tag = "journal",
+    params(("id" = i64, Path, description = "Fermentation id")),
+    responses(
+        (status = 200, description = "Temperature readings, taste profiles, photos and journal entries in date order", body = [TimelineEvent]),
+        (status = 401, description = "Not logged in"),
+        (status = 404, description = "Fermentation not found")
+    )
+)]
+pub async fn fermentation_timeline(
+    session: Session,
+    State(state): State<AppState>,
+    Path(fermentation_id): Path<i64>,
+) -> Result<Json<Vec<TimelineEvent>>, ApiError> {
+    // Get user from session
+    let user: UserSession = session
+        .get("user")
+        .await
+        .map_err(|e| ApiError::InternalError(format!("Failed to get session: {}", e)))?
+        .ok_or(ApiError::Unauthorized)?;
+
+    let fermentation_repo = FermentationRepository::new(state.db.clone());
+    let photo_repo = crate::photos::PhotoRepository::new(state.db.clone());
+
+    let map_error = |e: Box<dyn std::error::Error + Send + Sync>| {
+        let error_msg = e.to_string();
+        if error_msg.contains("not found") {
+            ApiError::NotFound(error_msg)
+        } else {
+            ApiError::DatabaseError(format!(
+                "Error building fermentation timeline: {}",
+                error_msg
+            ))
+        }
+    };
+
+    let temperature_logs = fermentation_repo
+        .find_temperature_logs_by_fermentation(fermentation_id, user.user_id)
+        .await
+        .map_err(map_error)?;
+    let taste_profiles = fermentation_repo
+        .find_taste_profiles_by_fermentation(fermentation_id, user.user_id)
+        .await
+        .map_err(map_error)?;
+    let journal_entries = fermentation_repo
+        .find_journal_entries_by_fermentation(fermentation_id, user.user_id)
+        .await
+        .map_err(map_error)?;
+    let photos = photo_repo
+        .list_by_fermentation(fermentation_id, user.user_id)
+        .await
+        .map_err(map_error)?;
+
+    let temp_unit = preferred_temp_unit(&state, user.user_id).await;
+
+    let mut events: Vec<TimelineEvent> = temperature_logs
+        .into_iter()
+        .map(|log| TimelineEvent::TemperatureLog(log.in_unit(&temp_unit)))
+        .chain(taste_profiles.into_iter().map(TimelineEvent::TasteProfile))
+        .chain(
+            photos
+                .into_iter()
+                .map(|photo| TimelineEvent::Photo(photo.into())),
+        )
+        .chain(journal_entries.into_iter().map(TimelineEvent::JournalEntry))
+        .collect();
+    events.sort_by_key(|event| event.occurred_at());
+
+    Ok(Json(events))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/fermentation/{id}/export",
+    tag = "fermentations",
+    params(("id" = i64, Path, description = "Fermentation id")),
+    responses(
+        (status = 200, description = "The fermentation with everything recorded against it", body = FermentationExport),
+        (status = 401, description = "Not logged in"),
+        (status = 404, description = "Fermentation not found")
+    )
+)]
+pub async fn export_fermentation(
+    session: Session,
+    State(state): State<AppState>,
+    Path(fermentation_id): Path<i64>,
+) -> Result<Json<FermentationExport>, ApiError> {
+    // Get user from session
+    let user: UserSession = session
+        .get("user")
+        .await
+        .map_err(|e| ApiError::InternalError(format!("Failed to get session: {}", e)))?
+        .ok_or(ApiError::Unauthorized)?;
+
+    let fermentation_repo = FermentationRepository::new(state.db.clone());
+
+    let export = fermentation_repo
+        .export_fermentation(fermentation_id, user.user_id)
+        .await
+        .map_err(|e| ApiError::DatabaseError(format!("Error exporting fermentation: {}", e)))?
+        .ok_or_else(|| ApiError::not_found("Fermentation not found"))?;
+
+    Ok(Json(export))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/fermentation/{id}/temperature",
+    tag = "temperature",
+    params(("id" = i64, Path, description = "Fermentation id")),
+    responses(
+        (status = 200, description = "Readings in the user's preferred unit", body = [TemperatureLogResponse]),
+        (status = 401, description = "Not logged in"),
+        (status = 404, description = "Fermentation not found")
+    )
+)]
+pub async fn list_temperature_logs(
+    session: Session,
+    State(state): State<AppState>,
+    Path(fermentation_id): Path<i64>,
+) -> Result<Json<Vec<TemperatureLogResponse>>, ApiError> {
+    // Get user from session
+    let user: UserSession = session
+        .get("user")
+        .await
+        .map_err(|e| ApiError::InternalError(format!("Failed to get session: {}", e)))?
+        .ok_or(ApiError::Unauthorized)?;
+
     let fermentation_repo = FermentationRepository::new(state.db.clone());
 
-    // Verify profile exists
-    let profile = fermentation_repo
-        .get_profile_by_id(request.profile_id)
+    let logs = fermentation_repo
+        .find_temperature_logs_by_fermentation(fermentation_id, user.user_id)
         .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
-        .ok_or(StatusCode::NOT_FOUND)?;
+        .map_err(|e| {
+            let error_msg = e.to_string();
+            if error_msg.contains("not found") {
+                ApiError::NotFound(error_msg)
+            } else {
+                ApiError::DatabaseError(format!("Error fetching temperature logs: {}", error_msg))
+            }
+        })?;
 
-    // Create the fermentation
-    let fermentation = fermentation_repo
-        .create_fermentation(user.user_id, request)
-        .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let temp_unit = preferred_temp_unit(&state, user.user_id).await;
 
-    Ok((
-        StatusCode::CREATED,
-        Json(FermentationResponse::from_fermentation_and_profile(
-            fermentation,
-            profile,
-        )),
+    Ok(Json(
+        logs.into_iter()
+            .map(|log| log.in_unit(&temp_unit))
+            .collect(),
     ))
 }
 
-pub async fn get_profiles(
+#[utoipa::path(
+    get,
+    path = "/api/fermentation/{id}/temperature.csv",
+    tag = "temperature",
+    params(("id" = i64, Path, description = "Fermentation id")),
+    responses(
+        (status = 200, description = "Temperature readings as CSV, oldest first", body = String, content_type = "text/csv"),
+        (status = 401, description = "Not logged in"),
+        (status = 404, description = "Fermentation not found")
+    )
+)]
+pub async fn export_temperature_logs_csv(
+    session: Session,
     State(state): State<AppState>,
-) -> Result<Json<Vec<crate::fermentation::models::FermentationProfile>>, StatusCode> {
+    Path(fermentation_id): Path<i64>,
+) -> Result<Response, ApiError> {
+    // Get user from session
+    let user: UserSession = session
+        .get("user")
+        .await
+        .map_err(|e| ApiError::InternalError(format!("Failed to get session: {}", e)))?
+        .ok_or(ApiError::Unauthorized)?;
+
     let fermentation_repo = FermentationRepository::new(state.db.clone());
 
-    let profiles = fermentation_repo
-        .get_all_profiles()
+    // Verify fermentation exists and belongs to user
+    let fermentation = fermentation_repo
+        .find_by_id(fermentation_id, user.user_id)
+        .await?
+        .ok_or_else(|| ApiError::not_found("Fermentation not found"))?;
+
+    let logs = fermentation_repo
+        .find_temperature_logs_by_fermentation(fermentation_id, user.user_id)
         .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        .map_err(|e| ApiError::DatabaseError(format!("Error fetching temperature logs: {}", e)))?;
+
+    let temp_unit = preferred_temp_unit(&state, user.user_id).await;
+
+    let mut csv = String::from("recorded_at,temperature_f,temperature_display,notes\n");
+    // Logs come back newest first; spreadsheets expect chronological order
+    for log in logs.into_iter().rev() {
+        let display_temp =
+            crate::users::temperature::convert_temp_for_display(log.temperature, &temp_unit);
+        csv.push_str(&format!(
+            "{},{:.1},{:.1},{}\n",
+            log.recorded_at.to_rfc3339(),
+            log.temperature,
+            display_temp,
+            escape_csv_field(log.notes.as_deref().unwrap_or(""))
+        ));
+    }
 
-    Ok(Json(profiles))
+    let disposition = format!(
+        "attachment; filename=\"{}-temperatures.csv\"",
+        csv_filename_stem(&fermentation.name)
+    );
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "text/csv; charset=utf-8".to_string()),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        csv,
+    )
+        .into_response())
 }
 
-pub async fn update_fermentation(
+#[utoipa::path(
+    post,
+    path = "/api/fermentation/{id}/temperature/import",
+    tag = "temperature",
+    params(("id" = i64, Path, description = "Fermentation id")),
+    request_body(content = String, content_type = "text/csv", description = "Rows of recorded_at,temperature_f[,notes], with an optional header row"),
+    responses(
+        (status = 201, description = "Imported temperature readings", body = [TemperatureLog]),
+        (status = 400, description = "Invalid rows; nothing was imported"),
+        (status = 401, description = "Not logged in"),
+        (status = 404, description = "Fermentation not found")
+    )
+)]
+pub async fn import_temperature_logs_csv(
     session: Session,
     State(state): State<AppState>,
-    Path(id): Path<i64>,
-    Json(request): Json<UpdateFermentationRequest>,
-) -> Result<Json<FermentationResponse>, StatusCode> {
+    Path(fermentation_id): Path<i64>,
+    body: String,
+) -> Result<Response, ApiError> {
     // Get user from session
-    let user_session: Option<UserSession> = session
+    let user: UserSession = session
         .get("user")
         .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-
-    let user = user_session.ok_or(StatusCode::UNAUTHORIZED)?;
+        .map_err(|e| ApiError::InternalError(format!("Failed to get session: {}", e)))?
+        .ok_or(ApiError::Unauthorized)?;
 
-    // Validate request fields
-    if let Some(ref name) = request.name {
-        if name.trim().is_empty() || name.len() > 255 {
-            return Err(StatusCode::BAD_REQUEST);
-        }
-    }
+    // Parse and validate every row before touching the database
+    let mut requests = Vec::new();
+    let mut errors = Vec::new();
 
-    // Validate date formats
-    if let Some(ref start_date) = request.start_date {
-        if chrono::DateTime::parse_from_rfc3339(start_date).is_err() {
-            return Err(StatusCode::BAD_REQUEST);
+    for (row, fields) in parse_csv_records(&body) {
+        if fields.len() == 1 && fields[0].trim().is_empty() {
+            continue;
         }
-    }
 
-    if let Some(ref target_date) = request.target_end_date {
-        if !target_date.is_empty() && chrono::DateTime::parse_from_rfc3339(target_date).is_err() {
-            return Err(StatusCode::BAD_REQUEST);
+        // Allow an optional header row
+        if row == 1 && fields.first().map(|f| f.trim()) == Some("recorded_at") {
+            continue;
         }
-    }
 
-    if let Some(ref actual_date) = request.actual_end_date {
-        if !actual_date.is_empty() && chrono::DateTime::parse_from_rfc3339(actual_date).is_err() {
-            return Err(StatusCode::BAD_REQUEST);
+        match parse_temperature_import_row(&fields, &state.config) {
+            Ok(request) => requests.push(request),
+            Err(error) => errors.push(TemperatureImportRowError { row, error }),
         }
     }
 
-    // Validate status
-    if let Some(ref status) = request.status {
-        if !matches!(
-            status.as_str(),
-            "active" | "paused" | "completed" | "failed"
-        ) {
-            return Err(StatusCode::BAD_REQUEST);
-        }
+    if !errors.is_empty() {
+        return Ok((
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({
+                "error": "Import rejected due to invalid rows",
+                "rows": errors,
+            })),
+        )
+            .into_response());
     }
 
-    // Validate success rating
-    if let Some(rating) = request.success_rating {
-        if !(1..=5).contains(&rating) {
-            return Err(StatusCode::BAD_REQUEST);
-        }
+    if requests.is_empty() {
+        return Err(ApiError::ValidationError(
+            "No temperature readings to import".to_string(),
+        ));
     }
 
     let fermentation_repo = FermentationRepository::new(state.db.clone());
 
-    // Update the fermentation
-    let fermentation = fermentation_repo
-        .update_fermentation(id, user.user_id, request)
+    let logs = fermentation_repo
+        .bulk_create_temperature_logs(fermentation_id, user.user_id, requests)
         .await
         .map_err(|e| {
-            tracing::error!("Error updating fermentation: {}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?
-        .ok_or(StatusCode::NOT_FOUND)?;
+            let error_msg = e.to_string();
+            if error_msg.contains("not found") {
+                ApiError::NotFound(error_msg)
+            } else {
+                ApiError::DatabaseError(format!("Error importing temperature logs: {}", error_msg))
+            }
+        })?;
 
-    // Get the profile for the response
-    let profile = fermentation_repo
-        .get_profile_by_id(fermentation.profile_id)
+    Ok((StatusCode::CREATED, Json(logs)).into_response())
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/fermentation/{id}/temperature/stats",
+    tag = "temperature",
+    params(("id" = i64, Path, description = "Fermentation id")),
+    responses(
+        (status = 200, description = "Summary of the readings", body = TemperatureStats),
+        (status = 401, description = "Not logged in"),
+        (status = 404, description = "Fermentation not found")
+    )
+)]
+pub async fn temperature_stats(
+    session: Session,
+    State(state): State<AppState>,
+    Path(fermentation_id): Path<i64>,
+) -> Result<Json<TemperatureStats>, ApiError> {
+    // Get user from session
+    let user: UserSession = session
+        .get("user")
         .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
-        .ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
+        .map_err(|e| ApiError::InternalError(format!("Failed to get session: {}", e)))?
+        .ok_or(ApiError::Unauthorized)?;
 
-    Ok(Json(FermentationResponse::from_fermentation_and_profile(
-        fermentation,
-        profile,
-    )))
+    let fermentation_repo = FermentationRepository::new(state.db.clone());
+
+    let stats = fermentation_repo
+        .temperature_stats(fermentation_id, user.user_id)
+        .await
+        .map_err(|e| {
+            let error_msg = e.to_string();
+            if error_msg.contains("not found") {
+                ApiError::NotFound(error_msg)
+            } else {
+                ApiError::DatabaseError(format!("Error computing temperature stats: {}", error_msg))
+            }
+        })?;
+
+    let temp_unit = preferred_temp_unit(&state, user.user_id).await;
+
+    Ok(Json(stats.in_unit(&temp_unit)))
 }
 
-pub async fn create_temperature_log(
+#[utoipa::path(
+    get,
+    path = "/api/fermentation/{id}/temperature/gaps",
+    tag = "temperature",
+    params(("id" = i64, Path, description = "Fermentation id")),
+    responses(
+        (status = 200, description = "Longest gap between readings", body = TemperatureGaps),
+        (status = 401, description = "Not logged in"),
+        (status = 404, description = "Fermentation not found")
+    )
+)]
+pub async fn temperature_gaps(
     session: Session,
     State(state): State<AppState>,
     Path(fermentation_id): Path<i64>,
-    Json(mut request): Json<CreateTemperatureLogRequest>,
-) -> Result<(StatusCode, Json<TemperatureLog>), StatusCode> {
+) -> Result<Json<TemperatureGaps>, ApiError> {
     // Get user from session
-    let user_session: Option<UserSession> = session
+    let user: UserSession = session
         .get("user")
         .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        .map_err(|e| ApiError::InternalError(format!("Failed to get session: {}", e)))?
+        .ok_or(ApiError::Unauthorized)?;
 
-    let user = user_session.ok_or(StatusCode::UNAUTHORIZED)?;
+    let fermentation_repo = FermentationRepository::new(state.db.clone());
 
-    // Determine the temperature unit from request or default to Fahrenheit
-    let temp_unit = if let Some(ref unit_str) = request.temp_unit {
-        if unit_str == "celsius" {
-            crate::users::TemperatureUnit::Celsius
-        } else {
-            crate::users::TemperatureUnit::Fahrenheit
-        }
-    } else {
-        crate::users::TemperatureUnit::Fahrenheit
-    };
+    let gaps = fermentation_repo
+        .temperature_gaps(fermentation_id, user.user_id)
+        .await
+        .map_err(|e| {
+            let error_msg = e.to_string();
+            if error_msg.contains("not found") {
+                ApiError::NotFound(error_msg)
+            } else {
+                ApiError::DatabaseError(format!("Error computing temperature gaps: {}", error_msg))
+            }
+        })?;
 
-    // Validate temperature value based on unit
-    let (min_temp, max_temp) = match temp_unit {
-        crate::users::TemperatureUnit::Celsius => (-18.0, 65.0), // Roughly 0°F to 150°F
-        crate::users::TemperatureUnit::Fahrenheit => (0.0, 150.0),
-    };
+    Ok(Json(gaps))
+}
 
-    if request.temperature.is_nan()
-        || request.temperature.is_infinite()
-        || request.temperature < min_temp
-        || request.temperature > max_temp
-    {
-        return Err(StatusCode::BAD_REQUEST);
+#[utoipa::path(
+    put,
+    path = "/api/fermentation/{id}/temperature/{log_id}",
+    tag = "temperature",
+    params(("id" = i64, Path, description = "Fermentation id"), ("log_id" = i64, Path, description = "Temperature log id")),
+    request_body = UpdateTemperatureLogRequest,
+    responses(
+        (status = 200, description = "Updated reading", body = TemperatureLog),
+        (status = 400, description = "Invalid input"),
+        (status = 401, description = "Not logged in"),
+        (status = 404, description = "Fermentation or reading not found")
+    )
+)]
+pub async fn update_temperature_log(
+    session: Session,
+    State(state): State<AppState>,
+    Path((fermentation_id, log_id)): Path<(i64, i64)>,
+    Json(mut request): Json<UpdateTemperatureLogRequest>,
+) -> Result<Json<TemperatureLog>, ApiError> {
+    // Get user from session
+    let user: UserSession = session
+        .get("user")
+        .await
+        .map_err(|e| ApiError::InternalError(format!("Failed to get session: {}", e)))?
+        .ok_or(ApiError::Unauthorized)?;
+
+    // Apply the same unit handling and bounds as on create
+    let temp_unit = parse_temp_unit(request.temp_unit.as_deref());
+
+    if !is_valid_temperature(request.temperature, &temp_unit, &state.config) {
+        return Err(ApiError::invalid_field(
+            "temperature",
+            "Temperature is outside the plausible range",
+        ));
     }
 
     // Convert temperature to Fahrenheit for storage
@@ -253,78 +2509,143 @@ pub async fn create_temperature_log(
     // Validate recorded_at format if provided
     if let Some(ref recorded_at) = request.recorded_at {
         if chrono::DateTime::parse_from_rfc3339(recorded_at).is_err() {
-            return Err(StatusCode::BAD_REQUEST);
+            return Err(ApiError::invalid_field(
+                "recorded_at",
+                "Recorded at must be an RFC 3339 date",
+            ));
         }
     }
 
     let fermentation_repo = FermentationRepository::new(state.db.clone());
 
-    // Create the temperature log
     let temperature_log = fermentation_repo
-        .create_temperature_log(fermentation_id, user.user_id, request)
+        .update_temperature_log(fermentation_id, log_id, user.user_id, request)
         .await
-        .map_err(|e| {
-            let error_msg = e.to_string();
-            tracing::error!("Error creating temperature log: {}", error_msg);
-            if error_msg.contains("not found") {
-                StatusCode::NOT_FOUND
-            } else {
-                StatusCode::INTERNAL_SERVER_ERROR
-            }
-        })?;
+        .map_err(|e| ApiError::DatabaseError(format!("Error updating temperature log: {}", e)))?
+        .ok_or_else(|| ApiError::not_found("Temperature log not found"))?;
 
-    Ok((StatusCode::CREATED, Json(temperature_log)))
+    Ok(Json(temperature_log))
 }
 
-pub async fn list_temperature_logs(
+#[utoipa::path(
+    delete,
+    path = "/api/fermentation/{id}/temperature/{log_id}",
+    tag = "temperature",
+    params(("id" = i64, Path, description = "Fermentation id"), ("log_id" = i64, Path, description = "Temperature log id")),
+    responses(
+        (status = 204, description = "Reading deleted"),
+        (status = 401, description = "Not logged in"),
+        (status = 404, description = "Fermentation or reading not found")
+    )
+)]
+pub async fn delete_temperature_log(
     session: Session,
     State(state): State<AppState>,
-    Path(fermentation_id): Path<i64>,
-) -> Result<Json<Vec<TemperatureLog>>, StatusCode> {
+    Path((fermentation_id, log_id)): Path<(i64, i64)>,
+) -> Result<StatusCode, ApiError> {
     // Get user from session
-    let user_session: Option<UserSession> = session
+    let user: UserSession = session
         .get("user")
         .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        .map_err(|e| ApiError::InternalError(format!("Failed to get session: {}", e)))?
+        .ok_or(ApiError::Unauthorized)?;
+
+    let fermentation_repo = FermentationRepository::new(state.db.clone());
+
+    let deleted = fermentation_repo
+        .delete_temperature_log(fermentation_id, log_id, user.user_id)
+        .await
+        .map_err(|e| ApiError::DatabaseError(format!("Error deleting temperature log: {}", e)))?;
+
+    if deleted {
+        Ok(StatusCode::NO_CONTENT)
+    } else {
+        Err(ApiError::not_found("Temperature log not found"))
+    }
+}
 
-    let user = user_session.ok_or(StatusCode::UNAUTHORIZED)?;
+#[utoipa::path(
+    delete,
+    path = "/api/fermentation/{id}/temperature",
+    tag = "temperature",
+    params(("id" = i64, Path, description = "Fermentation id"), DeleteTemperatureLogsQuery),
+    responses(
+        (status = 200, description = "All readings deleted", body = DeletedTemperatureLogs),
+        (status = 400, description = "confirm=true missing"),
+        (status = 401, description = "Not logged in"),
+        (status = 404, description = "Fermentation not found")
+    )
+)]
+pub async fn delete_all_temperature_logs(
+    session: Session,
+    State(state): State<AppState>,
+    Path(fermentation_id): Path<i64>,
+    Query(query): Query<DeleteTemperatureLogsQuery>,
+) -> Result<Json<DeletedTemperatureLogs>, ApiError> {
+    // Get user from session
+    let user: UserSession = session
+        .get("user")
+        .await
+        .map_err(|e| ApiError::InternalError(format!("Failed to get session: {}", e)))?
+        .ok_or(ApiError::Unauthorized)?;
+
+    if !query.confirm {
+        return Err(ApiError::invalid_field(
+            "confirm",
+            "Pass confirm=true to delete all temperature readings",
+        ));
+    }
 
     let fermentation_repo = FermentationRepository::new(state.db.clone());
 
-    let logs = fermentation_repo
-        .find_temperature_logs_by_fermentation(fermentation_id, user.user_id)
+    let deleted = fermentation_repo
+        .delete_all_temperature_logs(fermentation_id, user.user_id)
         .await
-        .map_err(|e| {
-            let error_msg = e.to_string();
-            tracing::error!("Error fetching temperature logs: {}", error_msg);
-            if error_msg.contains("not found") {
-                StatusCode::NOT_FOUND
-            } else {
-                StatusCode::INTERNAL_SERVER_ERROR
-            }
-        })?;
+        .map_err(|e| ApiError::DatabaseError(format!("Error deleting temperature logs: {}", e)))?
+        .ok_or_else(|| ApiError::not_found("Fermentation not found"))?;
 
-    Ok(Json(logs))
+    Ok(Json(DeletedTemperatureLogs { deleted }))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/fermentation/{id}/finish",
+    tag = "fermentations",
+    params(("id" = i64, Path, description = "Fermentation id")),
+    request_body = FinishFermentationRequest,
+    responses(
+        (status = 200, description = "Completed fermentation", body = FermentationResponse),
+        (status = 400, description = "Invalid input"),
+        (status = 401, description = "Not logged in"),
+        (status = 404, description = "Fermentation not found"),
+        (status = 409, description = "Fermentation already finished")
+    )
+)]
 pub async fn finish_fermentation(
     session: Session,
     State(state): State<AppState>,
     Path(fermentation_id): Path<i64>,
-    Json(request): Json<FinishFermentationRequest>,
-) -> Result<Json<FermentationResponse>, StatusCode> {
+    Json(mut request): Json<FinishFermentationRequest>,
+) -> Result<Json<FermentationResponse>, ApiError> {
     // Get user from session
-    let user_session: Option<UserSession> = session
+    let user: UserSession = session
         .get("user")
         .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        .map_err(|e| ApiError::InternalError(format!("Failed to get session: {}", e)))?
+        .ok_or(ApiError::Unauthorized)?;
 
-    let user = user_session.ok_or(StatusCode::UNAUTHORIZED)?;
+    normalize_text_field(&mut request.lessons_learned, "lessons_learned")?;
+    normalize_text_field(&mut request.taste_profile, "taste_profile")?;
+    // A blank initial taste profile is treated as not provided
+    request.taste_profile = request.taste_profile.filter(|text| !text.is_empty());
 
     // Validate success rating if provided
     if let Some(rating) = request.success_rating {
         if !(1..=5).contains(&rating) {
-            return Err(StatusCode::BAD_REQUEST);
+            return Err(ApiError::invalid_field(
+                "success_rating",
+                "Success rating must be between 1 and 5",
+            ));
         }
     }
 
@@ -335,17 +2656,19 @@ pub async fn finish_fermentation(
         .finish_fermentation(fermentation_id, user.user_id, request)
         .await
         .map_err(|e| {
-            tracing::error!("Error finishing fermentation: {}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
+            if e.to_string().contains("already completed") {
+                ApiError::Conflict(e.to_string())
+            } else {
+                ApiError::DatabaseError(format!("Error finishing fermentation: {}", e))
+            }
         })?
-        .ok_or(StatusCode::NOT_FOUND)?;
+        .ok_or_else(|| ApiError::not_found("Fermentation not found"))?;
 
     // Get the profile for the response
     let profile = fermentation_repo
         .get_profile_by_id(fermentation.profile_id)
-        .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
-        .ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
+        .await?
+        .ok_or_else(|| ApiError::InternalError("Fermentation profile not found".to_string()))?;
 
     Ok(Json(FermentationResponse::from_fermentation_and_profile(
         fermentation,
@@ -353,29 +2676,53 @@ pub async fn finish_fermentation(
     )))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/fermentation/{id}/taste-profiles",
+    tag = "taste-profiles",
+    params(("id" = i64, Path, description = "Fermentation id")),
+    request_body = CreateTasteProfileRequest,
+    responses(
+        (status = 201, description = "Tasting note recorded", body = TasteProfile),
+        (status = 400, description = "Invalid input"),
+        (status = 401, description = "Not logged in"),
+        (status = 404, description = "Fermentation not found")
+    )
+)]
 pub async fn create_taste_profile(
     session: Session,
     State(state): State<AppState>,
     Path(fermentation_id): Path<i64>,
-    Json(request): Json<CreateTasteProfileRequest>,
-) -> Result<(StatusCode, Json<TasteProfile>), StatusCode> {
+    Json(mut request): Json<CreateTasteProfileRequest>,
+) -> Result<(StatusCode, Json<TasteProfile>), ApiError> {
     // Get user from session
-    let user_session: Option<UserSession> = session
+    let user: UserSession = session
         .get("user")
         .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-
-    let user = user_session.ok_or(StatusCode::UNAUTHORIZED)?;
+        .map_err(|e| ApiError::InternalError(format!("Failed to get session: {}", e)))?
+        .ok_or(ApiError::Unauthorized)?;
 
     // Validate profile_text
-    if request.profile_text.trim().is_empty() {
-        return Err(StatusCode::BAD_REQUEST);
+    request.profile_text = request.profile_text.trim().to_string();
+    if request.profile_text.is_empty()
+        || request.profile_text.chars().count() > MAX_TEXT_FIELD_LENGTH
+    {
+        return Err(ApiError::invalid_field(
+            "profile_text",
+            format!(
+                "Taste profile must be between 1 and {} characters",
+                MAX_TEXT_FIELD_LENGTH
+            ),
+        ));
     }
 
     // Validate tasted_at format if provided
     if let Some(ref tasted_at) = request.tasted_at {
         if chrono::DateTime::parse_from_rfc3339(tasted_at).is_err() {
-            return Err(StatusCode::BAD_REQUEST);
+            return Err(ApiError::invalid_field(
+                "tasted_at",
+                "Tasted at must be an RFC 3339 date",
+            ));
         }
     }
 
@@ -387,29 +2734,38 @@ pub async fn create_taste_profile(
         .await
         .map_err(|e| {
             let error_msg = e.to_string();
-            tracing::error!("Error creating taste profile: {}", error_msg);
             if error_msg.contains("not found") {
-                StatusCode::NOT_FOUND
+                ApiError::NotFound(error_msg)
             } else {
-                StatusCode::INTERNAL_SERVER_ERROR
+                ApiError::DatabaseError(format!("Error creating taste profile: {}", error_msg))
             }
         })?;
 
     Ok((StatusCode::CREATED, Json(taste_profile)))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/fermentation/{id}/taste-profiles",
+    tag = "taste-profiles",
+    params(("id" = i64, Path, description = "Fermentation id")),
+    responses(
+        (status = 200, description = "Tasting notes for the fermentation", body = [TasteProfile]),
+        (status = 401, description = "Not logged in"),
+        (status = 404, description = "Fermentation not found")
+    )
+)]
 pub async fn list_taste_profiles(
     session: Session,
     State(state): State<AppState>,
     Path(fermentation_id): Path<i64>,
-) -> Result<Json<Vec<TasteProfile>>, StatusCode> {
+) -> Result<Json<Vec<TasteProfile>>, ApiError> {
     // Get user from session
-    let user_session: Option<UserSession> = session
+    let user: UserSession = session
         .get("user")
         .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-
-    let user = user_session.ok_or(StatusCode::UNAUTHORIZED)?;
+        .map_err(|e| ApiError::InternalError(format!("Failed to get session: {}", e)))?
+        .ok_or(ApiError::Unauthorized)?;
 
     let fermentation_repo = FermentationRepository::new(state.db.clone());
 
@@ -418,13 +2774,283 @@ pub async fn list_taste_profiles(
         .await
         .map_err(|e| {
             let error_msg = e.to_string();
-            tracing::error!("Error fetching taste profiles: {}", error_msg);
             if error_msg.contains("not found") {
-                StatusCode::NOT_FOUND
+                ApiError::NotFound(error_msg)
             } else {
-                StatusCode::INTERNAL_SERVER_ERROR
+                ApiError::DatabaseError(format!("Error fetching taste profiles: {}", error_msg))
             }
         })?;
 
     Ok(Json(profiles))
 }
+
+#[utoipa::path(
+    get,
+    path = "/api/taste-profiles",
+    tag = "taste-profiles",
+    params(TasteProfileListQuery),
+    responses(
+        (status = 200, description = "Tasting notes across all of the user's fermentations", body = [TasteProfileWithFermentation]),
+        (status = 401, description = "Not logged in")
+    )
+)]
+pub async fn list_all_taste_profiles(
+    session: Session,
+    State(state): State<AppState>,
+    Query(query): Query<TasteProfileListQuery>,
+) -> Result<Json<Vec<TasteProfileWithFermentation>>, ApiError> {
+    // Get user from session
+    let user: UserSession = session
+        .get("user")
+        .await
+        .map_err(|e| ApiError::InternalError(format!("Failed to get session: {}", e)))?
+        .ok_or(ApiError::Unauthorized)?;
+
+    let fermentation_repo = FermentationRepository::new(state.db.clone());
+
+    let profiles = fermentation_repo
+        .find_all_taste_profiles_by_user(user.user_id, query.search)
+        .await
+        .map_err(|e| ApiError::DatabaseError(format!("Error fetching taste profiles: {}", e)))?;
+
+    Ok(Json(profiles))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/search",
+    tag = "fermentations",
+    params(SearchQuery),
+    responses(
+        (status = 200, description = "Matching fermentations, taste profiles and temperature notes", body = SearchResults),
+        (status = 400, description = "Invalid input"),
+        (status = 401, description = "Not logged in")
+    )
+)]
+pub async fn global_search(
+    session: Session,
+    State(state): State<AppState>,
+    Query(query): Query<SearchQuery>,
+) -> Result<Json<SearchResults>, ApiError> {
+    // Get user from session
+    let user: UserSession = session
+        .get("user")
+        .await
+        .map_err(|e| ApiError::InternalError(format!("Failed to get session: {}", e)))?
+        .ok_or(ApiError::Unauthorized)?;
+
+    let term = query.q.unwrap_or_default();
+    let term = term.trim();
+    if term.is_empty() {
+        return Err(ApiError::invalid_field("q", "Search term is required"));
+    }
+
+    let fermentation_repo = FermentationRepository::new(state.db.clone());
+
+    let results = fermentation_repo
+        .global_search(user.user_id, term)
+        .await
+        .map_err(|e| ApiError::DatabaseError(format!("Error running search: {}", e)))?;
+
+    Ok(Json(results))
+}
+
+/// Tags are case-insensitive labels of at most 50 characters
+fn normalize_tag_name(tag: &str) -> Option<String> {
+    let name = tag.trim().to_lowercase();
+    if name.is_empty() || name.chars().count() > 50 {
+        None
+    } else {
+        Some(name)
+    }
+}
+
+/// Determine the temperature unit from an optional request value, defaulting to Fahrenheit
+fn parse_temp_unit(unit: Option<&str>) -> crate::users::TemperatureUnit {
+    match unit {
+        Some("celsius") => crate::users::TemperatureUnit::Celsius,
+        _ => crate::users::TemperatureUnit::Fahrenheit,
+    }
+}
+
+/// Validate a temperature value against the configured range, which is given in Fahrenheit
+/// (the storage unit) and applies to readings in either unit once converted
+fn is_valid_temperature(
+    temperature: f64,
+    unit: &crate::users::TemperatureUnit,
+    config: &crate::config::AppConfig,
+) -> bool {
+    let fahrenheit = crate::users::temperature::convert_temp_for_storage(temperature, unit);
+
+    fahrenheit.is_finite()
+        && fahrenheit >= config.min_temperature_fahrenheit
+        && fahrenheit <= config.max_temperature_fahrenheit
+}
+
+/// Validate that a pH value lies on the 0-14 scale
+fn is_valid_ph(ph: f64) -> bool {
+    ph.is_finite() && (0.0..=14.0).contains(&ph)
+}
+
+fn parse_measurement_kind(kind: &str) -> Result<MeasurementKind, ApiError> {
+    MeasurementKind::parse(kind).ok_or_else(|| {
+        ApiError::invalid_field("kind", "Kind must be one of: weight, volume, ph, gravity")
+    })
+}
+
+/// Validate that a specific gravity reading is plausible for a fermenting liquid
+fn is_valid_gravity(gravity: f64) -> bool {
+    gravity.is_finite() && (0.980..=1.200).contains(&gravity)
+}
+
+/// Validate one `recorded_at,temperature,temp_unit,notes` import row and convert it for storage
+fn parse_temperature_import_row(
+    fields: &[String],
+    config: &crate::config::AppConfig,
+) -> Result<CreateTemperatureLogRequest, String> {
+    if fields.len() < 2 || fields.len() > 4 {
+        return Err(format!("Expected 2 to 4 columns, found {}", fields.len()));
+    }
+
+    let recorded_at = fields[0].trim();
+    if chrono::DateTime::parse_from_rfc3339(recorded_at).is_err() {
+        return Err(format!("Invalid recorded_at '{}'", recorded_at));
+    }
+
+    let temperature: f64 = fields[1]
+        .trim()
+        .parse()
+        .map_err(|_| format!("Invalid temperature '{}'", fields[1].trim()))?;
+
+    let unit_str = fields.get(2).map(|u| u.trim()).unwrap_or("");
+    if !unit_str.is_empty() && !crate::users::TemperatureUnit::is_valid(unit_str) {
+        return Err(format!("Invalid temp_unit '{}'", unit_str));
+    }
+    let temp_unit = parse_temp_unit(Some(unit_str));
+
+    if !is_valid_temperature(temperature, &temp_unit, config) {
+        return Err(format!("Temperature {} is out of range", temperature));
+    }
+
+    let notes = fields
+        .get(3)
+        .map(|n| n.trim().to_string())
+        .filter(|n| !n.is_empty());
+
+    Ok(CreateTemperatureLogRequest {
+        temperature: crate::users::temperature::convert_temp_for_storage(temperature, &temp_unit),
+        temp_unit: Some(temp_unit.as_str().to_string()),
+        recorded_at: Some(recorded_at.to_string()),
+        notes,
+    })
+}
+
+/// Split CSV text into records of fields, honouring double-quoted values, which may span
+/// line breaks. Each record is paired with the line number it starts on.
+fn parse_csv_records(text: &str) -> Vec<(usize, Vec<String>)> {
+    let mut records = Vec::new();
+    let mut fields = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+    let mut line = 1;
+    let mut record_line = 1;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                current.push('"');
+                chars.next();
+            }
+            '"' => in_quotes = !in_quotes,
+            ',' if !in_quotes => fields.push(std::mem::take(&mut current)),
+            '\r' if !in_quotes && chars.peek() == Some(&'\n') => {}
+            '\n' if !in_quotes => {
+                fields.push(std::mem::take(&mut current));
+                records.push((record_line, std::mem::take(&mut fields)));
+                line += 1;
+                record_line = line;
+            }
+            _ => {
+                if c == '\n' {
+                    line += 1;
+                }
+                current.push(c);
+            }
+        }
+    }
+
+    if !current.is_empty() || !fields.is_empty() {
+        fields.push(current);
+        records.push((record_line, fields));
+    }
+
+    records
+}
+
+/// Quote a CSV field if it contains separators, quotes, or line breaks.
+/// Values a spreadsheet would evaluate as a formula are prefixed with `'`.
+fn escape_csv_field(value: &str) -> String {
+    let value = if value.starts_with(['=', '+', '-', '@']) {
+        format!("'{}", value)
+    } else {
+        value.to_string()
+    };
+
+    if value.contains(',') || value.contains('"') || value.contains('\n') || value.contains('\r') {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
+    }
+}
+
+/// Build a filesystem-safe filename stem from a fermentation name
+fn csv_filename_stem(name: &str) -> String {
+    let stem: String = name
+        .trim()
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+
+    if stem.is_empty() {
+        "fermentation".to_string()
+    } else {
+        stem
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/dashboard/stats",
+    tag = "dashboard",
+    responses(
+        (status = 200, description = "Counts and averages across the user's fermentations", body = DashboardStats),
+        (status = 401, description = "Not logged in")
+    )
+)]
+pub async fn dashboard_stats(
+    session: Session,
+    State(state): State<AppState>,
+) -> Result<Json<DashboardStats>, ApiError> {
+    // Get user from session
+    let user: UserSession = session
+        .get("user")
+        .await
+        .map_err(|e| ApiError::InternalError(format!("Failed to get session: {}", e)))?
+        .ok_or(ApiError::Unauthorized)?;
+
+    let fermentation_repo = FermentationRepository::new(state.db.clone());
+
+    let stats = fermentation_repo
+        .user_stats(user.user_id)
+        .await
+        .map_err(|e| ApiError::DatabaseError(format!("Error computing dashboard stats: {}", e)))?;
+
+    Ok(Json(stats))
+}
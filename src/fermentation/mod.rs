@@ -5,17 +5,37 @@ pub mod templates;
 
 // Re-export commonly used items for convenience
 pub use handlers::{
-    create_fermentation, create_taste_profile, create_temperature_log, finish_fermentation,
-    get_profiles, list_fermentations, list_taste_profiles, list_temperature_logs,
-    update_fermentation,
+    add_tag, archive_fermentation, bulk_update_status, clone_fermentation, create_fermentation,
+    create_gravity_log, create_journal_entry, create_measurement_log, create_ph_log,
+    create_taste_profile, create_temperature_log, create_webhook_token, dashboard_stats,
+    delete_all_temperature_logs, delete_fermentation, delete_journal_entry, delete_temperature_log,
+    export_fermentation, export_temperature_logs_csv, favorite_fermentation, fermentation_timeline,
+    finish_fermentation, get_fermentation, get_profiles, global_search, gravity_stats,
+    import_temperature_logs_csv, list_all_taste_profiles, list_by_ingredient, list_due_soon,
+    list_fermentations, list_gravity_logs, list_journal_entries, list_measurement_logs,
+    list_ph_logs, list_recent, list_tags, list_taste_profiles, list_temperature_logs,
+    patch_fermentation, profile_breakdown, profile_success, rating_distribution, remove_tag,
+    reopen_fermentation, restore_fermentation, revoke_webhook_token, share_fermentation,
+    temperature_gaps, temperature_stats, temperature_webhook, unarchive_fermentation,
+    unfavorite_fermentation, unshare_fermentation, update_fermentation, update_temperature_log,
 };
 pub use models::{
-    CreateFermentationRequest, CreateTasteProfileRequest, CreateTemperatureLogRequest,
-    Fermentation, FermentationProfile, FermentationResponse, FermentationStatus,
-    FinishFermentationRequest, TasteProfile, TemperatureLog, UpdateFermentationRequest,
+    BulkStatusRequest, BulkStatusResponse, CreateFermentationRequest, CreateGravityLogRequest,
+    CreateJournalEntryRequest, CreateMeasurementLogRequest, CreatePhLogRequest,
+    CreateTasteProfileRequest, CreateTemperatureLogRequest, DashboardStats,
+    DeleteTemperatureLogsQuery, DeletedTemperatureLogs, DueFermentation, DueSoonQuery,
+    Fermentation, FermentationExport, FermentationProfile, FermentationProfileResponse,
+    FermentationResponse, FermentationSearchHit, FermentationShare, FermentationStatus,
+    FinishFermentationRequest, GravityLog, GravityStats, Ingredient, IngredientQuery, JournalEntry,
+    MeasurementKind, MeasurementLog, MeasurementLogQuery, PhLog, ProfileSuccess, ProfileType,
+    RatingDistribution, RecentFermentationsQuery, SearchQuery, SearchResults, Tag, TagRequest,
+    TasteProfile, TasteProfileListQuery, TasteProfileWithFermentation, TemperatureGaps,
+    TemperatureImportRowError, TemperatureLog, TemperatureLogResponse, TemperatureLogSearchHit,
+    TemperatureStats, TimelineEvent, UpdateFermentationRequest, UpdateTemperatureLogRequest,
+    WebhookTokenResponse,
 };
 pub use repository::FermentationRepository;
 pub use templates::{
     edit_fermentation_handler, fermentation_detail_handler, fermentation_list_handler,
-    new_fermentation_handler,
+    new_fermentation_handler, shared_fermentation_handler,
 };
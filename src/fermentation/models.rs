@@ -1,13 +1,15 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum FermentationStatus {
     Active,
     Paused,
     Completed,
     Failed,
+    Archived,
 }
 
 impl FermentationStatus {
@@ -17,6 +19,32 @@ impl FermentationStatus {
             FermentationStatus::Paused => "paused",
             FermentationStatus::Completed => "completed",
             FermentationStatus::Failed => "failed",
+            FermentationStatus::Archived => "archived",
+        }
+    }
+
+    /// Human-friendly label for the HTML pages; JSON keeps the `as_str` values. Kept in one
+    /// place so the labels can be translated later.
+    pub fn display_label(&self) -> &str {
+        match self {
+            FermentationStatus::Active => "Active",
+            FermentationStatus::Paused => "Paused",
+            FermentationStatus::Completed => "Completed",
+            FermentationStatus::Failed => "Failed",
+            FermentationStatus::Archived => "Archived",
+        }
+    }
+
+    /// Strict counterpart of `From<String>`, rejecting unknown statuses instead of
+    /// falling back to active
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "active" => Some(FermentationStatus::Active),
+            "paused" => Some(FermentationStatus::Paused),
+            "completed" => Some(FermentationStatus::Completed),
+            "failed" => Some(FermentationStatus::Failed),
+            "archived" => Some(FermentationStatus::Archived),
+            _ => None,
         }
     }
 }
@@ -27,30 +55,129 @@ impl From<String> for FermentationStatus {
             "paused" => FermentationStatus::Paused,
             "completed" => FermentationStatus::Completed,
             "failed" => FermentationStatus::Failed,
+            "archived" => FermentationStatus::Archived,
             _ => FermentationStatus::Active,
         }
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Known profile types. Types are stored lowercase; other values are only accepted when
+/// `allow_custom_types` is enabled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ProfileType {
+    Vegetable,
+    Beverage,
+    Dairy,
+    Grain,
+    Bread,
+}
+
+impl ProfileType {
+    pub const ALL: [ProfileType; 5] = [
+        ProfileType::Vegetable,
+        ProfileType::Beverage,
+        ProfileType::Dairy,
+        ProfileType::Grain,
+        ProfileType::Bread,
+    ];
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            ProfileType::Vegetable => "vegetable",
+            ProfileType::Beverage => "beverage",
+            ProfileType::Dairy => "dairy",
+            ProfileType::Grain => "grain",
+            ProfileType::Bread => "bread",
+        }
+    }
+
+    /// Parse a type, ignoring case and surrounding whitespace
+    pub fn parse(s: &str) -> Option<Self> {
+        match Self::normalize(s).as_str() {
+            "vegetable" => Some(ProfileType::Vegetable),
+            "beverage" => Some(ProfileType::Beverage),
+            "dairy" => Some(ProfileType::Dairy),
+            "grain" => Some(ProfileType::Grain),
+            "bread" => Some(ProfileType::Bread),
+            _ => None,
+        }
+    }
+
+    /// The form profile types are stored and compared in, so "Vegetable " matches "vegetable"
+    pub fn normalize(s: &str) -> String {
+        s.trim().to_lowercase()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct FermentationProfile {
     pub id: i64,
     pub name: String,
     pub r#type: String,
     pub min_days: i32,
     pub max_days: i32,
+    /// Lower end of the recommended temperature range, stored in Fahrenheit
     pub temp_min: f64,
+    /// Upper end of the recommended temperature range, stored in Fahrenheit
     pub temp_max: f64,
     pub description: Option<String>,
     pub is_active: bool,
     pub created_at: DateTime<Utc>,
+    /// Admin who created the profile; `None` for the seeded profiles
+    #[serde(default)]
+    pub created_by: Option<i64>,
+    /// Typical ingredients, one per line, to pre-fill the new-fermentation form
+    #[serde(default)]
+    pub default_ingredients: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+impl FermentationProfile {
+    /// Convert the stored Fahrenheit temperature range into the given display unit
+    pub fn in_unit(mut self, unit: &crate::users::TemperatureUnit) -> Self {
+        use crate::users::temperature::convert_temp_for_display;
+
+        self.temp_min = convert_temp_for_display(self.temp_min, unit);
+        self.temp_max = convert_temp_for_display(self.temp_max, unit);
+        self
+    }
+
+    /// Keep the stored Fahrenheit range and add it converted to the given display unit
+    pub fn with_display_unit(
+        self,
+        unit: &crate::users::TemperatureUnit,
+    ) -> FermentationProfileResponse {
+        use crate::users::temperature::{convert_temp_for_display, get_unit_symbol};
+
+        FermentationProfileResponse {
+            temp_min_display: convert_temp_for_display(self.temp_min, unit),
+            temp_max_display: convert_temp_for_display(self.temp_max, unit),
+            temp_unit: unit.as_str().to_string(),
+            unit_symbol: get_unit_symbol(unit).to_string(),
+            profile: self,
+        }
+    }
+}
+
+/// A profile with its temperature range both as stored (Fahrenheit) and in the viewer's unit
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct FermentationProfileResponse {
+    #[serde(flatten)]
+    pub profile: FermentationProfile,
+    pub temp_min_display: f64,
+    pub temp_max_display: f64,
+    /// Unit of the display values, "fahrenheit" or "celsius"
+    pub temp_unit: String,
+    pub unit_symbol: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct Fermentation {
     pub id: i64,
     pub user_id: i64,
     pub profile_id: i64,
+    /// Sequential number among the owner's fermentations, starting at 1
+    pub batch_number: i64,
     pub name: String,
     pub start_date: DateTime<Utc>,
     pub target_end_date: Option<DateTime<Utc>>,
@@ -62,6 +189,10 @@ pub struct Fermentation {
     pub lessons_learned: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// Starred by the owner for quick access
+    pub is_favorite: bool,
+    /// Incremented on every change; send it back with an update to detect concurrent edits
+    pub version: i64,
     // Joined from profile
     pub profile_name: Option<String>,
     pub profile_type: Option<String>,
@@ -77,10 +208,12 @@ impl Fermentation {
             return false;
         }
 
-        // Don't show countdown if already completed or failed
+        // Don't show countdown if already completed, failed or archived
         if matches!(
             self.status,
-            FermentationStatus::Completed | FermentationStatus::Failed
+            FermentationStatus::Completed
+                | FermentationStatus::Failed
+                | FermentationStatus::Archived
         ) {
             return false;
         }
@@ -113,6 +246,94 @@ impl Fermentation {
         false
     }
 
+    /// Returns true if the fermentation is still active but past its target end date
+    pub fn is_overdue(&self) -> bool {
+        matches!(self.status, FermentationStatus::Active)
+            && self
+                .target_end_date
+                .is_some_and(|target| Utc::now() > target)
+    }
+
+    /// Whole days until the target end date (negative once it has passed)
+    pub fn days_remaining(&self) -> Option<i64> {
+        self.target_end_date
+            .map(|target| target.signed_duration_since(Utc::now()).num_days())
+    }
+
+    /// Whole days the fermentation actually took, once it has been completed
+    pub fn actual_duration_days(&self) -> Option<i64> {
+        match (&self.status, self.actual_end_date) {
+            (FermentationStatus::Completed, Some(end)) => {
+                Some(end.signed_duration_since(self.start_date).num_days())
+            }
+            _ => None,
+        }
+    }
+
+    /// Actual duration for display, e.g. "1 day" or "12 days"
+    pub fn actual_duration_display(&self) -> Option<String> {
+        self.actual_duration_days().map(|days| {
+            if days == 1 {
+                "1 day".to_string()
+            } else {
+                format!("{} days", days)
+            }
+        })
+    }
+
+    /// Whole days from the start to the target end date
+    pub fn target_duration_days(&self) -> Option<i64> {
+        self.target_end_date
+            .map(|target| target.signed_duration_since(self.start_date).num_days())
+    }
+
+    /// Entity tag for conditional requests, derived from the version every write bumps.
+    /// The remaining days are included because they shift with the clock rather than with edits,
+    /// and the embedded profile and the viewer's unit because either can change what the client
+    /// shows without a write to the fermentation.
+    pub fn etag(
+        &self,
+        profile: &FermentationProfile,
+        unit: &crate::users::TemperatureUnit,
+    ) -> String {
+        use std::hash::{Hash, Hasher};
+
+        let days_remaining = self
+            .days_remaining()
+            .map_or_else(|| "none".to_string(), |days| days.to_string());
+
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        serde_json::to_string(profile)
+            .unwrap_or_default()
+            .hash(&mut hasher);
+        unit.as_str().hash(&mut hasher);
+
+        format!(
+            "\"{}-{}-{}-{:x}\"",
+            self.id,
+            self.version,
+            days_remaining,
+            hasher.finish()
+        )
+    }
+
+    /// Returns the structured ingredient list, tolerating legacy plain-text values
+    pub fn ingredients(&self) -> Vec<Ingredient> {
+        self.ingredients_json
+            .as_deref()
+            .map(Ingredient::from_stored)
+            .unwrap_or_default()
+    }
+
+    /// Returns the ingredients as editable text, one per line
+    pub fn ingredients_text(&self) -> String {
+        self.ingredients()
+            .iter()
+            .map(|i| i.to_string())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
     /// Returns the countdown display string
     /// Format: "X days" for more than 1 day remaining
     /// Format: "Xh Ym" for final day (less than 24 hours)
@@ -150,45 +371,194 @@ impl Fermentation {
     }
 }
 
+/// A single ingredient of a fermentation, stored as a JSON list in `ingredients_json`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct Ingredient {
+    pub name: String,
+    pub quantity: Option<f64>,
+    pub unit: Option<String>,
+}
+
+impl Ingredient {
+    /// Parse a stored `ingredients_json` value. Older rows hold free text rather than JSON,
+    /// which is read as one ingredient per line.
+    pub fn from_stored(value: &str) -> Vec<Ingredient> {
+        serde_json::from_str(value).unwrap_or_else(|_| Self::parse_list(value))
+    }
+
+    /// Parse free text with one ingredient per line, e.g. "2 lbs cucumbers" or "dill"
+    pub fn parse_list(text: &str) -> Vec<Ingredient> {
+        text.lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(Self::parse_line)
+            .collect()
+    }
+
+    fn parse_line(line: &str) -> Ingredient {
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+
+        match tokens.first().and_then(|t| t.parse::<f64>().ok()) {
+            Some(quantity) if quantity.is_finite() && tokens.len() >= 3 => Ingredient {
+                name: tokens[2..].join(" "),
+                quantity: Some(quantity),
+                unit: Some(tokens[1].to_string()),
+            },
+            Some(quantity) if quantity.is_finite() && tokens.len() == 2 => Ingredient {
+                name: tokens[1].to_string(),
+                quantity: Some(quantity),
+                unit: None,
+            },
+            _ => Ingredient {
+                name: line.to_string(),
+                quantity: None,
+                unit: None,
+            },
+        }
+    }
+
+    /// Quantity and unit for display, e.g. "2 lbs"; empty if no quantity was given
+    pub fn amount_display(&self) -> String {
+        match (self.quantity, &self.unit) {
+            (Some(q), Some(u)) => format!("{} {}", q, u),
+            (Some(q), None) => q.to_string(),
+            (None, Some(u)) => u.clone(),
+            (None, None) => String::new(),
+        }
+    }
+}
+
+impl std::fmt::Display for Ingredient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let amount = self.amount_display();
+        if amount.is_empty() {
+            write!(f, "{}", self.name)
+        } else {
+            write!(f, "{} {}", amount, self.name)
+        }
+    }
+}
+
+/// Accepts either a JSON array of ingredients or free text (one ingredient per line)
+fn deserialize_ingredients<'de, D>(deserializer: D) -> Result<Option<Vec<Ingredient>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum IngredientsInput {
+        List(Vec<Ingredient>),
+        Text(String),
+    }
+
+    Ok(
+        Option::<IngredientsInput>::deserialize(deserializer)?.map(|input| match input {
+            IngredientsInput::List(list) => list,
+            IngredientsInput::Text(text) => Ingredient::parse_list(&text),
+        }),
+    )
+}
+
+/// Like `deserialize_ingredients`, but keeps an explicit `null` apart from a missing field
+fn deserialize_nullable_ingredients<'de, D>(
+    deserializer: D,
+) -> Result<Option<Option<Vec<Ingredient>>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    deserialize_ingredients(deserializer).map(Some)
+}
+
+/// For fields that can be cleared: only called when the field is present, so a missing
+/// field stays `None` (via `#[serde(default)]`) while `null` becomes `Some(None)`
+fn deserialize_nullable<'de, T, D>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    T: Deserialize<'de>,
+    D: serde::Deserializer<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
+/// Serialize an ingredient list for the `ingredients_json` column; empty lists are stored as NULL
+pub fn ingredients_to_json(ingredients: &[Ingredient]) -> Option<String> {
+    if ingredients.is_empty() {
+        None
+    } else {
+        serde_json::to_string(ingredients).ok()
+    }
+}
+
 #[derive(Debug, Serialize)]
 pub struct FermentationWithProfile {
     pub fermentation: Fermentation,
     pub profile: FermentationProfile,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct CreateFermentationRequest {
     pub profile_id: i64,
     pub name: String,
     pub start_date: String,              // ISO 8601 format
     pub target_end_date: Option<String>, // ISO 8601 format
     pub notes: Option<String>,
-    pub ingredients: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_ingredients")]
+    pub ingredients: Option<Vec<Ingredient>>,
 }
 
-#[derive(Debug, Deserialize)]
+/// Partial update: fields left out of the body are not touched. The clearable fields use
+/// `Option<Option<_>>` so that `null` (`Some(None)`) clears the value while a missing field
+/// (`None`) leaves it unchanged.
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct UpdateFermentationRequest {
     pub name: Option<String>,
-    pub start_date: Option<String>,      // ISO 8601 format
-    pub target_end_date: Option<String>, // ISO 8601 format
-    pub actual_end_date: Option<String>, // ISO 8601 format
+    pub start_date: Option<String>, // ISO 8601 format
+    /// ISO 8601 format; `null` or an empty string clears it
+    #[serde(default, deserialize_with = "deserialize_nullable")]
+    #[schema(value_type = Option<String>)]
+    pub target_end_date: Option<Option<String>>,
+    /// ISO 8601 format; `null` or an empty string clears it
+    #[serde(default, deserialize_with = "deserialize_nullable")]
+    #[schema(value_type = Option<String>)]
+    pub actual_end_date: Option<Option<String>>,
     pub status: Option<String>,
-    pub success_rating: Option<i32>,
-    pub notes: Option<String>,
-    pub ingredients: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_nullable")]
+    #[schema(value_type = Option<i32>)]
+    pub success_rating: Option<Option<i32>>,
+    #[serde(default, deserialize_with = "deserialize_nullable")]
+    #[schema(value_type = Option<String>)]
+    pub notes: Option<Option<String>>,
+    #[serde(default, deserialize_with = "deserialize_nullable_ingredients")]
+    #[schema(value_type = Option<Vec<Ingredient>>)]
+    pub ingredients: Option<Option<Vec<Ingredient>>>,
+    /// The `version` the client last read; if the fermentation has changed since, the update
+    /// is rejected with 409. Left out, the update applies unconditionally.
+    pub version: Option<i64>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct FermentationResponse {
     pub id: i64,
+    pub batch_number: i64,
     pub profile_id: i64,
     pub profile_name: String,
     pub name: String,
     pub start_date: DateTime<Utc>,
     pub target_end_date: Option<DateTime<Utc>>,
+    /// True when the target end date was filled in from the profile rather than set by the user
+    pub target_end_date_suggested: bool,
     pub status: FermentationStatus,
     pub notes: Option<String>,
     pub created_at: DateTime<Utc>,
+    pub is_overdue: bool,
+    pub days_remaining: Option<i64>,
+    /// Days from start to actual end date; only set for completed fermentations
+    pub actual_duration_days: Option<i64>,
+    /// Days from start to target end date
+    pub target_duration_days: Option<i64>,
+    pub is_favorite: bool,
+    pub version: i64,
+    /// The full profile, so clients can show the recommended conditions without another request
+    pub profile: FermentationProfile,
 }
 
 impl FermentationResponse {
@@ -197,15 +567,24 @@ impl FermentationResponse {
         profile: FermentationProfile,
     ) -> Self {
         Self {
+            is_overdue: fermentation.is_overdue(),
+            days_remaining: fermentation.days_remaining(),
+            actual_duration_days: fermentation.actual_duration_days(),
+            target_duration_days: fermentation.target_duration_days(),
+            is_favorite: fermentation.is_favorite,
+            version: fermentation.version,
             id: fermentation.id,
+            batch_number: fermentation.batch_number,
             profile_id: fermentation.profile_id,
-            profile_name: profile.name,
+            profile_name: profile.name.clone(),
             name: fermentation.name,
             start_date: fermentation.start_date,
             target_end_date: fermentation.target_end_date,
+            target_end_date_suggested: false,
             status: fermentation.status,
             notes: fermentation.notes,
             created_at: fermentation.created_at,
+            profile,
         }
     }
 }
@@ -224,6 +603,7 @@ mod tests {
             id: 1,
             user_id: 1,
             profile_id: 1,
+            batch_number: 1,
             name: "Test Fermentation".to_string(),
             start_date: now - Duration::days(3),
             target_end_date,
@@ -235,12 +615,83 @@ mod tests {
             lessons_learned: None,
             created_at: now,
             updated_at: now,
+            is_favorite: false,
+            version: 1,
             profile_name: Some("Test Profile".to_string()),
             profile_type: Some("test".to_string()),
             thumbnail_path: None,
         }
     }
 
+    #[test]
+    fn test_status_display_label() {
+        assert_eq!(FermentationStatus::Active.display_label(), "Active");
+        assert_eq!(FermentationStatus::Completed.display_label(), "Completed");
+        // The serialized value stays machine-readable
+        assert_eq!(
+            serde_json::to_value(FermentationStatus::Completed).unwrap(),
+            "completed"
+        );
+    }
+
+    #[test]
+    fn test_durations() {
+        let mut fermentation = create_test_fermentation(None, FermentationStatus::Active);
+        assert_eq!(fermentation.target_duration_days(), None);
+        assert_eq!(fermentation.actual_duration_days(), None);
+
+        fermentation.target_end_date = Some(fermentation.start_date + Duration::days(7));
+        assert_eq!(fermentation.target_duration_days(), Some(7));
+
+        // An end date alone isn't enough until the fermentation is completed
+        fermentation.actual_end_date = Some(fermentation.start_date + Duration::days(9));
+        assert_eq!(fermentation.actual_duration_days(), None);
+
+        fermentation.status = FermentationStatus::Completed;
+        assert_eq!(fermentation.actual_duration_days(), Some(9));
+        assert_eq!(
+            fermentation.actual_duration_display().as_deref(),
+            Some("9 days")
+        );
+    }
+
+    #[test]
+    fn test_etag_changes_with_version_profile_and_unit() {
+        use crate::users::TemperatureUnit;
+
+        let mut fermentation = create_test_fermentation(None, FermentationStatus::Active);
+        let mut profile = FermentationProfile {
+            id: 1,
+            name: "Test Profile".to_string(),
+            r#type: "test".to_string(),
+            min_days: 3,
+            max_days: 7,
+            temp_min: 65.0,
+            temp_max: 75.0,
+            description: None,
+            is_active: true,
+            created_at: Utc::now(),
+            created_by: None,
+            default_ingredients: None,
+        };
+        let etag = fermentation.etag(&profile, &TemperatureUnit::Fahrenheit);
+        assert_eq!(
+            etag,
+            fermentation.etag(&profile, &TemperatureUnit::Fahrenheit)
+        );
+        assert_ne!(etag, fermentation.etag(&profile, &TemperatureUnit::Celsius));
+
+        profile.temp_max = 78.0;
+        let edited_profile = fermentation.etag(&profile, &TemperatureUnit::Fahrenheit);
+        assert_ne!(etag, edited_profile);
+
+        fermentation.version += 1;
+        assert_ne!(
+            edited_profile,
+            fermentation.etag(&profile, &TemperatureUnit::Fahrenheit)
+        );
+    }
+
     #[test]
     fn test_should_show_countdown_with_future_target_active() {
         let future_date = Utc::now() + Duration::days(5);
@@ -348,10 +799,138 @@ mod tests {
         let fermentation = create_test_fermentation(None, FermentationStatus::Active);
         assert!(fermentation.countdown_display().is_none());
     }
+
+    #[test]
+    fn test_is_overdue_when_active_past_target() {
+        let past_date = Utc::now() - Duration::days(2) - Duration::hours(1);
+        let fermentation = create_test_fermentation(Some(past_date), FermentationStatus::Active);
+        assert!(fermentation.is_overdue());
+        assert_eq!(fermentation.days_remaining(), Some(-2));
+    }
+
+    #[test]
+    fn test_is_not_overdue_when_paused_or_completed() {
+        let past_date = Utc::now() - Duration::days(1);
+        let paused = create_test_fermentation(Some(past_date), FermentationStatus::Paused);
+        let completed = create_test_fermentation(Some(past_date), FermentationStatus::Completed);
+        assert!(!paused.is_overdue());
+        assert!(!completed.is_overdue());
+    }
+
+    #[test]
+    fn test_days_remaining_before_target() {
+        let future_date = Utc::now() + Duration::days(5) + Duration::hours(1);
+        let fermentation = create_test_fermentation(Some(future_date), FermentationStatus::Active);
+        assert!(!fermentation.is_overdue());
+        assert_eq!(fermentation.days_remaining(), Some(5));
+
+        let no_target = create_test_fermentation(None, FermentationStatus::Active);
+        assert!(!no_target.is_overdue());
+        assert_eq!(no_target.days_remaining(), None);
+    }
+
+    #[test]
+    fn test_ingredients_from_json_list() {
+        let mut fermentation = create_test_fermentation(None, FermentationStatus::Active);
+        fermentation.ingredients_json =
+            Some(r#"[{"name":"cabbage","quantity":2.0,"unit":"kg"},{"name":"salt"}]"#.to_string());
+
+        let ingredients = fermentation.ingredients();
+        assert_eq!(ingredients.len(), 2);
+        assert_eq!(ingredients[0].name, "cabbage");
+        assert_eq!(ingredients[0].amount_display(), "2 kg");
+        assert_eq!(ingredients[1].quantity, None);
+        assert_eq!(fermentation.ingredients_text(), "2 kg cabbage\nsalt");
+    }
+
+    #[test]
+    fn test_ingredients_tolerate_plain_text() {
+        let mut fermentation = create_test_fermentation(None, FermentationStatus::Active);
+        fermentation.ingredients_json = Some("cucumbers, salt, water".to_string());
+
+        let ingredients = fermentation.ingredients();
+        assert_eq!(ingredients.len(), 1);
+        assert_eq!(ingredients[0].name, "cucumbers, salt, water");
+        assert_eq!(ingredients[0].quantity, None);
+    }
+
+    #[test]
+    fn test_parse_ingredient_lines() {
+        let ingredients = Ingredient::parse_list("2 lbs cucumbers\n\n3 garlic\n  dill  ");
+
+        assert_eq!(
+            ingredients,
+            vec![
+                Ingredient {
+                    name: "cucumbers".to_string(),
+                    quantity: Some(2.0),
+                    unit: Some("lbs".to_string()),
+                },
+                Ingredient {
+                    name: "garlic".to_string(),
+                    quantity: Some(3.0),
+                    unit: None,
+                },
+                Ingredient {
+                    name: "dill".to_string(),
+                    quantity: None,
+                    unit: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_estimate_abv() {
+        assert_eq!(estimate_abv(1.050, 1.010), 5.25);
+        assert_eq!(estimate_abv(1.020, 1.020), 0.0);
+        // A rising gravity never yields a negative estimate
+        assert_eq!(estimate_abv(1.010, 1.020), 0.0);
+    }
+
+    #[test]
+    fn test_gravity_stats_needs_two_readings() {
+        let single = GravityStats::from_readings(1, Some(1.050), Some(1.050));
+        assert_eq!(single.estimated_abv, None);
+
+        let none = GravityStats::from_readings(0, None, None);
+        assert_eq!(none.estimated_abv, None);
+
+        let pair = GravityStats::from_readings(2, Some(1.060), Some(1.000));
+        assert_eq!(pair.estimated_abv, Some(7.88));
+    }
+
+    #[test]
+    fn test_temperature_gaps_from_readings() {
+        let now = Utc::now();
+        let readings = [
+            now - Duration::hours(30),
+            now - Duration::hours(26),
+            now - Duration::hours(14),
+            now - Duration::hours(12),
+        ];
+
+        let gaps = TemperatureGaps::from_readings(&readings, now);
+        assert_eq!(gaps.count, 4);
+        assert_eq!(gaps.longest_gap_hours, Some(12.0));
+        assert_eq!(gaps.longest_gap_started_at, Some(readings[1]));
+        assert_eq!(gaps.longest_gap_ended_at, Some(readings[2]));
+        assert_eq!(gaps.hours_since_last_reading, Some(12.0));
+
+        let single = TemperatureGaps::from_readings(&readings[..1], now);
+        assert_eq!(single.longest_gap_hours, None);
+        assert_eq!(single.hours_since_last_reading, Some(30.0));
+
+        let empty = TemperatureGaps::from_readings(&[], now);
+        assert_eq!(empty.count, 0);
+        assert_eq!(empty.last_recorded_at, None);
+        assert_eq!(empty.hours_since_last_reading, None);
+    }
 }
 
 /// Query parameters for filtering and sorting fermentations list
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct FermentationListQuery {
     /// Search term to filter by name, notes, or ingredients
     pub search: Option<String>,
@@ -359,26 +938,222 @@ pub struct FermentationListQuery {
     pub status: Option<String>,
     /// Filter by profile type
     pub profile_type: Option<String>,
-    /// Sort field: "name", "start_date", "status", "created_at"
+    /// Filter by tag name
+    pub tag: Option<String>,
+    /// Include archived fermentations when no status filter is given
+    pub include_archived: Option<bool>,
+    /// Only fermentations started at or after this time (RFC3339)
+    pub started_after: Option<String>,
+    /// Only fermentations started at or before this time (RFC3339)
+    pub started_before: Option<String>,
+    /// Only fermentations with (true) or without (false) at least one photo
+    pub has_photos: Option<bool>,
+    /// Only starred fermentations when true
+    pub favorites_only: Option<bool>,
+    /// Sort field: "name", "start_date", "status", "success_rating", "batch_number", "updated_at",
+    /// "created_at"
     pub sort_by: Option<String>,
     /// Sort order: "asc" or "desc"
     pub sort_order: Option<String>,
 }
 
+/// Optional lower and upper bounds on a fermentation's start date
+type StartDateRange = (Option<DateTime<Utc>>, Option<DateTime<Utc>>);
+
+impl FermentationListQuery {
+    /// Parse the `started_after`/`started_before` bounds, rejecting invalid dates
+    /// and ranges where the start is after the end
+    pub fn start_date_range(&self) -> Result<StartDateRange, String> {
+        let parse = |value: &Option<String>, field: &str| {
+            value
+                .as_deref()
+                .filter(|v| !v.trim().is_empty())
+                .map(|v| {
+                    DateTime::parse_from_rfc3339(v.trim())
+                        .map(|d| d.with_timezone(&Utc))
+                        .map_err(|e| format!("Invalid {} format: {}", field, e))
+                })
+                .transpose()
+        };
+
+        let after = parse(&self.started_after, "started_after")?;
+        let before = parse(&self.started_before, "started_before")?;
+
+        if let (Some(after), Some(before)) = (after, before) {
+            if after > before {
+                return Err("started_after must not be later than started_before".to_string());
+            }
+        }
+
+        Ok((after, before))
+    }
+}
+
 impl Default for FermentationListQuery {
     fn default() -> Self {
         Self {
             search: None,
             status: None,
             profile_type: None,
+            tag: None,
+            include_archived: None,
+            started_after: None,
+            started_before: None,
+            has_photos: None,
+            favorites_only: None,
             sort_by: Some("created_at".to_string()),
             sort_order: Some("desc".to_string()),
         }
     }
 }
 
+/// A user-defined label attached to fermentations
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct Tag {
+    pub id: i64,
+    pub name: String,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct TagRequest {
+    pub tag: String,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SearchQuery {
+    pub q: Option<String>,
+}
+
+/// A fermentation whose own fields matched a search
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct FermentationSearchHit {
+    pub id: i64,
+    pub name: String,
+    pub status: FermentationStatus,
+    pub start_date: DateTime<Utc>,
+    /// Which of "name", "notes" and "ingredients" matched
+    pub matched_fields: Vec<String>,
+}
+
+/// A temperature log whose notes matched a search
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct TemperatureLogSearchHit {
+    pub id: i64,
+    pub fermentation_id: i64,
+    pub fermentation_name: String,
+    pub recorded_at: DateTime<Utc>,
+    pub notes: String,
+}
+
+/// Search results across a user's data, grouped by type
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct SearchResults {
+    pub query: String,
+    pub fermentations: Vec<FermentationSearchHit>,
+    pub taste_profiles: Vec<TasteProfileWithFermentation>,
+    pub temperature_logs: Vec<TemperatureLogSearchHit>,
+}
+
+/// Default look-ahead window for the due-soon list
+pub const DEFAULT_DUE_SOON_DAYS: i64 = 3;
+/// Longest look-ahead window the due-soon list accepts
+pub const MAX_DUE_SOON_DAYS: i64 = 365;
+
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct DueSoonQuery {
+    /// How many days ahead to look (defaults to 3)
+    pub days: Option<i64>,
+}
+
+/// Default number of fermentations in the recently-active list
+pub const DEFAULT_RECENT_LIMIT: i64 = 5;
+/// Longest recently-active list that can be requested
+pub const MAX_RECENT_LIMIT: i64 = 50;
+
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct RecentFermentationsQuery {
+    /// How many fermentations to return (defaults to 5)
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct IngredientQuery {
+    /// Ingredient to look for, matched case-insensitively against ingredient names
+    pub name: Option<String>,
+}
+
+/// Most fermentations a single bulk status update may touch
+pub const MAX_BULK_STATUS_IDS: usize = 500;
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct BulkStatusRequest {
+    pub ids: Vec<i64>,
+    pub status: String,
+}
+
+/// Outcome of a bulk status update; ids the caller doesn't own, unknown ids and
+/// fermentations already in the target status count as skipped
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BulkStatusResponse {
+    pub updated: usize,
+    pub skipped: usize,
+}
+
+/// An active fermentation that reaches its target end date soon or already has
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DueFermentation {
+    #[serde(flatten)]
+    pub fermentation: Fermentation,
+    pub is_overdue: bool,
+    pub days_remaining: Option<i64>,
+}
+
+impl From<Fermentation> for DueFermentation {
+    fn from(fermentation: Fermentation) -> Self {
+        Self {
+            is_overdue: fermentation.is_overdue(),
+            days_remaining: fermentation.days_remaining(),
+            fermentation,
+        }
+    }
+}
+
+/// Aggregate figures summarising a user's fermentation activity
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct DashboardStats {
+    pub active_fermentations: i64,
+    pub completed_fermentations: i64,
+    pub average_success_rating: Option<f64>,
+    pub total_temperature_readings: i64,
+    pub most_used_profile_type: Option<String>,
+}
+
+/// How well a user's completed fermentations of one profile turned out
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ProfileSuccess {
+    pub profile_id: i64,
+    pub profile_name: String,
+    pub profile_type: String,
+    pub completed_fermentations: i64,
+    /// Mean of the ratings given; `None` when none of them were rated
+    pub average_success_rating: Option<f64>,
+}
+
+/// How a user's completed fermentations were rated, for a histogram
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct RatingDistribution {
+    /// Completed fermentations per success rating, with every rating from 1 to 5 present
+    pub ratings: std::collections::BTreeMap<i32, i64>,
+    /// Completed fermentations without a success rating
+    pub unrated: i64,
+}
+
 // Temperature logging models
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct TemperatureLog {
     pub id: i64,
     pub fermentation_id: i64,
@@ -388,7 +1163,226 @@ pub struct TemperatureLog {
     pub created_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Deserialize)]
+/// A public read-only link to one fermentation
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct FermentationShare {
+    pub slug: String,
+    /// Path of the public page, e.g. `/share/<slug>`
+    pub url: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Secret for `POST /api/fermentation/:id/temperature/webhook`. It is only shown once; a new
+/// one replaces the old.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct WebhookTokenResponse {
+    pub token: String,
+}
+
+impl TemperatureLog {
+    /// Convert the stored Fahrenheit reading into the given display unit
+    pub fn in_unit(self, unit: &crate::users::TemperatureUnit) -> TemperatureLogResponse {
+        use crate::users::temperature::{convert_temp_for_display, get_unit_symbol};
+
+        TemperatureLogResponse {
+            id: self.id,
+            fermentation_id: self.fermentation_id,
+            recorded_at: self.recorded_at,
+            temperature: convert_temp_for_display(self.temperature, unit),
+            notes: self.notes,
+            created_at: self.created_at,
+            temp_unit: unit.as_str().to_string(),
+            unit_symbol: get_unit_symbol(unit).to_string(),
+        }
+    }
+}
+
+/// A pH reading taken during a fermentation
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PhLog {
+    pub id: i64,
+    pub fermentation_id: i64,
+    pub recorded_at: DateTime<Utc>,
+    pub ph: f64,
+    pub notes: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreatePhLogRequest {
+    pub ph: f64,
+    pub recorded_at: Option<String>, // ISO 8601 format, optional (defaults to now)
+    pub notes: Option<String>,
+}
+
+/// What a measurement log records
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum MeasurementKind {
+    Weight,
+    Volume,
+    Ph,
+    Gravity,
+}
+
+impl MeasurementKind {
+    pub fn as_str(&self) -> &str {
+        match self {
+            MeasurementKind::Weight => "weight",
+            MeasurementKind::Volume => "volume",
+            MeasurementKind::Ph => "ph",
+            MeasurementKind::Gravity => "gravity",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "weight" => Some(MeasurementKind::Weight),
+            "volume" => Some(MeasurementKind::Volume),
+            "ph" => Some(MeasurementKind::Ph),
+            "gravity" => Some(MeasurementKind::Gravity),
+            _ => None,
+        }
+    }
+
+    /// Units accepted for this kind; the first one is used when a request omits the unit
+    pub fn units(&self) -> &'static [&'static str] {
+        match self {
+            MeasurementKind::Weight => &["g", "kg", "oz", "lb"],
+            MeasurementKind::Volume => &["ml", "l", "fl_oz", "gal"],
+            MeasurementKind::Ph => &["ph"],
+            MeasurementKind::Gravity => &["sg"],
+        }
+    }
+}
+
+/// A weight, volume, pH or gravity reading taken during a fermentation
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct MeasurementLog {
+    pub id: i64,
+    pub fermentation_id: i64,
+    pub kind: MeasurementKind,
+    pub value: f64,
+    pub unit: String,
+    pub recorded_at: DateTime<Utc>,
+    pub notes: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateMeasurementLogRequest {
+    pub kind: String,
+    pub value: f64,
+    pub unit: Option<String>, // defaults to the first unit of the kind
+    pub recorded_at: Option<String>, // ISO 8601 format, optional (defaults to now)
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct MeasurementLogQuery {
+    /// Only return readings of this kind
+    pub kind: Option<String>,
+}
+
+/// How far in the future a new fermentation's start date may lie, allowing for time zones
+pub const MAX_START_DATE_LEAD_HOURS: i64 = 24;
+
+/// How long a client-supplied idempotency key is remembered
+pub const IDEMPOTENCY_KEY_TTL_HOURS: i64 = 24;
+
+/// How long a deleted fermentation can still be restored before it becomes eligible for purging
+pub const FERMENTATION_RESTORE_WINDOW_DAYS: i64 = 30;
+
+/// Maximum length of a client-supplied idempotency key
+pub const MAX_IDEMPOTENCY_KEY_LENGTH: usize = 255;
+
+/// Maximum length of free-text fields such as notes, lessons learned and taste profiles
+pub const MAX_TEXT_FIELD_LENGTH: usize = 5000;
+
+/// Maximum length of a single journal entry
+pub const MAX_JOURNAL_ENTRY_LENGTH: usize = 5000;
+
+/// A dated diary observation for a fermentation
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct JournalEntry {
+    pub id: i64,
+    pub fermentation_id: i64,
+    pub entry_date: DateTime<Utc>,
+    pub body: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateJournalEntryRequest {
+    pub body: String,
+    pub entry_date: Option<String>, // ISO 8601 format, optional (defaults to now)
+}
+
+/// A specific gravity reading taken during a fermentation
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct GravityLog {
+    pub id: i64,
+    pub fermentation_id: i64,
+    pub recorded_at: DateTime<Utc>,
+    pub gravity: f64,
+    pub notes: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateGravityLogRequest {
+    pub gravity: f64,
+    pub recorded_at: Option<String>, // ISO 8601 format, optional (defaults to now)
+    pub notes: Option<String>,
+}
+
+/// Gravity figures for a fermentation, with an ABV estimate from the first and latest readings
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct GravityStats {
+    pub count: i64,
+    pub original_gravity: Option<f64>,
+    pub latest_gravity: Option<f64>,
+    pub estimated_abv: Option<f64>,
+}
+
+impl GravityStats {
+    /// Build the stats from the first and latest readings, if any
+    pub fn from_readings(count: i64, original: Option<f64>, latest: Option<f64>) -> Self {
+        let estimated_abv = match (original, latest) {
+            (Some(og), Some(fg)) if count > 1 => Some(estimate_abv(og, fg)),
+            _ => None,
+        };
+
+        Self {
+            count,
+            original_gravity: original,
+            latest_gravity: latest,
+            estimated_abv,
+        }
+    }
+}
+
+/// Estimate alcohol by volume (%) using the standard (OG - FG) × 131.25 approximation
+pub fn estimate_abv(original_gravity: f64, final_gravity: f64) -> f64 {
+    let abv = (original_gravity - final_gravity) * 131.25;
+    (abv.max(0.0) * 100.0).round() / 100.0
+}
+
+/// A temperature reading expressed in the user's preferred unit
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct TemperatureLogResponse {
+    pub id: i64,
+    pub fermentation_id: i64,
+    pub recorded_at: DateTime<Utc>,
+    pub temperature: f64,
+    pub notes: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub temp_unit: String,
+    pub unit_symbol: String,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct CreateTemperatureLogRequest {
     pub temperature: f64,
     pub temp_unit: Option<String>, // "fahrenheit" or "celsius", defaults to fahrenheit
@@ -396,8 +1390,106 @@ pub struct CreateTemperatureLogRequest {
     pub notes: Option<String>,
 }
 
+/// Aggregate temperature figures for a fermentation (stored in Fahrenheit)
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct TemperatureStats {
+    pub count: i64,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    pub mean: Option<f64>,
+    pub std_dev: Option<f64>,
+    pub first_recorded_at: Option<DateTime<Utc>>,
+    pub last_recorded_at: Option<DateTime<Utc>>,
+    pub span_hours: Option<f64>,
+    pub temp_unit: String,
+    pub unit_symbol: String,
+}
+
+impl TemperatureStats {
+    /// Convert the Fahrenheit figures into the given display unit
+    pub fn in_unit(mut self, unit: &crate::users::TemperatureUnit) -> Self {
+        use crate::users::temperature::convert_temp_for_display;
+
+        self.min = self.min.map(|t| convert_temp_for_display(t, unit));
+        self.max = self.max.map(|t| convert_temp_for_display(t, unit));
+        self.mean = self.mean.map(|t| convert_temp_for_display(t, unit));
+        // Spread only scales between units, it is not offset
+        if *unit == crate::users::TemperatureUnit::Celsius {
+            self.std_dev = self.std_dev.map(|d| d * 5.0 / 9.0);
+        }
+        self.temp_unit = unit.as_str().to_string();
+        self.unit_symbol = crate::users::temperature::get_unit_symbol(unit).to_string();
+        self
+    }
+}
+
+/// How regularly a fermentation's temperature has been monitored
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct TemperatureGaps {
+    pub count: i64,
+    /// Longest interval between two consecutive readings
+    pub longest_gap_hours: Option<f64>,
+    pub longest_gap_started_at: Option<DateTime<Utc>>,
+    pub longest_gap_ended_at: Option<DateTime<Utc>>,
+    pub last_recorded_at: Option<DateTime<Utc>>,
+    pub hours_since_last_reading: Option<f64>,
+}
+
+impl TemperatureGaps {
+    /// Diff adjacent readings, which must be ordered by `recorded_at` ascending
+    pub fn from_readings(recorded_at: &[DateTime<Utc>], now: DateTime<Utc>) -> Self {
+        let hours = |from: DateTime<Utc>, to: DateTime<Utc>| {
+            to.signed_duration_since(from).num_seconds() as f64 / 3600.0
+        };
+
+        let longest = recorded_at
+            .windows(2)
+            .map(|pair| (pair[0], pair[1]))
+            .max_by_key(|(start, end)| end.signed_duration_since(*start));
+        let last_recorded_at = recorded_at.last().copied();
+
+        Self {
+            count: recorded_at.len() as i64,
+            longest_gap_hours: longest.map(|(start, end)| hours(start, end)),
+            longest_gap_started_at: longest.map(|(start, _)| start),
+            longest_gap_ended_at: longest.map(|(_, end)| end),
+            last_recorded_at,
+            hours_since_last_reading: last_recorded_at.map(|last| hours(last, now)),
+        }
+    }
+}
+
+/// A single row that failed validation during a CSV temperature import
+#[derive(Debug, Serialize)]
+pub struct TemperatureImportRowError {
+    pub row: usize,
+    pub error: String,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateTemperatureLogRequest {
+    pub temperature: f64,
+    pub temp_unit: Option<String>, // "fahrenheit" or "celsius", defaults to fahrenheit
+    pub recorded_at: Option<String>, // ISO 8601 format, optional (keeps existing value)
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct DeleteTemperatureLogsQuery {
+    /// Must be `true`, so a stray request can't wipe every reading
+    #[serde(default)]
+    pub confirm: bool,
+}
+
+/// How many temperature readings a bulk delete removed
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DeletedTemperatureLogs {
+    pub deleted: usize,
+}
+
 // Taste profile models
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct TasteProfile {
     pub id: i64,
     pub fermentation_id: i64,
@@ -406,13 +1498,69 @@ pub struct TasteProfile {
     pub created_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Deserialize)]
+/// A single entry in a fermentation's combined history, tagged by its source
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TimelineEvent {
+    TemperatureLog(TemperatureLogResponse),
+    TasteProfile(TasteProfile),
+    Photo(crate::photos::PhotoResponse),
+    JournalEntry(JournalEntry),
+}
+
+impl TimelineEvent {
+    /// When the event happened, used to order the timeline
+    pub fn occurred_at(&self) -> DateTime<Utc> {
+        match self {
+            TimelineEvent::TemperatureLog(log) => log.recorded_at,
+            TimelineEvent::TasteProfile(profile) => profile.tasted_at,
+            TimelineEvent::Photo(photo) => photo.taken_at,
+            TimelineEvent::JournalEntry(entry) => entry.entry_date,
+        }
+    }
+}
+
+/// A fermentation together with everything recorded against it, for backups.
+/// Temperatures are exported as stored, in Fahrenheit.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FermentationExport {
+    pub fermentation: Fermentation,
+    pub tags: Vec<Tag>,
+    pub temperature_logs: Vec<TemperatureLog>,
+    pub ph_logs: Vec<PhLog>,
+    pub gravity_logs: Vec<GravityLog>,
+    pub measurement_logs: Vec<MeasurementLog>,
+    pub taste_profiles: Vec<TasteProfile>,
+    pub journal_entries: Vec<JournalEntry>,
+    pub photos: Vec<crate::photos::PhotoResponse>,
+}
+
+/// A taste profile joined with the fermentation it belongs to
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct TasteProfileWithFermentation {
+    pub id: i64,
+    pub fermentation_id: i64,
+    pub fermentation_name: String,
+    pub fermentation_start_date: DateTime<Utc>,
+    pub profile_text: String,
+    pub tasted_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct TasteProfileListQuery {
+    /// Search term matched against the profile text
+    pub search: Option<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct CreateTasteProfileRequest {
     pub profile_text: String,
     pub tasted_at: Option<String>, // ISO 8601 format, optional (defaults to now)
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct FinishFermentationRequest {
     pub success_rating: Option<i32>, // 1-5 rating
     pub lessons_learned: Option<String>,
@@ -1,10 +1,19 @@
 use crate::database::Database;
 use crate::fermentation::models::{
-    CreateFermentationRequest, CreateTemperatureLogRequest, Fermentation, FermentationListQuery,
-    FermentationProfile, FermentationStatus, TemperatureLog, UpdateFermentationRequest,
+    ingredients_to_json, CreateFermentationRequest, CreateGravityLogRequest,
+    CreateJournalEntryRequest, CreateMeasurementLogRequest, CreatePhLogRequest,
+    CreateTemperatureLogRequest, DashboardStats, Fermentation, FermentationExport,
+    FermentationListQuery, FermentationProfile, FermentationSearchHit, FermentationShare,
+    FermentationStatus, GravityLog, GravityStats, JournalEntry, MeasurementKind, MeasurementLog,
+    PhLog, ProfileSuccess, ProfileType, RatingDistribution, SearchResults, Tag,
+    TasteProfileWithFermentation, TemperatureGaps, TemperatureLog, TemperatureLogSearchHit,
+    TemperatureStats, UpdateFermentationRequest, UpdateTemperatureLogRequest,
+    FERMENTATION_RESTORE_WINDOW_DAYS, IDEMPOTENCY_KEY_TTL_HOURS,
 };
+use crate::users::auth::{generate_token, hash_token, verify_token};
 use chrono::{DateTime, Utc};
 use rusqlite::OptionalExtension;
+use std::collections::BTreeMap;
 use std::sync::Arc;
 
 pub struct FermentationRepository {
@@ -21,6 +30,20 @@ impl FermentationRepository {
         user_id: i64,
         request: CreateFermentationRequest,
     ) -> Result<Fermentation, Box<dyn std::error::Error + Send + Sync>> {
+        let (fermentation, _) = self
+            .create_fermentation_with_idempotency_key(user_id, request, None)
+            .await?;
+        Ok(fermentation)
+    }
+
+    /// Create a fermentation, recording the idempotency key in the same transaction.
+    /// If another request already claimed the key, its fermentation is returned with `false`.
+    pub async fn create_fermentation_with_idempotency_key(
+        &self,
+        user_id: i64,
+        request: CreateFermentationRequest,
+        idempotency_key: Option<&str>,
+    ) -> Result<(Fermentation, bool), Box<dyn std::error::Error + Send + Sync>> {
         // Parse dates
         let start_date = DateTime::parse_from_rfc3339(&request.start_date)
             .map_err(|e| format!("Invalid start_date format: {}", e))?
@@ -39,19 +62,26 @@ impl FermentationRepository {
         let db = self.db.clone();
         let name = request.name.clone();
         let notes = request.notes.clone();
-        let ingredients_json = request.ingredients.clone();
+        let ingredients_json = request.ingredients.as_deref().and_then(ingredients_to_json);
         let profile_id = request.profile_id;
+        let idempotency_key = idempotency_key.map(str::to_string);
+        let cutoff = (Utc::now() - chrono::Duration::hours(IDEMPOTENCY_KEY_TTL_HOURS))
+            .format("%Y-%m-%d %H:%M:%S")
+            .to_string();
 
-        let fermentation_id = tokio::task::spawn_blocking(move || -> Result<i64, Box<dyn std::error::Error + Send + Sync>> {
-            let conn = db.get_connection().lock().unwrap();
+        let (fermentation_id, created) = tokio::task::spawn_blocking(move || -> Result<(i64, bool), Box<dyn std::error::Error + Send + Sync>> {
+            let mut conn = db.get_connection().lock()?;
 
             let start_date_str = start_date.format("%Y-%m-%d %H:%M:%S").to_string();
             let target_end_date_str = target_end_date
                 .map(|d| d.format("%Y-%m-%d %H:%M:%S").to_string());
 
-            conn.execute(
-                "INSERT INTO fermentations (user_id, profile_id, name, start_date, target_end_date, status, notes, ingredients_json)
-                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            let tx = conn.transaction_with_behavior(rusqlite::TransactionBehavior::Immediate)?;
+            let batch_number = next_batch_number(&tx, user_id)?;
+
+            tx.execute(
+                "INSERT INTO fermentations (user_id, profile_id, name, start_date, target_end_date, status, notes, ingredients_json, batch_number)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
                 rusqlite::params![
                     user_id,
                     profile_id,
@@ -61,18 +91,94 @@ impl FermentationRepository {
                     "active",
                     notes,
                     ingredients_json,
+                    batch_number,
                 ],
             )?;
 
-            let fermentation_id = conn.last_insert_rowid();
-            Ok(fermentation_id)
+            let fermentation_id = tx.last_insert_rowid();
+
+            if let Some(ref key) = idempotency_key {
+                // An expired key may be claimed again
+                tx.execute(
+                    "DELETE FROM idempotency_keys WHERE user_id = ?1 AND idempotency_key = ?2 AND created_at < ?3",
+                    rusqlite::params![user_id, key, &cutoff],
+                )?;
+
+                let result = tx.execute(
+                    "INSERT INTO idempotency_keys (user_id, idempotency_key, fermentation_id) VALUES (?1, ?2, ?3)",
+                    rusqlite::params![user_id, key, fermentation_id],
+                );
+
+                match result {
+                    Ok(_) => {}
+                    Err(rusqlite::Error::SqliteFailure(err, _))
+                        if err.code == rusqlite::ErrorCode::ConstraintViolation =>
+                    {
+                        // A concurrent request won the key; discard this insert and return its fermentation
+                        tx.rollback()?;
+                        let existing_id = conn.query_row(
+                            "SELECT fermentation_id FROM idempotency_keys WHERE user_id = ?1 AND idempotency_key = ?2",
+                            rusqlite::params![user_id, key],
+                            |row| row.get(0),
+                        )?;
+                        return Ok((existing_id, false));
+                    }
+                    Err(e) => return Err(e.into()),
+                }
+            }
+
+            tx.commit()?;
+            Ok((fermentation_id, true))
         })
         .await??;
 
         // Use the find_by_id from main branch which returns Option<Fermentation>
-        self.find_by_id(fermentation_id, user_id)
+        let fermentation = self
+            .find_by_id(fermentation_id, user_id)
             .await?
-            .ok_or_else(|| "Failed to retrieve created fermentation".into())
+            .ok_or("Failed to retrieve created fermentation")?;
+        Ok((fermentation, created))
+    }
+
+    /// Start a new active fermentation from an existing one owned by the user.
+    /// Copies the profile, name (suffixed with "(copy)"), notes and ingredients; returns None if not found.
+    pub async fn clone_fermentation(
+        &self,
+        id: i64,
+        user_id: i64,
+    ) -> Result<Option<Fermentation>, Box<dyn std::error::Error + Send + Sync>> {
+        let db = self.db.clone();
+        let start_date_str = Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+
+        let fermentation_id = tokio::task::spawn_blocking(
+            move || -> Result<Option<i64>, Box<dyn std::error::Error + Send + Sync>> {
+                let mut conn = db.get_connection().lock()?;
+
+                let tx = conn.transaction_with_behavior(rusqlite::TransactionBehavior::Immediate)?;
+                let batch_number = next_batch_number(&tx, user_id)?;
+
+                let inserted = tx.execute(
+                    "INSERT INTO fermentations (user_id, profile_id, name, start_date, status, notes, ingredients_json, batch_number)
+                     SELECT user_id, profile_id, name || ' (copy)', ?1, 'active', notes, ingredients_json, ?4
+                     FROM fermentations WHERE id = ?2 AND user_id = ?3 AND deleted_at IS NULL",
+                    rusqlite::params![&start_date_str, id, user_id, batch_number],
+                )?;
+
+                if inserted == 0 {
+                    return Ok(None);
+                }
+
+                let fermentation_id = tx.last_insert_rowid();
+                tx.commit()?;
+                Ok(Some(fermentation_id))
+            },
+        )
+        .await??;
+
+        match fermentation_id {
+            Some(fermentation_id) => self.find_by_id(fermentation_id, user_id).await,
+            None => Ok(None),
+        }
     }
 
     pub async fn find_all_by_user(
@@ -84,6 +190,11 @@ impl FermentationRepository {
         let search = query.search.clone();
         let status = query.status.clone();
         let profile_type = query.profile_type.clone();
+        let tag = query.tag.clone();
+        let include_archived = query.include_archived.unwrap_or(false);
+        let (started_after, started_before) = query.start_date_range()?;
+        let has_photos = query.has_photos;
+        let favorites_only = query.favorites_only.unwrap_or(false);
         let sort_by = query
             .sort_by
             .clone()
@@ -95,50 +206,106 @@ impl FermentationRepository {
 
         tokio::task::spawn_blocking(
             move || -> Result<Vec<Fermentation>, Box<dyn std::error::Error + Send + Sync>> {
-                let conn = db.get_connection().lock().unwrap();
+                let conn = db.get_connection().lock()?;
 
                 // Build dynamic WHERE clause
-                let mut where_clauses = vec!["f.user_id = ?1".to_string()];
+                let mut where_clauses = vec![
+                    "f.user_id = ?1".to_string(),
+                    "f.deleted_at IS NULL".to_string(),
+                ];
                 let mut params: Vec<Box<dyn rusqlite::ToSql>> = vec![Box::new(user_id)];
 
-                // Add search filter
+                // Add search filter; every whitespace-separated term has to match somewhere
+                // (LIKE is case-insensitive)
                 if let Some(search_term) = search {
-                    if !search_term.trim().is_empty() {
+                    for token in search_term.split_whitespace() {
                         where_clauses.push(
                             "(f.name LIKE ? OR f.notes LIKE ? OR f.ingredients_json LIKE ?)"
                                 .to_string(),
                         );
-                        let search_pattern = format!("%{}%", search_term);
+                        let search_pattern = format!("%{}%", token);
                         params.push(Box::new(search_pattern.clone()));
                         params.push(Box::new(search_pattern.clone()));
                         params.push(Box::new(search_pattern));
                     }
                 }
 
-                // Add status filter
-                if let Some(status_filter) = status {
-                    if !status_filter.trim().is_empty() {
+                // Add status filter; archived fermentations are hidden unless requested
+                match status.filter(|s| !s.trim().is_empty()) {
+                    Some(status_filter) => {
                         where_clauses.push("f.status = ?".to_string());
                         params.push(Box::new(status_filter));
                     }
+                    None if !include_archived => {
+                        where_clauses.push("f.status != 'archived'".to_string());
+                    }
+                    None => {}
                 }
 
                 // Add profile type filter
                 if let Some(profile_type_filter) = profile_type {
                     if !profile_type_filter.trim().is_empty() {
-                        where_clauses.push("p.type = ?".to_string());
-                        params.push(Box::new(profile_type_filter));
+                        // Compare normalized so older mixed-case types still match
+                        where_clauses.push("LOWER(TRIM(p.type)) = ?".to_string());
+                        params.push(Box::new(ProfileType::normalize(&profile_type_filter)));
+                    }
+                }
+
+                // Add tag filter
+                if let Some(tag_filter) = tag {
+                    if !tag_filter.trim().is_empty() {
+                        where_clauses.push(
+                            "EXISTS (SELECT 1 FROM fermentation_tags ft JOIN tags t ON t.id = ft.tag_id
+                                     WHERE ft.fermentation_id = f.id AND t.name = ?)"
+                                .to_string(),
+                        );
+                        params.push(Box::new(tag_filter.trim().to_lowercase()));
+                    }
+                }
+
+                // Add start date range filters
+                if let Some(after) = started_after {
+                    where_clauses.push("f.start_date >= ?".to_string());
+                    params.push(Box::new(after.format("%Y-%m-%d %H:%M:%S").to_string()));
+                }
+
+                if let Some(before) = started_before {
+                    where_clauses.push("f.start_date <= ?".to_string());
+                    params.push(Box::new(before.format("%Y-%m-%d %H:%M:%S").to_string()));
+                }
+
+                // Add photo presence filter
+                if let Some(has_photos) = has_photos {
+                    let exists = "EXISTS (SELECT 1 FROM fermentation_photos ph WHERE ph.fermentation_id = f.id)";
+                    if has_photos {
+                        where_clauses.push(exists.to_string());
+                    } else {
+                        where_clauses.push(format!("NOT {}", exists));
                     }
                 }
 
+                if favorites_only {
+                    where_clauses.push("f.is_favorite = 1".to_string());
+                }
+
                 // Build ORDER BY clause
                 let sort_column = match sort_by.as_str() {
                     "name" => "f.name",
                     "start_date" => "f.start_date",
                     "status" => "f.status",
+                    "success_rating" => "f.success_rating",
+                    "batch_number" => "f.batch_number",
+                    "updated_at" => "f.updated_at",
                     _ => "f.created_at",
                 };
 
+                // Unrated fermentations go last whichever direction ratings are sorted in
+                let nulls_last = if sort_column == "f.success_rating" {
+                    "f.success_rating IS NULL, "
+                } else {
+                    ""
+                };
+
                 let order_direction = if sort_order.to_lowercase() == "asc" {
                     "ASC"
                 } else {
@@ -148,12 +315,14 @@ impl FermentationRepository {
                 let query = format!(
                     "SELECT f.id, f.user_id, f.profile_id, f.name, f.start_date, f.target_end_date,
                         f.actual_end_date, f.status, f.success_rating, f.notes, f.ingredients_json,
-                        f.lessons_learned, f.created_at, f.updated_at, p.name as profile_name, p.type as profile_type
+                        f.lessons_learned, f.created_at, f.updated_at, p.name as profile_name, p.type as profile_type,
+                        f.batch_number, f.is_favorite, f.version
                      FROM fermentations f
                      LEFT JOIN fermentation_profiles p ON f.profile_id = p.id
                      WHERE {}
-                     ORDER BY {} {}",
+                     ORDER BY {}{} {}",
                     where_clauses.join(" AND "),
+                    nulls_last,
                     sort_column,
                     order_direction
                 );
@@ -163,27 +332,7 @@ impl FermentationRepository {
                     params.iter().map(|p| p.as_ref()).collect();
 
                 let fermentations = stmt
-                    .query_map(params_refs.as_slice(), |row| {
-                        Ok(Fermentation {
-                            id: row.get(0)?,
-                            user_id: row.get(1)?,
-                            profile_id: row.get(2)?,
-                            name: row.get(3)?,
-                            start_date: parse_datetime(row.get::<_, String>(4)?),
-                            target_end_date: row.get::<_, Option<String>>(5)?.map(parse_datetime),
-                            actual_end_date: row.get::<_, Option<String>>(6)?.map(parse_datetime),
-                            status: FermentationStatus::from(row.get::<_, String>(7)?),
-                            success_rating: row.get(8)?,
-                            notes: row.get(9)?,
-                            ingredients_json: row.get(10)?,
-                            lessons_learned: row.get(11)?,
-                            created_at: parse_datetime(row.get::<_, String>(12)?),
-                            updated_at: parse_datetime(row.get::<_, String>(13)?),
-                            profile_name: row.get(14)?,
-                            profile_type: row.get(15)?,
-                            thumbnail_path: None,
-                        })
-                    })?
+                    .query_map(params_refs.as_slice(), map_fermentation)?
                     .collect::<Result<Vec<_>, _>>()?;
 
                 Ok(fermentations)
@@ -201,39 +350,20 @@ impl FermentationRepository {
 
         tokio::task::spawn_blocking(
             move || -> Result<Option<Fermentation>, Box<dyn std::error::Error + Send + Sync>> {
-                let conn = db.get_connection().lock().unwrap();
+                let conn = db.get_connection().lock()?;
 
                 let mut stmt = conn.prepare(
                     "SELECT f.id, f.user_id, f.profile_id, f.name, f.start_date, f.target_end_date,
                         f.actual_end_date, f.status, f.success_rating, f.notes, f.ingredients_json,
-                        f.lessons_learned, f.created_at, f.updated_at, p.name as profile_name, p.type as profile_type
+                        f.lessons_learned, f.created_at, f.updated_at, p.name as profile_name, p.type as profile_type,
+                        f.batch_number, f.is_favorite, f.version
                  FROM fermentations f
                  LEFT JOIN fermentation_profiles p ON f.profile_id = p.id
-                 WHERE f.id = ?1 AND f.user_id = ?2",
+                 WHERE f.id = ?1 AND f.user_id = ?2 AND f.deleted_at IS NULL",
                 )?;
 
                 let fermentation = stmt
-                    .query_row([id, user_id], |row| {
-                        Ok(Fermentation {
-                            id: row.get(0)?,
-                            user_id: row.get(1)?,
-                            profile_id: row.get(2)?,
-                            name: row.get(3)?,
-                            start_date: parse_datetime(row.get::<_, String>(4)?),
-                            target_end_date: row.get::<_, Option<String>>(5)?.map(parse_datetime),
-                            actual_end_date: row.get::<_, Option<String>>(6)?.map(parse_datetime),
-                            status: FermentationStatus::from(row.get::<_, String>(7)?),
-                            success_rating: row.get(8)?,
-                            notes: row.get(9)?,
-                            ingredients_json: row.get(10)?,
-                            lessons_learned: row.get(11)?,
-                            created_at: parse_datetime(row.get::<_, String>(12)?),
-                            updated_at: parse_datetime(row.get::<_, String>(13)?),
-                            profile_name: row.get(14)?,
-                            profile_type: row.get(15)?,
-                            thumbnail_path: None,
-                        })
-                    })
+                    .query_row([id, user_id], map_fermentation)
                     .optional()?;
 
                 Ok(fermentation)
@@ -242,225 +372,1872 @@ impl FermentationRepository {
         .await?
     }
 
-    pub async fn get_profile_by_id(
+    /// Active fermentations whose target end date is before `until` (including ones already
+    /// past it), soonest first
+    pub async fn find_due_before(
         &self,
-        id: i64,
-    ) -> Result<Option<FermentationProfile>, Box<dyn std::error::Error + Send + Sync>> {
+        user_id: i64,
+        until: DateTime<Utc>,
+    ) -> Result<Vec<Fermentation>, Box<dyn std::error::Error + Send + Sync>> {
         let db = self.db.clone();
+        let until = until.format("%Y-%m-%d %H:%M:%S").to_string();
 
-        tokio::task::spawn_blocking(move || -> Result<Option<FermentationProfile>, Box<dyn std::error::Error + Send + Sync>> {
-            let conn = db.get_connection().lock().unwrap();
+        tokio::task::spawn_blocking(
+            move || -> Result<Vec<Fermentation>, Box<dyn std::error::Error + Send + Sync>> {
+                let conn = db.get_connection().lock()?;
 
-            let mut stmt = conn.prepare(
-                "SELECT id, name, type, min_days, max_days, temp_min, temp_max, description, is_active, created_at
-                 FROM fermentation_profiles WHERE id = ?1"
-            )?;
+                let mut stmt = conn.prepare(
+                    "SELECT f.id, f.user_id, f.profile_id, f.name, f.start_date, f.target_end_date,
+                        f.actual_end_date, f.status, f.success_rating, f.notes, f.ingredients_json,
+                        f.lessons_learned, f.created_at, f.updated_at, p.name as profile_name, p.type as profile_type,
+                        f.batch_number, f.is_favorite, f.version
+                 FROM fermentations f
+                 LEFT JOIN fermentation_profiles p ON f.profile_id = p.id
+                 WHERE f.user_id = ?1 AND f.deleted_at IS NULL AND f.status = 'active'
+                   AND f.target_end_date IS NOT NULL AND f.target_end_date <= ?2
+                 ORDER BY f.target_end_date ASC, f.id ASC",
+                )?;
 
-            let profile = stmt.query_row([id], |row| {
-                Ok(FermentationProfile {
-                    id: row.get(0)?,
-                    name: row.get(1)?,
-                    r#type: row.get(2)?,
-                    min_days: row.get(3)?,
-                    max_days: row.get(4)?,
-                    temp_min: row.get(5)?,
-                    temp_max: row.get(6)?,
-                    description: row.get(7)?,
-                    is_active: row.get::<_, i32>(8)? != 0,
-                    created_at: parse_datetime(row.get::<_, String>(9)?),
-                })
-            }).optional()?;
+                let fermentations = stmt
+                    .query_map(rusqlite::params![user_id, &until], map_fermentation)?
+                    .collect::<Result<Vec<_>, _>>()?;
 
-            Ok(profile)
-        })
+                Ok(fermentations)
+            },
+        )
         .await?
     }
 
-    pub async fn get_all_profiles(
+    /// The user's fermentations in any status, most recently updated first
+    pub async fn find_recently_updated(
         &self,
-    ) -> Result<Vec<FermentationProfile>, Box<dyn std::error::Error + Send + Sync>> {
+        user_id: i64,
+        limit: i64,
+    ) -> Result<Vec<Fermentation>, Box<dyn std::error::Error + Send + Sync>> {
         let db = self.db.clone();
 
-        tokio::task::spawn_blocking(move || -> Result<Vec<FermentationProfile>, Box<dyn std::error::Error + Send + Sync>> {
-            let conn = db.get_connection().lock().unwrap();
+        tokio::task::spawn_blocking(
+            move || -> Result<Vec<Fermentation>, Box<dyn std::error::Error + Send + Sync>> {
+                let conn = db.get_connection().lock()?;
 
-            let mut stmt = conn.prepare(
-                "SELECT id, name, type, min_days, max_days, temp_min, temp_max, description, is_active, created_at
-                 FROM fermentation_profiles WHERE is_active = 1 ORDER BY name"
-            )?;
+                let mut stmt = conn.prepare(
+                    "SELECT f.id, f.user_id, f.profile_id, f.name, f.start_date, f.target_end_date,
+                        f.actual_end_date, f.status, f.success_rating, f.notes, f.ingredients_json,
+                        f.lessons_learned, f.created_at, f.updated_at, p.name as profile_name, p.type as profile_type,
+                        f.batch_number, f.is_favorite, f.version
+                 FROM fermentations f
+                 LEFT JOIN fermentation_profiles p ON f.profile_id = p.id
+                 WHERE f.user_id = ?1 AND f.deleted_at IS NULL
+                 ORDER BY f.updated_at DESC, f.id DESC
+                 LIMIT ?2",
+                )?;
 
-            let profiles = stmt.query_map([], |row| {
-                Ok(FermentationProfile {
-                    id: row.get(0)?,
-                    name: row.get(1)?,
-                    r#type: row.get(2)?,
-                    min_days: row.get(3)?,
-                    max_days: row.get(4)?,
-                    temp_min: row.get(5)?,
-                    temp_max: row.get(6)?,
-                    description: row.get(7)?,
-                    is_active: row.get::<_, i32>(8)? != 0,
-                    created_at: parse_datetime(row.get::<_, String>(9)?),
-                })
-            })?.collect::<Result<Vec<_>, _>>()?;
+                let fermentations = stmt
+                    .query_map([user_id, limit], map_fermentation)?
+                    .collect::<Result<Vec<_>, _>>()?;
 
-            Ok(profiles)
-        })
+                Ok(fermentations)
+            },
+        )
         .await?
     }
 
-    pub async fn update_fermentation(
+    /// The user's fermentations with an ingredient whose name contains `name`, newest first.
+    /// Structured ingredient lists are matched by ingredient name only; older free-text
+    /// lists are matched as a whole.
+    pub async fn find_by_ingredient(
         &self,
-        id: i64,
         user_id: i64,
-        request: UpdateFermentationRequest,
-    ) -> Result<Option<Fermentation>, Box<dyn std::error::Error + Send + Sync>> {
-        // First verify the fermentation exists and belongs to the user
-        if self.find_by_id(id, user_id).await?.is_none() {
-            return Ok(None);
-        }
+        name: &str,
+    ) -> Result<Vec<Fermentation>, Box<dyn std::error::Error + Send + Sync>> {
+        let db = self.db.clone();
+        // Treat LIKE wildcards in the ingredient name literally
+        let pattern = format!(
+            "%{}%",
+            name.replace('\\', "\\\\")
+                .replace('%', "\\%")
+                .replace('_', "\\_")
+        );
 
-        // Parse dates if provided
-        let start_date = if let Some(ref date_str) = request.start_date {
-            Some(
-                DateTime::parse_from_rfc3339(date_str)
-                    .map_err(|e| format!("Invalid start_date format: {}", e))?
-                    .with_timezone(&Utc),
-            )
-        } else {
-            None
-        };
+        tokio::task::spawn_blocking(
+            move || -> Result<Vec<Fermentation>, Box<dyn std::error::Error + Send + Sync>> {
+                let conn = db.get_connection().lock()?;
 
-        let target_end_date = if let Some(ref date_str) = request.target_end_date {
-            Some(
-                DateTime::parse_from_rfc3339(date_str)
-                    .map_err(|e| format!("Invalid target_end_date format: {}", e))?
-                    .with_timezone(&Utc),
-            )
-        } else {
-            None
-        };
+                let mut stmt = conn.prepare(
+                    "SELECT f.id, f.user_id, f.profile_id, f.name, f.start_date, f.target_end_date,
+                        f.actual_end_date, f.status, f.success_rating, f.notes, f.ingredients_json,
+                        f.lessons_learned, f.created_at, f.updated_at, p.name as profile_name, p.type as profile_type,
+                        f.batch_number, f.is_favorite, f.version
+                 FROM fermentations f
+                 LEFT JOIN fermentation_profiles p ON f.profile_id = p.id
+                 WHERE f.user_id = ?1 AND f.deleted_at IS NULL
+                   AND CASE
+                       WHEN json_valid(f.ingredients_json) AND json_type(f.ingredients_json) = 'array'
+                       THEN EXISTS (
+                           SELECT 1 FROM json_each(f.ingredients_json) i
+                           WHERE json_extract(i.value, '$.name') LIKE ?2 ESCAPE '\\'
+                       )
+                       ELSE f.ingredients_json LIKE ?2 ESCAPE '\\'
+                   END
+                 ORDER BY f.start_date DESC, f.id DESC",
+                )?;
 
-        let actual_end_date = if let Some(ref date_str) = request.actual_end_date {
-            Some(
-                DateTime::parse_from_rfc3339(date_str)
-                    .map_err(|e| format!("Invalid actual_end_date format: {}", e))?
-                    .with_timezone(&Utc),
-            )
-        } else {
-            None
-        };
+                let fermentations = stmt
+                    .query_map(rusqlite::params![user_id, &pattern], map_fermentation)?
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                Ok(fermentations)
+            },
+        )
+        .await?
+    }
 
+    /// Active fermentations that have a target end date, soonest first
+    pub async fn find_scheduled(
+        &self,
+        user_id: i64,
+    ) -> Result<Vec<Fermentation>, Box<dyn std::error::Error + Send + Sync>> {
         let db = self.db.clone();
-        let name = request.name.clone();
-        let status = request.status.clone();
-        let success_rating = request.success_rating;
-        let notes = request.notes.clone();
-        let ingredients_json = request.ingredients.clone();
 
         tokio::task::spawn_blocking(
-            move || -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-                let conn = db.get_connection().lock().unwrap();
+            move || -> Result<Vec<Fermentation>, Box<dyn std::error::Error + Send + Sync>> {
+                let conn = db.get_connection().lock()?;
 
-                // Build dynamic UPDATE query based on provided fields
-                let mut updates = Vec::new();
-                let mut params: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
+                let mut stmt = conn.prepare(
+                    "SELECT f.id, f.user_id, f.profile_id, f.name, f.start_date, f.target_end_date,
+                        f.actual_end_date, f.status, f.success_rating, f.notes, f.ingredients_json,
+                        f.lessons_learned, f.created_at, f.updated_at, p.name as profile_name, p.type as profile_type,
+                        f.batch_number, f.is_favorite, f.version
+                 FROM fermentations f
+                 LEFT JOIN fermentation_profiles p ON f.profile_id = p.id
+                 WHERE f.user_id = ?1 AND f.deleted_at IS NULL AND f.status = 'active'
+                   AND f.target_end_date IS NOT NULL
+                 ORDER BY f.target_end_date ASC, f.id ASC",
+                )?;
 
-                if let Some(n) = name {
-                    updates.push("name = ?");
-                    params.push(Box::new(n));
-                }
+                let fermentations = stmt
+                    .query_map([user_id], map_fermentation)?
+                    .collect::<Result<Vec<_>, _>>()?;
 
-                if let Some(d) = start_date {
-                    updates.push("start_date = ?");
-                    params.push(Box::new(d.format("%Y-%m-%d %H:%M:%S").to_string()));
-                }
+                Ok(fermentations)
+            },
+        )
+        .await?
+    }
 
-                if request.target_end_date.is_some() {
-                    if let Some(d) = target_end_date {
-                        updates.push("target_end_date = ?");
-                        params.push(Box::new(d.format("%Y-%m-%d %H:%M:%S").to_string()));
-                    } else {
-                        updates.push("target_end_date = NULL");
-                    }
-                }
+    /// Archive a fermentation, remembering its current status so it can be restored
+    pub async fn archive_fermentation(
+        &self,
+        id: i64,
+        user_id: i64,
+    ) -> Result<Option<Fermentation>, Box<dyn std::error::Error + Send + Sync>> {
+        let db = self.db.clone();
 
-                if request.actual_end_date.is_some() {
-                    if let Some(d) = actual_end_date {
-                        updates.push("actual_end_date = ?");
-                        params.push(Box::new(d.format("%Y-%m-%d %H:%M:%S").to_string()));
-                    } else {
-                        updates.push("actual_end_date = NULL");
-                    }
-                }
+        tokio::task::spawn_blocking(
+            move || -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+                let conn = db.get_connection().lock()?;
 
-                if let Some(s) = status {
-                    updates.push("status = ?");
-                    params.push(Box::new(s));
-                }
+                conn.execute(
+                    "UPDATE fermentations
+                     SET archived_from_status = status, status = 'archived', updated_at = CURRENT_TIMESTAMP, version = version + 1
+                     WHERE id = ?1 AND user_id = ?2 AND status != 'archived' AND deleted_at IS NULL",
+                    [id, user_id],
+                )?;
 
-                if request.success_rating.is_some() {
-                    if let Some(r) = success_rating {
-                        updates.push("success_rating = ?");
-                        params.push(Box::new(r));
-                    } else {
-                        updates.push("success_rating = NULL");
-                    }
-                }
+                Ok(())
+            },
+        )
+        .await??;
 
-                if request.notes.is_some() {
-                    if let Some(n) = notes {
-                        updates.push("notes = ?");
-                        params.push(Box::new(n));
-                    } else {
-                        updates.push("notes = NULL");
+        self.find_by_id(id, user_id).await
+    }
+
+    /// Star or unstar a fermentation
+    pub async fn set_favorite(
+        &self,
+        id: i64,
+        user_id: i64,
+        is_favorite: bool,
+    ) -> Result<Option<Fermentation>, Box<dyn std::error::Error + Send + Sync>> {
+        let db = self.db.clone();
+
+        tokio::task::spawn_blocking(
+            move || -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+                let conn = db.get_connection().lock()?;
+
+                // The version moves on so cached copies (ETags) pick up the new flag
+                conn.execute(
+                    "UPDATE fermentations
+                     SET is_favorite = ?1, updated_at = CURRENT_TIMESTAMP, version = version + 1
+                     WHERE id = ?2 AND user_id = ?3 AND is_favorite != ?1 AND deleted_at IS NULL",
+                    rusqlite::params![is_favorite as i32, id, user_id],
+                )?;
+
+                Ok(())
+            },
+        )
+        .await??;
+
+        self.find_by_id(id, user_id).await
+    }
+
+    /// Move several of the user's fermentations to `status` in one transaction,
+    /// returning how many changed. Ids the user doesn't own are skipped.
+    pub async fn bulk_update_status(
+        &self,
+        ids: Vec<i64>,
+        user_id: i64,
+        status: FermentationStatus,
+    ) -> Result<usize, Box<dyn std::error::Error + Send + Sync>> {
+        let db = self.db.clone();
+
+        tokio::task::spawn_blocking(
+            move || -> Result<usize, Box<dyn std::error::Error + Send + Sync>> {
+                let mut conn = db.get_connection().lock()?;
+                let tx =
+                    conn.transaction_with_behavior(rusqlite::TransactionBehavior::Immediate)?;
+
+                let mut updated = 0;
+                for id in ids {
+                    // Archiving remembers the previous status so it can be unarchived later
+                    updated += tx.execute(
+                        "UPDATE fermentations
+                         SET archived_from_status = CASE
+                                 WHEN ?1 != 'archived' THEN NULL
+                                 WHEN status = 'archived' THEN archived_from_status
+                                 ELSE status
+                             END,
+                             status = ?1, updated_at = CURRENT_TIMESTAMP, version = version + 1
+                         WHERE id = ?2 AND user_id = ?3 AND status != ?1 AND deleted_at IS NULL",
+                        rusqlite::params![status.as_str(), id, user_id],
+                    )?;
+                }
+
+                tx.commit()?;
+                Ok(updated)
+            },
+        )
+        .await?
+    }
+
+    /// Restore an archived fermentation to the status it had before archiving
+    pub async fn unarchive_fermentation(
+        &self,
+        id: i64,
+        user_id: i64,
+    ) -> Result<Option<Fermentation>, Box<dyn std::error::Error + Send + Sync>> {
+        let db = self.db.clone();
+
+        tokio::task::spawn_blocking(
+            move || -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+                let conn = db.get_connection().lock()?;
+
+                conn.execute(
+                    "UPDATE fermentations
+                     SET status = COALESCE(archived_from_status, 'completed'), archived_from_status = NULL,
+                         updated_at = CURRENT_TIMESTAMP, version = version + 1
+                     WHERE id = ?1 AND user_id = ?2 AND status = 'archived' AND deleted_at IS NULL",
+                    [id, user_id],
+                )?;
+
+                Ok(())
+            },
+        )
+        .await??;
+
+        self.find_by_id(id, user_id).await
+    }
+
+    /// Find the fermentation previously created with this idempotency key, if the key hasn't expired
+    pub async fn find_by_idempotency_key(
+        &self,
+        user_id: i64,
+        key: &str,
+    ) -> Result<Option<Fermentation>, Box<dyn std::error::Error + Send + Sync>> {
+        let db = self.db.clone();
+        let key = key.to_string();
+        let cutoff = (Utc::now() - chrono::Duration::hours(IDEMPOTENCY_KEY_TTL_HOURS))
+            .format("%Y-%m-%d %H:%M:%S")
+            .to_string();
+
+        let fermentation_id = tokio::task::spawn_blocking(
+            move || -> Result<Option<i64>, Box<dyn std::error::Error + Send + Sync>> {
+                let conn = db.get_connection().lock()?;
+
+                // Expired keys may be reused, so clear them out before looking up
+                conn.execute(
+                    "DELETE FROM idempotency_keys WHERE created_at < ?1",
+                    [&cutoff],
+                )?;
+
+                let fermentation_id = conn
+                    .query_row(
+                        "SELECT fermentation_id FROM idempotency_keys WHERE user_id = ?1 AND idempotency_key = ?2",
+                        rusqlite::params![user_id, &key],
+                        |row| row.get(0),
+                    )
+                    .optional()?;
+
+                Ok(fermentation_id)
+            },
+        )
+        .await??;
+
+        match fermentation_id {
+            Some(id) => self.find_by_id(id, user_id).await,
+            None => Ok(None),
+        }
+    }
+
+    /// Return a completed or failed fermentation to active, keeping its rating and taste profiles
+    pub async fn reopen_fermentation(
+        &self,
+        id: i64,
+        user_id: i64,
+    ) -> Result<Option<Fermentation>, Box<dyn std::error::Error + Send + Sync>> {
+        let db = self.db.clone();
+
+        tokio::task::spawn_blocking(
+            move || -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+                let conn = db.get_connection().lock()?;
+
+                conn.execute(
+                    "UPDATE fermentations
+                     SET status = 'active', actual_end_date = NULL, updated_at = CURRENT_TIMESTAMP, version = version + 1
+                     WHERE id = ?1 AND user_id = ?2 AND status IN ('completed', 'failed')
+                       AND deleted_at IS NULL",
+                    [id, user_id],
+                )?;
+
+                Ok(())
+            },
+        )
+        .await??;
+
+        self.find_by_id(id, user_id).await
+    }
+
+    /// Mark a fermentation as deleted. Returns false if it does not exist or is already deleted.
+    pub async fn soft_delete_fermentation(
+        &self,
+        id: i64,
+        user_id: i64,
+    ) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+        let db = self.db.clone();
+
+        tokio::task::spawn_blocking(
+            move || -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+                let mut conn = db.get_connection().lock()?;
+                let tx = conn.transaction()?;
+
+                let rows_affected = tx.execute(
+                    "UPDATE fermentations SET deleted_at = CURRENT_TIMESTAMP
+                     WHERE id = ?1 AND user_id = ?2 AND deleted_at IS NULL",
+                    [id, user_id],
+                )?;
+
+                // Release the idempotency key, so replaying the create makes a new fermentation
+                // rather than pointing at one that can no longer be found
+                tx.execute(
+                    "DELETE FROM idempotency_keys WHERE fermentation_id = ?1 AND user_id = ?2",
+                    [id, user_id],
+                )?;
+
+                tx.commit()?;
+                Ok(rows_affected > 0)
+            },
+        )
+        .await?
+    }
+
+    /// Undo a soft delete, as long as it happened within the restore window
+    pub async fn restore_fermentation(
+        &self,
+        id: i64,
+        user_id: i64,
+    ) -> Result<Option<Fermentation>, Box<dyn std::error::Error + Send + Sync>> {
+        let cutoff = (Utc::now() - chrono::Duration::days(FERMENTATION_RESTORE_WINDOW_DAYS))
+            .format("%Y-%m-%d %H:%M:%S")
+            .to_string();
+        let db = self.db.clone();
+
+        let restored = tokio::task::spawn_blocking(
+            move || -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+                let conn = db.get_connection().lock()?;
+
+                let rows_affected = conn.execute(
+                    "UPDATE fermentations SET deleted_at = NULL
+                     WHERE id = ?1 AND user_id = ?2 AND deleted_at IS NOT NULL AND deleted_at >= ?3",
+                    rusqlite::params![id, user_id, &cutoff],
+                )?;
+
+                Ok(rows_affected > 0)
+            },
+        )
+        .await??;
+
+        if !restored {
+            return Ok(None);
+        }
+
+        self.find_by_id(id, user_id).await
+    }
+
+    /// Permanently remove fermentations that were soft-deleted before `cutoff`, along with
+    /// their dependent rows. Returns the purged ids so callers can clean up uploaded files.
+    pub async fn purge_deleted_older_than(
+        &self,
+        cutoff: DateTime<Utc>,
+    ) -> Result<Vec<i64>, Box<dyn std::error::Error + Send + Sync>> {
+        let cutoff = cutoff.format("%Y-%m-%d %H:%M:%S").to_string();
+        let db = self.db.clone();
+
+        tokio::task::spawn_blocking(
+            move || -> Result<Vec<i64>, Box<dyn std::error::Error + Send + Sync>> {
+                let mut conn = db.get_connection().lock()?;
+                let tx = conn.transaction_with_behavior(rusqlite::TransactionBehavior::Immediate)?;
+
+                let fermentation_ids = {
+                    let mut stmt = tx.prepare(
+                        "SELECT id FROM fermentations WHERE deleted_at IS NOT NULL AND deleted_at < ?1",
+                    )?;
+                    let ids = stmt
+                        .query_map([&cutoff], |row| row.get::<_, i64>(0))?
+                        .collect::<Result<Vec<_>, _>>()?;
+                    ids
+                };
+
+                // Logs, photos, tags and shares are removed through ON DELETE CASCADE
+                for &fermentation_id in &fermentation_ids {
+                    tx.execute("DELETE FROM fermentations WHERE id = ?1", [fermentation_id])?;
+                }
+
+                tx.commit()?;
+                Ok(fermentation_ids)
+            },
+        )
+        .await?
+    }
+
+    /// Gather a fermentation and all of its logs, taste profiles, journal entries and photo metadata
+    pub async fn export_fermentation(
+        &self,
+        id: i64,
+        user_id: i64,
+    ) -> Result<Option<FermentationExport>, Box<dyn std::error::Error + Send + Sync>> {
+        let Some(fermentation) = self.find_by_id(id, user_id).await? else {
+            return Ok(None);
+        };
+
+        let photos = crate::photos::PhotoRepository::new(self.db.clone())
+            .list_by_fermentation(id, user_id)
+            .await?
+            .into_iter()
+            .map(Into::into)
+            .collect();
+
+        Ok(Some(FermentationExport {
+            tags: self.tags_for_fermentation(id, user_id).await?,
+            temperature_logs: self
+                .find_temperature_logs_by_fermentation(id, user_id)
+                .await?,
+            ph_logs: self.find_ph_logs_by_fermentation(id, user_id).await?,
+            gravity_logs: self.find_gravity_logs_by_fermentation(id, user_id).await?,
+            measurement_logs: self
+                .find_measurement_logs_by_fermentation(id, user_id, None)
+                .await?,
+            taste_profiles: self
+                .find_taste_profiles_by_fermentation(id, user_id)
+                .await?,
+            journal_entries: self
+                .find_journal_entries_by_fermentation(id, user_id)
+                .await?,
+            photos,
+            fermentation,
+        }))
+    }
+
+    /// Export every fermentation the user owns, including archived ones, oldest first
+    pub async fn export_all_for_user(
+        &self,
+        user_id: i64,
+    ) -> Result<Vec<FermentationExport>, Box<dyn std::error::Error + Send + Sync>> {
+        let db = self.db.clone();
+
+        let ids = tokio::task::spawn_blocking(
+            move || -> Result<Vec<i64>, Box<dyn std::error::Error + Send + Sync>> {
+                let conn = db.get_connection().lock()?;
+
+                let mut stmt = conn.prepare(
+                    "SELECT id FROM fermentations WHERE user_id = ?1 AND deleted_at IS NULL ORDER BY created_at ASC, id ASC",
+                )?;
+                let ids = stmt
+                    .query_map([user_id], |row| row.get(0))?
+                    .collect::<Result<Vec<i64>, _>>()?;
+
+                Ok(ids)
+            },
+        )
+        .await??;
+
+        let mut exports = Vec::with_capacity(ids.len());
+        for id in ids {
+            if let Some(export) = self.export_fermentation(id, user_id).await? {
+                exports.push(export);
+            }
+        }
+
+        Ok(exports)
+    }
+
+    /// Attach a tag to a fermentation, creating the user's tag on first use
+    pub async fn add_tag(
+        &self,
+        fermentation_id: i64,
+        user_id: i64,
+        name: &str,
+    ) -> Result<Vec<Tag>, Box<dyn std::error::Error + Send + Sync>> {
+        // Verify the fermentation exists and belongs to the user
+        if self.find_by_id(fermentation_id, user_id).await?.is_none() {
+            return Err("Fermentation not found".into());
+        }
+
+        let db = self.db.clone();
+        let name = name.to_string();
+
+        tokio::task::spawn_blocking(
+            move || -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+                let mut conn = db.get_connection().lock()?;
+                let tx = conn.transaction_with_behavior(rusqlite::TransactionBehavior::Immediate)?;
+
+                tx.execute(
+                    "INSERT OR IGNORE INTO tags (user_id, name) VALUES (?1, ?2)",
+                    rusqlite::params![user_id, &name],
+                )?;
+
+                let tag_id: i64 = tx.query_row(
+                    "SELECT id FROM tags WHERE user_id = ?1 AND name = ?2",
+                    rusqlite::params![user_id, &name],
+                    |row| row.get(0),
+                )?;
+
+                tx.execute(
+                    "INSERT OR IGNORE INTO fermentation_tags (fermentation_id, tag_id) VALUES (?1, ?2)",
+                    [fermentation_id, tag_id],
+                )?;
+
+                tx.commit()?;
+                Ok(())
+            },
+        )
+        .await??;
+
+        self.tags_for_fermentation(fermentation_id, user_id).await
+    }
+
+    /// Detach a tag from a fermentation. The tag itself is kept for reuse.
+    pub async fn remove_tag(
+        &self,
+        fermentation_id: i64,
+        user_id: i64,
+        name: &str,
+    ) -> Result<Vec<Tag>, Box<dyn std::error::Error + Send + Sync>> {
+        // Verify the fermentation exists and belongs to the user
+        if self.find_by_id(fermentation_id, user_id).await?.is_none() {
+            return Err("Fermentation not found".into());
+        }
+
+        let db = self.db.clone();
+        let name = name.to_string();
+
+        tokio::task::spawn_blocking(
+            move || -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+                let conn = db.get_connection().lock()?;
+
+                conn.execute(
+                    "DELETE FROM fermentation_tags
+                     WHERE fermentation_id = ?1
+                       AND tag_id IN (SELECT id FROM tags WHERE user_id = ?2 AND name = ?3)",
+                    rusqlite::params![fermentation_id, user_id, &name],
+                )?;
+
+                Ok(())
+            },
+        )
+        .await??;
+
+        self.tags_for_fermentation(fermentation_id, user_id).await
+    }
+
+    pub async fn tags_for_fermentation(
+        &self,
+        fermentation_id: i64,
+        user_id: i64,
+    ) -> Result<Vec<Tag>, Box<dyn std::error::Error + Send + Sync>> {
+        let db = self.db.clone();
+
+        tokio::task::spawn_blocking(
+            move || -> Result<Vec<Tag>, Box<dyn std::error::Error + Send + Sync>> {
+                let conn = db.get_connection().lock()?;
+
+                let mut stmt = conn.prepare(
+                    "SELECT t.id, t.name
+                     FROM tags t
+                     JOIN fermentation_tags ft ON ft.tag_id = t.id
+                     WHERE ft.fermentation_id = ?1 AND t.user_id = ?2
+                     ORDER BY t.name",
+                )?;
+
+                let tags = stmt
+                    .query_map([fermentation_id, user_id], |row| {
+                        Ok(Tag {
+                            id: row.get(0)?,
+                            name: row.get(1)?,
+                        })
+                    })?
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                Ok(tags)
+            },
+        )
+        .await?
+    }
+
+    /// Count active fermentations across all users
+    pub async fn count_active(&self) -> Result<i64, Box<dyn std::error::Error + Send + Sync>> {
+        let db = self.db.clone();
+
+        tokio::task::spawn_blocking(
+            move || -> Result<i64, Box<dyn std::error::Error + Send + Sync>> {
+                let conn = db.get_connection().lock()?;
+
+                let count: i64 = conn.query_row(
+                    "SELECT COUNT(*) FROM fermentations WHERE status = 'active' AND deleted_at IS NULL",
+                    [],
+                    |row| row.get(0),
+                )?;
+
+                Ok(count)
+            },
+        )
+        .await?
+    }
+
+    pub async fn user_stats(
+        &self,
+        user_id: i64,
+    ) -> Result<DashboardStats, Box<dyn std::error::Error + Send + Sync>> {
+        let db = self.db.clone();
+
+        tokio::task::spawn_blocking(
+            move || -> Result<DashboardStats, Box<dyn std::error::Error + Send + Sync>> {
+                let conn = db.get_connection().lock()?;
+
+                let (active_fermentations, completed_fermentations, average_success_rating) = conn
+                    .query_row(
+                        "SELECT
+                            COALESCE(SUM(CASE WHEN status = 'active' THEN 1 ELSE 0 END), 0),
+                            COALESCE(SUM(CASE WHEN status = 'completed' THEN 1 ELSE 0 END), 0),
+                            AVG(success_rating)
+                         FROM fermentations
+                         WHERE user_id = ?1 AND deleted_at IS NULL",
+                        [user_id],
+                        |row| {
+                            Ok((
+                                row.get::<_, i64>(0)?,
+                                row.get::<_, i64>(1)?,
+                                row.get::<_, Option<f64>>(2)?,
+                            ))
+                        },
+                    )?;
+
+                let total_temperature_readings: i64 = conn.query_row(
+                    "SELECT COUNT(*)
+                     FROM temperature_logs t
+                     INNER JOIN fermentations f ON t.fermentation_id = f.id
+                     WHERE f.user_id = ?1 AND f.deleted_at IS NULL",
+                    [user_id],
+                    |row| row.get(0),
+                )?;
+
+                let most_used_profile_type: Option<String> = conn
+                    .query_row(
+                        "SELECT p.type
+                         FROM fermentations f
+                         INNER JOIN fermentation_profiles p ON f.profile_id = p.id
+                         WHERE f.user_id = ?1 AND f.deleted_at IS NULL
+                         GROUP BY p.type
+                         ORDER BY COUNT(*) DESC, p.type ASC
+                         LIMIT 1",
+                        [user_id],
+                        |row| row.get(0),
+                    )
+                    .optional()?;
+
+                Ok(DashboardStats {
+                    active_fermentations,
+                    completed_fermentations,
+                    average_success_rating,
+                    total_temperature_readings,
+                    most_used_profile_type,
+                })
+            },
+        )
+        .await?
+    }
+
+    /// Count the user's fermentations grouped by profile type
+    pub async fn profile_type_breakdown(
+        &self,
+        user_id: i64,
+    ) -> Result<BTreeMap<String, i64>, Box<dyn std::error::Error + Send + Sync>> {
+        let db = self.db.clone();
+
+        tokio::task::spawn_blocking(
+            move || -> Result<BTreeMap<String, i64>, Box<dyn std::error::Error + Send + Sync>> {
+                let conn = db.get_connection().lock()?;
+
+                let mut stmt = conn.prepare(
+                    "SELECT p.type, COUNT(*)
+                     FROM fermentations f
+                     INNER JOIN fermentation_profiles p ON f.profile_id = p.id
+                     WHERE f.user_id = ?1 AND f.deleted_at IS NULL
+                     GROUP BY p.type",
+                )?;
+
+                let breakdown = stmt
+                    .query_map([user_id], |row| Ok((row.get(0)?, row.get(1)?)))?
+                    .collect::<Result<BTreeMap<String, i64>, _>>()?;
+
+                Ok(breakdown)
+            },
+        )
+        .await?
+    }
+
+    /// Count the user's completed fermentations per success rating
+    pub async fn rating_distribution(
+        &self,
+        user_id: i64,
+    ) -> Result<RatingDistribution, Box<dyn std::error::Error + Send + Sync>> {
+        let db = self.db.clone();
+
+        tokio::task::spawn_blocking(
+            move || -> Result<RatingDistribution, Box<dyn std::error::Error + Send + Sync>> {
+                let conn = db.get_connection().lock()?;
+
+                let mut stmt = conn.prepare(
+                    "SELECT success_rating, COUNT(*)
+                     FROM fermentations
+                     WHERE user_id = ?1 AND status = 'completed' AND deleted_at IS NULL
+                     GROUP BY success_rating",
+                )?;
+
+                let counts = stmt
+                    .query_map([user_id], |row| {
+                        Ok((row.get::<_, Option<i32>>(0)?, row.get::<_, i64>(1)?))
+                    })?
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                // Ratings nobody gave still show up, so a histogram has all five bars
+                let mut distribution = RatingDistribution {
+                    ratings: (1..=5).map(|rating| (rating, 0)).collect(),
+                    unrated: 0,
+                };
+                for (rating, count) in counts {
+                    match rating {
+                        Some(rating) => *distribution.ratings.entry(rating).or_insert(0) += count,
+                        None => distribution.unrated += count,
+                    }
+                }
+
+                Ok(distribution)
+            },
+        )
+        .await?
+    }
+
+    /// Average success rating and completed count per profile, for profiles the user has
+    /// completed at least one fermentation with
+    pub async fn profile_success(
+        &self,
+        user_id: i64,
+    ) -> Result<Vec<ProfileSuccess>, Box<dyn std::error::Error + Send + Sync>> {
+        let db = self.db.clone();
+
+        tokio::task::spawn_blocking(
+            move || -> Result<Vec<ProfileSuccess>, Box<dyn std::error::Error + Send + Sync>> {
+                let conn = db.get_connection().lock()?;
+
+                let mut stmt = conn.prepare(
+                    "SELECT p.id, p.name, p.type, COUNT(*), AVG(f.success_rating)
+                     FROM fermentations f
+                     INNER JOIN fermentation_profiles p ON f.profile_id = p.id
+                     WHERE f.user_id = ?1 AND f.status = 'completed' AND f.deleted_at IS NULL
+                     GROUP BY p.id, p.name, p.type
+                     ORDER BY p.name ASC",
+                )?;
+
+                let profiles = stmt
+                    .query_map([user_id], |row| {
+                        Ok(ProfileSuccess {
+                            profile_id: row.get(0)?,
+                            profile_name: row.get(1)?,
+                            profile_type: row.get(2)?,
+                            completed_fermentations: row.get(3)?,
+                            average_success_rating: row.get(4)?,
+                        })
+                    })?
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                Ok(profiles)
+            },
+        )
+        .await?
+    }
+
+    pub async fn get_profile_by_id(
+        &self,
+        id: i64,
+    ) -> Result<Option<FermentationProfile>, Box<dyn std::error::Error + Send + Sync>> {
+        let db = self.db.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<Option<FermentationProfile>, Box<dyn std::error::Error + Send + Sync>> {
+            let conn = db.get_connection().lock()?;
+
+            let mut stmt = conn.prepare(
+                "SELECT id, name, type, min_days, max_days, temp_min, temp_max, description, is_active, created_at, created_by, default_ingredients
+                 FROM fermentation_profiles WHERE id = ?1"
+            )?;
+
+            let profile = stmt.query_row([id], |row| {
+                Ok(FermentationProfile {
+                    id: row.get(0)?,
+                    name: row.get(1)?,
+                    r#type: row.get(2)?,
+                    min_days: row.get(3)?,
+                    max_days: row.get(4)?,
+                    temp_min: row.get(5)?,
+                    temp_max: row.get(6)?,
+                    description: row.get(7)?,
+                    is_active: row.get::<_, i32>(8)? != 0,
+                    created_at: parse_datetime(row.get::<_, String>(9)?),
+                    created_by: row.get(10)?,
+                    default_ingredients: row.get(11)?,
+                })
+            }).optional()?;
+
+            Ok(profile)
+        })
+        .await?
+    }
+
+    pub async fn get_all_profiles(
+        &self,
+    ) -> Result<Vec<FermentationProfile>, Box<dyn std::error::Error + Send + Sync>> {
+        let db = self.db.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<Vec<FermentationProfile>, Box<dyn std::error::Error + Send + Sync>> {
+            let conn = db.get_connection().lock()?;
+
+            let mut stmt = conn.prepare(
+                "SELECT id, name, type, min_days, max_days, temp_min, temp_max, description, is_active, created_at, created_by, default_ingredients
+                 FROM fermentation_profiles WHERE is_active = 1 ORDER BY name"
+            )?;
+
+            let profiles = stmt.query_map([], |row| {
+                Ok(FermentationProfile {
+                    id: row.get(0)?,
+                    name: row.get(1)?,
+                    r#type: row.get(2)?,
+                    min_days: row.get(3)?,
+                    max_days: row.get(4)?,
+                    temp_min: row.get(5)?,
+                    temp_max: row.get(6)?,
+                    description: row.get(7)?,
+                    is_active: row.get::<_, i32>(8)? != 0,
+                    created_at: parse_datetime(row.get::<_, String>(9)?),
+                    created_by: row.get(10)?,
+                    default_ingredients: row.get(11)?,
+                })
+            })?.collect::<Result<Vec<_>, _>>()?;
+
+            Ok(profiles)
+        })
+        .await?
+    }
+
+    pub async fn update_fermentation(
+        &self,
+        id: i64,
+        user_id: i64,
+        request: UpdateFermentationRequest,
+    ) -> Result<Option<Fermentation>, Box<dyn std::error::Error + Send + Sync>> {
+        // First verify the fermentation exists and belongs to the user
+        if self.find_by_id(id, user_id).await?.is_none() {
+            return Ok(None);
+        }
+
+        // Parse dates if provided
+        let start_date = if let Some(ref date_str) = request.start_date {
+            Some(
+                DateTime::parse_from_rfc3339(date_str)
+                    .map_err(|e| format!("Invalid start_date format: {}", e))?
+                    .with_timezone(&Utc),
+            )
+        } else {
+            None
+        };
+
+        let target_end_date =
+            parse_clearable_date(request.target_end_date.as_ref(), "target_end_date")?;
+        let actual_end_date =
+            parse_clearable_date(request.actual_end_date.as_ref(), "actual_end_date")?;
+
+        let db = self.db.clone();
+        let name = request.name.clone();
+        let status = request.status.clone();
+        let success_rating = request.success_rating;
+        let notes = request.notes.clone();
+        let ingredients_json = request
+            .ingredients
+            .as_ref()
+            .map(|ingredients| ingredients.as_deref().and_then(ingredients_to_json));
+        let expected_version = request.version;
+
+        tokio::task::spawn_blocking(
+            move || -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+                let conn = db.get_connection().lock()?;
+
+                // Build dynamic UPDATE query based on provided fields
+                let mut updates = Vec::new();
+                let mut params: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
+
+                if let Some(n) = name {
+                    updates.push("name = ?");
+                    params.push(Box::new(n));
+                }
+
+                if let Some(d) = start_date {
+                    updates.push("start_date = ?");
+                    params.push(Box::new(d.format("%Y-%m-%d %H:%M:%S").to_string()));
+                }
+
+                match target_end_date {
+                    Some(Some(d)) => {
+                        updates.push("target_end_date = ?");
+                        params.push(Box::new(d.format("%Y-%m-%d %H:%M:%S").to_string()));
+                    }
+                    Some(None) => updates.push("target_end_date = NULL"),
+                    None => {}
+                }
+
+                match actual_end_date {
+                    Some(Some(d)) => {
+                        updates.push("actual_end_date = ?");
+                        params.push(Box::new(d.format("%Y-%m-%d %H:%M:%S").to_string()));
+                    }
+                    Some(None) => updates.push("actual_end_date = NULL"),
+                    None => {}
+                }
+
+                if let Some(s) = status {
+                    updates.push("status = ?");
+                    params.push(Box::new(s));
+                }
+
+                match success_rating {
+                    Some(Some(r)) => {
+                        updates.push("success_rating = ?");
+                        params.push(Box::new(r));
+                    }
+                    Some(None) => updates.push("success_rating = NULL"),
+                    None => {}
+                }
+
+                match notes {
+                    Some(Some(n)) => {
+                        updates.push("notes = ?");
+                        params.push(Box::new(n));
+                    }
+                    Some(None) => updates.push("notes = NULL"),
+                    None => {}
+                }
+
+                match ingredients_json {
+                    Some(Some(i)) => {
+                        updates.push("ingredients_json = ?");
+                        params.push(Box::new(i));
+                    }
+                    Some(None) => updates.push("ingredients_json = NULL"),
+                    None => {}
+                }
+
+                // Always update the updated_at timestamp and version
+                updates.push("updated_at = CURRENT_TIMESTAMP");
+                updates.push("version = version + 1");
+
+                let mut query = format!(
+                    "UPDATE fermentations SET {} WHERE id = ? AND user_id = ? AND deleted_at IS NULL",
+                    updates.join(", ")
+                );
+
+                params.push(Box::new(id));
+                params.push(Box::new(user_id));
+
+                // Checking the version in the same statement leaves no window for another write
+                if let Some(version) = expected_version {
+                    query.push_str(" AND version = ?");
+                    params.push(Box::new(version));
+                }
+
+                let params_refs: Vec<&dyn rusqlite::ToSql> =
+                    params.iter().map(|p| p.as_ref()).collect();
+
+                let rows = conn.execute(&query, params_refs.as_slice())?;
+
+                // The fermentation exists (checked above), so no match means a stale version
+                if rows == 0 && expected_version.is_some() {
+                    return Err(
+                        "Fermentation has been changed since it was loaded; reload and try again"
+                            .into(),
+                    );
+                }
+
+                Ok(())
+            },
+        )
+        .await??;
+
+        // Return the updated fermentation
+        self.find_by_id(id, user_id).await
+    }
+
+    /// Share a fermentation publicly. Sharing an already shared fermentation returns the
+    /// existing link, so links handed out earlier keep working.
+    pub async fn create_share(
+        &self,
+        id: i64,
+        user_id: i64,
+    ) -> Result<Option<FermentationShare>, Box<dyn std::error::Error + Send + Sync>> {
+        if self.find_by_id(id, user_id).await?.is_none() {
+            return Ok(None);
+        }
+
+        let db = self.db.clone();
+        let slug = generate_token();
+
+        tokio::task::spawn_blocking(
+            move || -> Result<Option<FermentationShare>, Box<dyn std::error::Error + Send + Sync>> {
+                let conn = db.get_connection().lock()?;
+
+                conn.execute(
+                    "INSERT OR IGNORE INTO fermentation_shares (fermentation_id, slug) VALUES (?1, ?2)",
+                    rusqlite::params![id, &slug],
+                )?;
+
+                let share = conn.query_row(
+                    "SELECT slug, created_at FROM fermentation_shares WHERE fermentation_id = ?1",
+                    [id],
+                    |row| {
+                        let slug: String = row.get(0)?;
+                        Ok(FermentationShare {
+                            url: format!("/share/{}", slug),
+                            slug,
+                            created_at: parse_datetime(row.get::<_, String>(1)?),
+                        })
+                    },
+                )?;
+
+                Ok(Some(share))
+            },
+        )
+        .await?
+    }
+
+    /// Revoke a fermentation's public link. Returns false if it wasn't shared.
+    pub async fn delete_share(
+        &self,
+        id: i64,
+        user_id: i64,
+    ) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+        let db = self.db.clone();
+
+        tokio::task::spawn_blocking(
+            move || -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+                let conn = db.get_connection().lock()?;
+
+                let deleted = conn.execute(
+                    "DELETE FROM fermentation_shares
+                     WHERE fermentation_id = ?1
+                       AND fermentation_id IN (SELECT id FROM fermentations WHERE user_id = ?2)",
+                    rusqlite::params![id, user_id],
+                )?;
+
+                Ok(deleted > 0)
+            },
+        )
+        .await?
+    }
+
+    /// Look up a shared fermentation by its public slug; no ownership check
+    pub async fn find_by_share_slug(
+        &self,
+        slug: &str,
+    ) -> Result<Option<Fermentation>, Box<dyn std::error::Error + Send + Sync>> {
+        let db = self.db.clone();
+        let slug = slug.to_string();
+
+        tokio::task::spawn_blocking(
+            move || -> Result<Option<Fermentation>, Box<dyn std::error::Error + Send + Sync>> {
+                let conn = db.get_connection().lock()?;
+
+                let fermentation = conn
+                    .query_row(
+                        "SELECT f.id, f.user_id, f.profile_id, f.name, f.start_date, f.target_end_date,
+                            f.actual_end_date, f.status, f.success_rating, f.notes, f.ingredients_json,
+                            f.lessons_learned, f.created_at, f.updated_at, p.name as profile_name, p.type as profile_type,
+                            f.batch_number, f.is_favorite, f.version
+                         FROM fermentation_shares s
+                         INNER JOIN fermentations f ON s.fermentation_id = f.id
+                         LEFT JOIN fermentation_profiles p ON f.profile_id = p.id
+                         WHERE s.slug = ?1 AND f.deleted_at IS NULL",
+                        [&slug],
+                        map_fermentation,
+                    )
+                    .optional()?;
+
+                Ok(fermentation)
+            },
+        )
+        .await?
+    }
+
+    /// Give the fermentation a new webhook token, replacing any previous one. Returns the raw
+    /// token, or `None` if the fermentation doesn't exist or belongs to someone else.
+    pub async fn create_webhook_token(
+        &self,
+        id: i64,
+        user_id: i64,
+    ) -> Result<Option<String>, Box<dyn std::error::Error + Send + Sync>> {
+        let token = generate_token();
+        let token_hash = hash_token(&token);
+        let db = self.db.clone();
+
+        let updated = tokio::task::spawn_blocking(
+            move || -> Result<usize, Box<dyn std::error::Error + Send + Sync>> {
+                let conn = db.get_connection().lock()?;
+
+                Ok(conn.execute(
+                    "UPDATE fermentations SET webhook_token_hash = ?1
+                     WHERE id = ?2 AND user_id = ?3 AND deleted_at IS NULL",
+                    rusqlite::params![&token_hash, id, user_id],
+                )?)
+            },
+        )
+        .await??;
+
+        Ok((updated > 0).then_some(token))
+    }
+
+    /// Disable the webhook. Returns false if the fermentation wasn't found.
+    pub async fn revoke_webhook_token(
+        &self,
+        id: i64,
+        user_id: i64,
+    ) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+        let db = self.db.clone();
+
+        tokio::task::spawn_blocking(
+            move || -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+                let conn = db.get_connection().lock()?;
+
+                let updated = conn.execute(
+                    "UPDATE fermentations SET webhook_token_hash = NULL
+                     WHERE id = ?1 AND user_id = ?2 AND deleted_at IS NULL",
+                    rusqlite::params![id, user_id],
+                )?;
+
+                Ok(updated > 0)
+            },
+        )
+        .await?
+    }
+
+    /// Check a webhook token, returning the id of the fermentation's owner if it matches
+    pub async fn verify_webhook_token(
+        &self,
+        id: i64,
+        token: &str,
+    ) -> Result<Option<i64>, Box<dyn std::error::Error + Send + Sync>> {
+        let db = self.db.clone();
+        let token = token.to_string();
+
+        tokio::task::spawn_blocking(
+            move || -> Result<Option<i64>, Box<dyn std::error::Error + Send + Sync>> {
+                let conn = db.get_connection().lock()?;
+
+                let row: Option<(i64, Option<String>)> = conn
+                    .query_row(
+                        "SELECT user_id, webhook_token_hash FROM fermentations
+                         WHERE id = ?1 AND deleted_at IS NULL",
+                        [id],
+                        |row| Ok((row.get(0)?, row.get(1)?)),
+                    )
+                    .optional()?;
+                drop(conn);
+
+                let Some((user_id, Some(token_hash))) = row else {
+                    return Ok(None);
+                };
+
+                if !verify_token(&token, &token_hash)? {
+                    return Ok(None);
+                }
+
+                Ok(Some(user_id))
+            },
+        )
+        .await?
+    }
+
+    pub async fn create_temperature_log(
+        &self,
+        fermentation_id: i64,
+        user_id: i64,
+        request: CreateTemperatureLogRequest,
+    ) -> Result<TemperatureLog, Box<dyn std::error::Error + Send + Sync>> {
+        // Verify the fermentation exists and belongs to the user
+        if self.find_by_id(fermentation_id, user_id).await?.is_none() {
+            return Err("Fermentation not found".into());
+        }
+
+        // Parse recorded_at date or use current time
+        let recorded_at = if let Some(ref date_str) = request.recorded_at {
+            DateTime::parse_from_rfc3339(date_str)
+                .map_err(|e| format!("Invalid recorded_at format: {}", e))?
+                .with_timezone(&Utc)
+        } else {
+            Utc::now()
+        };
+
+        let db = self.db.clone();
+        let temperature = request.temperature;
+        let notes = request.notes.clone();
+
+        let log_id = tokio::task::spawn_blocking(
+            move || -> Result<i64, Box<dyn std::error::Error + Send + Sync>> {
+                let conn = db.get_connection().lock()?;
+
+                let recorded_at_str = recorded_at.format("%Y-%m-%d %H:%M:%S").to_string();
+
+                conn.execute(
+                "INSERT INTO temperature_logs (fermentation_id, recorded_at, temperature, notes)
+                 VALUES (?1, ?2, ?3, ?4)",
+                rusqlite::params![
+                    fermentation_id,
+                    &recorded_at_str,
+                    temperature,
+                    notes,
+                ],
+            )?;
+
+                let log_id = conn.last_insert_rowid();
+                Ok(log_id)
+            },
+        )
+        .await??;
+
+        // Retrieve the created log
+        self.find_temperature_log_by_id(log_id)
+            .await?
+            .ok_or_else(|| "Failed to retrieve created temperature log".into())
+    }
+
+    /// Insert many temperature logs in a single transaction so imports are all-or-nothing
+    pub async fn bulk_create_temperature_logs(
+        &self,
+        fermentation_id: i64,
+        user_id: i64,
+        requests: Vec<CreateTemperatureLogRequest>,
+    ) -> Result<Vec<TemperatureLog>, Box<dyn std::error::Error + Send + Sync>> {
+        // Verify the fermentation exists and belongs to the user
+        if self.find_by_id(fermentation_id, user_id).await?.is_none() {
+            return Err("Fermentation not found".into());
+        }
+
+        let mut rows = Vec::with_capacity(requests.len());
+        for request in requests {
+            let recorded_at = if let Some(ref date_str) = request.recorded_at {
+                DateTime::parse_from_rfc3339(date_str)
+                    .map_err(|e| format!("Invalid recorded_at format: {}", e))?
+                    .with_timezone(&Utc)
+            } else {
+                Utc::now()
+            };
+            rows.push((
+                recorded_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+                request.temperature,
+                request.notes,
+            ));
+        }
+
+        let db = self.db.clone();
+
+        let log_ids = tokio::task::spawn_blocking(
+            move || -> Result<Vec<i64>, Box<dyn std::error::Error + Send + Sync>> {
+                let mut conn = db.get_connection().lock()?;
+                let tx = conn.transaction_with_behavior(rusqlite::TransactionBehavior::Immediate)?;
+
+                let mut log_ids = Vec::with_capacity(rows.len());
+                for (recorded_at_str, temperature, notes) in rows {
+                    tx.execute(
+                        "INSERT INTO temperature_logs (fermentation_id, recorded_at, temperature, notes)
+                         VALUES (?1, ?2, ?3, ?4)",
+                        rusqlite::params![fermentation_id, &recorded_at_str, temperature, notes],
+                    )?;
+                    log_ids.push(tx.last_insert_rowid());
+                }
+
+                tx.commit()?;
+                Ok(log_ids)
+            },
+        )
+        .await??;
+
+        let mut logs = Vec::with_capacity(log_ids.len());
+        for log_id in log_ids {
+            if let Some(log) = self.find_temperature_log_by_id(log_id).await? {
+                logs.push(log);
+            }
+        }
+
+        Ok(logs)
+    }
+
+    pub async fn find_temperature_logs_by_fermentation(
+        &self,
+        fermentation_id: i64,
+        user_id: i64,
+    ) -> Result<Vec<TemperatureLog>, Box<dyn std::error::Error + Send + Sync>> {
+        // Verify the fermentation exists and belongs to the user
+        if self.find_by_id(fermentation_id, user_id).await?.is_none() {
+            return Err("Fermentation not found".into());
+        }
+
+        let db = self.db.clone();
+
+        tokio::task::spawn_blocking(
+            move || -> Result<Vec<TemperatureLog>, Box<dyn std::error::Error + Send + Sync>> {
+                let conn = db.get_connection().lock()?;
+
+                let mut stmt = conn.prepare(
+                    "SELECT id, fermentation_id, recorded_at, temperature, notes, created_at
+                     FROM temperature_logs
+                     WHERE fermentation_id = ?1
+                     ORDER BY recorded_at DESC",
+                )?;
+
+                let logs = stmt
+                    .query_map([fermentation_id], |row| {
+                        Ok(TemperatureLog {
+                            id: row.get(0)?,
+                            fermentation_id: row.get(1)?,
+                            recorded_at: parse_datetime(row.get::<_, String>(2)?),
+                            temperature: row.get(3)?,
+                            notes: row.get(4)?,
+                            created_at: parse_datetime(row.get::<_, String>(5)?),
+                        })
+                    })?
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                Ok(logs)
+            },
+        )
+        .await?
+    }
+
+    pub async fn update_temperature_log(
+        &self,
+        fermentation_id: i64,
+        log_id: i64,
+        user_id: i64,
+        request: UpdateTemperatureLogRequest,
+    ) -> Result<Option<TemperatureLog>, Box<dyn std::error::Error + Send + Sync>> {
+        // Verify the fermentation exists and belongs to the user
+        if self.find_by_id(fermentation_id, user_id).await?.is_none() {
+            return Ok(None);
+        }
+
+        let recorded_at = if let Some(ref date_str) = request.recorded_at {
+            Some(
+                DateTime::parse_from_rfc3339(date_str)
+                    .map_err(|e| format!("Invalid recorded_at format: {}", e))?
+                    .with_timezone(&Utc),
+            )
+        } else {
+            None
+        };
+
+        let db = self.db.clone();
+        let temperature = request.temperature;
+        let notes = request.notes.clone();
+
+        let rows_updated = tokio::task::spawn_blocking(
+            move || -> Result<usize, Box<dyn std::error::Error + Send + Sync>> {
+                let conn = db.get_connection().lock()?;
+
+                let recorded_at_str =
+                    recorded_at.map(|d| d.format("%Y-%m-%d %H:%M:%S").to_string());
+
+                let rows = conn.execute(
+                    "UPDATE temperature_logs
+                     SET temperature = ?1, notes = ?2, recorded_at = COALESCE(?3, recorded_at)
+                     WHERE id = ?4 AND fermentation_id = ?5",
+                    rusqlite::params![temperature, notes, recorded_at_str, log_id, fermentation_id],
+                )?;
+
+                Ok(rows)
+            },
+        )
+        .await??;
+
+        if rows_updated == 0 {
+            return Ok(None);
+        }
+
+        self.find_temperature_log_by_id(log_id).await
+    }
+
+    /// Delete a temperature log, returning false if the log or fermentation doesn't belong to the user
+    pub async fn delete_temperature_log(
+        &self,
+        fermentation_id: i64,
+        log_id: i64,
+        user_id: i64,
+    ) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+        // Verify the fermentation exists and belongs to the user
+        if self.find_by_id(fermentation_id, user_id).await?.is_none() {
+            return Ok(false);
+        }
+
+        let db = self.db.clone();
+
+        tokio::task::spawn_blocking(
+            move || -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+                let conn = db.get_connection().lock()?;
+
+                let rows = conn.execute(
+                    "DELETE FROM temperature_logs WHERE id = ?1 AND fermentation_id = ?2",
+                    rusqlite::params![log_id, fermentation_id],
+                )?;
+
+                Ok(rows > 0)
+            },
+        )
+        .await?
+    }
+
+    /// Delete every temperature log of a fermentation, returning how many were removed, or
+    /// `None` if the fermentation doesn't belong to the user
+    pub async fn delete_all_temperature_logs(
+        &self,
+        fermentation_id: i64,
+        user_id: i64,
+    ) -> Result<Option<usize>, Box<dyn std::error::Error + Send + Sync>> {
+        // Verify the fermentation exists and belongs to the user
+        if self.find_by_id(fermentation_id, user_id).await?.is_none() {
+            return Ok(None);
+        }
+
+        let db = self.db.clone();
+
+        tokio::task::spawn_blocking(
+            move || -> Result<Option<usize>, Box<dyn std::error::Error + Send + Sync>> {
+                let conn = db.get_connection().lock()?;
+
+                let rows = conn.execute(
+                    "DELETE FROM temperature_logs WHERE fermentation_id = ?1",
+                    [fermentation_id],
+                )?;
+
+                Ok(Some(rows))
+            },
+        )
+        .await?
+    }
+
+    /// Intervals between a fermentation's temperature readings, to spot lapses in monitoring
+    pub async fn temperature_gaps(
+        &self,
+        fermentation_id: i64,
+        user_id: i64,
+    ) -> Result<TemperatureGaps, Box<dyn std::error::Error + Send + Sync>> {
+        // Verify the fermentation exists and belongs to the user
+        if self.find_by_id(fermentation_id, user_id).await?.is_none() {
+            return Err("Fermentation not found".into());
+        }
+
+        let db = self.db.clone();
+
+        tokio::task::spawn_blocking(
+            move || -> Result<TemperatureGaps, Box<dyn std::error::Error + Send + Sync>> {
+                let conn = db.get_connection().lock()?;
+
+                let mut stmt = conn.prepare(
+                    "SELECT recorded_at FROM temperature_logs
+                     WHERE fermentation_id = ?1
+                     ORDER BY recorded_at ASC",
+                )?;
+                let recorded_at = stmt
+                    .query_map([fermentation_id], |row| {
+                        Ok(parse_datetime(row.get::<_, String>(0)?))
+                    })?
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                Ok(TemperatureGaps::from_readings(&recorded_at, Utc::now()))
+            },
+        )
+        .await?
+    }
+
+    /// Compute min/max/mean/standard deviation of a fermentation's temperature logs
+    pub async fn temperature_stats(
+        &self,
+        fermentation_id: i64,
+        user_id: i64,
+    ) -> Result<TemperatureStats, Box<dyn std::error::Error + Send + Sync>> {
+        // Verify the fermentation exists and belongs to the user
+        if self.find_by_id(fermentation_id, user_id).await?.is_none() {
+            return Err("Fermentation not found".into());
+        }
+
+        let db = self.db.clone();
+
+        tokio::task::spawn_blocking(
+            move || -> Result<TemperatureStats, Box<dyn std::error::Error + Send + Sync>> {
+                let conn = db.get_connection().lock()?;
+
+                // count, min, max, mean, first and last reading
+                type Summary = (
+                    i64,
+                    Option<f64>,
+                    Option<f64>,
+                    Option<f64>,
+                    Option<String>,
+                    Option<String>,
+                );
+                let (count, min, max, mean, first, last): Summary = conn.query_row(
+                    "SELECT COUNT(*), MIN(temperature), MAX(temperature), AVG(temperature),
+                            MIN(recorded_at), MAX(recorded_at)
+                     FROM temperature_logs
+                     WHERE fermentation_id = ?1",
+                    [fermentation_id],
+                    |row| {
+                        Ok((
+                            row.get(0)?,
+                            row.get(1)?,
+                            row.get(2)?,
+                            row.get(3)?,
+                            row.get(4)?,
+                            row.get(5)?,
+                        ))
+                    },
+                )?;
+
+                // SQLite has no STDDEV, so compute the population deviation from the rows
+                let std_dev = match mean {
+                    Some(mean) if count > 0 => {
+                        let mut stmt = conn.prepare(
+                            "SELECT temperature FROM temperature_logs WHERE fermentation_id = ?1",
+                        )?;
+                        let temperatures = stmt
+                            .query_map([fermentation_id], |row| row.get::<_, f64>(0))?
+                            .collect::<Result<Vec<_>, _>>()?;
+
+                        let variance = temperatures.iter().map(|t| (t - mean).powi(2)).sum::<f64>()
+                            / count as f64;
+                        Some(variance.sqrt())
                     }
-                }
+                    _ => None,
+                };
 
-                if request.ingredients.is_some() {
-                    if let Some(i) = ingredients_json {
-                        updates.push("ingredients_json = ?");
-                        params.push(Box::new(i));
-                    } else {
-                        updates.push("ingredients_json = NULL");
+                let first_recorded_at = first.map(parse_datetime);
+                let last_recorded_at = last.map(parse_datetime);
+                let span_hours = match (first_recorded_at, last_recorded_at) {
+                    (Some(first), Some(last)) => {
+                        Some(last.signed_duration_since(first).num_seconds() as f64 / 3600.0)
                     }
-                }
+                    _ => None,
+                };
 
-                // Always update the updated_at timestamp
-                updates.push("updated_at = CURRENT_TIMESTAMP");
+                Ok(TemperatureStats {
+                    count,
+                    min,
+                    max,
+                    mean,
+                    std_dev,
+                    first_recorded_at,
+                    last_recorded_at,
+                    span_hours,
+                    temp_unit: "fahrenheit".to_string(),
+                    unit_symbol: "°F".to_string(),
+                })
+            },
+        )
+        .await?
+    }
 
-                if updates.is_empty() {
-                    return Ok(());
-                }
+    pub async fn create_ph_log(
+        &self,
+        fermentation_id: i64,
+        user_id: i64,
+        request: CreatePhLogRequest,
+    ) -> Result<PhLog, Box<dyn std::error::Error + Send + Sync>> {
+        // Verify the fermentation exists and belongs to the user
+        if self.find_by_id(fermentation_id, user_id).await?.is_none() {
+            return Err("Fermentation not found".into());
+        }
 
-                let query = format!(
-                    "UPDATE fermentations SET {} WHERE id = ? AND user_id = ?",
-                    updates.join(", ")
-                );
+        // Parse recorded_at date or use current time
+        let recorded_at = if let Some(ref date_str) = request.recorded_at {
+            DateTime::parse_from_rfc3339(date_str)
+                .map_err(|e| format!("Invalid recorded_at format: {}", e))?
+                .with_timezone(&Utc)
+        } else {
+            Utc::now()
+        };
 
-                params.push(Box::new(id));
-                params.push(Box::new(user_id));
+        let db = self.db.clone();
+        let ph = request.ph;
+        let notes = request.notes;
 
-                let params_refs: Vec<&dyn rusqlite::ToSql> =
-                    params.iter().map(|p| p.as_ref()).collect();
+        let log_id = tokio::task::spawn_blocking(
+            move || -> Result<i64, Box<dyn std::error::Error + Send + Sync>> {
+                let conn = db.get_connection().lock()?;
+
+                let recorded_at_str = recorded_at.format("%Y-%m-%d %H:%M:%S").to_string();
+
+                conn.execute(
+                    "INSERT INTO ph_logs (fermentation_id, recorded_at, ph, notes)
+                     VALUES (?1, ?2, ?3, ?4)",
+                    rusqlite::params![fermentation_id, &recorded_at_str, ph, notes],
+                )?;
+
+                let log_id = conn.last_insert_rowid();
+                Ok(log_id)
+            },
+        )
+        .await??;
+
+        // Retrieve the created log
+        self.find_ph_log_by_id(log_id)
+            .await?
+            .ok_or_else(|| "Failed to retrieve created pH log".into())
+    }
+
+    pub async fn find_ph_logs_by_fermentation(
+        &self,
+        fermentation_id: i64,
+        user_id: i64,
+    ) -> Result<Vec<PhLog>, Box<dyn std::error::Error + Send + Sync>> {
+        // Verify the fermentation exists and belongs to the user
+        if self.find_by_id(fermentation_id, user_id).await?.is_none() {
+            return Err("Fermentation not found".into());
+        }
+
+        let db = self.db.clone();
+
+        tokio::task::spawn_blocking(
+            move || -> Result<Vec<PhLog>, Box<dyn std::error::Error + Send + Sync>> {
+                let conn = db.get_connection().lock()?;
+
+                let mut stmt = conn.prepare(
+                    "SELECT id, fermentation_id, recorded_at, ph, notes, created_at
+                     FROM ph_logs
+                     WHERE fermentation_id = ?1
+                     ORDER BY recorded_at DESC",
+                )?;
+
+                let logs = stmt
+                    .query_map([fermentation_id], |row| {
+                        Ok(PhLog {
+                            id: row.get(0)?,
+                            fermentation_id: row.get(1)?,
+                            recorded_at: parse_datetime(row.get::<_, String>(2)?),
+                            ph: row.get(3)?,
+                            notes: row.get(4)?,
+                            created_at: parse_datetime(row.get::<_, String>(5)?),
+                        })
+                    })?
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                Ok(logs)
+            },
+        )
+        .await?
+    }
+
+    async fn find_ph_log_by_id(
+        &self,
+        id: i64,
+    ) -> Result<Option<PhLog>, Box<dyn std::error::Error + Send + Sync>> {
+        let db = self.db.clone();
+
+        tokio::task::spawn_blocking(
+            move || -> Result<Option<PhLog>, Box<dyn std::error::Error + Send + Sync>> {
+                let conn = db.get_connection().lock()?;
+
+                let mut stmt = conn.prepare(
+                    "SELECT id, fermentation_id, recorded_at, ph, notes, created_at
+                     FROM ph_logs
+                     WHERE id = ?1",
+                )?;
+
+                let log = stmt
+                    .query_row([id], |row| {
+                        Ok(PhLog {
+                            id: row.get(0)?,
+                            fermentation_id: row.get(1)?,
+                            recorded_at: parse_datetime(row.get::<_, String>(2)?),
+                            ph: row.get(3)?,
+                            notes: row.get(4)?,
+                            created_at: parse_datetime(row.get::<_, String>(5)?),
+                        })
+                    })
+                    .optional()?;
+
+                Ok(log)
+            },
+        )
+        .await?
+    }
+
+    pub async fn create_measurement_log(
+        &self,
+        fermentation_id: i64,
+        user_id: i64,
+        kind: MeasurementKind,
+        request: CreateMeasurementLogRequest,
+    ) -> Result<MeasurementLog, Box<dyn std::error::Error + Send + Sync>> {
+        // Verify the fermentation exists and belongs to the user
+        if self.find_by_id(fermentation_id, user_id).await?.is_none() {
+            return Err("Fermentation not found".into());
+        }
+
+        // Parse recorded_at date or use current time
+        let recorded_at = if let Some(ref date_str) = request.recorded_at {
+            DateTime::parse_from_rfc3339(date_str)
+                .map_err(|e| format!("Invalid recorded_at format: {}", e))?
+                .with_timezone(&Utc)
+        } else {
+            Utc::now()
+        };
+
+        let db = self.db.clone();
+        let value = request.value;
+        let unit = request.unit.unwrap_or_else(|| kind.units()[0].to_string());
+        let notes = request.notes;
+
+        let log_id = tokio::task::spawn_blocking(
+            move || -> Result<i64, Box<dyn std::error::Error + Send + Sync>> {
+                let conn = db.get_connection().lock()?;
+
+                let recorded_at_str = recorded_at.format("%Y-%m-%d %H:%M:%S").to_string();
+
+                conn.execute(
+                    "INSERT INTO measurement_logs (fermentation_id, kind, value, unit, recorded_at, notes)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                    rusqlite::params![
+                        fermentation_id,
+                        kind.as_str(),
+                        value,
+                        &unit,
+                        &recorded_at_str,
+                        notes
+                    ],
+                )?;
+
+                let log_id = conn.last_insert_rowid();
+                Ok(log_id)
+            },
+        )
+        .await??;
+
+        // Retrieve the created log
+        self.find_measurement_log_by_id(log_id)
+            .await?
+            .ok_or_else(|| "Failed to retrieve created measurement log".into())
+    }
+
+    /// List a fermentation's measurement logs, newest first, optionally limited to one kind
+    pub async fn find_measurement_logs_by_fermentation(
+        &self,
+        fermentation_id: i64,
+        user_id: i64,
+        kind: Option<MeasurementKind>,
+    ) -> Result<Vec<MeasurementLog>, Box<dyn std::error::Error + Send + Sync>> {
+        // Verify the fermentation exists and belongs to the user
+        if self.find_by_id(fermentation_id, user_id).await?.is_none() {
+            return Err("Fermentation not found".into());
+        }
+
+        let db = self.db.clone();
+
+        tokio::task::spawn_blocking(
+            move || -> Result<Vec<MeasurementLog>, Box<dyn std::error::Error + Send + Sync>> {
+                let conn = db.get_connection().lock()?;
+
+                let mut stmt = conn.prepare(
+                    "SELECT id, fermentation_id, kind, value, unit, recorded_at, notes, created_at
+                     FROM measurement_logs
+                     WHERE fermentation_id = ?1 AND (?2 IS NULL OR kind = ?2)
+                     ORDER BY recorded_at DESC",
+                )?;
+
+                let logs = stmt
+                    .query_map(
+                        rusqlite::params![fermentation_id, kind.map(|k| k.as_str().to_string())],
+                        map_measurement_log,
+                    )?
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                Ok(logs)
+            },
+        )
+        .await?
+    }
+
+    async fn find_measurement_log_by_id(
+        &self,
+        id: i64,
+    ) -> Result<Option<MeasurementLog>, Box<dyn std::error::Error + Send + Sync>> {
+        let db = self.db.clone();
+
+        tokio::task::spawn_blocking(
+            move || -> Result<Option<MeasurementLog>, Box<dyn std::error::Error + Send + Sync>> {
+                let conn = db.get_connection().lock()?;
+
+                let mut stmt = conn.prepare(
+                    "SELECT id, fermentation_id, kind, value, unit, recorded_at, notes, created_at
+                     FROM measurement_logs
+                     WHERE id = ?1",
+                )?;
 
-                conn.execute(&query, params_refs.as_slice())?;
+                let log = stmt.query_row([id], map_measurement_log).optional()?;
 
-                Ok(())
+                Ok(log)
             },
         )
-        .await??;
-
-        // Return the updated fermentation
-        self.find_by_id(id, user_id).await
+        .await?
     }
 
-    pub async fn create_temperature_log(
+    pub async fn create_gravity_log(
         &self,
         fermentation_id: i64,
         user_id: i64,
-        request: CreateTemperatureLogRequest,
-    ) -> Result<TemperatureLog, Box<dyn std::error::Error + Send + Sync>> {
+        request: CreateGravityLogRequest,
+    ) -> Result<GravityLog, Box<dyn std::error::Error + Send + Sync>> {
         // Verify the fermentation exists and belongs to the user
         if self.find_by_id(fermentation_id, user_id).await?.is_none() {
             return Err("Fermentation not found".into());
@@ -476,25 +2253,20 @@ impl FermentationRepository {
         };
 
         let db = self.db.clone();
-        let temperature = request.temperature;
-        let notes = request.notes.clone();
+        let gravity = request.gravity;
+        let notes = request.notes;
 
         let log_id = tokio::task::spawn_blocking(
             move || -> Result<i64, Box<dyn std::error::Error + Send + Sync>> {
-                let conn = db.get_connection().lock().unwrap();
+                let conn = db.get_connection().lock()?;
 
                 let recorded_at_str = recorded_at.format("%Y-%m-%d %H:%M:%S").to_string();
 
                 conn.execute(
-                "INSERT INTO temperature_logs (fermentation_id, recorded_at, temperature, notes)
-                 VALUES (?1, ?2, ?3, ?4)",
-                rusqlite::params![
-                    fermentation_id,
-                    &recorded_at_str,
-                    temperature,
-                    notes,
-                ],
-            )?;
+                    "INSERT INTO gravity_logs (fermentation_id, recorded_at, gravity, notes)
+                     VALUES (?1, ?2, ?3, ?4)",
+                    rusqlite::params![fermentation_id, &recorded_at_str, gravity, notes],
+                )?;
 
                 let log_id = conn.last_insert_rowid();
                 Ok(log_id)
@@ -503,16 +2275,16 @@ impl FermentationRepository {
         .await??;
 
         // Retrieve the created log
-        self.find_temperature_log_by_id(log_id)
+        self.find_gravity_log_by_id(log_id)
             .await?
-            .ok_or_else(|| "Failed to retrieve created temperature log".into())
+            .ok_or_else(|| "Failed to retrieve created gravity log".into())
     }
 
-    pub async fn find_temperature_logs_by_fermentation(
+    pub async fn find_gravity_logs_by_fermentation(
         &self,
         fermentation_id: i64,
         user_id: i64,
-    ) -> Result<Vec<TemperatureLog>, Box<dyn std::error::Error + Send + Sync>> {
+    ) -> Result<Vec<GravityLog>, Box<dyn std::error::Error + Send + Sync>> {
         // Verify the fermentation exists and belongs to the user
         if self.find_by_id(fermentation_id, user_id).await?.is_none() {
             return Err("Fermentation not found".into());
@@ -521,23 +2293,23 @@ impl FermentationRepository {
         let db = self.db.clone();
 
         tokio::task::spawn_blocking(
-            move || -> Result<Vec<TemperatureLog>, Box<dyn std::error::Error + Send + Sync>> {
-                let conn = db.get_connection().lock().unwrap();
+            move || -> Result<Vec<GravityLog>, Box<dyn std::error::Error + Send + Sync>> {
+                let conn = db.get_connection().lock()?;
 
                 let mut stmt = conn.prepare(
-                    "SELECT id, fermentation_id, recorded_at, temperature, notes, created_at
-                     FROM temperature_logs
+                    "SELECT id, fermentation_id, recorded_at, gravity, notes, created_at
+                     FROM gravity_logs
                      WHERE fermentation_id = ?1
                      ORDER BY recorded_at DESC",
                 )?;
 
                 let logs = stmt
                     .query_map([fermentation_id], |row| {
-                        Ok(TemperatureLog {
+                        Ok(GravityLog {
                             id: row.get(0)?,
                             fermentation_id: row.get(1)?,
                             recorded_at: parse_datetime(row.get::<_, String>(2)?),
-                            temperature: row.get(3)?,
+                            gravity: row.get(3)?,
                             notes: row.get(4)?,
                             created_at: parse_datetime(row.get::<_, String>(5)?),
                         })
@@ -550,6 +2322,238 @@ impl FermentationRepository {
         .await?
     }
 
+    /// Summarise a fermentation's gravity readings, using the earliest as the original gravity
+    pub async fn gravity_stats(
+        &self,
+        fermentation_id: i64,
+        user_id: i64,
+    ) -> Result<GravityStats, Box<dyn std::error::Error + Send + Sync>> {
+        // Verify the fermentation exists and belongs to the user
+        if self.find_by_id(fermentation_id, user_id).await?.is_none() {
+            return Err("Fermentation not found".into());
+        }
+
+        let db = self.db.clone();
+
+        tokio::task::spawn_blocking(
+            move || -> Result<GravityStats, Box<dyn std::error::Error + Send + Sync>> {
+                let conn = db.get_connection().lock()?;
+
+                let count: i64 = conn.query_row(
+                    "SELECT COUNT(*) FROM gravity_logs WHERE fermentation_id = ?1",
+                    [fermentation_id],
+                    |row| row.get(0),
+                )?;
+
+                let original: Option<f64> = conn
+                    .query_row(
+                        "SELECT gravity FROM gravity_logs WHERE fermentation_id = ?1
+                         ORDER BY recorded_at ASC, id ASC LIMIT 1",
+                        [fermentation_id],
+                        |row| row.get(0),
+                    )
+                    .optional()?;
+
+                let latest: Option<f64> = conn
+                    .query_row(
+                        "SELECT gravity FROM gravity_logs WHERE fermentation_id = ?1
+                         ORDER BY recorded_at DESC, id DESC LIMIT 1",
+                        [fermentation_id],
+                        |row| row.get(0),
+                    )
+                    .optional()?;
+
+                Ok(GravityStats::from_readings(count, original, latest))
+            },
+        )
+        .await?
+    }
+
+    async fn find_gravity_log_by_id(
+        &self,
+        id: i64,
+    ) -> Result<Option<GravityLog>, Box<dyn std::error::Error + Send + Sync>> {
+        let db = self.db.clone();
+
+        tokio::task::spawn_blocking(
+            move || -> Result<Option<GravityLog>, Box<dyn std::error::Error + Send + Sync>> {
+                let conn = db.get_connection().lock()?;
+
+                let mut stmt = conn.prepare(
+                    "SELECT id, fermentation_id, recorded_at, gravity, notes, created_at
+                     FROM gravity_logs
+                     WHERE id = ?1",
+                )?;
+
+                let log = stmt
+                    .query_row([id], |row| {
+                        Ok(GravityLog {
+                            id: row.get(0)?,
+                            fermentation_id: row.get(1)?,
+                            recorded_at: parse_datetime(row.get::<_, String>(2)?),
+                            gravity: row.get(3)?,
+                            notes: row.get(4)?,
+                            created_at: parse_datetime(row.get::<_, String>(5)?),
+                        })
+                    })
+                    .optional()?;
+
+                Ok(log)
+            },
+        )
+        .await?
+    }
+
+    pub async fn create_journal_entry(
+        &self,
+        fermentation_id: i64,
+        user_id: i64,
+        request: CreateJournalEntryRequest,
+    ) -> Result<JournalEntry, Box<dyn std::error::Error + Send + Sync>> {
+        // Verify the fermentation exists and belongs to the user
+        if self.find_by_id(fermentation_id, user_id).await?.is_none() {
+            return Err("Fermentation not found".into());
+        }
+
+        // Parse entry_date or use current time
+        let entry_date = if let Some(ref date_str) = request.entry_date {
+            DateTime::parse_from_rfc3339(date_str)
+                .map_err(|e| format!("Invalid entry_date format: {}", e))?
+                .with_timezone(&Utc)
+        } else {
+            Utc::now()
+        };
+
+        let db = self.db.clone();
+        let body = request.body;
+
+        let entry_id = tokio::task::spawn_blocking(
+            move || -> Result<i64, Box<dyn std::error::Error + Send + Sync>> {
+                let conn = db.get_connection().lock()?;
+
+                let entry_date_str = entry_date.format("%Y-%m-%d %H:%M:%S").to_string();
+
+                conn.execute(
+                    "INSERT INTO journal_entries (fermentation_id, entry_date, body)
+                     VALUES (?1, ?2, ?3)",
+                    rusqlite::params![fermentation_id, &entry_date_str, body],
+                )?;
+
+                Ok(conn.last_insert_rowid())
+            },
+        )
+        .await??;
+
+        // Retrieve the created entry
+        self.find_journal_entry_by_id(entry_id)
+            .await?
+            .ok_or_else(|| "Failed to retrieve created journal entry".into())
+    }
+
+    /// List a fermentation's journal entries in chronological order
+    pub async fn find_journal_entries_by_fermentation(
+        &self,
+        fermentation_id: i64,
+        user_id: i64,
+    ) -> Result<Vec<JournalEntry>, Box<dyn std::error::Error + Send + Sync>> {
+        // Verify the fermentation exists and belongs to the user
+        if self.find_by_id(fermentation_id, user_id).await?.is_none() {
+            return Err("Fermentation not found".into());
+        }
+
+        let db = self.db.clone();
+
+        tokio::task::spawn_blocking(
+            move || -> Result<Vec<JournalEntry>, Box<dyn std::error::Error + Send + Sync>> {
+                let conn = db.get_connection().lock()?;
+
+                let mut stmt = conn.prepare(
+                    "SELECT id, fermentation_id, entry_date, body, created_at
+                     FROM journal_entries
+                     WHERE fermentation_id = ?1
+                     ORDER BY entry_date ASC, id ASC",
+                )?;
+
+                let entries = stmt
+                    .query_map([fermentation_id], |row| {
+                        Ok(JournalEntry {
+                            id: row.get(0)?,
+                            fermentation_id: row.get(1)?,
+                            entry_date: parse_datetime(row.get::<_, String>(2)?),
+                            body: row.get(3)?,
+                            created_at: parse_datetime(row.get::<_, String>(4)?),
+                        })
+                    })?
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                Ok(entries)
+            },
+        )
+        .await?
+    }
+
+    pub async fn delete_journal_entry(
+        &self,
+        fermentation_id: i64,
+        entry_id: i64,
+        user_id: i64,
+    ) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+        // Verify the fermentation exists and belongs to the user
+        if self.find_by_id(fermentation_id, user_id).await?.is_none() {
+            return Ok(false);
+        }
+
+        let db = self.db.clone();
+
+        tokio::task::spawn_blocking(
+            move || -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+                let conn = db.get_connection().lock()?;
+
+                let rows = conn.execute(
+                    "DELETE FROM journal_entries WHERE id = ?1 AND fermentation_id = ?2",
+                    rusqlite::params![entry_id, fermentation_id],
+                )?;
+
+                Ok(rows > 0)
+            },
+        )
+        .await?
+    }
+
+    async fn find_journal_entry_by_id(
+        &self,
+        id: i64,
+    ) -> Result<Option<JournalEntry>, Box<dyn std::error::Error + Send + Sync>> {
+        let db = self.db.clone();
+
+        tokio::task::spawn_blocking(
+            move || -> Result<Option<JournalEntry>, Box<dyn std::error::Error + Send + Sync>> {
+                let conn = db.get_connection().lock()?;
+
+                let mut stmt = conn.prepare(
+                    "SELECT id, fermentation_id, entry_date, body, created_at
+                     FROM journal_entries
+                     WHERE id = ?1",
+                )?;
+
+                let entry = stmt
+                    .query_row([id], |row| {
+                        Ok(JournalEntry {
+                            id: row.get(0)?,
+                            fermentation_id: row.get(1)?,
+                            entry_date: parse_datetime(row.get::<_, String>(2)?),
+                            body: row.get(3)?,
+                            created_at: parse_datetime(row.get::<_, String>(4)?),
+                        })
+                    })
+                    .optional()?;
+
+                Ok(entry)
+            },
+        )
+        .await?
+    }
+
     async fn find_temperature_log_by_id(
         &self,
         id: i64,
@@ -558,7 +2562,7 @@ impl FermentationRepository {
 
         tokio::task::spawn_blocking(
             move || -> Result<Option<TemperatureLog>, Box<dyn std::error::Error + Send + Sync>> {
-                let conn = db.get_connection().lock().unwrap();
+                let conn = db.get_connection().lock()?;
 
                 let mut stmt = conn.prepare(
                     "SELECT id, fermentation_id, recorded_at, temperature, notes, created_at
@@ -585,33 +2589,46 @@ impl FermentationRepository {
         .await?
     }
 
+    /// Complete a fermentation, recording its first taste profile if one is given. Both writes
+    /// happen in one transaction. Finishing an already completed fermentation fails with
+    /// "Fermentation is already completed" rather than overwriting its end date and rating.
     pub async fn finish_fermentation(
         &self,
         fermentation_id: i64,
         user_id: i64,
         request: crate::fermentation::models::FinishFermentationRequest,
     ) -> Result<Option<Fermentation>, Box<dyn std::error::Error + Send + Sync>> {
-        // Verify the fermentation exists and belongs to the user
-        let fermentation = self.find_by_id(fermentation_id, user_id).await?;
-        if fermentation.is_none() {
-            return Ok(None);
-        }
-
         let db = self.db.clone();
         let success_rating = request.success_rating;
         let lessons_learned = request.lessons_learned.clone();
         let taste_profile = request.taste_profile.clone();
 
-        tokio::task::spawn_blocking(move || -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-            let conn = db.get_connection().lock().unwrap();
+        let found = tokio::task::spawn_blocking(move || -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+            let mut conn = db.get_connection().lock()?;
+            // Immediate, so a concurrent finish can't slip in between the check and the update
+            let tx = conn.transaction_with_behavior(rusqlite::TransactionBehavior::Immediate)?;
+
+            let status: Option<String> = tx
+                .query_row(
+                    "SELECT status FROM fermentations WHERE id = ?1 AND user_id = ?2 AND deleted_at IS NULL",
+                    [fermentation_id, user_id],
+                    |row| row.get(0),
+                )
+                .optional()?;
+
+            match status.as_deref() {
+                None => return Ok(false),
+                Some("completed") => return Err("Fermentation is already completed".into()),
+                Some(_) => {}
+            }
 
             // Update fermentation to completed status
             let now = Utc::now();
             let actual_end_date_str = now.format("%Y-%m-%d %H:%M:%S").to_string();
 
             // Use a single UPDATE statement with all fields, passing NULL for optional ones
-            conn.execute(
-                "UPDATE fermentations SET status = ?, actual_end_date = ?, success_rating = ?, lessons_learned = ?, updated_at = CURRENT_TIMESTAMP WHERE id = ? AND user_id = ?",
+            tx.execute(
+                "UPDATE fermentations SET status = ?, actual_end_date = ?, success_rating = ?, lessons_learned = ?, updated_at = CURRENT_TIMESTAMP, version = version + 1 WHERE id = ? AND user_id = ? AND deleted_at IS NULL",
                 rusqlite::params![
                     "completed",
                     actual_end_date_str,
@@ -626,7 +2643,7 @@ impl FermentationRepository {
             if let Some(profile_text) = taste_profile {
                 if !profile_text.trim().is_empty() {
                     let tasted_at_str = now.format("%Y-%m-%d %H:%M:%S").to_string();
-                    conn.execute(
+                    tx.execute(
                         "INSERT INTO taste_profiles (fermentation_id, profile_text, tasted_at)
                          VALUES (?1, ?2, ?3)",
                         rusqlite::params![fermentation_id, profile_text, tasted_at_str],
@@ -634,10 +2651,15 @@ impl FermentationRepository {
                 }
             }
 
-            Ok(())
+            tx.commit()?;
+            Ok(true)
         })
         .await??;
 
+        if !found {
+            return Ok(None);
+        }
+
         // Return the updated fermentation
         self.find_by_id(fermentation_id, user_id).await
     }
@@ -666,7 +2688,7 @@ impl FermentationRepository {
         let profile_text = request.profile_text.clone();
 
         let profile_id = tokio::task::spawn_blocking(move || -> Result<i64, Box<dyn std::error::Error + Send + Sync>> {
-            let conn = db.get_connection().lock().unwrap();
+            let conn = db.get_connection().lock()?;
 
             let tasted_at_str = tasted_at.format("%Y-%m-%d %H:%M:%S").to_string();
 
@@ -700,7 +2722,7 @@ impl FermentationRepository {
 
         tokio::task::spawn_blocking(
             move || -> Result<Vec<crate::fermentation::models::TasteProfile>, Box<dyn std::error::Error + Send + Sync>> {
-                let conn = db.get_connection().lock().unwrap();
+                let conn = db.get_connection().lock()?;
 
                 let mut stmt = conn.prepare(
                     "SELECT id, fermentation_id, profile_text, tasted_at, created_at
@@ -727,6 +2749,135 @@ impl FermentationRepository {
         .await?
     }
 
+    /// All taste profiles across the user's fermentations, newest first,
+    /// optionally filtered by a search term in the profile text
+    pub async fn find_all_taste_profiles_by_user(
+        &self,
+        user_id: i64,
+        search: Option<String>,
+    ) -> Result<Vec<TasteProfileWithFermentation>, Box<dyn std::error::Error + Send + Sync>> {
+        let db = self.db.clone();
+        let search_pattern = search
+            .filter(|term| !term.trim().is_empty())
+            .map(|term| format!("%{}%", term.trim()));
+
+        tokio::task::spawn_blocking(
+            move || -> Result<Vec<TasteProfileWithFermentation>, Box<dyn std::error::Error + Send + Sync>> {
+                let conn = db.get_connection().lock()?;
+
+                let mut stmt = conn.prepare(
+                    "SELECT tp.id, tp.fermentation_id, f.name, f.start_date, tp.profile_text, tp.tasted_at, tp.created_at
+                     FROM taste_profiles tp
+                     JOIN fermentations f ON tp.fermentation_id = f.id
+                     WHERE f.user_id = ?1 AND f.deleted_at IS NULL
+                       AND (?2 IS NULL OR tp.profile_text LIKE ?2)
+                     ORDER BY tp.tasted_at DESC",
+                )?;
+
+                let profiles = stmt
+                    .query_map(rusqlite::params![user_id, search_pattern], |row| {
+                        Ok(TasteProfileWithFermentation {
+                            id: row.get(0)?,
+                            fermentation_id: row.get(1)?,
+                            fermentation_name: row.get(2)?,
+                            fermentation_start_date: parse_datetime(row.get::<_, String>(3)?),
+                            profile_text: row.get(4)?,
+                            tasted_at: parse_datetime(row.get::<_, String>(5)?),
+                            created_at: parse_datetime(row.get::<_, String>(6)?),
+                        })
+                    })?
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                Ok(profiles)
+            },
+        )
+        .await?
+    }
+
+    /// Search fermentation names, notes and ingredients, taste profile text,
+    /// and temperature log notes for the user
+    pub async fn global_search(
+        &self,
+        user_id: i64,
+        query: &str,
+    ) -> Result<SearchResults, Box<dyn std::error::Error + Send + Sync>> {
+        let taste_profiles = self
+            .find_all_taste_profiles_by_user(user_id, Some(query.to_string()))
+            .await?;
+
+        let db = self.db.clone();
+        let pattern = format!("%{}%", query);
+
+        let (fermentations, temperature_logs) = tokio::task::spawn_blocking(
+            move || -> Result<
+                (Vec<FermentationSearchHit>, Vec<TemperatureLogSearchHit>),
+                Box<dyn std::error::Error + Send + Sync>,
+            > {
+                let conn = db.get_connection().lock()?;
+
+                let mut stmt = conn.prepare(
+                    "SELECT id, name, status, start_date,
+                            name LIKE ?2, COALESCE(notes LIKE ?2, 0), COALESCE(ingredients_json LIKE ?2, 0)
+                     FROM fermentations
+                     WHERE user_id = ?1 AND deleted_at IS NULL
+                       AND (name LIKE ?2 OR notes LIKE ?2 OR ingredients_json LIKE ?2)
+                     ORDER BY start_date DESC",
+                )?;
+
+                let fermentations = stmt
+                    .query_map(rusqlite::params![user_id, &pattern], |row| {
+                        let matched_fields = [("name", 4), ("notes", 5), ("ingredients", 6)]
+                            .into_iter()
+                            .filter_map(|(field, idx)| match row.get::<_, bool>(idx) {
+                                Ok(true) => Some(Ok(field.to_string())),
+                                Ok(false) => None,
+                                Err(e) => Some(Err(e)),
+                            })
+                            .collect::<Result<Vec<_>, _>>()?;
+
+                        Ok(FermentationSearchHit {
+                            id: row.get(0)?,
+                            name: row.get(1)?,
+                            status: FermentationStatus::from(row.get::<_, String>(2)?),
+                            start_date: parse_datetime(row.get::<_, String>(3)?),
+                            matched_fields,
+                        })
+                    })?
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                let mut stmt = conn.prepare(
+                    "SELECT t.id, t.fermentation_id, f.name, t.recorded_at, t.notes
+                     FROM temperature_logs t
+                     JOIN fermentations f ON t.fermentation_id = f.id
+                     WHERE f.user_id = ?1 AND f.deleted_at IS NULL AND t.notes LIKE ?2
+                     ORDER BY t.recorded_at DESC",
+                )?;
+
+                let temperature_logs = stmt
+                    .query_map(rusqlite::params![user_id, &pattern], |row| {
+                        Ok(TemperatureLogSearchHit {
+                            id: row.get(0)?,
+                            fermentation_id: row.get(1)?,
+                            fermentation_name: row.get(2)?,
+                            recorded_at: parse_datetime(row.get::<_, String>(3)?),
+                            notes: row.get(4)?,
+                        })
+                    })?
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                Ok((fermentations, temperature_logs))
+            },
+        )
+        .await??;
+
+        Ok(SearchResults {
+            query: query.to_string(),
+            fermentations,
+            taste_profiles,
+            temperature_logs,
+        })
+    }
+
     async fn find_taste_profile_by_id(
         &self,
         id: i64,
@@ -735,7 +2886,7 @@ impl FermentationRepository {
 
         tokio::task::spawn_blocking(
             move || -> Result<Option<crate::fermentation::models::TasteProfile>, Box<dyn std::error::Error + Send + Sync>> {
-                let conn = db.get_connection().lock().unwrap();
+                let conn = db.get_connection().lock()?;
 
                 let mut stmt = conn.prepare(
                     "SELECT id, fermentation_id, profile_text, tasted_at, created_at
@@ -762,6 +2913,73 @@ impl FermentationRepository {
     }
 }
 
+/// Map a row selected with the column list used by `find_by_id`
+/// Parse a date field of a partial update: `None` when absent, `Some(None)` when cleared
+/// with `null` or an empty string
+fn parse_clearable_date(
+    value: Option<&Option<String>>,
+    field: &str,
+) -> Result<Option<Option<DateTime<Utc>>>, Box<dyn std::error::Error + Send + Sync>> {
+    match value {
+        None => Ok(None),
+        Some(None) => Ok(Some(None)),
+        Some(Some(date_str)) if date_str.is_empty() => Ok(Some(None)),
+        Some(Some(date_str)) => Ok(Some(Some(
+            DateTime::parse_from_rfc3339(date_str)
+                .map_err(|e| format!("Invalid {} format: {}", field, e))?
+                .with_timezone(&Utc),
+        ))),
+    }
+}
+
+fn map_fermentation(row: &rusqlite::Row) -> rusqlite::Result<Fermentation> {
+    Ok(Fermentation {
+        id: row.get(0)?,
+        user_id: row.get(1)?,
+        profile_id: row.get(2)?,
+        name: row.get(3)?,
+        start_date: parse_datetime(row.get::<_, String>(4)?),
+        target_end_date: row.get::<_, Option<String>>(5)?.map(parse_datetime),
+        actual_end_date: row.get::<_, Option<String>>(6)?.map(parse_datetime),
+        status: FermentationStatus::from(row.get::<_, String>(7)?),
+        success_rating: row.get(8)?,
+        notes: row.get(9)?,
+        ingredients_json: row.get(10)?,
+        lessons_learned: row.get(11)?,
+        created_at: parse_datetime(row.get::<_, String>(12)?),
+        updated_at: parse_datetime(row.get::<_, String>(13)?),
+        profile_name: row.get(14)?,
+        profile_type: row.get(15)?,
+        batch_number: row.get(16)?,
+        is_favorite: row.get::<_, i32>(17)? != 0,
+        version: row.get(18)?,
+        thumbnail_path: None,
+    })
+}
+
+/// The next batch number for a user. Soft-deleted fermentations keep their numbers so
+/// labels are never reused.
+fn next_batch_number(conn: &rusqlite::Connection, user_id: i64) -> rusqlite::Result<i64> {
+    conn.query_row(
+        "SELECT COALESCE(MAX(batch_number), 0) + 1 FROM fermentations WHERE user_id = ?1",
+        [user_id],
+        |row| row.get(0),
+    )
+}
+
+fn map_measurement_log(row: &rusqlite::Row) -> rusqlite::Result<MeasurementLog> {
+    Ok(MeasurementLog {
+        id: row.get(0)?,
+        fermentation_id: row.get(1)?,
+        kind: MeasurementKind::parse(&row.get::<_, String>(2)?).unwrap_or(MeasurementKind::Weight),
+        value: row.get(3)?,
+        unit: row.get(4)?,
+        recorded_at: parse_datetime(row.get::<_, String>(5)?),
+        notes: row.get(6)?,
+        created_at: parse_datetime(row.get::<_, String>(7)?),
+    })
+}
+
 fn parse_datetime(s: String) -> DateTime<Utc> {
     // SQLite stores timestamps as strings, parse them
     // Format: YYYY-MM-DD HH:MM:SS
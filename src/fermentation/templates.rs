@@ -1,10 +1,12 @@
 use askama::Template;
 use axum::extract::{Path, Query, State};
+use axum::http::{HeaderMap, StatusCode};
 use axum::response::{Html, Redirect};
 use tower_sessions::Session;
 
-use crate::fermentation::models::{Fermentation, FermentationListQuery};
+use crate::fermentation::models::{Fermentation, FermentationListQuery, FermentationProfile};
 use crate::fermentation::repository::FermentationRepository;
+use crate::templates::DateStyle;
 use crate::users::UserSession;
 use crate::AppState;
 
@@ -19,11 +21,13 @@ pub struct FermentationListTemplate {
     pub profile_type_filter: String,
     pub sort_by: String,
     pub sort_order: String,
+    pub date_style: DateStyle,
 }
 
 pub async fn fermentation_list_handler(
     State(state): State<AppState>,
     session: Session,
+    headers: HeaderMap,
     Query(query): Query<FermentationListQuery>,
 ) -> Result<Html<String>, Redirect> {
     // Get user from session
@@ -71,6 +75,7 @@ pub async fn fermentation_list_handler(
                 .sort_order
                 .clone()
                 .unwrap_or_else(|| "desc".to_string()),
+            date_style: DateStyle::from_headers(&headers),
         };
 
         Ok(Html(template.render().unwrap_or_else(|e| {
@@ -87,7 +92,27 @@ pub async fn fermentation_list_handler(
 #[template(path = "fermentation/new.html")]
 pub struct NewFermentationTemplate {
     pub title: String,
-    pub temp_unit: String,
+    pub profiles: Vec<FermentationProfile>,
+    pub temp_unit_symbol: String,
+    /// The user's preferred profile, selected initially
+    pub default_profile_id: Option<i64>,
+}
+
+impl NewFermentationTemplate {
+    fn is_default(&self, id: &i64) -> bool {
+        self.default_profile_id == Some(*id)
+    }
+}
+
+/// Convert a profile's temperature range into the user's unit, rounded for display
+fn profile_for_display(
+    profile: FermentationProfile,
+    unit: &crate::users::TemperatureUnit,
+) -> FermentationProfile {
+    let mut profile = profile.in_unit(unit);
+    profile.temp_min = (profile.temp_min * 10.0).round() / 10.0;
+    profile.temp_max = (profile.temp_max * 10.0).round() / 10.0;
+    profile
 }
 
 pub async fn new_fermentation_handler(
@@ -98,21 +123,36 @@ pub async fn new_fermentation_handler(
     let user_session: Option<UserSession> = session.get("user").await.unwrap_or(None);
 
     if let Some(user) = user_session {
-        // Fetch user details to get temperature preference
+        // Fetch user details to get temperature preference and default profile
         // Default to Fahrenheit if user not found (should never happen in normal flow)
         let user_repo = crate::users::UserRepository::new(state.db.clone());
-        let temp_unit = user_repo
+        let (temp_unit, default_profile_id) = user_repo
             .find_by_id(user.user_id)
             .await
-            .map(|u| u.preferred_temp_unit.as_str().to_string())
+            .map(|u| (u.preferred_temp_unit, u.default_profile_id))
             .unwrap_or_else(|e| {
-                tracing::warn!("Could not fetch user temperature preference: {}", e);
-                "fahrenheit".to_string()
+                tracing::warn!("Could not fetch user preferences: {}", e);
+                (crate::users::TemperatureUnit::Fahrenheit, None)
             });
 
+        // Include each profile's recommended conditions so they can be shown at selection time
+        let repo = FermentationRepository::new(state.db.clone());
+        let profiles = repo
+            .get_all_profiles()
+            .await
+            .unwrap_or_else(|e| {
+                tracing::error!("Error fetching fermentation profiles: {}", e);
+                Vec::new()
+            })
+            .into_iter()
+            .map(|profile| profile_for_display(profile, &temp_unit))
+            .collect();
+
         let template = NewFermentationTemplate {
             title: "New Fermentation - Raugupatis Log".to_string(),
-            temp_unit,
+            profiles,
+            temp_unit_symbol: crate::users::temperature::get_unit_symbol(&temp_unit).to_string(),
+            default_profile_id,
         };
 
         Ok(Html(
@@ -134,13 +174,25 @@ pub struct FermentationDetailTemplate {
     pub photos: Vec<crate::photos::FermentationPhoto>,
     pub temperature_logs: Vec<crate::fermentation::models::TemperatureLog>,
     pub taste_profiles: Vec<crate::fermentation::models::TasteProfile>,
+    pub profile: Option<FermentationProfile>,
+    pub timeline: Vec<TimelineEntry>,
     pub temp_unit: String,
     pub temp_unit_symbol: String,
+    pub date_style: DateStyle,
+}
+
+/// A dated event in the detail page timeline
+pub struct TimelineEntry {
+    pub at: chrono::DateTime<chrono::Utc>,
+    pub icon: &'static str,
+    pub label: String,
+    pub detail: Option<String>,
 }
 
 pub async fn fermentation_detail_handler(
     State(state): State<AppState>,
     session: Session,
+    headers: HeaderMap,
     Path(id): Path<i64>,
 ) -> Result<Html<String>, Redirect> {
     // Get user from session
@@ -168,8 +220,11 @@ pub async fn fermentation_detail_handler(
                     first_name: None,
                     last_name: None,
                     is_locked: false,
+                    email_verified: false,
                     created_at: chrono::Utc::now(),
                     updated_at: chrono::Utc::now(),
+                    last_login_at: None,
+                    default_profile_id: None,
                 }
             });
 
@@ -213,17 +268,63 @@ pub async fn fermentation_detail_handler(
                         Vec::new()
                     });
 
+                // Fetch journal entries for this fermentation
+                let journal_entries = repo
+                    .find_journal_entries_by_fermentation(id, user.user_id)
+                    .await
+                    .unwrap_or_else(|e| {
+                        tracing::error!("Error fetching journal entries: {}", e);
+                        Vec::new()
+                    });
+
                 let temp_unit_symbol =
                     crate::users::temperature::get_unit_symbol(&user_details.preferred_temp_unit);
 
+                // Interleave journal entries, temperature readings and photos chronologically
+                let mut timeline: Vec<TimelineEntry> = journal_entries
+                    .into_iter()
+                    .map(|entry| TimelineEntry {
+                        at: entry.entry_date,
+                        icon: "📓",
+                        label: "Journal entry".to_string(),
+                        detail: Some(entry.body),
+                    })
+                    .chain(temperature_logs.iter().map(|log| TimelineEntry {
+                        at: log.recorded_at,
+                        icon: "🌡️",
+                        label: format!("Temperature {}{}", log.temperature, temp_unit_symbol),
+                        detail: log.notes.clone().filter(|n| !n.is_empty()),
+                    }))
+                    .chain(photos.iter().map(|photo| TimelineEntry {
+                        at: photo.taken_at,
+                        icon: "📷",
+                        label: format!("Photo ({})", photo.stage.as_str()),
+                        detail: photo.caption.clone().filter(|c| !c.is_empty()),
+                    }))
+                    .collect();
+                timeline.sort_by_key(|entry| entry.at);
+
+                // Fetch the profile so its temperature range can be shown in the user's unit
+                let profile = repo
+                    .get_profile_by_id(fermentation.profile_id)
+                    .await
+                    .unwrap_or_else(|e| {
+                        tracing::error!("Error fetching fermentation profile: {}", e);
+                        None
+                    })
+                    .map(|profile| profile_for_display(profile, &user_details.preferred_temp_unit));
+
                 let template = FermentationDetailTemplate {
                     title: format!("{} - Raugupatis Log", fermentation.name),
                     fermentation,
                     photos,
                     temperature_logs,
                     taste_profiles,
+                    profile,
+                    timeline,
                     temp_unit: user_details.preferred_temp_unit.as_str().to_string(),
                     temp_unit_symbol: temp_unit_symbol.to_string(),
+                    date_style: DateStyle::from_headers(&headers),
                 };
 
                 Ok(Html(template.render().unwrap_or_else(|e| {
@@ -246,6 +347,94 @@ pub async fn fermentation_detail_handler(
     }
 }
 
+#[derive(Template)]
+#[template(path = "fermentation/shared.html")]
+pub struct SharedFermentationTemplate {
+    pub title: String,
+    pub fermentation: Fermentation,
+    pub photos: Vec<crate::photos::FermentationPhoto>,
+    pub temperature_logs: Vec<crate::fermentation::models::TemperatureLog>,
+    pub profile: Option<FermentationProfile>,
+    pub temp_unit_symbol: String,
+    pub date_style: DateStyle,
+}
+
+/// Public read-only page for a fermentation shared with `POST /api/fermentation/:id/share`.
+/// Nothing but the shared fermentation is shown, and there are no editing controls.
+pub async fn shared_fermentation_handler(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(slug): Path<String>,
+) -> Result<Html<String>, StatusCode> {
+    let repo = FermentationRepository::new(state.db.clone());
+    let photo_repo = crate::photos::PhotoRepository::new(state.db.clone());
+    let user_repo = crate::users::UserRepository::new(state.db.clone());
+
+    let fermentation = match repo.find_by_share_slug(&slug).await {
+        Ok(Some(fermentation)) => fermentation,
+        Ok(None) => return Err(StatusCode::NOT_FOUND),
+        Err(e) => {
+            tracing::error!("Error fetching shared fermentation: {}", e);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+
+    // Temperatures are shown in the owner's preferred unit
+    let temp_unit = user_repo
+        .find_by_id(fermentation.user_id)
+        .await
+        .map(|u| u.preferred_temp_unit)
+        .unwrap_or_else(|e| {
+            tracing::warn!("Could not fetch owner temperature preference: {}", e);
+            crate::users::TemperatureUnit::Fahrenheit
+        });
+
+    let photos = photo_repo
+        .find_by_fermentation(fermentation.id)
+        .await
+        .unwrap_or_else(|e| {
+            tracing::error!("Error fetching photos: {}", e);
+            Vec::new()
+        });
+
+    let mut temperature_logs = repo
+        .find_temperature_logs_by_fermentation(fermentation.id, fermentation.user_id)
+        .await
+        .unwrap_or_else(|e| {
+            tracing::error!("Error fetching temperature logs: {}", e);
+            Vec::new()
+        });
+    for log in &mut temperature_logs {
+        log.temperature =
+            crate::users::temperature::convert_temp_for_display(log.temperature, &temp_unit);
+        log.temperature = (log.temperature * 10.0).round() / 10.0;
+    }
+
+    let profile = repo
+        .get_profile_by_id(fermentation.profile_id)
+        .await
+        .unwrap_or_else(|e| {
+            tracing::error!("Error fetching fermentation profile: {}", e);
+            None
+        })
+        .map(|profile| profile_for_display(profile, &temp_unit));
+
+    let template = SharedFermentationTemplate {
+        title: format!("{} - Raugupatis Log", fermentation.name),
+        fermentation,
+        photos,
+        temperature_logs,
+        profile,
+        temp_unit_symbol: crate::users::temperature::get_unit_symbol(&temp_unit).to_string(),
+        date_style: DateStyle::from_headers(&headers),
+    };
+
+    Ok(Html(template.render().unwrap_or_else(|e| {
+        tracing::error!("Failed to render shared fermentation template: {}", e);
+        "Error rendering shared fermentation".to_string()
+    })))
+}
+
 #[derive(Template)]
 #[template(path = "fermentation/edit.html")]
 pub struct EditFermentationTemplate {
@@ -1,4 +1,5 @@
 use axum::{
+    http::{header, HeaderName, HeaderValue, Method},
     routing::{get, post},
     Router,
 };
@@ -6,26 +7,37 @@ use std::sync::Arc;
 use time::Duration;
 use tower::ServiceBuilder;
 use tower_http::{
-    compression::CompressionLayer, cors::CorsLayer, services::ServeDir, trace::TraceLayer,
+    compression::CompressionLayer, cors::CorsLayer, limit::RequestBodyLimitLayer,
+    services::ServeDir, trace::TraceLayer,
 };
-use tower_sessions::{Expiry, SessionManagerLayer};
+use tower_sessions::{cookie::SameSite, Expiry, SessionManagerLayer};
 use tower_sessions_rusqlite_store::{tokio_rusqlite, RusqliteStore};
 
 pub mod admin;
+pub mod calendar;
 pub mod config;
 pub mod database;
+pub mod error;
 pub mod fermentation;
+pub mod metrics;
+pub mod openapi;
 pub mod photos;
+pub mod request_id;
 pub mod templates;
 pub mod users;
 
 pub use config::AppConfig;
 pub use database::Database;
+pub use error::ApiError;
 
 #[derive(Clone)]
 pub struct AppState {
     pub db: Arc<Database>,
     pub config: Arc<AppConfig>,
+    /// When the application started, used to report uptime
+    pub started_at: std::time::Instant,
+    /// Request, login and fermentation counters served at `/metrics`
+    pub metrics: Arc<metrics::Metrics>,
 }
 
 pub async fn create_router(app_state: AppState) -> Router {
@@ -38,18 +50,38 @@ pub async fn create_router(app_state: AppState) -> Router {
     // Create session store using tokio-rusqlite connection
     let session_store = RusqliteStore::new(session_conn);
 
-    // Create session layer with 24 hour expiration
+    let same_site = match app_state.config.session_same_site.to_lowercase().as_str() {
+        "strict" => SameSite::Strict,
+        _ => SameSite::Lax,
+    };
+
+    // Create session layer with the configured inactivity expiration. The cookie is only
+    // marked Secure outside development and test so local HTTP setups keep working.
     let session_layer = SessionManagerLayer::new(session_store)
-        .with_expiry(Expiry::OnInactivity(Duration::hours(24)));
+        .with_expiry(Expiry::OnInactivity(Duration::hours(
+            app_state.config.session_ttl_hours,
+        )))
+        .with_http_only(true)
+        .with_same_site(same_site)
+        .with_secure(!app_state.config.is_local());
+
+    // Restrict cross-origin requests outside development and test
+    let cors = cors_layer(&app_state.config);
 
-    // Create uploads directory if it doesn't exist
+    // Created at startup by AppConfig::prepare_uploads_dir
     let uploads_dir = app_state.config.uploads_dir.clone();
-    std::fs::create_dir_all(&uploads_dir).expect("Failed to create uploads directory");
 
     Router::new()
         .route("/", get(crate::templates::home_handler))
         .route("/register", get(crate::users::register_handler))
         .route("/health", get(health_handler))
+        .route("/health/detailed", get(detailed_health_handler))
+        .route("/metrics", get(crate::metrics::metrics_handler))
+        .route(
+            "/api/calendar.ics",
+            get(crate::calendar::calendar_feed_handler),
+        )
+        .route("/api/openapi.json", get(crate::openapi::openapi_handler))
         .route("/login", get(crate::users::login_handler))
         .route("/dashboard", get(crate::templates::dashboard_handler))
         .route(
@@ -64,6 +96,10 @@ pub async fn create_router(app_state: AppState) -> Router {
             "/fermentation/:id",
             get(crate::fermentation::fermentation_detail_handler),
         )
+        .route(
+            "/share/:slug",
+            get(crate::fermentation::shared_fermentation_handler),
+        )
         .route(
             "/fermentation/:id/edit",
             get(crate::fermentation::edit_fermentation_handler),
@@ -81,10 +117,30 @@ pub async fn create_router(app_state: AppState) -> Router {
         .route("/api/users/register", post(crate::users::register_user))
         .route("/api/users/login", post(crate::users::login_user))
         .route("/api/users/logout", post(crate::users::logout_user))
+        .route(
+            "/api/users/logout-all",
+            post(crate::users::logout_all_sessions),
+        )
         .route(
             "/api/fermentations",
             get(crate::fermentation::list_fermentations),
         )
+        .route(
+            "/api/fermentations/due-soon",
+            get(crate::fermentation::list_due_soon),
+        )
+        .route(
+            "/api/fermentations/by-ingredient",
+            get(crate::fermentation::list_by_ingredient),
+        )
+        .route(
+            "/api/fermentations/recent",
+            get(crate::fermentation::list_recent),
+        )
+        .route(
+            "/api/fermentations/bulk-status",
+            post(crate::fermentation::bulk_update_status),
+        )
         .route(
             "/api/fermentation/profiles",
             get(crate::fermentation::get_profiles),
@@ -95,23 +151,56 @@ pub async fn create_router(app_state: AppState) -> Router {
         )
         .route(
             "/api/fermentation/:id",
-            axum::routing::put(crate::fermentation::update_fermentation),
+            get(crate::fermentation::get_fermentation)
+                .put(crate::fermentation::update_fermentation)
+                .patch(crate::fermentation::patch_fermentation)
+                .delete(crate::fermentation::delete_fermentation),
         )
+        .route("/api/users/me", get(crate::users::current_user))
         .route("/api/users/profile", post(crate::users::update_profile))
         .route("/api/users/password", post(crate::users::change_password))
-        .route("/api/admin/users", get(crate::admin::list_users))
-        .route("/api/admin/users", post(crate::admin::create_user))
+        .route("/api/users/change-email", post(crate::users::change_email))
+        .route(
+            "/api/users/password-reset/request",
+            post(crate::users::request_password_reset),
+        )
+        .route(
+            "/api/users/password-reset/confirm",
+            post(crate::users::confirm_password_reset),
+        )
+        .route("/api/users/verify-email", post(crate::users::verify_email))
+        .route("/api/users/export", get(crate::users::export_account))
+        .route(
+            "/api/users/account",
+            axum::routing::delete(crate::users::delete_account),
+        )
+        .route(
+            "/api/users/calendar-token",
+            post(crate::users::create_calendar_token).delete(crate::users::revoke_calendar_token),
+        )
+        .route(
+            "/api/admin/users",
+            get(crate::admin::list_users).post(crate::admin::create_user),
+        )
         .route(
             "/api/admin/users/:id",
-            axum::routing::put(crate::admin::update_user),
+            get(crate::admin::get_user)
+                .put(crate::admin::update_user)
+                .delete(crate::admin::delete_user),
         )
         .route("/api/admin/users/:id/lock", post(crate::admin::lock_user))
         .route(
-            "/api/admin/users/:id",
-            axum::routing::delete(crate::admin::delete_user),
+            "/api/admin/profiles",
+            get(crate::admin::list_all_profiles).post(crate::admin::create_profile),
+        )
+        .route(
+            "/api/admin/profiles/usage",
+            get(crate::admin::profile_usage),
+        )
+        .route(
+            "/api/admin/profiles/:id",
+            axum::routing::put(crate::admin::update_profile).delete(crate::admin::delete_profile),
         )
-        .route("/api/admin/profiles", get(crate::admin::list_all_profiles))
-        .route("/api/admin/profiles", post(crate::admin::create_profile))
         .route(
             "/api/admin/profiles/:id/copy",
             post(crate::admin::copy_profile),
@@ -121,47 +210,235 @@ pub async fn create_router(app_state: AppState) -> Router {
             post(crate::admin::set_profile_active_status),
         )
         .route(
-            "/api/fermentation/:id/photos",
-            post(crate::photos::upload_photo),
+            "/api/admin/profiles/:id/owner",
+            axum::routing::put(crate::admin::reassign_profile),
+        )
+        .route(
+            "/api/admin/invites",
+            get(crate::admin::list_invites).post(crate::admin::create_invite),
         )
         .route(
             "/api/fermentation/:id/photos",
             get(crate::photos::list_photos),
         )
         .route(
-            "/api/fermentation/:id/temperature",
-            post(crate::fermentation::create_temperature_log),
+            "/api/fermentation/:id/photos/:photo_id",
+            axum::routing::delete(crate::photos::delete_photo),
+        )
+        .route(
+            "/api/fermentation/:id/photos/:photo_id/feature",
+            post(crate::photos::feature_photo),
         )
         .route(
             "/api/fermentation/:id/temperature",
-            get(crate::fermentation::list_temperature_logs),
+            get(crate::fermentation::list_temperature_logs)
+                .post(crate::fermentation::create_temperature_log)
+                .delete(crate::fermentation::delete_all_temperature_logs),
+        )
+        .route(
+            "/api/fermentation/:id/ph",
+            get(crate::fermentation::list_ph_logs).post(crate::fermentation::create_ph_log),
+        )
+        .route(
+            "/api/fermentation/:id/gravity",
+            get(crate::fermentation::list_gravity_logs)
+                .post(crate::fermentation::create_gravity_log),
+        )
+        .route(
+            "/api/fermentation/:id/measurements",
+            get(crate::fermentation::list_measurement_logs)
+                .post(crate::fermentation::create_measurement_log),
+        )
+        .route(
+            "/api/fermentation/:id/gravity/stats",
+            get(crate::fermentation::gravity_stats),
+        )
+        .route(
+            "/api/fermentation/:id/journal",
+            get(crate::fermentation::list_journal_entries)
+                .post(crate::fermentation::create_journal_entry),
+        )
+        .route(
+            "/api/fermentation/:id/journal/:entry_id",
+            axum::routing::delete(crate::fermentation::delete_journal_entry),
+        )
+        .route(
+            "/api/fermentation/:id/timeline",
+            get(crate::fermentation::fermentation_timeline),
+        )
+        .route(
+            "/api/fermentation/:id/temperature.csv",
+            get(crate::fermentation::export_temperature_logs_csv),
+        )
+        .route(
+            "/api/fermentation/:id/temperature/import",
+            post(crate::fermentation::import_temperature_logs_csv),
+        )
+        .route(
+            "/api/fermentation/:id/temperature/stats",
+            get(crate::fermentation::temperature_stats),
+        )
+        .route(
+            "/api/fermentation/:id/temperature/gaps",
+            get(crate::fermentation::temperature_gaps),
+        )
+        .route(
+            "/api/fermentation/:id/temperature/webhook",
+            post(crate::fermentation::temperature_webhook),
+        )
+        .route(
+            "/api/fermentation/:id/temperature/webhook-token",
+            post(crate::fermentation::create_webhook_token)
+                .delete(crate::fermentation::revoke_webhook_token),
+        )
+        .route(
+            "/api/fermentation/:id/temperature/:log_id",
+            axum::routing::put(crate::fermentation::update_temperature_log)
+                .delete(crate::fermentation::delete_temperature_log),
+        )
+        .route(
+            "/api/fermentation/:id/clone",
+            post(crate::fermentation::clone_fermentation),
+        )
+        .route(
+            "/api/fermentation/:id/archive",
+            post(crate::fermentation::archive_fermentation),
+        )
+        .route(
+            "/api/fermentation/:id/unarchive",
+            post(crate::fermentation::unarchive_fermentation),
+        )
+        .route(
+            "/api/fermentation/:id/share",
+            post(crate::fermentation::share_fermentation)
+                .delete(crate::fermentation::unshare_fermentation),
+        )
+        .route(
+            "/api/fermentation/:id/favorite",
+            post(crate::fermentation::favorite_fermentation),
+        )
+        .route(
+            "/api/fermentation/:id/unfavorite",
+            post(crate::fermentation::unfavorite_fermentation),
+        )
+        .route(
+            "/api/fermentation/:id/export",
+            get(crate::fermentation::export_fermentation),
+        )
+        .route(
+            "/api/fermentation/:id/reopen",
+            post(crate::fermentation::reopen_fermentation),
+        )
+        .route(
+            "/api/fermentation/:id/restore",
+            post(crate::fermentation::restore_fermentation),
+        )
+        .route(
+            "/api/fermentation/:id/tags",
+            get(crate::fermentation::list_tags)
+                .post(crate::fermentation::add_tag)
+                .delete(crate::fermentation::remove_tag),
         )
         .route(
             "/api/fermentation/:id/finish",
             post(crate::fermentation::finish_fermentation),
         )
+        .route("/api/search", get(crate::fermentation::global_search))
         .route(
-            "/api/fermentation/:id/taste-profiles",
-            post(crate::fermentation::create_taste_profile),
+            "/api/dashboard/stats",
+            get(crate::fermentation::dashboard_stats),
+        )
+        .route(
+            "/api/dashboard/profile-breakdown",
+            get(crate::fermentation::profile_breakdown),
+        )
+        .route(
+            "/api/dashboard/profile-success",
+            get(crate::fermentation::profile_success),
+        )
+        .route(
+            "/api/dashboard/ratings",
+            get(crate::fermentation::rating_distribution),
+        )
+        .route(
+            "/api/taste-profiles",
+            get(crate::fermentation::list_all_taste_profiles),
         )
         .route(
             "/api/fermentation/:id/taste-profiles",
-            get(crate::fermentation::list_taste_profiles),
+            get(crate::fermentation::list_taste_profiles)
+                .post(crate::fermentation::create_taste_profile),
+        )
+        // Limit request bodies for everything registered above; photo uploads are added
+        // afterwards so they keep their own, higher limit
+        .layer(RequestBodyLimitLayer::new(
+            app_state.config.max_request_body_bytes,
+        ))
+        .route(
+            "/api/fermentation/:id/photos",
+            post(crate::photos::upload_photo).layer(axum::extract::DefaultBodyLimit::max(
+                crate::photos::MAX_PHOTO_UPLOAD_BODY,
+            )),
         )
         .nest_service("/uploads", ServeDir::new(&uploads_dir))
+        .route_layer(axum::middleware::from_fn_with_state(
+            app_state.clone(),
+            crate::metrics::track_requests,
+        ))
         .with_state(app_state)
         .layer(
             ServiceBuilder::new()
-                .layer(TraceLayer::new_for_http())
+                .layer(axum::middleware::from_fn(
+                    crate::request_id::assign_request_id,
+                ))
+                .layer(TraceLayer::new_for_http().make_span_with(crate::request_id::make_span))
                 .layer(session_layer)
                 .layer(CompressionLayer::new())
-                .layer(CorsLayer::permissive()),
+                .layer(cors),
         )
 }
 
-use axum::{extract::State, http::StatusCode};
+use axum::{extract::State, http::StatusCode, Json};
 use tracing::warn;
 
+/// Build the CORS policy. Development and test stay permissive; everywhere else only the
+/// configured origins may make credentialed requests, since the session travels in a cookie.
+fn cors_layer(config: &AppConfig) -> CorsLayer {
+    if config.is_local() {
+        return CorsLayer::permissive();
+    }
+
+    let origins: Vec<HeaderValue> = config
+        .allowed_origins
+        .iter()
+        .filter_map(|origin| match HeaderValue::from_str(origin.trim()) {
+            Ok(value) => Some(value),
+            Err(_) => {
+                warn!("Ignoring invalid CORS origin: {}", origin);
+                None
+            }
+        })
+        .collect();
+
+    CorsLayer::new()
+        .allow_origin(origins)
+        .allow_credentials(true)
+        .allow_methods([
+            Method::GET,
+            Method::POST,
+            Method::PUT,
+            Method::PATCH,
+            Method::DELETE,
+        ])
+        .allow_headers([
+            header::CONTENT_TYPE,
+            header::IF_NONE_MATCH,
+            HeaderName::from_static("idempotency-key"),
+            crate::request_id::X_REQUEST_ID.clone(),
+        ])
+        .expose_headers([header::ETAG, crate::request_id::X_REQUEST_ID.clone()])
+}
+
 async fn health_handler(State(state): State<AppState>) -> Result<&'static str, StatusCode> {
     match state.db.health_check().await {
         Ok(_) => Ok("OK"),
@@ -171,3 +448,40 @@ async fn health_handler(State(state): State<AppState>) -> Result<&'static str, S
         }
     }
 }
+
+async fn detailed_health_handler(
+    State(state): State<AppState>,
+) -> (StatusCode, Json<serde_json::Value>) {
+    // The endpoint is public, so the error itself is only logged
+    let database_connected = match state.db.health_check().await {
+        Ok(_) => true,
+        Err(e) => {
+            warn!("Health check failed: {}", e);
+            false
+        }
+    };
+
+    let migration_version = state.db.migration_version().await.ok();
+
+    let fermentation_repo = crate::fermentation::FermentationRepository::new(state.db.clone());
+    let active_fermentations = fermentation_repo.count_active().await.ok();
+
+    let status = if database_connected {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+
+    (
+        status,
+        Json(serde_json::json!({
+            "status": if database_connected { "ok" } else { "unavailable" },
+            "database": {
+                "connected": database_connected,
+                "migration_version": migration_version,
+            },
+            "uptime_seconds": state.started_at.elapsed().as_secs(),
+            "active_fermentations": active_fermentations,
+        })),
+    )
+}
@@ -21,17 +21,31 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     let config = Arc::new(AppConfig::load()?);
     info!("Configuration loaded successfully");
 
+    // Fail now rather than on the first photo upload
+    config.prepare_uploads_dir()?;
+    info!("Uploads directory ready at {}", config.uploads_dir);
+
     // Initialize database
     let db = Arc::new(Database::new(&config.database_url).await?);
     info!("Database initialized successfully");
 
     // Run migrations
-    db.migrate().await?;
+    db.migrate(config.seed_default_profiles).await?;
     info!("Database migrations completed");
 
+    // Photos stored before sizes were recorded would otherwise escape the storage quota
+    let backfilled = raugupatis_log::photos::PhotoRepository::new(db.clone())
+        .backfill_file_sizes(&config.uploads_dir)
+        .await?;
+    if backfilled > 0 {
+        info!("Recorded the size of {} existing photos", backfilled);
+    }
+
     let app_state = AppState {
-        db,
+        db: db.clone(),
         config: config.clone(),
+        started_at: std::time::Instant::now(),
+        metrics: Arc::new(Default::default()),
     };
 
     let app = create_router(app_state).await;
@@ -39,7 +53,37 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     let listener = TcpListener::bind(&config.server_address).await?;
     info!("Server starting on {}", config.server_address);
 
-    axum::serve(listener, app).await?;
+    // Stop accepting connections on Ctrl-C or SIGTERM and let in-flight requests finish
+    axum::serve(listener, app)
+        .with_graceful_shutdown(shutdown_signal())
+        .await?;
+
+    info!("Server stopped, closing database");
+    db.checkpoint().await?;
 
     Ok(())
 }
+
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        tokio::signal::ctrl_c()
+            .await
+            .expect("Failed to install Ctrl-C handler");
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("Failed to install SIGTERM handler")
+            .recv()
+            .await;
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => info!("Received Ctrl-C, shutting down"),
+        _ = terminate => info!("Received SIGTERM, shutting down"),
+    }
+}
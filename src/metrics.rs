@@ -0,0 +1,171 @@
+use axum::{
+    extract::{MatchedPath, Request, State},
+    http::header,
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+use crate::AppState;
+
+/// In-process counters exposed at `/metrics` in the Prometheus text format.
+/// Counters start at zero whenever the application starts.
+#[derive(Debug, Default)]
+pub struct Metrics {
+    requests_total: AtomicU64,
+    /// Request counts keyed by method and route pattern (e.g. `/api/fermentation/:id`)
+    requests_by_endpoint: Mutex<BTreeMap<(String, String), u64>>,
+    login_successes: AtomicU64,
+    login_failures: AtomicU64,
+    fermentations_created: AtomicU64,
+}
+
+impl Metrics {
+    pub fn record_request(&self, method: &str, path: &str) {
+        self.requests_total.fetch_add(1, Ordering::Relaxed);
+        *self
+            .requests_by_endpoint
+            .lock()
+            .unwrap()
+            .entry((method.to_string(), path.to_string()))
+            .or_insert(0) += 1;
+    }
+
+    pub fn record_login(&self, success: bool) {
+        let counter = if success {
+            &self.login_successes
+        } else {
+            &self.login_failures
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_fermentation_created(&self) {
+        self.fermentations_created.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Render all counters in the Prometheus text exposition format
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+
+        write_counter_header(
+            &mut out,
+            "raugupatis_http_requests_total",
+            "HTTP requests handled by a route",
+        );
+        let _ = writeln!(
+            out,
+            "raugupatis_http_requests_total {}",
+            self.requests_total.load(Ordering::Relaxed)
+        );
+
+        write_counter_header(
+            &mut out,
+            "raugupatis_http_requests_by_endpoint_total",
+            "HTTP requests by method and route",
+        );
+        for ((method, path), count) in self.requests_by_endpoint.lock().unwrap().iter() {
+            let _ = writeln!(
+                out,
+                "raugupatis_http_requests_by_endpoint_total{{method=\"{}\",path=\"{}\"}} {}",
+                escape_label(method),
+                escape_label(path),
+                count
+            );
+        }
+
+        write_counter_header(
+            &mut out,
+            "raugupatis_logins_total",
+            "Login attempts by result",
+        );
+        let _ = writeln!(
+            out,
+            "raugupatis_logins_total{{result=\"success\"}} {}",
+            self.login_successes.load(Ordering::Relaxed)
+        );
+        let _ = writeln!(
+            out,
+            "raugupatis_logins_total{{result=\"failure\"}} {}",
+            self.login_failures.load(Ordering::Relaxed)
+        );
+
+        write_counter_header(
+            &mut out,
+            "raugupatis_fermentations_created_total",
+            "Fermentations created, including clones",
+        );
+        let _ = writeln!(
+            out,
+            "raugupatis_fermentations_created_total {}",
+            self.fermentations_created.load(Ordering::Relaxed)
+        );
+
+        out
+    }
+}
+
+fn write_counter_header(out: &mut String, name: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} counter", name);
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// Count each routed request under its route pattern rather than the concrete path,
+/// so ids in URLs don't create a new series per fermentation
+pub async fn track_requests(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if let Some(path) = request.extensions().get::<MatchedPath>() {
+        state
+            .metrics
+            .record_request(request.method().as_str(), path.as_str());
+    }
+
+    next.run(request).await
+}
+
+pub async fn metrics_handler(State(state): State<AppState>) -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        state.metrics.render(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_counters() {
+        let metrics = Metrics::default();
+        metrics.record_request("GET", "/api/fermentation/:id");
+        metrics.record_request("GET", "/api/fermentation/:id");
+        metrics.record_request("POST", "/api/fermentation");
+        metrics.record_login(true);
+        metrics.record_login(false);
+        metrics.record_login(false);
+        metrics.record_fermentation_created();
+
+        let output = metrics.render();
+        assert!(output.contains("raugupatis_http_requests_total 3\n"));
+        assert!(output.contains(
+            "raugupatis_http_requests_by_endpoint_total{method=\"GET\",path=\"/api/fermentation/:id\"} 2\n"
+        ));
+        assert!(output.contains("raugupatis_logins_total{result=\"success\"} 1\n"));
+        assert!(output.contains("raugupatis_logins_total{result=\"failure\"} 2\n"));
+        assert!(output.contains("raugupatis_fermentations_created_total 1\n"));
+        assert!(output.contains("# TYPE raugupatis_logins_total counter\n"));
+    }
+}
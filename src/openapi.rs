@@ -0,0 +1,237 @@
+use axum::Json;
+use utoipa::{
+    openapi::security::{ApiKey, ApiKeyValue, Http, HttpAuthScheme, SecurityScheme},
+    Modify, OpenApi,
+};
+
+/// OpenAPI 3 description of the JSON API. Paths come from the `#[utoipa::path]` annotations on
+/// the handlers and schemas from the request and response models, so the document changes
+/// along with the code.
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "Raugupatis Log API",
+        description = "Track fermentations, their temperature readings and tasting notes"
+    ),
+    paths(
+        crate::users::handlers::register_user,
+        crate::users::handlers::login_user,
+        crate::users::handlers::logout_user,
+        crate::users::handlers::logout_all_sessions,
+        crate::users::handlers::current_user,
+        crate::users::handlers::update_profile,
+        crate::users::handlers::change_password,
+        crate::users::handlers::change_email,
+        crate::users::handlers::request_password_reset,
+        crate::users::handlers::confirm_password_reset,
+        crate::users::handlers::verify_email,
+        crate::users::handlers::export_account,
+        crate::users::handlers::delete_account,
+        crate::users::handlers::create_calendar_token,
+        crate::users::handlers::revoke_calendar_token,
+        crate::fermentation::handlers::list_fermentations,
+        crate::fermentation::handlers::list_recent,
+        crate::fermentation::handlers::list_by_ingredient,
+        crate::fermentation::handlers::list_due_soon,
+        crate::fermentation::handlers::bulk_update_status,
+        crate::fermentation::handlers::global_search,
+        crate::fermentation::handlers::get_profiles,
+        crate::fermentation::handlers::create_fermentation,
+        crate::fermentation::handlers::get_fermentation,
+        crate::fermentation::handlers::update_fermentation,
+        crate::fermentation::handlers::patch_fermentation,
+        crate::fermentation::handlers::delete_fermentation,
+        crate::fermentation::handlers::restore_fermentation,
+        crate::fermentation::handlers::clone_fermentation,
+        crate::fermentation::handlers::archive_fermentation,
+        crate::fermentation::handlers::unarchive_fermentation,
+        crate::fermentation::handlers::finish_fermentation,
+        crate::fermentation::handlers::reopen_fermentation,
+        crate::fermentation::handlers::export_fermentation,
+        crate::fermentation::handlers::list_tags,
+        crate::fermentation::handlers::add_tag,
+        crate::fermentation::handlers::remove_tag,
+        crate::fermentation::handlers::share_fermentation,
+        crate::fermentation::handlers::unshare_fermentation,
+        crate::fermentation::handlers::favorite_fermentation,
+        crate::fermentation::handlers::unfavorite_fermentation,
+        crate::fermentation::handlers::create_temperature_log,
+        crate::fermentation::handlers::list_temperature_logs,
+        crate::fermentation::handlers::update_temperature_log,
+        crate::fermentation::handlers::delete_temperature_log,
+        crate::fermentation::handlers::delete_all_temperature_logs,
+        crate::fermentation::handlers::temperature_stats,
+        crate::fermentation::handlers::temperature_gaps,
+        crate::fermentation::handlers::export_temperature_logs_csv,
+        crate::fermentation::handlers::import_temperature_logs_csv,
+        crate::fermentation::handlers::temperature_webhook,
+        crate::fermentation::handlers::create_webhook_token,
+        crate::fermentation::handlers::revoke_webhook_token,
+        crate::fermentation::handlers::create_ph_log,
+        crate::fermentation::handlers::list_ph_logs,
+        crate::fermentation::handlers::create_gravity_log,
+        crate::fermentation::handlers::list_gravity_logs,
+        crate::fermentation::handlers::gravity_stats,
+        crate::fermentation::handlers::create_measurement_log,
+        crate::fermentation::handlers::list_measurement_logs,
+        crate::fermentation::handlers::create_journal_entry,
+        crate::fermentation::handlers::list_journal_entries,
+        crate::fermentation::handlers::delete_journal_entry,
+        crate::fermentation::handlers::fermentation_timeline,
+        crate::fermentation::handlers::create_taste_profile,
+        crate::fermentation::handlers::list_taste_profiles,
+        crate::fermentation::handlers::list_all_taste_profiles,
+        crate::fermentation::handlers::dashboard_stats,
+        crate::fermentation::handlers::profile_breakdown,
+        crate::fermentation::handlers::rating_distribution,
+        crate::fermentation::handlers::profile_success,
+        crate::photos::handlers::upload_photo,
+        crate::photos::handlers::list_photos,
+        crate::photos::handlers::delete_photo,
+        crate::photos::handlers::feature_photo,
+        crate::admin::users::handlers::list_users,
+        crate::admin::users::handlers::create_user,
+        crate::admin::users::handlers::get_user,
+        crate::admin::users::handlers::update_user,
+        crate::admin::users::handlers::lock_user,
+        crate::admin::users::handlers::delete_user,
+        crate::admin::profiles::handlers::list_all_profiles,
+        crate::admin::profiles::handlers::profile_usage,
+        crate::admin::profiles::handlers::create_profile,
+        crate::admin::profiles::handlers::update_profile,
+        crate::admin::profiles::handlers::delete_profile,
+        crate::admin::profiles::handlers::copy_profile,
+        crate::admin::profiles::handlers::set_profile_active_status,
+        crate::admin::profiles::handlers::reassign_profile,
+        crate::admin::invites::handlers::list_invites,
+        crate::admin::invites::handlers::create_invite,
+    ),
+    components(schemas(
+        crate::users::TemperatureUnit,
+        crate::users::UserRole,
+        crate::users::ExperienceLevel,
+        crate::users::CreateUserRequest,
+        crate::users::UserResponse,
+        crate::users::AccountExport,
+        crate::users::LoginRequest,
+        crate::users::LoginResponse,
+        crate::users::UpdateProfileRequest,
+        crate::users::ChangePasswordRequest,
+        crate::users::ChangeEmailRequest,
+        crate::users::DeleteAccountRequest,
+        crate::users::PasswordResetRequest,
+        crate::users::PasswordResetConfirmRequest,
+        crate::users::VerifyEmailRequest,
+        crate::users::CalendarTokenResponse,
+        crate::fermentation::FermentationStatus,
+        crate::fermentation::FermentationProfile,
+        crate::fermentation::FermentationProfileResponse,
+        crate::fermentation::Fermentation,
+        crate::fermentation::Ingredient,
+        crate::fermentation::CreateFermentationRequest,
+        crate::fermentation::UpdateFermentationRequest,
+        crate::fermentation::FermentationResponse,
+        crate::fermentation::DueFermentation,
+        crate::fermentation::BulkStatusRequest,
+        crate::fermentation::BulkStatusResponse,
+        crate::fermentation::FinishFermentationRequest,
+        crate::fermentation::FermentationExport,
+        crate::fermentation::FermentationShare,
+        crate::fermentation::Tag,
+        crate::fermentation::TagRequest,
+        crate::fermentation::SearchResults,
+        crate::fermentation::FermentationSearchHit,
+        crate::fermentation::TemperatureLogSearchHit,
+        crate::fermentation::TemperatureLog,
+        crate::fermentation::TemperatureLogResponse,
+        crate::fermentation::CreateTemperatureLogRequest,
+        crate::fermentation::UpdateTemperatureLogRequest,
+        crate::fermentation::DeletedTemperatureLogs,
+        crate::fermentation::TemperatureStats,
+        crate::fermentation::TemperatureGaps,
+        crate::fermentation::WebhookTokenResponse,
+        crate::fermentation::PhLog,
+        crate::fermentation::CreatePhLogRequest,
+        crate::fermentation::GravityLog,
+        crate::fermentation::CreateGravityLogRequest,
+        crate::fermentation::GravityStats,
+        crate::fermentation::MeasurementKind,
+        crate::fermentation::MeasurementLog,
+        crate::fermentation::CreateMeasurementLogRequest,
+        crate::fermentation::JournalEntry,
+        crate::fermentation::CreateJournalEntryRequest,
+        crate::fermentation::TimelineEvent,
+        crate::fermentation::TasteProfile,
+        crate::fermentation::TasteProfileWithFermentation,
+        crate::fermentation::CreateTasteProfileRequest,
+        crate::fermentation::DashboardStats,
+        crate::fermentation::RatingDistribution,
+        crate::fermentation::ProfileSuccess,
+        crate::photos::PhotoResponse,
+        crate::admin::AdminUserResponse,
+        crate::admin::AdminUserDetailResponse,
+        crate::admin::AdminUserListResponse,
+        crate::admin::AdminCreateUserRequest,
+        crate::admin::AdminUpdateUserRequest,
+        crate::admin::LockUserRequest,
+        crate::admin::AdminProfileResponse,
+        crate::admin::ProfileUsageResponse,
+        crate::admin::CreateProfileRequest,
+        crate::admin::AdminUpdateProfileRequest,
+        crate::admin::CopyProfileRequest,
+        crate::admin::DeactivateProfileRequest,
+        crate::admin::ReassignProfileRequest,
+        crate::admin::CreateInviteRequest,
+        crate::admin::InviteCode,
+    )),
+    modifiers(&SessionCookie, &WebhookToken),
+    security(("session_cookie" = [])),
+    tags(
+        (name = "users", description = "Registration, login and account settings"),
+        (name = "fermentations", description = "Fermentation batches and profiles"),
+        (name = "temperature", description = "Temperature readings of a fermentation"),
+        (name = "measurements", description = "pH, gravity, weight and volume readings of a fermentation"),
+        (name = "journal", description = "Diary entries and the combined timeline of a fermentation"),
+        (name = "photos", description = "Photos of a fermentation"),
+        (name = "taste-profiles", description = "Tasting notes"),
+        (name = "dashboard", description = "Figures summarising the user's fermentations"),
+        (name = "admin", description = "User, profile and invite management, admins only")
+    )
+)]
+pub struct ApiDoc;
+
+/// Endpoints other than registration, login and the public lookups authenticate with the
+/// session cookie set by login
+struct SessionCookie;
+
+impl Modify for SessionCookie {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        openapi
+            .components
+            .get_or_insert_with(Default::default)
+            .add_security_scheme(
+                "session_cookie",
+                SecurityScheme::ApiKey(ApiKey::Cookie(ApiKeyValue::new("id"))),
+            );
+    }
+}
+
+/// The temperature webhook takes the fermentation's webhook token as a bearer token instead,
+/// so devices don't need to log in
+struct WebhookToken;
+
+impl Modify for WebhookToken {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        openapi
+            .components
+            .get_or_insert_with(Default::default)
+            .add_security_scheme(
+                "webhook_token",
+                SecurityScheme::Http(Http::new(HttpAuthScheme::Bearer)),
+            );
+    }
+}
+
+pub async fn openapi_handler() -> Json<utoipa::openapi::OpenApi> {
+    Json(ApiDoc::openapi())
+}
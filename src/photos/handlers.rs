@@ -2,10 +2,11 @@ use axum::extract::{Multipart, Path, State};
 use axum::http::StatusCode;
 use axum::Json;
 use chrono::Utc;
+use image::{DynamicImage, ImageDecoder, ImageError, ImageFormat};
 use tower_sessions::Session;
 
 use crate::fermentation::repository::FermentationRepository;
-use crate::photos::models::{PhotoResponse, PhotoStage};
+use crate::photos::models::{NewPhoto, PhotoResponse, PhotoStage};
 use crate::photos::repository::PhotoRepository;
 use crate::users::UserSession;
 use crate::AppState;
@@ -13,6 +14,32 @@ use crate::AppState;
 use std::fs;
 use std::io::Write;
 
+/// Maximum size of an uploaded photo in bytes (10 MB)
+pub const MAX_PHOTO_SIZE: usize = 10 * 1024 * 1024;
+
+/// Request body limit for photo uploads: the photo plus room for the other form fields
+pub const MAX_PHOTO_UPLOAD_BODY: usize = MAX_PHOTO_SIZE + 1024 * 1024;
+
+/// Maximum width/height of generated thumbnails in pixels
+pub const THUMBNAIL_MAX_SIZE: u32 = 300;
+
+/// JPEG quality used when a rotated photo has to be re-encoded
+const REENCODE_JPEG_QUALITY: u8 = 90;
+
+#[utoipa::path(
+    post,
+    path = "/api/fermentation/{id}/photos",
+    tag = "photos",
+    params(("id" = i64, Path, description = "Fermentation id")),
+    request_body(content_type = "multipart/form-data", description = "An image in the `photo` field, with optional `stage` (start, progress or end) and `caption` fields"),
+    responses(
+        (status = 201, description = "Photo stored, with a thumbnail when the image could be decoded", body = PhotoResponse),
+        (status = 400, description = "Missing photo or not an image"),
+        (status = 401, description = "Not logged in"),
+        (status = 404, description = "Fermentation not found"),
+        (status = 413, description = "Photo too large or storage quota exceeded")
+    )
+)]
 pub async fn upload_photo(
     State(state): State<AppState>,
     session: Session,
@@ -37,6 +64,7 @@ pub async fn upload_photo(
 
     let mut file_data: Option<Vec<u8>> = None;
     let mut file_name: Option<String> = None;
+    let mut content_type: Option<String> = None;
     let mut caption: Option<String> = None;
     let mut stage = PhotoStage::Progress;
 
@@ -53,13 +81,9 @@ pub async fn upload_photo(
                 file_name = field
                     .file_name()
                     .map(|name| sanitize_filename(name.to_string()));
-                file_data = Some(
-                    field
-                        .bytes()
-                        .await
-                        .map_err(|_| StatusCode::BAD_REQUEST)?
-                        .to_vec(),
-                );
+                content_type = field.content_type().map(|ct| ct.to_lowercase());
+                // Reading past the body limit reports 413 Payload Too Large
+                file_data = Some(field.bytes().await.map_err(|e| e.status())?.to_vec());
             }
             "caption" => {
                 caption = field
@@ -81,10 +105,15 @@ pub async fn upload_photo(
     let file_name = file_name.ok_or(StatusCode::BAD_REQUEST)?;
 
     // Validate file size (max 10MB)
-    if file_data.len() > 10 * 1024 * 1024 {
+    if file_data.len() > MAX_PHOTO_SIZE {
         return Err(StatusCode::PAYLOAD_TOO_LARGE);
     }
 
+    // Validate content type
+    if !content_type.is_some_and(|ct| ct.starts_with("image/")) {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
     // Validate file extension
     let extension = std::path::Path::new(&file_name)
         .extension()
@@ -108,28 +137,97 @@ pub async fn upload_photo(
     let unique_filename = format!("{}_{:x}.{}", timestamp, nanos, extension);
     let file_path = format!("{}/{}", fermentation_dir, unique_filename);
 
+    // Turn phone photos upright and drop their EXIF metadata (GPS position, camera details)
+    let ProcessedPhoto { data, image } =
+        tokio::task::spawn_blocking(move || process_photo(file_data))
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+            .map_err(|e| {
+                tracing::error!("Failed to process photo: {}", e);
+                StatusCode::INTERNAL_SERVER_ERROR
+            })?;
+
+    // Keep a single user from filling the disk
+    let photo_repo = PhotoRepository::new(state.db.clone());
+    let used_bytes = photo_repo
+        .total_bytes_for_user(user.user_id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    if used_bytes as u64 + data.len() as u64 > state.config.max_photo_bytes_per_user {
+        return Err(StatusCode::PAYLOAD_TOO_LARGE);
+    }
+
     // Write file to disk
     let mut file = fs::File::create(&file_path).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    file.write_all(&file_data)
+    file.write_all(&data)
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
+    // Generate a thumbnail; if the image can't be decoded the original is used instead
+    let thumbnail_filename = format!("{}_{:x}_thumb.jpg", timestamp, nanos);
+    let thumbnail_file_path = format!("{}/{}", fermentation_dir, thumbnail_filename);
+    let thumbnail_created = match image {
+        Some(image) => {
+            let thumbnail_target = thumbnail_file_path.clone();
+            let thumbnail_result =
+                tokio::task::spawn_blocking(move || generate_thumbnail(&image, &thumbnail_target))
+                    .await
+                    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+            match thumbnail_result {
+                Ok(()) => true,
+                Err(e) => {
+                    tracing::warn!("Could not generate thumbnail: {}", e);
+                    false
+                }
+            }
+        }
+        None => false,
+    };
+
     // Store photo metadata in database
-    let photo_repo = PhotoRepository::new(state.db.clone());
     let relative_path = format!("{}/{}", fermentation.id, unique_filename);
+    let relative_thumbnail_path =
+        thumbnail_created.then(|| format!("{}/{}", fermentation.id, thumbnail_filename));
+
+    // The thumbnail counts towards the quota along with the original
+    let thumbnail_size = if thumbnail_created {
+        fs::metadata(&thumbnail_file_path).map_or(0, |metadata| metadata.len())
+    } else {
+        0
+    };
 
     let photo = photo_repo
-        .create_photo(fermentation.id, relative_path, caption, Utc::now(), stage)
+        .create_photo(NewPhoto {
+            fermentation_id: fermentation.id,
+            file_path: relative_path,
+            file_size: (data.len() as u64 + thumbnail_size) as i64,
+            thumbnail_path: relative_thumbnail_path,
+            caption,
+            taken_at: Utc::now(),
+            stage,
+        })
         .await
         .map_err(|e| {
             tracing::error!("Error creating photo record: {}", e);
-            // Clean up file if database insert fails
+            // Clean up files if database insert fails
             let _ = fs::remove_file(&file_path);
+            let _ = fs::remove_file(&thumbnail_file_path);
             StatusCode::INTERNAL_SERVER_ERROR
         })?;
 
     Ok((StatusCode::CREATED, Json(PhotoResponse::from(photo))))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/fermentation/{id}/photos",
+    tag = "photos",
+    params(("id" = i64, Path, description = "Fermentation id")),
+    responses(
+        (status = 200, description = "The fermentation's photos", body = [PhotoResponse]),
+        (status = 401, description = "Not logged in"),
+        (status = 404, description = "Fermentation not found")
+    )
+)]
 pub async fn list_photos(
     State(state): State<AppState>,
     session: Session,
@@ -154,7 +252,7 @@ pub async fn list_photos(
     // Get photos
     let photo_repo = PhotoRepository::new(state.db.clone());
     let photos = photo_repo
-        .find_by_fermentation(fermentation_id)
+        .list_by_fermentation(fermentation_id, user.user_id)
         .await
         .map_err(|e| {
             tracing::error!("Error fetching photos: {}", e);
@@ -164,6 +262,237 @@ pub async fn list_photos(
     Ok(Json(photos.into_iter().map(PhotoResponse::from).collect()))
 }
 
+#[utoipa::path(
+    delete,
+    path = "/api/fermentation/{id}/photos/{photo_id}",
+    tag = "photos",
+    params(
+        ("id" = i64, Path, description = "Fermentation id"),
+        ("photo_id" = i64, Path, description = "Photo id")
+    ),
+    responses(
+        (status = 204, description = "Photo and its files deleted"),
+        (status = 401, description = "Not logged in"),
+        (status = 404, description = "Photo not found")
+    )
+)]
+pub async fn delete_photo(
+    State(state): State<AppState>,
+    session: Session,
+    Path((fermentation_id, photo_id)): Path<(i64, i64)>,
+) -> Result<StatusCode, StatusCode> {
+    // Get user from session
+    let user_session: Option<UserSession> = session
+        .get("user")
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let user = user_session.ok_or(StatusCode::UNAUTHORIZED)?;
+
+    let photo_repo = PhotoRepository::new(state.db.clone());
+    let photo = photo_repo
+        .delete_photo(fermentation_id, photo_id, user.user_id)
+        .await
+        .map_err(|e| {
+            tracing::error!("Error deleting photo: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    // The row is gone; a missing or undeletable file shouldn't fail the request
+    for relative_path in std::iter::once(&photo.file_path).chain(photo.thumbnail_path.as_ref()) {
+        let file_path = format!("{}/{}", state.config.uploads_dir, relative_path);
+        if let Err(e) = fs::remove_file(&file_path) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                tracing::warn!("Failed to remove photo file {}: {}", file_path, e);
+            }
+        }
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Feature a photo so it is used as the fermentation's thumbnail
+#[utoipa::path(
+    post,
+    path = "/api/fermentation/{id}/photos/{photo_id}/feature",
+    tag = "photos",
+    params(
+        ("id" = i64, Path, description = "Fermentation id"),
+        ("photo_id" = i64, Path, description = "Photo id")
+    ),
+    responses(
+        (status = 200, description = "Photo now used as the fermentation's thumbnail", body = PhotoResponse),
+        (status = 401, description = "Not logged in"),
+        (status = 404, description = "Photo not found")
+    )
+)]
+pub async fn feature_photo(
+    State(state): State<AppState>,
+    session: Session,
+    Path((fermentation_id, photo_id)): Path<(i64, i64)>,
+) -> Result<Json<PhotoResponse>, StatusCode> {
+    // Get user from session
+    let user_session: Option<UserSession> = session
+        .get("user")
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let user = user_session.ok_or(StatusCode::UNAUTHORIZED)?;
+
+    let photo_repo = PhotoRepository::new(state.db.clone());
+    let photo = photo_repo
+        .feature_photo(fermentation_id, photo_id, user.user_id)
+        .await
+        .map_err(|e| {
+            tracing::error!("Error featuring photo: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    Ok(Json(PhotoResponse::from(photo)))
+}
+
+/// An upload ready to be stored, with the decoded image for the thumbnail when there is one
+struct ProcessedPhoto {
+    data: Vec<u8>,
+    image: Option<DynamicImage>,
+}
+
+/// What decoding an upload found out about it
+struct DecodedPhoto {
+    image: DynamicImage,
+    reoriented: bool,
+    has_exif: bool,
+}
+
+/// Remove the upload directories of fermentations whose rows are already deleted. Failures
+/// are only logged, since there is nothing left to roll back.
+pub(crate) fn remove_fermentation_uploads(uploads_dir: &str, fermentation_ids: &[i64]) {
+    for fermentation_id in fermentation_ids {
+        let dir = format!("{}/{}", uploads_dir, fermentation_id);
+        if let Err(e) = fs::remove_dir_all(&dir) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                tracing::warn!(
+                    "Failed to remove uploads for fermentation {}: {}",
+                    fermentation_id,
+                    e
+                );
+            }
+        }
+    }
+}
+
+/// Decode an upload, apply its EXIF orientation and remove its metadata, since phone cameras
+/// embed the GPS position and photos can be shown on public share pages.
+///
+/// JPEGs that are already upright have their metadata segments cut out without touching the
+/// image data. Photos that needed turning, and other formats carrying EXIF, are re-encoded,
+/// which drops all metadata. Undecodable uploads are stored as they are.
+fn process_photo(data: Vec<u8>) -> Result<ProcessedPhoto, ImageError> {
+    let decoded = match decode_upright(&data) {
+        Ok(decoded) => decoded,
+        Err(e) => {
+            tracing::warn!("Could not decode photo: {}", e);
+            let data = strip_jpeg_metadata(&data).unwrap_or(data);
+            return Ok(ProcessedPhoto { data, image: None });
+        }
+    };
+
+    let data = if decoded.reoriented {
+        encode_image(&decoded.image, image::guess_format(&data)?)?
+    } else if let Some(stripped) = strip_jpeg_metadata(&data) {
+        stripped
+    } else if decoded.has_exif {
+        encode_image(&decoded.image, image::guess_format(&data)?)?
+    } else {
+        data
+    };
+
+    Ok(ProcessedPhoto {
+        data,
+        image: Some(decoded.image),
+    })
+}
+
+/// Decode an image and turn it upright
+fn decode_upright(data: &[u8]) -> Result<DecodedPhoto, ImageError> {
+    let mut decoder = image::ImageReader::new(std::io::Cursor::new(data))
+        .with_guessed_format()?
+        .into_decoder()?;
+    let orientation = decoder.orientation()?;
+    let has_exif = decoder.exif_metadata()?.is_some();
+    let mut image = DynamicImage::from_decoder(decoder)?;
+    image.apply_orientation(orientation);
+
+    Ok(DecodedPhoto {
+        image,
+        reoriented: orientation != image::metadata::Orientation::NoTransforms,
+        has_exif,
+    })
+}
+
+/// Copy a JPEG without its APP1 (EXIF, XMP) and APP13 (IPTC) segments. Returns `None` if the
+/// data isn't a JPEG or its segments can't be followed.
+fn strip_jpeg_metadata(data: &[u8]) -> Option<Vec<u8>> {
+    if !data.starts_with(&[0xFF, 0xD8]) {
+        return None;
+    }
+
+    let mut stripped = Vec::with_capacity(data.len());
+    stripped.extend_from_slice(&data[..2]);
+    let mut pos = 2;
+    loop {
+        if *data.get(pos)? != 0xFF {
+            return None;
+        }
+        match *data.get(pos + 1)? {
+            // Fill byte before the actual marker
+            0xFF => pos += 1,
+            // Start of scan or end of image: the rest is image data
+            0xDA | 0xD9 => {
+                stripped.extend_from_slice(&data[pos..]);
+                return Some(stripped);
+            }
+            // Markers without a length
+            0x01 | 0xD0..=0xD7 => {
+                stripped.extend_from_slice(&data[pos..pos + 2]);
+                pos += 2;
+            }
+            marker => {
+                let length = u16::from_be_bytes([*data.get(pos + 2)?, *data.get(pos + 3)?]);
+                let end = pos + 2 + length as usize;
+                if length < 2 || end > data.len() {
+                    return None;
+                }
+                if !matches!(marker, 0xE1 | 0xED) {
+                    stripped.extend_from_slice(&data[pos..end]);
+                }
+                pos = end;
+            }
+        }
+    }
+}
+
+fn encode_image(image: &DynamicImage, format: ImageFormat) -> Result<Vec<u8>, ImageError> {
+    let mut buffer = std::io::Cursor::new(Vec::new());
+    match format {
+        ImageFormat::Jpeg => image.to_rgb8().write_with_encoder(
+            image::codecs::jpeg::JpegEncoder::new_with_quality(&mut buffer, REENCODE_JPEG_QUALITY),
+        )?,
+        _ => image.write_to(&mut buffer, format)?,
+    }
+    Ok(buffer.into_inner())
+}
+
+/// Downscale an image to fit within `THUMBNAIL_MAX_SIZE` and save it as a JPEG
+fn generate_thumbnail(image: &DynamicImage, path: &str) -> Result<(), ImageError> {
+    let thumbnail = image.thumbnail(THUMBNAIL_MAX_SIZE, THUMBNAIL_MAX_SIZE);
+    thumbnail
+        .to_rgb8()
+        .save_with_format(path, ImageFormat::Jpeg)
+}
+
 fn sanitize_filename(filename: String) -> String {
     // Remove any path components and keep only the filename
     let filename = std::path::Path::new(&filename)
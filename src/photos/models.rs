@@ -1,5 +1,6 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -34,20 +35,46 @@ pub struct FermentationPhoto {
     pub id: i64,
     pub fermentation_id: i64,
     pub file_path: String,
+    pub thumbnail_path: Option<String>,
     pub caption: Option<String>,
     pub taken_at: DateTime<Utc>,
     pub stage: PhotoStage,
     pub created_at: DateTime<Utc>,
+    /// Used as the fermentation's thumbnail in place of the stage-based pick
+    pub is_featured: bool,
 }
 
-#[derive(Debug, Serialize)]
+impl FermentationPhoto {
+    /// Path to show in galleries and lists: the thumbnail when one exists, otherwise the original
+    pub fn display_path(&self) -> &str {
+        self.thumbnail_path.as_deref().unwrap_or(&self.file_path)
+    }
+}
+
+/// A photo that has been written to disk and is about to be recorded
+#[derive(Debug, Clone)]
+pub struct NewPhoto {
+    pub fermentation_id: i64,
+    /// Path of the original, relative to the uploads directory
+    pub file_path: String,
+    /// Bytes on disk for the original and its thumbnail together
+    pub file_size: i64,
+    pub thumbnail_path: Option<String>,
+    pub caption: Option<String>,
+    pub taken_at: DateTime<Utc>,
+    pub stage: PhotoStage,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
 pub struct PhotoResponse {
     pub id: i64,
     pub fermentation_id: i64,
     pub file_path: String,
+    pub thumbnail_path: Option<String>,
     pub caption: Option<String>,
     pub taken_at: DateTime<Utc>,
     pub stage: String,
+    pub is_featured: bool,
 }
 
 impl From<FermentationPhoto> for PhotoResponse {
@@ -56,9 +83,11 @@ impl From<FermentationPhoto> for PhotoResponse {
             id: photo.id,
             fermentation_id: photo.fermentation_id,
             file_path: photo.file_path,
+            thumbnail_path: photo.thumbnail_path,
             caption: photo.caption,
             taken_at: photo.taken_at,
             stage: photo.stage.as_str().to_string(),
+            is_featured: photo.is_featured,
         }
     }
 }
@@ -1,5 +1,5 @@
 use crate::database::Database;
-use crate::photos::models::{FermentationPhoto, PhotoStage};
+use crate::photos::models::{FermentationPhoto, NewPhoto, PhotoStage};
 use chrono::{DateTime, Utc};
 use rusqlite::OptionalExtension;
 use std::sync::Arc;
@@ -15,27 +15,25 @@ impl PhotoRepository {
 
     pub async fn create_photo(
         &self,
-        fermentation_id: i64,
-        file_path: String,
-        caption: Option<String>,
-        taken_at: DateTime<Utc>,
-        stage: PhotoStage,
+        photo: NewPhoto,
     ) -> Result<FermentationPhoto, Box<dyn std::error::Error + Send + Sync>> {
         let db = self.db.clone();
-        let stage_str = stage.as_str().to_string();
+        let stage_str = photo.stage.as_str().to_string();
 
         let photo_id = tokio::task::spawn_blocking(move || -> Result<i64, Box<dyn std::error::Error + Send + Sync>> {
-            let conn = db.get_connection().lock().unwrap();
+            let conn = db.get_connection().lock()?;
 
-            let taken_at_str = taken_at.format("%Y-%m-%d %H:%M:%S").to_string();
+            let taken_at_str = photo.taken_at.format("%Y-%m-%d %H:%M:%S").to_string();
 
             conn.execute(
-                "INSERT INTO fermentation_photos (fermentation_id, file_path, caption, taken_at, stage)
-                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                "INSERT INTO fermentation_photos (fermentation_id, file_path, file_size, thumbnail_path, caption, taken_at, stage)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
                 rusqlite::params![
-                    fermentation_id,
-                    &file_path,
-                    caption,
+                    photo.fermentation_id,
+                    &photo.file_path,
+                    photo.file_size,
+                    photo.thumbnail_path,
+                    photo.caption,
                     &taken_at_str,
                     &stage_str,
                 ],
@@ -59,10 +57,10 @@ impl PhotoRepository {
 
         tokio::task::spawn_blocking(
             move || -> Result<Option<FermentationPhoto>, Box<dyn std::error::Error + Send + Sync>> {
-                let conn = db.get_connection().lock().unwrap();
+                let conn = db.get_connection().lock()?;
 
                 let mut stmt = conn.prepare(
-                    "SELECT id, fermentation_id, file_path, caption, taken_at, stage, created_at
+                    "SELECT id, fermentation_id, file_path, caption, taken_at, stage, created_at, thumbnail_path, is_featured
                      FROM fermentation_photos WHERE id = ?1",
                 )?;
 
@@ -72,10 +70,12 @@ impl PhotoRepository {
                             id: row.get(0)?,
                             fermentation_id: row.get(1)?,
                             file_path: row.get(2)?,
+                            thumbnail_path: row.get(7)?,
                             caption: row.get(3)?,
                             taken_at: parse_datetime(row.get::<_, String>(4)?),
                             stage: PhotoStage::from(row.get::<_, String>(5)?),
                             created_at: parse_datetime(row.get::<_, String>(6)?),
+                            is_featured: row.get::<_, i64>(8)? != 0,
                         })
                     })
                     .optional()?;
@@ -94,10 +94,10 @@ impl PhotoRepository {
 
         tokio::task::spawn_blocking(
             move || -> Result<Vec<FermentationPhoto>, Box<dyn std::error::Error + Send + Sync>> {
-                let conn = db.get_connection().lock().unwrap();
+                let conn = db.get_connection().lock()?;
 
                 let mut stmt = conn.prepare(
-                    "SELECT id, fermentation_id, file_path, caption, taken_at, stage, created_at
+                    "SELECT id, fermentation_id, file_path, caption, taken_at, stage, created_at, thumbnail_path, is_featured
                      FROM fermentation_photos 
                      WHERE fermentation_id = ?1
                      ORDER BY taken_at ASC, created_at ASC",
@@ -109,10 +109,54 @@ impl PhotoRepository {
                             id: row.get(0)?,
                             fermentation_id: row.get(1)?,
                             file_path: row.get(2)?,
+                            thumbnail_path: row.get(7)?,
+                            caption: row.get(3)?,
+                            taken_at: parse_datetime(row.get::<_, String>(4)?),
+                            stage: PhotoStage::from(row.get::<_, String>(5)?),
+                            created_at: parse_datetime(row.get::<_, String>(6)?),
+                            is_featured: row.get::<_, i64>(8)? != 0,
+                        })
+                    })?
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                Ok(photos)
+            },
+        )
+        .await?
+    }
+
+    /// List a fermentation's photos ordered by `taken_at`, only if the fermentation belongs to the user
+    pub async fn list_by_fermentation(
+        &self,
+        fermentation_id: i64,
+        user_id: i64,
+    ) -> Result<Vec<FermentationPhoto>, Box<dyn std::error::Error + Send + Sync>> {
+        let db = self.db.clone();
+
+        tokio::task::spawn_blocking(
+            move || -> Result<Vec<FermentationPhoto>, Box<dyn std::error::Error + Send + Sync>> {
+                let conn = db.get_connection().lock()?;
+
+                let mut stmt = conn.prepare(
+                    "SELECT p.id, p.fermentation_id, p.file_path, p.caption, p.taken_at, p.stage, p.created_at, p.thumbnail_path, p.is_featured
+                     FROM fermentation_photos p
+                     JOIN fermentations f ON p.fermentation_id = f.id
+                     WHERE p.fermentation_id = ?1 AND f.user_id = ?2 AND f.deleted_at IS NULL
+                     ORDER BY p.taken_at ASC, p.created_at ASC",
+                )?;
+
+                let photos = stmt
+                    .query_map([fermentation_id, user_id], |row| {
+                        Ok(FermentationPhoto {
+                            id: row.get(0)?,
+                            fermentation_id: row.get(1)?,
+                            file_path: row.get(2)?,
+                            thumbnail_path: row.get(7)?,
                             caption: row.get(3)?,
                             taken_at: parse_datetime(row.get::<_, String>(4)?),
                             stage: PhotoStage::from(row.get::<_, String>(5)?),
                             created_at: parse_datetime(row.get::<_, String>(6)?),
+                            is_featured: row.get::<_, i64>(8)? != 0,
                         })
                     })?
                     .collect::<Result<Vec<_>, _>>()?;
@@ -123,7 +167,183 @@ impl PhotoRepository {
         .await?
     }
 
-    /// Get the thumbnail photo for a fermentation based on its status
+    /// Delete a photo row if it belongs to the given fermentation owned by the user.
+    /// Returns the deleted photo so the caller can remove its file.
+    pub async fn delete_photo(
+        &self,
+        fermentation_id: i64,
+        photo_id: i64,
+        user_id: i64,
+    ) -> Result<Option<FermentationPhoto>, Box<dyn std::error::Error + Send + Sync>> {
+        let db = self.db.clone();
+
+        tokio::task::spawn_blocking(
+            move || -> Result<Option<FermentationPhoto>, Box<dyn std::error::Error + Send + Sync>> {
+                let conn = db.get_connection().lock()?;
+
+                let photo = conn
+                    .query_row(
+                        "SELECT p.id, p.fermentation_id, p.file_path, p.caption, p.taken_at, p.stage, p.created_at, p.thumbnail_path, p.is_featured
+                         FROM fermentation_photos p
+                         JOIN fermentations f ON p.fermentation_id = f.id
+                         WHERE p.id = ?1 AND p.fermentation_id = ?2 AND f.user_id = ?3
+                           AND f.deleted_at IS NULL",
+                        [photo_id, fermentation_id, user_id],
+                        |row| {
+                            Ok(FermentationPhoto {
+                                id: row.get(0)?,
+                                fermentation_id: row.get(1)?,
+                                file_path: row.get(2)?,
+                                thumbnail_path: row.get(7)?,
+                                caption: row.get(3)?,
+                                taken_at: parse_datetime(row.get::<_, String>(4)?),
+                                stage: PhotoStage::from(row.get::<_, String>(5)?),
+                                created_at: parse_datetime(row.get::<_, String>(6)?),
+                                is_featured: row.get::<_, i64>(8)? != 0,
+                            })
+                        },
+                    )
+                    .optional()?;
+
+                if photo.is_some() {
+                    conn.execute("DELETE FROM fermentation_photos WHERE id = ?1", [photo_id])?;
+                }
+
+                Ok(photo)
+            },
+        )
+        .await?
+    }
+
+    /// Total size in bytes of the photos stored for all of a user's fermentations
+    pub async fn total_bytes_for_user(
+        &self,
+        user_id: i64,
+    ) -> Result<i64, Box<dyn std::error::Error + Send + Sync>> {
+        let db = self.db.clone();
+
+        tokio::task::spawn_blocking(
+            move || -> Result<i64, Box<dyn std::error::Error + Send + Sync>> {
+                let conn = db.get_connection().lock()?;
+
+                let total = conn.query_row(
+                    "SELECT COALESCE(SUM(p.file_size), 0)
+                 FROM fermentation_photos p
+                 JOIN fermentations f ON p.fermentation_id = f.id
+                 WHERE f.user_id = ?1",
+                    [user_id],
+                    |row| row.get(0),
+                )?;
+
+                Ok(total)
+            },
+        )
+        .await?
+    }
+
+    /// Fill in the size of photos recorded before sizes were tracked (they were stored as 0) from
+    /// the files in `uploads_dir`, so they count towards the quota. Returns the number updated.
+    pub async fn backfill_file_sizes(
+        &self,
+        uploads_dir: &str,
+    ) -> Result<usize, Box<dyn std::error::Error + Send + Sync>> {
+        let db = self.db.clone();
+        let uploads_dir = std::path::PathBuf::from(uploads_dir);
+
+        tokio::task::spawn_blocking(
+            move || -> Result<usize, Box<dyn std::error::Error + Send + Sync>> {
+                let conn = db.get_connection().lock()?;
+
+                let mut stmt = conn.prepare(
+                    "SELECT id, file_path, thumbnail_path FROM fermentation_photos WHERE file_size = 0",
+                )?;
+                let photos = stmt
+                    .query_map([], |row| {
+                        Ok((
+                            row.get::<_, i64>(0)?,
+                            row.get::<_, String>(1)?,
+                            row.get::<_, Option<String>>(2)?,
+                        ))
+                    })?
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                let mut updated = 0;
+                for (id, file_path, thumbnail_path) in photos {
+                    // Files that have gone missing take no space and keep counting as 0
+                    let size: u64 = std::iter::once(file_path)
+                        .chain(thumbnail_path)
+                        .filter_map(|path| std::fs::metadata(uploads_dir.join(path)).ok())
+                        .map(|metadata| metadata.len())
+                        .sum();
+                    if size > 0 {
+                        conn.execute(
+                            "UPDATE fermentation_photos SET file_size = ?1 WHERE id = ?2",
+                            rusqlite::params![size as i64, id],
+                        )?;
+                        updated += 1;
+                    }
+                }
+
+                Ok(updated)
+            },
+        )
+        .await?
+    }
+
+    /// Make a photo the fermentation's featured photo, clearing the flag from its other photos.
+    /// Returns None if the photo doesn't belong to a fermentation owned by the user.
+    pub async fn feature_photo(
+        &self,
+        fermentation_id: i64,
+        photo_id: i64,
+        user_id: i64,
+    ) -> Result<Option<FermentationPhoto>, Box<dyn std::error::Error + Send + Sync>> {
+        let db = self.db.clone();
+
+        let featured = tokio::task::spawn_blocking(
+            move || -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+                let mut conn = db.get_connection().lock()?;
+                let tx = conn.transaction_with_behavior(rusqlite::TransactionBehavior::Immediate)?;
+
+                let owned: Option<i64> = tx
+                    .query_row(
+                        "SELECT p.id FROM fermentation_photos p
+                         JOIN fermentations f ON p.fermentation_id = f.id
+                         WHERE p.id = ?1 AND p.fermentation_id = ?2 AND f.user_id = ?3
+                           AND f.deleted_at IS NULL",
+                        [photo_id, fermentation_id, user_id],
+                        |row| row.get(0),
+                    )
+                    .optional()?;
+
+                if owned.is_none() {
+                    return Ok(false);
+                }
+
+                tx.execute(
+                    "UPDATE fermentation_photos SET is_featured = 0 WHERE fermentation_id = ?1 AND id != ?2",
+                    [fermentation_id, photo_id],
+                )?;
+                tx.execute(
+                    "UPDATE fermentation_photos SET is_featured = 1 WHERE id = ?1",
+                    [photo_id],
+                )?;
+
+                tx.commit()?;
+                Ok(true)
+            },
+        )
+        .await??;
+
+        if !featured {
+            return Ok(None);
+        }
+
+        self.find_by_id(photo_id).await
+    }
+
+    /// Get the thumbnail photo for a fermentation: the featured photo if one is set,
+    /// otherwise one chosen by status
     /// For active/paused fermentations: returns first "start" stage photo
     /// For completed/failed fermentations: returns first "end" stage photo, falling back to first "start" stage photo
     pub async fn get_thumbnail_for_fermentation(
@@ -136,12 +356,12 @@ impl PhotoRepository {
 
         tokio::task::spawn_blocking(
             move || -> Result<Option<String>, Box<dyn std::error::Error + Send + Sync>> {
-                let conn = db.get_connection().lock().unwrap();
+                let conn = db.get_connection().lock()?;
 
                 // Helper function to query photo by stage
                 let query_photo_by_stage = |stage: &str| -> rusqlite::Result<Option<String>> {
                     let mut stmt = conn.prepare(
-                        "SELECT file_path FROM fermentation_photos 
+                        "SELECT COALESCE(thumbnail_path, file_path) FROM fermentation_photos
                          WHERE fermentation_id = ?1 AND stage = ?2
                          ORDER BY taken_at ASC, created_at ASC
                          LIMIT 1",
@@ -153,16 +373,28 @@ impl PhotoRepository {
                     .optional()
                 };
 
+                let featured = conn
+                    .query_row(
+                        "SELECT COALESCE(thumbnail_path, file_path) FROM fermentation_photos
+                         WHERE fermentation_id = ?1 AND is_featured = 1",
+                        [fermentation_id],
+                        |row| row.get::<_, String>(0),
+                    )
+                    .optional()?;
+                if featured.is_some() {
+                    return Ok(featured);
+                }
+
                 // Determine which stage to prioritize based on fermentation status
                 // Using string literals that match FermentationStatus::as_str() values
-                let (primary_stage, fallback_stage) = if status == "completed" || status == "failed"
-                {
-                    // For finished fermentations, prefer "end" stage, fallback to "start"
-                    ("end", Some("start"))
-                } else {
-                    // For active/paused fermentations, only look for "start" stage
-                    ("start", None)
-                };
+                let (primary_stage, fallback_stage) =
+                    if status == "completed" || status == "failed" || status == "archived" {
+                        // For finished fermentations, prefer "end" stage, fallback to "start"
+                        ("end", Some("start"))
+                    } else {
+                        // For active/paused fermentations, only look for "start" stage
+                        ("start", None)
+                    };
 
                 // Try to get the primary stage photo first
                 if let Some(photo) = query_photo_by_stage(primary_stage)? {
@@ -0,0 +1,69 @@
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use axum::{
+    body::Body,
+    extract::Request,
+    http::{HeaderName, HeaderValue},
+    middleware::Next,
+    response::Response,
+};
+use tracing::Span;
+
+pub static X_REQUEST_ID: HeaderName = HeaderName::from_static("x-request-id");
+
+/// Longest client-supplied request id that is kept as-is
+const MAX_REQUEST_ID_LENGTH: usize = 128;
+
+/// Reuse the caller's `X-Request-Id` when it looks sane, otherwise generate one, and echo
+/// it on the response. Runs before the trace layer so the id is part of every log span.
+pub async fn assign_request_id(mut request: Request, next: Next) -> Response {
+    let request_id = request
+        .headers()
+        .get(&X_REQUEST_ID)
+        .filter(|value| is_valid_request_id(value))
+        .cloned()
+        .unwrap_or_else(generate_request_id);
+
+    request
+        .headers_mut()
+        .insert(X_REQUEST_ID.clone(), request_id.clone());
+
+    let mut response = next.run(request).await;
+    response
+        .headers_mut()
+        .insert(X_REQUEST_ID.clone(), request_id);
+    response
+}
+
+/// Span for the trace layer, carrying the request id next to the usual request details
+pub fn make_span(request: &axum::http::Request<Body>) -> Span {
+    let request_id = request
+        .headers()
+        .get(&X_REQUEST_ID)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or("-");
+
+    tracing::info_span!(
+        "request",
+        method = %request.method(),
+        uri = %request.uri(),
+        version = ?request.version(),
+        request_id = %request_id,
+    )
+}
+
+fn is_valid_request_id(value: &HeaderValue) -> bool {
+    let bytes = value.as_bytes();
+    !bytes.is_empty()
+        && bytes.len() <= MAX_REQUEST_ID_LENGTH
+        && bytes
+            .iter()
+            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_' | b'.'))
+}
+
+/// Random 128-bit id, hex encoded
+fn generate_request_id() -> HeaderValue {
+    let mut bytes = [0u8; 16];
+    OsRng.fill_bytes(&mut bytes);
+    let id: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+    HeaderValue::from_str(&id).expect("hex is a valid header value")
+}
@@ -3,7 +3,9 @@ use crate::users::{UserResponse, UserSession};
 use crate::AppState;
 use askama::Template;
 use axum::extract::State;
+use axum::http::{header, HeaderMap};
 use axum::response::{Html, Redirect};
+use chrono::{DateTime, Utc};
 use tower_sessions::Session;
 
 #[derive(Template)]
@@ -63,3 +65,108 @@ pub async fn dashboard_handler(
             .unwrap_or_else(|_| "Template render error".to_string()),
     ))
 }
+
+/// How dates are written on HTML pages, chosen from the browser's `Accept-Language`.
+/// US English (and requests without a language) keep the spelled-out month-first format;
+/// every other locale gets unambiguous ISO 8601 dates.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DateStyle {
+    Us,
+    Iso,
+}
+
+impl DateStyle {
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        headers
+            .get(header::ACCEPT_LANGUAGE)
+            .and_then(|value| value.to_str().ok())
+            .map(Self::from_accept_language)
+            .unwrap_or(DateStyle::Us)
+    }
+
+    /// Pick the style for the most preferred language range, e.g. `de-DE,de;q=0.9,en;q=0.8`
+    pub fn from_accept_language(value: &str) -> Self {
+        let mut preferred: Option<(&str, f32)> = None;
+
+        for range in value.split(',') {
+            let mut parts = range.split(';');
+            let tag = parts.next().unwrap_or("").trim();
+            let quality = parts
+                .filter_map(|param| param.trim().strip_prefix("q="))
+                .find_map(|q| q.parse::<f32>().ok())
+                .unwrap_or(1.0);
+
+            if tag.is_empty() || tag == "*" || quality <= 0.0 {
+                continue;
+            }
+            // Earlier ranges win ties
+            if preferred.map_or(true, |(_, best)| quality > best) {
+                preferred = Some((tag, quality));
+            }
+        }
+
+        match preferred {
+            None => DateStyle::Us,
+            Some((tag, _)) if tag.eq_ignore_ascii_case("en") => DateStyle::Us,
+            Some((tag, _)) if tag.eq_ignore_ascii_case("en-us") => DateStyle::Us,
+            Some(_) => DateStyle::Iso,
+        }
+    }
+
+    /// Calendar date, e.g. "January 15, 2024" or "2024-01-15"
+    pub fn date(&self, at: &DateTime<Utc>) -> String {
+        let format = match self {
+            DateStyle::Us => "%B %d, %Y",
+            DateStyle::Iso => "%Y-%m-%d",
+        };
+        at.format(format).to_string()
+    }
+
+    /// Date and time, e.g. "January 15, 2024 at 02:30 PM" or "2024-01-15 14:30"
+    pub fn date_time(&self, at: &DateTime<Utc>) -> String {
+        let format = match self {
+            DateStyle::Us => "%B %d, %Y at %I:%M %p",
+            DateStyle::Iso => "%Y-%m-%d %H:%M",
+        };
+        at.format(format).to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_date_style_from_accept_language() {
+        assert_eq!(
+            DateStyle::from_accept_language("en-US,en;q=0.9"),
+            DateStyle::Us
+        );
+        assert_eq!(DateStyle::from_accept_language("en"), DateStyle::Us);
+        assert_eq!(
+            DateStyle::from_accept_language("de-DE,de;q=0.9"),
+            DateStyle::Iso
+        );
+        assert_eq!(DateStyle::from_accept_language("en-GB"), DateStyle::Iso);
+        assert_eq!(
+            DateStyle::from_accept_language("fr;q=0.5,en-US;q=0.8"),
+            DateStyle::Us
+        );
+        assert_eq!(DateStyle::from_accept_language("*"), DateStyle::Us);
+        assert_eq!(DateStyle::from_accept_language(""), DateStyle::Us);
+    }
+
+    #[test]
+    fn test_date_style_formats() {
+        let at = chrono::NaiveDate::from_ymd_opt(2024, 1, 15)
+            .unwrap()
+            .and_hms_opt(14, 30, 0)
+            .unwrap()
+            .and_utc();
+
+        assert_eq!(DateStyle::Us.date(&at), "January 15, 2024");
+        assert_eq!(DateStyle::Us.date_time(&at), "January 15, 2024 at 02:30 PM");
+        assert_eq!(DateStyle::Iso.date(&at), "2024-01-15");
+        assert_eq!(DateStyle::Iso.date_time(&at), "2024-01-15 14:30");
+    }
+}
@@ -1,7 +1,14 @@
 use argon2::{
-    password_hash::{rand_core::OsRng, PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
-    Argon2,
+    password_hash::{
+        rand_core::{OsRng, RngCore},
+        PasswordHash, PasswordHasher, PasswordVerifier, SaltString,
+    },
+    Algorithm, Argon2, Params, Version,
 };
+use sha2::{Digest, Sha256};
+use subtle::ConstantTimeEq;
+
+use crate::config::AppConfig;
 
 pub fn hash_password(password: &str) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
     let salt = SaltString::generate(&mut OsRng);
@@ -15,6 +22,8 @@ pub fn hash_password(password: &str) -> Result<String, Box<dyn std::error::Error
     Ok(password_hash)
 }
 
+/// Verify a password against its stored PHC hash. The hash comparison in `argon2` runs in
+/// constant time.
 pub fn verify_password(
     password: &str,
     password_hash: &str,
@@ -29,6 +38,91 @@ pub fn verify_password(
         .is_ok())
 }
 
+/// Whether a stored hash was produced with a different algorithm, version or cost parameters
+/// than `hash_password` uses today, so it should be replaced after the next successful login
+pub fn needs_rehash(password_hash: &str) -> bool {
+    let parsed = match PasswordHash::new(password_hash) {
+        Ok(parsed) => parsed,
+        Err(_) => return true,
+    };
+
+    let argon2 = Argon2::default();
+    if parsed.algorithm != Algorithm::default().ident()
+        || parsed.version != Some(Version::default().into())
+    {
+        return true;
+    }
+
+    match Params::try_from(&parsed) {
+        Ok(params) => {
+            let current = argon2.params();
+            params.m_cost() != current.m_cost()
+                || params.t_cost() != current.t_cost()
+                || params.p_cost() != current.p_cost()
+        }
+        Err(_) => true,
+    }
+}
+
+/// Check a password against the configured policy, returning the first unmet requirement
+/// phrased to follow "Password must ..."
+pub fn password_policy_violation(password: &str, config: &AppConfig) -> Option<String> {
+    if password.chars().count() < config.password_min_length {
+        return Some(format!(
+            "be at least {} characters long",
+            config.password_min_length
+        ));
+    }
+
+    if config.password_require_digit && !password.chars().any(|c| c.is_ascii_digit()) {
+        return Some("contain at least one digit".to_string());
+    }
+
+    if config.password_require_non_alphanumeric && password.chars().all(|c| c.is_alphanumeric()) {
+        return Some("contain at least one character that is not a letter or digit".to_string());
+    }
+
+    None
+}
+
+/// Number of consecutive failed logins before an account is temporarily throttled
+pub const MAX_FAILED_LOGIN_ATTEMPTS: i64 = 5;
+
+/// Window (and throttle duration) for counting failed logins, in minutes
+pub const LOGIN_THROTTLE_MINUTES: i64 = 15;
+
+/// Generate a random URL-safe token secret (32 bytes, hex encoded)
+pub fn generate_token() -> String {
+    let mut bytes = [0u8; 32];
+    OsRng.fill_bytes(&mut bytes);
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Hash a token from `generate_token` for storage. The token already carries 256 random bits,
+/// so a SHA-256 digest is enough and checking it doesn't cost a password KDF per request.
+pub fn hash_token(token: &str) -> String {
+    Sha256::digest(token.as_bytes())
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// Check a token against the digest stored by `hash_token`, in constant time. Tokens issued
+/// before the switch were stored as Argon2 PHC strings and are still verified that way.
+pub fn verify_token(
+    token: &str,
+    token_hash: &str,
+) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+    if token_hash.starts_with('$') {
+        return verify_password(token, token_hash);
+    }
+
+    Ok(hash_token(token)
+        .as_bytes()
+        .ct_eq(token_hash.as_bytes())
+        .into())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -42,6 +136,93 @@ mod tests {
         assert!(!verify_password("wrong_password", &hash).unwrap());
     }
 
+    #[test]
+    fn test_verify_and_rehash_outdated_hash() {
+        let password = "my_secure_password_123";
+
+        // A hash with weaker cost parameters than the current defaults
+        let weak = Argon2::new(
+            Algorithm::Argon2id,
+            Version::V0x13,
+            Params::new(1024, 1, 1, None).unwrap(),
+        );
+        let salt = SaltString::generate(&mut OsRng);
+        let old_hash = weak
+            .hash_password(password.as_bytes(), &salt)
+            .unwrap()
+            .to_string();
+
+        assert!(verify_password(password, &old_hash).unwrap());
+        assert!(needs_rehash(&old_hash));
+
+        // Other Argon2 variants are upgraded too
+        let argon2i = Argon2::new(Algorithm::Argon2i, Version::V0x13, Params::default());
+        let argon2i_hash = argon2i
+            .hash_password(password.as_bytes(), &salt)
+            .unwrap()
+            .to_string();
+        assert!(verify_password(password, &argon2i_hash).unwrap());
+        assert!(needs_rehash(&argon2i_hash));
+
+        let new_hash = hash_password(password).unwrap();
+        assert!(verify_password(password, &new_hash).unwrap());
+        assert!(!needs_rehash(&new_hash));
+        assert!(needs_rehash("not a phc string"));
+    }
+
+    #[test]
+    fn test_hash_and_verify_token() {
+        let token = generate_token();
+        let hash = hash_token(&token);
+
+        assert_eq!(hash.len(), 64);
+        assert!(verify_token(&token, &hash).unwrap());
+        assert!(!verify_token(&generate_token(), &hash).unwrap());
+        assert!(!verify_token(&token, "").unwrap());
+
+        // Tokens stored before the switch to SHA-256 still verify
+        let legacy_hash = hash_password(&token).unwrap();
+        assert!(verify_token(&token, &legacy_hash).unwrap());
+        assert!(!verify_token("wrong", &legacy_hash).unwrap());
+    }
+
+    #[test]
+    fn test_password_policy() {
+        let lenient = AppConfig::default();
+        assert_eq!(password_policy_violation("password", &lenient), None);
+        assert_eq!(
+            password_policy_violation("short", &lenient).as_deref(),
+            Some("be at least 8 characters long")
+        );
+
+        // Length counts characters, not UTF-8 bytes
+        assert_eq!(
+            password_policy_violation("ąčęėįš", &lenient).as_deref(),
+            Some("be at least 8 characters long")
+        );
+        assert_eq!(password_policy_violation("ąčęėįšųū", &lenient), None);
+
+        let strict = AppConfig {
+            password_min_length: 10,
+            password_require_digit: true,
+            password_require_non_alphanumeric: true,
+            ..AppConfig::default()
+        };
+        assert_eq!(
+            password_policy_violation("password", &strict).as_deref(),
+            Some("be at least 10 characters long")
+        );
+        assert_eq!(
+            password_policy_violation("longpassword", &strict).as_deref(),
+            Some("contain at least one digit")
+        );
+        assert_eq!(
+            password_policy_violation("longpassword1", &strict).as_deref(),
+            Some("contain at least one character that is not a letter or digit")
+        );
+        assert_eq!(password_policy_violation("long-password1", &strict), None);
+    }
+
     #[test]
     fn test_different_hashes_for_same_password() {
         let password = "same_password";
@@ -55,4 +236,14 @@ mod tests {
         assert!(verify_password(password, &hash1).unwrap());
         assert!(verify_password(password, &hash2).unwrap());
     }
+
+    #[test]
+    fn test_generate_token_is_random_hex() {
+        let token1 = generate_token();
+        let token2 = generate_token();
+
+        assert_eq!(token1.len(), 64);
+        assert!(token1.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_ne!(token1, token2);
+    }
 }
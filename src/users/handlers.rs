@@ -1,66 +1,69 @@
-use axum::{
-    extract::State,
-    http::StatusCode,
-    response::{IntoResponse, Response},
-    Json,
-};
+use axum::{extract::State, http::StatusCode, Json};
 use serde_json::json;
 use time::Duration;
 use tower_sessions::{Expiry, Session};
 
-use crate::users::auth::{hash_password, verify_password};
+use crate::error::ApiError;
+use crate::users::auth::{
+    hash_password, needs_rehash, password_policy_violation, verify_password, LOGIN_THROTTLE_MINUTES,
+};
 use crate::users::models::{
-    ChangePasswordRequest, CreateUserRequest, ExperienceLevel, LoginRequest, LoginResponse,
-    TemperatureUnit, UpdateProfileRequest, UserResponse, UserSession,
+    AccountExport, CalendarTokenResponse, ChangeEmailRequest, ChangePasswordRequest,
+    CreateUserRequest, DeleteAccountRequest, ExperienceLevel, LoginRequest, LoginResponse,
+    PasswordResetConfirmRequest, PasswordResetRequest, TemperatureUnit, UpdateProfileRequest, User,
+    UserResponse, UserSession, VerifyEmailRequest,
 };
 use crate::users::repository::UserRepository;
 use crate::AppState;
 
-#[derive(Debug)]
-pub enum ApiError {
-    UserAlreadyExists,
-    ValidationError(String),
-    DatabaseError(String),
-    InternalError(String),
-    Unauthorized,
-}
-
-impl IntoResponse for ApiError {
-    fn into_response(self) -> Response {
-        let (status, error_message) = match self {
-            ApiError::UserAlreadyExists => (
-                StatusCode::CONFLICT,
-                "User with this email already exists".to_string(),
-            ),
-            ApiError::ValidationError(msg) => (StatusCode::BAD_REQUEST, msg),
-            ApiError::DatabaseError(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg),
-            ApiError::InternalError(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg),
-            ApiError::Unauthorized => (StatusCode::UNAUTHORIZED, "Unauthorized".to_string()),
-        };
-
-        let body = Json(json!({
-            "error": error_message,
-        }));
-
-        (status, body).into_response()
-    }
-}
-
+#[utoipa::path(
+    post,
+    path = "/api/users/register",
+    tag = "users",
+    request_body = CreateUserRequest,
+    responses(
+        (status = 201, description = "User registered", body = UserResponse),
+        (status = 400, description = "Invalid input or invite code"),
+        (status = 403, description = "Registration is closed"),
+        (status = 409, description = "Email already registered")
+    ),
+    security(())
+)]
 pub async fn register_user(
     State(state): State<AppState>,
     Json(request): Json<CreateUserRequest>,
 ) -> Result<(StatusCode, Json<UserResponse>), ApiError> {
+    if !state.config.registration_open {
+        return Err(ApiError::Forbidden(
+            "Registration is closed on this instance".to_string(),
+        ));
+    }
+
+    // Only checked for presence here; the code is redeemed together with creating the user
+    let invite_code = if state.config.require_invite {
+        let code = request
+            .invite_code
+            .as_deref()
+            .map(str::trim)
+            .filter(|code| !code.is_empty())
+            .ok_or_else(|| {
+                ApiError::invalid_field("invite_code", "An invite code is required to register")
+            })?;
+        Some(code.to_string())
+    } else {
+        None
+    };
+
     // Validate email format
     if !is_valid_email(&request.email) {
-        return Err(ApiError::ValidationError(
-            "Invalid email format".to_string(),
-        ));
+        return Err(ApiError::invalid_field("email", "Invalid email format"));
     }
 
     // Validate password strength
-    if request.password.len() < 8 {
-        return Err(ApiError::ValidationError(
-            "Password must be at least 8 characters long".to_string(),
+    if let Some(requirement) = password_policy_violation(&request.password, &state.config) {
+        return Err(ApiError::invalid_field(
+            "password",
+            format!("Password must {}", requirement),
         ));
     }
 
@@ -79,14 +82,80 @@ pub async fn register_user(
     }
 
     // Create the user
-    let user = user_repo
-        .create_user(request)
-        .await
-        .map_err(|e| ApiError::InternalError(format!("Failed to create user: {}", e)))?;
+    let user = match invite_code {
+        Some(code) => user_repo.create_user_with_invite(request, code).await,
+        None => user_repo.create_user(request).await,
+    }
+    .map_err(|e| {
+        if e.to_string().contains("Invite code") {
+            ApiError::invalid_field("invite_code", e.to_string())
+        } else {
+            ApiError::InternalError(format!("Failed to create user: {}", e))
+        }
+    })?;
+
+    issue_email_verification(&state, &user_repo, &user).await;
 
     Ok((StatusCode::CREATED, Json(UserResponse::from(user))))
 }
 
+/// Create a verification token for the user's current address and deliver it.
+/// Failures are logged rather than returned so they never block the calling request.
+async fn issue_email_verification(state: &AppState, user_repo: &UserRepository, user: &User) {
+    match user_repo
+        .create_email_verification_token(user.id, &user.email)
+        .await
+    {
+        Ok(token) => {
+            // No mail transport is configured yet; expose the token in development only
+            if state.config.environment == "development" {
+                tracing::info!("Email verification token for {}: {}", user.email, token);
+            } else {
+                tracing::info!("Email verification requested for user {}", user.id);
+            }
+        }
+        Err(e) => tracing::error!("Failed to create email verification token: {}", e),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/users/verify-email",
+    tag = "users",
+    request_body = VerifyEmailRequest,
+    responses(
+        (status = 200, description = "Email verified"),
+        (status = 400, description = "Invalid or expired token")
+    ),
+    security(())
+)]
+pub async fn verify_email(
+    State(state): State<AppState>,
+    Json(request): Json<VerifyEmailRequest>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let user_repo = UserRepository::new(state.db.clone());
+
+    user_repo
+        .consume_email_verification_token(&request.token)
+        .await
+        .map_err(|e| ApiError::DatabaseError(format!("Failed to verify email token: {}", e)))?
+        .ok_or_else(|| ApiError::invalid_field("token", "Invalid or expired verification token"))?;
+
+    Ok(Json(json!({
+        "success": true,
+        "message": "Email address verified successfully"
+    })))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/users/logout",
+    tag = "users",
+    responses(
+        (status = 200, description = "Logged out")
+    ),
+    security(())
+)]
 pub async fn logout_user(session: Session) -> Result<Json<serde_json::Value>, ApiError> {
     // Clear the session
     session
@@ -100,6 +169,46 @@ pub async fn logout_user(session: Session) -> Result<Json<serde_json::Value>, Ap
     })))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/users/logout-all",
+    tag = "users",
+    responses(
+        (status = 200, description = "All of the user's sessions ended"),
+        (status = 401, description = "Not logged in")
+    )
+)]
+pub async fn logout_all_sessions(
+    session: Session,
+    State(state): State<AppState>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    // Check if user is authenticated
+    let user_session: UserSession = session
+        .get("user")
+        .await
+        .map_err(|e| ApiError::InternalError(format!("Failed to get session: {}", e)))?
+        .ok_or(ApiError::Unauthorized)?;
+
+    let user_repo = UserRepository::new(state.db.clone());
+
+    let sessions_terminated = user_repo
+        .delete_all_sessions(user_session.user_id)
+        .await
+        .map_err(|e| ApiError::DatabaseError(format!("Failed to end sessions: {}", e)))?;
+
+    // The current session was removed from the store as well; clear its cookie too
+    session
+        .flush()
+        .await
+        .map_err(|e| ApiError::InternalError(format!("Failed to clear session: {}", e)))?;
+
+    Ok(Json(json!({
+        "success": true,
+        "message": "Logged out of all sessions",
+        "sessions_terminated": sessions_terminated
+    })))
+}
+
 fn is_valid_email(email: &str) -> bool {
     // Basic email validation per RFC 5322 simplified rules
     // Must have exactly one @ separating local and domain parts
@@ -122,6 +231,17 @@ fn is_valid_email(email: &str) -> bool {
     domain_parts.len() >= 2 && domain_parts.iter().all(|part| !part.is_empty())
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/users/login",
+    tag = "users",
+    request_body = LoginRequest,
+    responses(
+        (status = 200, description = "Logged in; sets the session cookie", body = LoginResponse),
+        (status = 401, description = "Invalid credentials or account locked")
+    ),
+    security(())
+)]
 pub async fn login_user(
     session: Session,
     State(state): State<AppState>,
@@ -142,6 +262,7 @@ pub async fn login_user(
     let user = match user_repo.find_by_email(&request.email).await {
         Ok(Some(user)) => user,
         Ok(None) => {
+            state.metrics.record_login(false);
             return Ok(Json(LoginResponse {
                 success: false,
                 user: None,
@@ -165,9 +286,50 @@ pub async fn login_user(
         }));
     }
 
+    // Check if logins are throttled after repeated failures
+    let throttled_until = user_repo
+        .login_throttled_until(user.id)
+        .await
+        .map_err(|e| ApiError::DatabaseError(format!("Failed to check login attempts: {}", e)))?;
+
+    if let Some(until) = throttled_until {
+        let minutes = (until - chrono::Utc::now()).num_minutes() + 1;
+        return Ok(Json(LoginResponse {
+            success: false,
+            user: None,
+            message: format!(
+                "Too many failed login attempts. Please try again in {} minute{}.",
+                minutes,
+                if minutes == 1 { "" } else { "s" }
+            ),
+        }));
+    }
+
     // Verify password
     match verify_password(&request.password, &user.password_hash) {
         Ok(true) => {
+            state.metrics.record_login(true);
+            let mut user = user;
+            user_repo.reset_failed_logins(user.id).await.map_err(|e| {
+                ApiError::DatabaseError(format!("Failed to reset login attempts: {}", e))
+            })?;
+
+            // Upgrade hashes made with older parameters while the plaintext is at hand
+            if needs_rehash(&user.password_hash) {
+                match hash_password(&request.password) {
+                    Ok(new_hash) => {
+                        if let Err(e) = user_repo.update_password(user.id, new_hash).await {
+                            tracing::warn!("Failed to upgrade password hash: {}", e);
+                        }
+                    }
+                    Err(e) => tracing::warn!("Failed to rehash password: {}", e),
+                }
+            }
+            user.last_login_at =
+                Some(user_repo.record_login(user.id).await.map_err(|e| {
+                    ApiError::DatabaseError(format!("Failed to record login: {}", e))
+                })?);
+
             // Create server-side session
             let user_session = UserSession {
                 user_id: user.id,
@@ -175,11 +337,11 @@ pub async fn login_user(
                 role: user.role.clone(),
             };
             // Set session expiry based on remember_me flag
-            // Default: 24 hours, Remember me: 5 days (120 hours)
+            // Defaults: 24 hours, Remember me: 5 days (see AppConfig)
             let expiry_duration = if request.remember_me {
-                Duration::hours(120) // 5 days
+                Duration::days(state.config.remember_me_days)
             } else {
-                Duration::hours(24) // 1 day (default)
+                Duration::hours(state.config.session_ttl_hours)
             };
 
             session.set_expiry(Some(Expiry::OnInactivity(expiry_duration)));
@@ -190,17 +352,47 @@ pub async fn login_user(
                 .await
                 .map_err(|e| ApiError::InternalError(format!("Failed to create session: {}", e)))?;
 
+            // Persist now so the session has an id that can be tied to the user
+            session
+                .save()
+                .await
+                .map_err(|e| ApiError::InternalError(format!("Failed to save session: {}", e)))?;
+            if let Some(session_id) = session.id() {
+                user_repo
+                    .record_session(user.id, &session_id.to_string())
+                    .await
+                    .map_err(|e| {
+                        ApiError::DatabaseError(format!("Failed to record session: {}", e))
+                    })?;
+            }
+
             Ok(Json(LoginResponse {
                 success: true,
                 user: Some(UserResponse::from(user)),
                 message: "Login successful".to_string(),
             }))
         }
-        Ok(false) => Ok(Json(LoginResponse {
-            success: false,
-            user: None,
-            message: "Invalid email or password".to_string(),
-        })),
+        Ok(false) => {
+            state.metrics.record_login(false);
+            let throttled = user_repo.record_failed_login(user.id).await.map_err(|e| {
+                ApiError::DatabaseError(format!("Failed to record login attempt: {}", e))
+            })?;
+
+            let message = if throttled {
+                format!(
+                    "Too many failed login attempts. Please try again in {} minutes.",
+                    LOGIN_THROTTLE_MINUTES
+                )
+            } else {
+                "Invalid email or password".to_string()
+            };
+
+            Ok(Json(LoginResponse {
+                success: false,
+                user: None,
+                message,
+            }))
+        }
         Err(e) => Err(ApiError::InternalError(format!(
             "Failed to verify password: {}",
             e
@@ -208,6 +400,55 @@ pub async fn login_user(
     }
 }
 
+/// Return the user behind the session cookie, so clients can restore their state after a reload
+#[utoipa::path(
+    get,
+    path = "/api/users/me",
+    tag = "users",
+    responses(
+        (status = 200, description = "The logged-in user", body = UserResponse),
+        (status = 401, description = "Not logged in")
+    )
+)]
+pub async fn current_user(
+    session: Session,
+    State(state): State<AppState>,
+) -> Result<Json<UserResponse>, ApiError> {
+    // Check if user is authenticated
+    let user_session: UserSession = session
+        .get("user")
+        .await
+        .map_err(|e| ApiError::InternalError(format!("Failed to get session: {}", e)))?
+        .ok_or(ApiError::Unauthorized)?;
+
+    let user_repo = UserRepository::new(state.db.clone());
+
+    // A session whose user no longer exists is treated as logged out
+    let user = user_repo
+        .find_by_id(user_session.user_id)
+        .await
+        .map_err(|e| {
+            if e.to_string().contains("no rows") {
+                ApiError::Unauthorized
+            } else {
+                ApiError::DatabaseError(format!("Failed to find user: {}", e))
+            }
+        })?;
+
+    Ok(Json(UserResponse::from(user)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/users/profile",
+    tag = "users",
+    request_body = UpdateProfileRequest,
+    responses(
+        (status = 200, description = "Updated user", body = UserResponse),
+        (status = 400, description = "Invalid input"),
+        (status = 401, description = "Not logged in")
+    )
+)]
 pub async fn update_profile(
     session: Session,
     State(state): State<AppState>,
@@ -222,24 +463,41 @@ pub async fn update_profile(
 
     // Validate experience level
     if !ExperienceLevel::is_valid(&request.experience_level) {
-        return Err(ApiError::ValidationError(
-            "Invalid experience level. Must be 'beginner', 'intermediate', or 'advanced'"
-                .to_string(),
+        return Err(ApiError::invalid_field(
+            "experience_level",
+            "Invalid experience level. Must be 'beginner', 'intermediate', or 'advanced'",
         ));
     }
 
     // Validate temperature unit
     if !TemperatureUnit::is_valid(&request.preferred_temp_unit) {
-        return Err(ApiError::ValidationError(
-            "Invalid temperature unit. Must be 'fahrenheit' or 'celsius'".to_string(),
+        return Err(ApiError::invalid_field(
+            "preferred_temp_unit",
+            "Invalid temperature unit. Must be 'fahrenheit' or 'celsius'",
         ));
     }
 
+    // Validate default profile
+    if let Some(profile_id) = request.default_profile_id {
+        let fermentation_repo = crate::fermentation::FermentationRepository::new(state.db.clone());
+        let profile = fermentation_repo
+            .get_profile_by_id(profile_id)
+            .await
+            .map_err(|e| ApiError::DatabaseError(format!("Failed to find profile: {}", e)))?;
+
+        if !profile.is_some_and(|p| p.is_active) {
+            return Err(ApiError::invalid_field(
+                "default_profile_id",
+                "Default profile must be an active fermentation profile",
+            ));
+        }
+    }
+
     let experience_level = ExperienceLevel::from(request.experience_level);
     let preferred_temp_unit = TemperatureUnit::from(request.preferred_temp_unit);
     let user_repo = UserRepository::new(state.db.clone());
 
-    // Update the user's profile (experience level, temperature unit, first name, and last name)
+    // Update the user's profile (experience level, temperature unit, names and default profile)
     let updated_user = user_repo
         .update_profile(
             user_session.user_id,
@@ -247,6 +505,7 @@ pub async fn update_profile(
             preferred_temp_unit,
             request.first_name,
             request.last_name,
+            request.default_profile_id,
         )
         .await
         .map_err(|e| ApiError::DatabaseError(format!("Failed to update profile: {}", e)))?;
@@ -254,6 +513,17 @@ pub async fn update_profile(
     Ok(Json(UserResponse::from(updated_user)))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/users/password",
+    tag = "users",
+    request_body = ChangePasswordRequest,
+    responses(
+        (status = 200, description = "Password changed"),
+        (status = 400, description = "Invalid input"),
+        (status = 401, description = "Not logged in")
+    )
+)]
 pub async fn change_password(
     session: Session,
     State(state): State<AppState>,
@@ -267,9 +537,10 @@ pub async fn change_password(
         .ok_or(ApiError::Unauthorized)?;
 
     // Validate new password strength
-    if request.new_password.len() < 8 {
-        return Err(ApiError::ValidationError(
-            "New password must be at least 8 characters long".to_string(),
+    if let Some(requirement) = password_policy_violation(&request.new_password, &state.config) {
+        return Err(ApiError::invalid_field(
+            "new_password",
+            format!("New password must {}", requirement),
         ));
     }
 
@@ -301,8 +572,9 @@ pub async fn change_password(
                 "message": "Password changed successfully"
             })))
         }
-        Ok(false) => Err(ApiError::ValidationError(
-            "Current password is incorrect".to_string(),
+        Ok(false) => Err(ApiError::invalid_field(
+            "current_password",
+            "Current password is incorrect",
         )),
         Err(e) => Err(ApiError::InternalError(format!(
             "Failed to verify password: {}",
@@ -311,6 +583,358 @@ pub async fn change_password(
     }
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/users/change-email",
+    tag = "users",
+    request_body = ChangeEmailRequest,
+    responses(
+        (status = 200, description = "Email changed"),
+        (status = 400, description = "Invalid input"),
+        (status = 401, description = "Not logged in"),
+        (status = 409, description = "Email already registered")
+    )
+)]
+pub async fn change_email(
+    session: Session,
+    State(state): State<AppState>,
+    Json(request): Json<ChangeEmailRequest>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    // Check if user is authenticated
+    let user_session: UserSession = session
+        .get("user")
+        .await
+        .map_err(|e| ApiError::InternalError(format!("Failed to get session: {}", e)))?
+        .ok_or(ApiError::Unauthorized)?;
+
+    let new_email = request.new_email.trim();
+
+    // Validate email format the same way registration does
+    if !is_valid_email(new_email) {
+        return Err(ApiError::invalid_field("new_email", "Invalid email format"));
+    }
+
+    let user_repo = UserRepository::new(state.db.clone());
+
+    let user = user_repo
+        .find_by_id(user_session.user_id)
+        .await
+        .map_err(|e| ApiError::DatabaseError(format!("Failed to find user: {}", e)))?;
+
+    // Re-authenticate before changing the login identity
+    match verify_password(&request.current_password, &user.password_hash) {
+        Ok(true) => {}
+        Ok(false) => {
+            return Err(ApiError::invalid_field(
+                "current_password",
+                "Current password is incorrect",
+            ))
+        }
+        Err(e) => {
+            return Err(ApiError::InternalError(format!(
+                "Failed to verify password: {}",
+                e
+            )))
+        }
+    }
+
+    if new_email == user.email {
+        return Err(ApiError::invalid_field(
+            "new_email",
+            "New email must be different from the current email",
+        ));
+    }
+
+    let updated = user_repo
+        .update_email(user.id, new_email)
+        .await
+        .map_err(|e| ApiError::DatabaseError(format!("Failed to update email: {}", e)))?;
+
+    if !updated {
+        return Err(ApiError::UserAlreadyExists);
+    }
+
+    let user = user_repo
+        .find_by_id(user.id)
+        .await
+        .map_err(|e| ApiError::DatabaseError(format!("Failed to find user: {}", e)))?;
+
+    // Keep the session in step with the new address
+    session
+        .insert(
+            "user",
+            UserSession {
+                email: user.email.clone(),
+                ..user_session
+            },
+        )
+        .await
+        .map_err(|e| ApiError::InternalError(format!("Failed to update session: {}", e)))?;
+
+    issue_email_verification(&state, &user_repo, &user).await;
+
+    Ok(Json(json!({
+        "success": true,
+        "message": "Email changed successfully. Please verify your new address",
+        "user": UserResponse::from(user)
+    })))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/users/password-reset/request",
+    tag = "users",
+    request_body = PasswordResetRequest,
+    responses(
+        (status = 200, description = "Reset link sent if the account exists")
+    ),
+    security(())
+)]
+pub async fn request_password_reset(
+    State(state): State<AppState>,
+    Json(request): Json<PasswordResetRequest>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let user_repo = UserRepository::new(state.db.clone());
+
+    // Always answer the same way so the endpoint can't be used to discover accounts
+    match user_repo.find_by_email(&request.email).await {
+        Ok(Some(user)) => match user_repo.create_password_reset_token(user.id).await {
+            Ok(token) => {
+                // No mail transport is configured yet; expose the token in development only
+                if state.config.environment == "development" {
+                    tracing::info!("Password reset token for {}: {}", user.email, token);
+                } else {
+                    tracing::info!("Password reset requested for user {}", user.id);
+                }
+            }
+            Err(e) => tracing::error!("Failed to create password reset token: {}", e),
+        },
+        Ok(None) => {}
+        Err(e) => tracing::error!("Failed to look up user for password reset: {}", e),
+    }
+
+    Ok(Json(json!({
+        "success": true,
+        "message": "If an account with that email exists, a password reset link has been sent"
+    })))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/users/password-reset/confirm",
+    tag = "users",
+    request_body = PasswordResetConfirmRequest,
+    responses(
+        (status = 200, description = "Password reset"),
+        (status = 400, description = "Invalid or expired token, or weak password")
+    ),
+    security(())
+)]
+pub async fn confirm_password_reset(
+    State(state): State<AppState>,
+    Json(request): Json<PasswordResetConfirmRequest>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    // Validate new password strength
+    if let Some(requirement) = password_policy_violation(&request.new_password, &state.config) {
+        return Err(ApiError::invalid_field(
+            "new_password",
+            format!("New password must {}", requirement),
+        ));
+    }
+
+    let user_repo = UserRepository::new(state.db.clone());
+
+    let user_id = user_repo
+        .consume_password_reset_token(&request.token)
+        .await
+        .map_err(|e| ApiError::DatabaseError(format!("Failed to verify reset token: {}", e)))?
+        .ok_or_else(|| ApiError::invalid_field("token", "Invalid or expired reset token"))?;
+
+    let new_password_hash = hash_password(&request.new_password)
+        .map_err(|e| ApiError::InternalError(format!("Failed to hash password: {}", e)))?;
+
+    user_repo
+        .update_password(user_id, new_password_hash)
+        .await
+        .map_err(|e| ApiError::DatabaseError(format!("Failed to update password: {}", e)))?;
+
+    user_repo
+        .reset_failed_logins(user_id)
+        .await
+        .map_err(|e| ApiError::DatabaseError(format!("Failed to reset login attempts: {}", e)))?;
+
+    Ok(Json(json!({
+        "success": true,
+        "message": "Password has been reset successfully"
+    })))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/users/export",
+    tag = "users",
+    responses(
+        (status = 200, description = "Everything stored about the user", body = AccountExport),
+        (status = 401, description = "Not logged in")
+    )
+)]
+pub async fn export_account(
+    session: Session,
+    State(state): State<AppState>,
+) -> Result<Json<AccountExport>, ApiError> {
+    // Check if user is authenticated
+    let user_session: UserSession = session
+        .get("user")
+        .await
+        .map_err(|e| ApiError::InternalError(format!("Failed to get session: {}", e)))?
+        .ok_or(ApiError::Unauthorized)?;
+
+    let user_repo = UserRepository::new(state.db.clone());
+    let fermentation_repo = crate::fermentation::FermentationRepository::new(state.db.clone());
+
+    let user = user_repo
+        .find_by_id(user_session.user_id)
+        .await
+        .map_err(|e| ApiError::DatabaseError(format!("Failed to find user: {}", e)))?;
+
+    let fermentations = fermentation_repo
+        .export_all_for_user(user.id)
+        .await
+        .map_err(|e| ApiError::DatabaseError(format!("Failed to export fermentations: {}", e)))?;
+
+    Ok(Json(AccountExport {
+        user: UserResponse::from(user),
+        exported_at: chrono::Utc::now(),
+        fermentations,
+    }))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/users/account",
+    tag = "users",
+    request_body = DeleteAccountRequest,
+    responses(
+        (status = 204, description = "Account deleted"),
+        (status = 401, description = "Not logged in")
+    )
+)]
+pub async fn delete_account(
+    session: Session,
+    State(state): State<AppState>,
+    Json(request): Json<DeleteAccountRequest>,
+) -> Result<StatusCode, ApiError> {
+    // Check if user is authenticated
+    let user_session: UserSession = session
+        .get("user")
+        .await
+        .map_err(|e| ApiError::InternalError(format!("Failed to get session: {}", e)))?
+        .ok_or(ApiError::Unauthorized)?;
+
+    let user_repo = UserRepository::new(state.db.clone());
+
+    let user = user_repo
+        .find_by_id(user_session.user_id)
+        .await
+        .map_err(|e| ApiError::DatabaseError(format!("Failed to find user: {}", e)))?;
+
+    // Re-authenticate before destroying any data
+    match verify_password(&request.password, &user.password_hash) {
+        Ok(true) => {}
+        Ok(false) => return Err(ApiError::Unauthorized),
+        Err(e) => {
+            return Err(ApiError::InternalError(format!(
+                "Failed to verify password: {}",
+                e
+            )))
+        }
+    }
+
+    let fermentation_ids = user_repo
+        .delete_account(user.id)
+        .await
+        .map_err(|e| ApiError::DatabaseError(format!("Failed to delete account: {}", e)))?;
+
+    crate::photos::handlers::remove_fermentation_uploads(
+        &state.config.uploads_dir,
+        &fermentation_ids,
+    );
+
+    // Invalidate the session cookie
+    session
+        .flush()
+        .await
+        .map_err(|e| ApiError::InternalError(format!("Failed to clear session: {}", e)))?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/users/calendar-token",
+    tag = "users",
+    responses(
+        (status = 201, description = "New calendar feed token; any previous one stops working", body = CalendarTokenResponse),
+        (status = 401, description = "Not logged in")
+    )
+)]
+pub async fn create_calendar_token(
+    session: Session,
+    State(state): State<AppState>,
+) -> Result<(StatusCode, Json<CalendarTokenResponse>), ApiError> {
+    // Check if user is authenticated
+    let user_session: UserSession = session
+        .get("user")
+        .await
+        .map_err(|e| ApiError::InternalError(format!("Failed to get session: {}", e)))?
+        .ok_or(ApiError::Unauthorized)?;
+
+    let user_repo = UserRepository::new(state.db.clone());
+
+    let token = user_repo
+        .create_calendar_token(user_session.user_id)
+        .await
+        .map_err(|e| ApiError::DatabaseError(format!("Failed to create calendar token: {}", e)))?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(CalendarTokenResponse {
+            url: format!("/api/calendar.ics?token={}", token),
+            token,
+        }),
+    ))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/users/calendar-token",
+    tag = "users",
+    responses(
+        (status = 204, description = "Calendar feed disabled"),
+        (status = 401, description = "Not logged in")
+    )
+)]
+pub async fn revoke_calendar_token(
+    session: Session,
+    State(state): State<AppState>,
+) -> Result<StatusCode, ApiError> {
+    // Check if user is authenticated
+    let user_session: UserSession = session
+        .get("user")
+        .await
+        .map_err(|e| ApiError::InternalError(format!("Failed to get session: {}", e)))?
+        .ok_or(ApiError::Unauthorized)?;
+
+    let user_repo = UserRepository::new(state.db.clone());
+
+    user_repo
+        .revoke_calendar_token(user_session.user_id)
+        .await
+        .map_err(|e| ApiError::DatabaseError(format!("Failed to revoke calendar token: {}", e)))?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -6,10 +6,16 @@ pub mod temperature;
 pub mod templates;
 
 // Re-export commonly used items for convenience
-pub use handlers::{change_password, login_user, logout_user, register_user, update_profile};
+pub use handlers::{
+    change_email, change_password, confirm_password_reset, create_calendar_token, current_user,
+    delete_account, export_account, login_user, logout_all_sessions, logout_user, register_user,
+    request_password_reset, revoke_calendar_token, update_profile, verify_email,
+};
 pub use models::{
-    ChangePasswordRequest, CreateUserRequest, ExperienceLevel, LoginRequest, LoginResponse,
-    TemperatureUnit, UpdateProfileRequest, User, UserResponse, UserRole, UserSession,
+    AccountExport, CalendarTokenResponse, ChangeEmailRequest, ChangePasswordRequest,
+    CreateUserRequest, DeleteAccountRequest, ExperienceLevel, LoginRequest, LoginResponse,
+    PasswordResetConfirmRequest, PasswordResetRequest, TemperatureUnit, UpdateProfileRequest, User,
+    UserResponse, UserRole, UserSession, VerifyEmailRequest,
 };
 pub use repository::UserRepository;
 pub use temperature::{
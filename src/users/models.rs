@@ -1,7 +1,8 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum TemperatureUnit {
     Fahrenheit,
@@ -42,7 +43,7 @@ impl From<String> for TemperatureUnit {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum UserRole {
     User,
@@ -74,7 +75,7 @@ impl From<String> for UserRole {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum ExperienceLevel {
     Beginner,
@@ -130,11 +131,15 @@ pub struct User {
     pub first_name: Option<String>,
     pub last_name: Option<String>,
     pub is_locked: bool,
+    pub email_verified: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    pub last_login_at: Option<DateTime<Utc>>,
+    /// Profile pre-selected when starting a new fermentation
+    pub default_profile_id: Option<i64>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct CreateUserRequest {
     pub email: String,
     pub password: String,
@@ -144,9 +149,12 @@ pub struct CreateUserRequest {
     pub first_name: Option<String>,
     #[serde(default)]
     pub last_name: Option<String>,
+    /// Invite code from an admin; required when the instance only allows invited sign-ups
+    #[serde(default)]
+    pub invite_code: Option<String>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct UserResponse {
     pub id: i64,
     pub email: String,
@@ -155,7 +163,12 @@ pub struct UserResponse {
     pub preferred_temp_unit: TemperatureUnit,
     pub first_name: Option<String>,
     pub last_name: Option<String>,
+    pub email_verified: bool,
     pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub last_login_at: Option<DateTime<Utc>>,
+    /// Profile pre-selected when starting a new fermentation
+    pub default_profile_id: Option<i64>,
 }
 
 impl From<User> for UserResponse {
@@ -168,12 +181,24 @@ impl From<User> for UserResponse {
             preferred_temp_unit: user.preferred_temp_unit,
             first_name: user.first_name,
             last_name: user.last_name,
+            email_verified: user.email_verified,
             created_at: user.created_at,
+            updated_at: user.updated_at,
+            last_login_at: user.last_login_at,
+            default_profile_id: user.default_profile_id,
         }
     }
 }
 
-#[derive(Debug, Deserialize)]
+/// Everything the app stores about a user, for download
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AccountExport {
+    pub user: UserResponse,
+    pub exported_at: DateTime<Utc>,
+    pub fermentations: Vec<crate::fermentation::FermentationExport>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct LoginRequest {
     pub email: String,
     pub password: String,
@@ -181,7 +206,7 @@ pub struct LoginRequest {
     pub remember_me: bool,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct LoginResponse {
     pub success: bool,
     pub user: Option<UserResponse>,
@@ -195,7 +220,7 @@ pub struct UserSession {
     pub role: UserRole,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct UpdateProfileRequest {
     pub experience_level: String,
     pub preferred_temp_unit: String,
@@ -203,10 +228,49 @@ pub struct UpdateProfileRequest {
     pub first_name: Option<String>,
     #[serde(default)]
     pub last_name: Option<String>,
+    /// Must reference an active profile; omit or send null to clear
+    #[serde(default)]
+    pub default_profile_id: Option<i64>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct ChangePasswordRequest {
     pub current_password: String,
     pub new_password: String,
 }
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ChangeEmailRequest {
+    pub current_password: String,
+    pub new_email: String,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct DeleteAccountRequest {
+    pub password: String,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct PasswordResetRequest {
+    pub email: String,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct PasswordResetConfirmRequest {
+    pub token: String,
+    pub new_password: String,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct VerifyEmailRequest {
+    pub token: String,
+}
+
+/// Subscription details for the calendar feed. The token is only shown once; a new one
+/// replaces the old.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CalendarTokenResponse {
+    pub token: String,
+    /// Path of the feed with the token filled in, for calendar apps to subscribe to
+    pub url: String,
+}
@@ -1,10 +1,46 @@
 use crate::database::Database;
-use crate::users::auth::hash_password;
+use crate::users::auth::{
+    generate_token, hash_password, hash_token, verify_password, verify_token,
+    LOGIN_THROTTLE_MINUTES, MAX_FAILED_LOGIN_ATTEMPTS,
+};
 use crate::users::models::{CreateUserRequest, ExperienceLevel, TemperatureUnit, User, UserRole};
 use chrono::{DateTime, Utc};
 use rusqlite::OptionalExtension;
 use std::sync::Arc;
 
+/// Delete a user within the caller's transaction, returning the IDs of their fermentations so
+/// the uploads can be removed once it commits. Used by both self-service and admin deletion.
+pub(crate) fn delete_user_rows(
+    tx: &rusqlite::Transaction,
+    user_id: i64,
+) -> Result<Vec<i64>, rusqlite::Error> {
+    let fermentation_ids = {
+        let mut stmt = tx.prepare("SELECT id FROM fermentations WHERE user_id = ?1")?;
+        let ids = stmt
+            .query_map([user_id], |row| row.get::<_, i64>(0))?
+            .collect::<Result<Vec<_>, _>>()?;
+        ids
+    };
+
+    // Fermentations with their logs, photos and shares, tags, tokens and idempotency keys go
+    // with the user through ON DELETE CASCADE, and invite codes they created or redeemed are
+    // kept with the reference cleared. Only the references declared without an ON DELETE
+    // action are handled here.
+    tx.execute(
+        "UPDATE fermentation_profiles SET created_by = NULL WHERE created_by = ?1",
+        [user_id],
+    )?;
+    tx.execute(
+        "DELETE FROM tower_sessions
+         WHERE id IN (SELECT session_id FROM user_sessions WHERE user_id = ?1)",
+        [user_id],
+    )?;
+    tx.execute("DELETE FROM user_sessions WHERE user_id = ?1", [user_id])?;
+    tx.execute("DELETE FROM users WHERE id = ?1", [user_id])?;
+
+    Ok(fermentation_ids)
+}
+
 pub struct UserRepository {
     db: Arc<Database>,
 }
@@ -18,29 +54,58 @@ impl UserRepository {
         &self,
         request: CreateUserRequest,
     ) -> Result<User, Box<dyn std::error::Error + Send + Sync>> {
-        let password_hash = hash_password(&request.password)?;
-        let experience_level = request
-            .experience_level
-            .map(ExperienceLevel::from)
-            .unwrap_or(ExperienceLevel::Beginner);
-
+        let new_user = NewUser::from_request(request)?;
         let db = self.db.clone();
-        let email = request.email.clone();
-        let experience_level_str = experience_level.as_str().to_string();
-        let first_name = request.first_name.clone();
-        let last_name = request.last_name.clone();
 
-        let user_id = tokio::task::spawn_blocking(move || -> Result<i64, Box<dyn std::error::Error + Send + Sync>> {
-            let conn = db.get_connection().lock().unwrap();
+        let user_id = tokio::task::spawn_blocking(
+            move || -> Result<i64, Box<dyn std::error::Error + Send + Sync>> {
+                let conn = db.get_connection().lock()?;
+                Ok(new_user.insert(&conn)?)
+            },
+        )
+        .await??;
 
-            conn.execute(
-                "INSERT INTO users (email, password_hash, role, experience_level, first_name, last_name) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
-                rusqlite::params![&email, &password_hash, "user", &experience_level_str, &first_name, &last_name],
+        self.find_by_id(user_id).await
+    }
+
+    /// Create a user and redeem their invite code in one transaction, so a code can't be
+    /// used twice and no account is created when the code is invalid, used or expired
+    pub async fn create_user_with_invite(
+        &self,
+        request: CreateUserRequest,
+        invite_code: String,
+    ) -> Result<User, Box<dyn std::error::Error + Send + Sync>> {
+        let new_user = NewUser::from_request(request)?;
+        let db = self.db.clone();
+
+        let user_id = tokio::task::spawn_blocking(
+            move || -> Result<i64, Box<dyn std::error::Error + Send + Sync>> {
+                let mut conn = db.get_connection().lock()?;
+                let tx =
+                    conn.transaction_with_behavior(rusqlite::TransactionBehavior::Immediate)?;
+
+                let invite_id: Option<i64> = tx
+                    .query_row(
+                        "SELECT id FROM invite_codes
+                     WHERE code = ?1 AND used_at IS NULL
+                       AND (expires_at IS NULL OR expires_at > CURRENT_TIMESTAMP)",
+                        [&invite_code],
+                        |row| row.get(0),
+                    )
+                    .optional()?;
+                let invite_id =
+                    invite_id.ok_or("Invite code is invalid, already used or expired")?;
+
+                let user_id = new_user.insert(&tx)?;
+                tx.execute(
+                "UPDATE invite_codes SET used_by = ?1, used_at = CURRENT_TIMESTAMP WHERE id = ?2",
+                [user_id, invite_id],
             )?;
 
-            let user_id = conn.last_insert_rowid();
-            Ok(user_id)
-        })
+                tx.commit()?;
+                Ok(user_id)
+            },
+        )
         .await??;
 
         self.find_by_id(user_id).await
@@ -54,10 +119,10 @@ impl UserRepository {
         let email = email.to_string();
 
         tokio::task::spawn_blocking(move || -> Result<Option<User>, Box<dyn std::error::Error + Send + Sync>> {
-            let conn = db.get_connection().lock().unwrap();
+            let conn = db.get_connection().lock()?;
 
             let mut stmt = conn.prepare(
-                "SELECT id, email, password_hash, role, experience_level, preferred_temp_unit, first_name, last_name, is_locked, created_at, updated_at
+                "SELECT id, email, password_hash, role, experience_level, preferred_temp_unit, first_name, last_name, is_locked, created_at, updated_at, email_verified, last_login_at, default_profile_id
                  FROM users WHERE email = ?1"
             )?;
 
@@ -74,6 +139,9 @@ impl UserRepository {
                     is_locked: row.get::<_, i64>(8)? != 0,
                     created_at: parse_datetime(row.get::<_, String>(9)?),
                     updated_at: parse_datetime(row.get::<_, String>(10)?),
+                    email_verified: row.get::<_, i64>(11)? != 0,
+                    last_login_at: row.get::<_, Option<String>>(12)?.map(parse_datetime),
+                    default_profile_id: row.get(13)?,
                 })
             }).optional()?;
 
@@ -89,10 +157,10 @@ impl UserRepository {
         let db = self.db.clone();
 
         tokio::task::spawn_blocking(move || -> Result<User, Box<dyn std::error::Error + Send + Sync>> {
-            let conn = db.get_connection().lock().unwrap();
+            let conn = db.get_connection().lock()?;
 
             let mut stmt = conn.prepare(
-                "SELECT id, email, password_hash, role, experience_level, preferred_temp_unit, first_name, last_name, is_locked, created_at, updated_at
+                "SELECT id, email, password_hash, role, experience_level, preferred_temp_unit, first_name, last_name, is_locked, created_at, updated_at, email_verified, last_login_at, default_profile_id
                  FROM users WHERE id = ?1"
             )?;
 
@@ -109,6 +177,9 @@ impl UserRepository {
                     is_locked: row.get::<_, i64>(8)? != 0,
                     created_at: parse_datetime(row.get::<_, String>(9)?),
                     updated_at: parse_datetime(row.get::<_, String>(10)?),
+                    email_verified: row.get::<_, i64>(11)? != 0,
+                    last_login_at: row.get::<_, Option<String>>(12)?.map(parse_datetime),
+                    default_profile_id: row.get(13)?,
                 })
             })?;
 
@@ -126,7 +197,7 @@ impl UserRepository {
         let experience_level_str = experience_level.as_str().to_string();
 
         tokio::task::spawn_blocking(move || -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-            let conn = db.get_connection().lock().unwrap();
+            let conn = db.get_connection().lock()?;
 
             conn.execute(
                 "UPDATE users SET experience_level = ?1, updated_at = CURRENT_TIMESTAMP WHERE id = ?2",
@@ -147,17 +218,18 @@ impl UserRepository {
         preferred_temp_unit: TemperatureUnit,
         first_name: Option<String>,
         last_name: Option<String>,
+        default_profile_id: Option<i64>,
     ) -> Result<User, Box<dyn std::error::Error + Send + Sync>> {
         let db = self.db.clone();
         let experience_level_str = experience_level.as_str().to_string();
         let temp_unit_str = preferred_temp_unit.as_str().to_string();
 
         tokio::task::spawn_blocking(move || -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-            let conn = db.get_connection().lock().unwrap();
+            let conn = db.get_connection().lock()?;
 
             conn.execute(
-                "UPDATE users SET experience_level = ?1, preferred_temp_unit = ?2, first_name = ?3, last_name = ?4, updated_at = CURRENT_TIMESTAMP WHERE id = ?5",
-                rusqlite::params![&experience_level_str, &temp_unit_str, &first_name, &last_name, user_id],
+                "UPDATE users SET experience_level = ?1, preferred_temp_unit = ?2, first_name = ?3, last_name = ?4, default_profile_id = ?5, updated_at = CURRENT_TIMESTAMP WHERE id = ?6",
+                rusqlite::params![&experience_level_str, &temp_unit_str, &first_name, &last_name, default_profile_id, user_id],
             )?;
 
             Ok(())
@@ -176,7 +248,7 @@ impl UserRepository {
 
         tokio::task::spawn_blocking(
             move || -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-                let conn = db.get_connection().lock().unwrap();
+                let conn = db.get_connection().lock()?;
 
                 conn.execute(
                 "UPDATE users SET password_hash = ?1, updated_at = CURRENT_TIMESTAMP WHERE id = ?2",
@@ -188,6 +260,551 @@ impl UserRepository {
         )
         .await?
     }
+
+    /// Change the user's email address and mark it unverified until it is confirmed again.
+    /// Returns false if the address is already used by another account.
+    pub async fn update_email(
+        &self,
+        user_id: i64,
+        new_email: &str,
+    ) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+        let db = self.db.clone();
+        let new_email = new_email.to_string();
+
+        tokio::task::spawn_blocking(
+            move || -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+                let conn = db.get_connection().lock()?;
+
+                let result = conn.execute(
+                    "UPDATE users SET email = ?1, email_verified = 0, updated_at = CURRENT_TIMESTAMP WHERE id = ?2",
+                    rusqlite::params![&new_email, user_id],
+                );
+
+                match result {
+                    Ok(_) => {}
+                    Err(rusqlite::Error::SqliteFailure(err, _))
+                        if err.code == rusqlite::ErrorCode::ConstraintViolation =>
+                    {
+                        return Ok(false);
+                    }
+                    Err(e) => return Err(e.into()),
+                }
+
+                // Outstanding tokens were issued for the old address
+                conn.execute(
+                    "DELETE FROM email_verification_tokens WHERE user_id = ?1",
+                    [user_id],
+                )?;
+
+                Ok(true)
+            },
+        )
+        .await?
+    }
+
+    /// Return the time until which logins are throttled for this user, if still in effect
+    pub async fn login_throttled_until(
+        &self,
+        user_id: i64,
+    ) -> Result<Option<DateTime<Utc>>, Box<dyn std::error::Error + Send + Sync>> {
+        let db = self.db.clone();
+
+        let locked_until = tokio::task::spawn_blocking(
+            move || -> Result<Option<String>, Box<dyn std::error::Error + Send + Sync>> {
+                let conn = db.get_connection().lock()?;
+
+                let locked_until: Option<String> = conn.query_row(
+                    "SELECT locked_until FROM users WHERE id = ?1",
+                    [user_id],
+                    |row| row.get(0),
+                )?;
+
+                Ok(locked_until)
+            },
+        )
+        .await??;
+
+        Ok(locked_until
+            .map(parse_datetime)
+            .filter(|until| *until > Utc::now()))
+    }
+
+    /// Record a failed login attempt. Once `MAX_FAILED_LOGIN_ATTEMPTS` consecutive failures
+    /// happen within `LOGIN_THROTTLE_MINUTES`, logins are throttled for that long.
+    /// Returns true if this attempt triggered the throttle.
+    pub async fn record_failed_login(
+        &self,
+        user_id: i64,
+    ) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+        let db = self.db.clone();
+
+        tokio::task::spawn_blocking(
+            move || -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+                let conn = db.get_connection().lock()?;
+
+                let now = Utc::now();
+                let window = chrono::Duration::minutes(LOGIN_THROTTLE_MINUTES);
+
+                // Count the failure in a single statement so concurrent attempts can't lose updates,
+                // starting a new window if there is no recent failure
+                let attempts: i64 = conn.query_row(
+                    "UPDATE users SET
+                        failed_login_attempts = CASE WHEN first_failed_login_at > ?1 THEN failed_login_attempts + 1 ELSE 1 END,
+                        first_failed_login_at = CASE WHEN first_failed_login_at > ?1 THEN first_failed_login_at ELSE ?2 END
+                     WHERE id = ?3
+                     RETURNING failed_login_attempts",
+                    rusqlite::params![
+                        (now - window).format("%Y-%m-%d %H:%M:%S").to_string(),
+                        now.format("%Y-%m-%d %H:%M:%S").to_string(),
+                        user_id
+                    ],
+                    |row| row.get(0),
+                )?;
+
+                let throttled = attempts >= MAX_FAILED_LOGIN_ATTEMPTS;
+
+                if throttled {
+                    // Start the throttle and clear the counter for the next window
+                    conn.execute(
+                        "UPDATE users SET failed_login_attempts = 0, first_failed_login_at = NULL, locked_until = ?1 WHERE id = ?2",
+                        rusqlite::params![
+                            (now + window).format("%Y-%m-%d %H:%M:%S").to_string(),
+                            user_id
+                        ],
+                    )?;
+                }
+
+                Ok(throttled)
+            },
+        )
+        .await?
+    }
+
+    /// Clear failed login tracking after a successful login or password reset
+    pub async fn reset_failed_logins(
+        &self,
+        user_id: i64,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let db = self.db.clone();
+
+        tokio::task::spawn_blocking(
+            move || -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+                let conn = db.get_connection().lock()?;
+
+                conn.execute(
+                    "UPDATE users SET failed_login_attempts = 0, first_failed_login_at = NULL, locked_until = NULL WHERE id = ?1",
+                    [user_id],
+                )?;
+
+                Ok(())
+            },
+        )
+        .await?
+    }
+
+    /// Stamp a successful login on the user. Returns the recorded time.
+    pub async fn record_login(
+        &self,
+        user_id: i64,
+    ) -> Result<DateTime<Utc>, Box<dyn std::error::Error + Send + Sync>> {
+        let db = self.db.clone();
+
+        tokio::task::spawn_blocking(
+            move || -> Result<DateTime<Utc>, Box<dyn std::error::Error + Send + Sync>> {
+                let conn = db.get_connection().lock()?;
+                let now = Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+
+                conn.execute(
+                    "UPDATE users SET last_login_at = ?1 WHERE id = ?2",
+                    rusqlite::params![&now, user_id],
+                )?;
+
+                Ok(parse_datetime(now))
+            },
+        )
+        .await?
+    }
+
+    /// Remember that a session store id belongs to a user so it can be revoked later
+    pub async fn record_session(
+        &self,
+        user_id: i64,
+        session_id: &str,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let db = self.db.clone();
+        let session_id = session_id.to_string();
+
+        tokio::task::spawn_blocking(
+            move || -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+                let conn = db.get_connection().lock()?;
+
+                conn.execute(
+                    "INSERT OR REPLACE INTO user_sessions (session_id, user_id) VALUES (?1, ?2)",
+                    rusqlite::params![&session_id, user_id],
+                )?;
+
+                Ok(())
+            },
+        )
+        .await?
+    }
+
+    /// Remove every stored session of a user. Returns the number of sessions terminated.
+    pub async fn delete_all_sessions(
+        &self,
+        user_id: i64,
+    ) -> Result<usize, Box<dyn std::error::Error + Send + Sync>> {
+        let db = self.db.clone();
+
+        tokio::task::spawn_blocking(
+            move || -> Result<usize, Box<dyn std::error::Error + Send + Sync>> {
+                let mut conn = db.get_connection().lock()?;
+                let tx =
+                    conn.transaction_with_behavior(rusqlite::TransactionBehavior::Immediate)?;
+
+                let terminated = tx.execute(
+                    "DELETE FROM tower_sessions
+                     WHERE id IN (SELECT session_id FROM user_sessions WHERE user_id = ?1)",
+                    [user_id],
+                )?;
+                tx.execute("DELETE FROM user_sessions WHERE user_id = ?1", [user_id])?;
+
+                tx.commit()?;
+                Ok(terminated)
+            },
+        )
+        .await?
+    }
+
+    /// Delete a user and all of their fermentation data in a single transaction.
+    /// Returns the IDs of the deleted fermentations so their uploads can be cleaned up.
+    pub async fn delete_account(
+        &self,
+        user_id: i64,
+    ) -> Result<Vec<i64>, Box<dyn std::error::Error + Send + Sync>> {
+        let db = self.db.clone();
+
+        tokio::task::spawn_blocking(
+            move || -> Result<Vec<i64>, Box<dyn std::error::Error + Send + Sync>> {
+                let mut conn = db.get_connection().lock()?;
+                let tx =
+                    conn.transaction_with_behavior(rusqlite::TransactionBehavior::Immediate)?;
+
+                let fermentation_ids = delete_user_rows(&tx, user_id)?;
+
+                tx.commit()?;
+                Ok(fermentation_ids)
+            },
+        )
+        .await?
+    }
+
+    /// Create a single-use password reset token valid for one hour.
+    /// Returns the raw token in the form `<id>.<secret>`; only a hash of the secret is stored.
+    pub async fn create_password_reset_token(
+        &self,
+        user_id: i64,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let secret = generate_token();
+        let token_hash = hash_password(&secret)?;
+        let expires_at = (Utc::now() + chrono::Duration::hours(1))
+            .format("%Y-%m-%d %H:%M:%S")
+            .to_string();
+
+        let db = self.db.clone();
+
+        let token_id = tokio::task::spawn_blocking(
+            move || -> Result<i64, Box<dyn std::error::Error + Send + Sync>> {
+                let conn = db.get_connection().lock()?;
+
+                conn.execute(
+                    "INSERT INTO password_reset_tokens (user_id, token_hash, expires_at) VALUES (?1, ?2, ?3)",
+                    rusqlite::params![user_id, &token_hash, &expires_at],
+                )?;
+
+                Ok(conn.last_insert_rowid())
+            },
+        )
+        .await??;
+
+        Ok(format!("{}.{}", token_id, secret))
+    }
+
+    /// Validate and delete a password reset token, returning the owning user ID if it was valid
+    pub async fn consume_password_reset_token(
+        &self,
+        token: &str,
+    ) -> Result<Option<i64>, Box<dyn std::error::Error + Send + Sync>> {
+        let Some((id_part, secret)) = token.split_once('.') else {
+            return Ok(None);
+        };
+        let Ok(token_id) = id_part.parse::<i64>() else {
+            return Ok(None);
+        };
+
+        let db = self.db.clone();
+        let secret = secret.to_string();
+
+        tokio::task::spawn_blocking(
+            move || -> Result<Option<i64>, Box<dyn std::error::Error + Send + Sync>> {
+                let conn = db.get_connection().lock()?;
+
+                let row: Option<(i64, String, String)> = conn
+                    .query_row(
+                        "SELECT user_id, token_hash, expires_at FROM password_reset_tokens WHERE id = ?1",
+                        [token_id],
+                        |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+                    )
+                    .optional()?;
+
+                let Some((user_id, token_hash, expires_at)) = row else {
+                    return Ok(None);
+                };
+
+                if !verify_password(&secret, &token_hash)? {
+                    return Ok(None);
+                }
+
+                // Tokens are single-use: remove it whether it is still valid or has expired
+                conn.execute(
+                    "DELETE FROM password_reset_tokens WHERE id = ?1",
+                    [token_id],
+                )?;
+
+                if parse_datetime(expires_at) <= Utc::now() {
+                    return Ok(None);
+                }
+
+                Ok(Some(user_id))
+            },
+        )
+        .await?
+    }
+
+    /// Create a calendar feed token, replacing any previous one. Returns the raw token in the
+    /// form `<user id>.<secret>`; only a hash of the secret is stored.
+    pub async fn create_calendar_token(
+        &self,
+        user_id: i64,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let secret = generate_token();
+        let token_hash = hash_token(&secret);
+
+        let db = self.db.clone();
+
+        tokio::task::spawn_blocking(
+            move || -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+                let conn = db.get_connection().lock()?;
+
+                conn.execute(
+                    "UPDATE users SET calendar_token_hash = ?1 WHERE id = ?2",
+                    rusqlite::params![&token_hash, user_id],
+                )?;
+
+                Ok(())
+            },
+        )
+        .await??;
+
+        Ok(format!("{}.{}", user_id, secret))
+    }
+
+    /// Stop the user's calendar feed from being served
+    pub async fn revoke_calendar_token(
+        &self,
+        user_id: i64,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let db = self.db.clone();
+
+        tokio::task::spawn_blocking(
+            move || -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+                let conn = db.get_connection().lock()?;
+
+                conn.execute(
+                    "UPDATE users SET calendar_token_hash = NULL WHERE id = ?1",
+                    [user_id],
+                )?;
+
+                Ok(())
+            },
+        )
+        .await?
+    }
+
+    /// Check a calendar feed token, returning the user ID if it is valid. Unlike the
+    /// single-use tokens it stays valid until replaced or revoked; locked users get nothing.
+    pub async fn verify_calendar_token(
+        &self,
+        token: &str,
+    ) -> Result<Option<i64>, Box<dyn std::error::Error + Send + Sync>> {
+        let Some((id_part, secret)) = token.split_once('.') else {
+            return Ok(None);
+        };
+        let Ok(user_id) = id_part.parse::<i64>() else {
+            return Ok(None);
+        };
+
+        let db = self.db.clone();
+        let secret = secret.to_string();
+
+        tokio::task::spawn_blocking(
+            move || -> Result<Option<i64>, Box<dyn std::error::Error + Send + Sync>> {
+                let conn = db.get_connection().lock()?;
+
+                let row: Option<(Option<String>, bool)> = conn
+                    .query_row(
+                        "SELECT calendar_token_hash, is_locked FROM users WHERE id = ?1",
+                        [user_id],
+                        |row| Ok((row.get(0)?, row.get::<_, i64>(1)? != 0)),
+                    )
+                    .optional()?;
+                drop(conn);
+
+                let Some((Some(token_hash), false)) = row else {
+                    return Ok(None);
+                };
+
+                if !verify_token(&secret, &token_hash)? {
+                    return Ok(None);
+                }
+
+                Ok(Some(user_id))
+            },
+        )
+        .await?
+    }
+
+    /// Create a single-use email verification token for the given address, valid for 24 hours.
+    /// Returns the raw token in the form `<id>.<secret>`; only a hash of the secret is stored.
+    pub async fn create_email_verification_token(
+        &self,
+        user_id: i64,
+        email: &str,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let secret = generate_token();
+        let token_hash = hash_password(&secret)?;
+        let expires_at = (Utc::now() + chrono::Duration::hours(24))
+            .format("%Y-%m-%d %H:%M:%S")
+            .to_string();
+
+        let db = self.db.clone();
+        let email = email.to_string();
+
+        let token_id = tokio::task::spawn_blocking(
+            move || -> Result<i64, Box<dyn std::error::Error + Send + Sync>> {
+                let conn = db.get_connection().lock()?;
+
+                conn.execute(
+                    "INSERT INTO email_verification_tokens (user_id, email, token_hash, expires_at) VALUES (?1, ?2, ?3, ?4)",
+                    rusqlite::params![user_id, &email, &token_hash, &expires_at],
+                )?;
+
+                Ok(conn.last_insert_rowid())
+            },
+        )
+        .await??;
+
+        Ok(format!("{}.{}", token_id, secret))
+    }
+
+    /// Validate and delete an email verification token, marking the user's email as verified.
+    /// Returns the user ID if the token was valid and still matches the user's current address.
+    pub async fn consume_email_verification_token(
+        &self,
+        token: &str,
+    ) -> Result<Option<i64>, Box<dyn std::error::Error + Send + Sync>> {
+        let Some((id_part, secret)) = token.split_once('.') else {
+            return Ok(None);
+        };
+        let Ok(token_id) = id_part.parse::<i64>() else {
+            return Ok(None);
+        };
+
+        let db = self.db.clone();
+        let secret = secret.to_string();
+
+        tokio::task::spawn_blocking(
+            move || -> Result<Option<i64>, Box<dyn std::error::Error + Send + Sync>> {
+                let conn = db.get_connection().lock()?;
+
+                let row: Option<(i64, String, String, String)> = conn
+                    .query_row(
+                        "SELECT user_id, email, token_hash, expires_at FROM email_verification_tokens WHERE id = ?1",
+                        [token_id],
+                        |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+                    )
+                    .optional()?;
+
+                let Some((user_id, email, token_hash, expires_at)) = row else {
+                    return Ok(None);
+                };
+
+                if !verify_password(&secret, &token_hash)? {
+                    return Ok(None);
+                }
+
+                // Tokens are single-use: remove it whether it is still valid or has expired
+                conn.execute(
+                    "DELETE FROM email_verification_tokens WHERE id = ?1",
+                    [token_id],
+                )?;
+
+                if parse_datetime(expires_at) <= Utc::now() {
+                    return Ok(None);
+                }
+
+                // Only verify the address the token was issued for
+                let updated = conn.execute(
+                    "UPDATE users SET email_verified = 1, updated_at = CURRENT_TIMESTAMP WHERE id = ?1 AND email = ?2",
+                    rusqlite::params![user_id, &email],
+                )?;
+
+                if updated == 0 {
+                    return Ok(None);
+                }
+
+                Ok(Some(user_id))
+            },
+        )
+        .await?
+    }
+}
+
+/// A self-registered user ready to be inserted, with the password already hashed
+struct NewUser {
+    email: String,
+    password_hash: String,
+    experience_level: String,
+    first_name: Option<String>,
+    last_name: Option<String>,
+}
+
+impl NewUser {
+    fn from_request(
+        request: CreateUserRequest,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        let experience_level = request
+            .experience_level
+            .map(ExperienceLevel::from)
+            .unwrap_or(ExperienceLevel::Beginner);
+
+        Ok(Self {
+            password_hash: hash_password(&request.password)?,
+            email: request.email,
+            experience_level: experience_level.as_str().to_string(),
+            first_name: request.first_name,
+            last_name: request.last_name,
+        })
+    }
+
+    fn insert(&self, conn: &rusqlite::Connection) -> rusqlite::Result<i64> {
+        conn.execute(
+            "INSERT INTO users (email, password_hash, role, experience_level, first_name, last_name) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            rusqlite::params![&self.email, &self.password_hash, "user", &self.experience_level, &self.first_name, &self.last_name],
+        )?;
+
+        Ok(conn.last_insert_rowid())
+    }
 }
 
 fn parse_datetime(s: String) -> DateTime<Utc> {
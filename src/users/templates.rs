@@ -31,11 +31,17 @@ pub async fn login_handler() -> Html<String> {
 #[template(path = "users/register.html")]
 pub struct RegisterTemplate {
     pub title: String,
+    /// When registration is closed the page explains that instead of showing the form
+    pub registration_open: bool,
+    /// Whether the form asks for an invite code
+    pub require_invite: bool,
 }
 
-pub async fn register_handler() -> Html<String> {
+pub async fn register_handler(State(state): State<AppState>) -> Html<String> {
     let template = RegisterTemplate {
         title: "Register - Raugupatis Log".to_string(),
+        registration_open: state.config.registration_open,
+        require_invite: state.config.require_invite,
     };
 
     Html(
@@ -50,6 +56,14 @@ pub async fn register_handler() -> Html<String> {
 pub struct ProfileTemplate {
     pub title: String,
     pub user: UserResponse,
+    /// Active profiles to choose the default from
+    pub profiles: Vec<crate::fermentation::FermentationProfile>,
+}
+
+impl ProfileTemplate {
+    fn is_default(&self, id: &i64) -> bool {
+        self.user.default_profile_id == Some(*id)
+    }
 }
 
 pub async fn profile_handler(
@@ -71,9 +85,19 @@ pub async fn profile_handler(
         .await
         .map_err(|_| Redirect::to("/login"))?;
 
+    let fermentation_repo = crate::fermentation::FermentationRepository::new(state.db.clone());
+    let profiles = fermentation_repo
+        .get_all_profiles()
+        .await
+        .unwrap_or_else(|e| {
+            tracing::error!("Error fetching fermentation profiles: {}", e);
+            Vec::new()
+        });
+
     let template = ProfileTemplate {
         title: "Edit Profile - Raugupatis Log".to_string(),
         user: UserResponse::from(user),
+        profiles,
     };
 
     Ok(Html(
@@ -230,7 +230,7 @@ async fn test_create_profile_success() {
 
     let create_body = json!({
         "name": "Test Ferment",
-        "type": "test",
+        "type": "vegetable",
         "min_days": 3,
         "max_days": 7,
         "temp_min": 65.0,
@@ -260,7 +260,7 @@ async fn test_create_profile_success() {
     let profile: serde_json::Value = serde_json::from_slice(&body).unwrap();
 
     assert_eq!(profile["name"], "Test Ferment");
-    assert_eq!(profile["type"], "test");
+    assert_eq!(profile["type"], "vegetable");
     assert_eq!(profile["is_active"], true);
 }
 
@@ -272,7 +272,7 @@ async fn test_create_profile_validation_errors() {
     // Test empty name
     let invalid_body = json!({
         "name": "",
-        "type": "test",
+        "type": "vegetable",
         "min_days": 3,
         "max_days": 7,
         "temp_min": 65.0,
@@ -298,7 +298,7 @@ async fn test_create_profile_validation_errors() {
     // Test min_days > max_days
     let invalid_body = json!({
         "name": "Test",
-        "type": "test",
+        "type": "vegetable",
         "min_days": 10,
         "max_days": 5,
         "temp_min": 65.0,
@@ -324,7 +324,7 @@ async fn test_create_profile_validation_errors() {
     // Test temp_min >= temp_max
     let invalid_body = json!({
         "name": "Test",
-        "type": "test",
+        "type": "vegetable",
         "min_days": 3,
         "max_days": 7,
         "temp_min": 75.0,
@@ -387,7 +387,7 @@ async fn test_create_profile_requires_admin() {
 
     let create_body = json!({
         "name": "Test Ferment",
-        "type": "test",
+        "type": "vegetable",
         "min_days": 3,
         "max_days": 7,
         "temp_min": 65.0,
@@ -449,6 +449,79 @@ async fn test_copy_profile_success() {
     assert_eq!(profile["is_active"], true);
 }
 
+#[tokio::test]
+async fn test_copy_profile_is_full_duplicate() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = create_and_login_admin(&app_state).await;
+
+    // Edit the source first so the copy can't just be matching the seed data
+    let (status, source) = common::send_json(
+        &app_state,
+        common::authed_request(
+            "PUT",
+            "/api/admin/profiles/1",
+            &cookie,
+            Some(json!({
+                "name": "Pickles",
+                "type": "vegetable",
+                "min_days": 4,
+                "max_days": 9,
+                "temp_min": 62.5,
+                "temp_max": 71.5,
+                "description": "Half-sour deli style",
+                "default_ingredients": "1 kg cucumbers\n2 tbsp salt"
+            })),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    // Copies of retired profiles start out active
+    let (status, _) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri("/api/admin/profiles/1/status")
+            .method("POST")
+            .header("Content-Type", "application/json")
+            .header("Cookie", &cookie)
+            .body(Body::from(json!({ "is_active": false }).to_string()))
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, copy) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri("/api/admin/profiles/1/copy")
+            .method("POST")
+            .header("Content-Type", "application/json")
+            .header("Cookie", &cookie)
+            .body(Body::from(
+                json!({ "new_name": "Deli Pickles" }).to_string(),
+            ))
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    assert_ne!(copy["id"], source["id"]);
+    assert_eq!(copy["name"], "Deli Pickles");
+    assert_eq!(copy["is_active"], true);
+    for field in [
+        "type",
+        "min_days",
+        "max_days",
+        "temp_min",
+        "temp_max",
+        "description",
+        "default_ingredients",
+    ] {
+        assert_eq!(copy[field], source[field], "{} differs", field);
+    }
+    assert_eq!(copy["description"], "Half-sour deli style");
+}
+
 #[tokio::test]
 async fn test_copy_profile_nonexistent() {
     let app_state = common::create_test_app_state().await;
@@ -680,3 +753,597 @@ async fn test_deactivated_profile_not_in_user_list() {
     let has_deactivated_profile = profiles_array.iter().any(|p| p["id"] == 1);
     assert!(!has_deactivated_profile);
 }
+
+#[tokio::test]
+async fn test_update_profile_success() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = create_and_login_admin(&app_state).await;
+
+    // A fermentation referencing the profile before it is edited
+    let fermentation_id =
+        common::create_test_fermentation(&app_state, &cookie, "Dill Pickles").await;
+
+    let (status, profile) = common::send_json(
+        &app_state,
+        common::authed_request(
+            "PUT",
+            "/api/admin/profiles/1",
+            &cookie,
+            Some(json!({
+                "name": "Brined Pickles",
+                "type": "vegetable",
+                "min_days": 4,
+                "max_days": 10,
+                "temp_min": 62.0,
+                "temp_max": 72.0,
+                "description": "Updated description"
+            })),
+        ),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(profile["id"], 1);
+    assert_eq!(profile["name"], "Brined Pickles");
+    assert_eq!(profile["min_days"], 4);
+    assert_eq!(profile["max_days"], 10);
+    assert_eq!(profile["temp_max"], 72.0);
+    assert_eq!(profile["description"], "Updated description");
+    assert_eq!(profile["is_active"], true);
+
+    // The existing fermentation still references the same profile
+    let (status, fermentations) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri("/api/fermentations")
+            .header("Cookie", &cookie)
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let fermentation = &fermentations.as_array().unwrap()[0];
+    assert_eq!(fermentation["id"], fermentation_id);
+    assert_eq!(fermentation["profile_id"], 1);
+    assert_eq!(fermentation["profile_name"], "Brined Pickles");
+}
+
+#[tokio::test]
+async fn test_update_profile_default_ingredients() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = create_and_login_admin(&app_state).await;
+
+    let update = |default_ingredients: serde_json::Value| {
+        common::authed_request(
+            "PUT",
+            "/api/admin/profiles/1",
+            &cookie,
+            Some(json!({
+                "name": "Pickles",
+                "type": "vegetable",
+                "min_days": 3,
+                "max_days": 7,
+                "temp_min": 65.0,
+                "temp_max": 75.0,
+                "default_ingredients": default_ingredients
+            })),
+        )
+    };
+
+    let (status, profile) =
+        common::send_json(&app_state, update(json!("  1 kg cucumbers\n2 tbsp salt\n"))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        profile["default_ingredients"],
+        "1 kg cucumbers\n2 tbsp salt"
+    );
+
+    // The suggestion is offered wherever profiles are picked
+    let (status, profiles) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri("/api/fermentation/profiles")
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let pickles = profiles
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|p| p["id"] == 1)
+        .unwrap();
+    assert_eq!(
+        pickles["default_ingredients"],
+        "1 kg cucumbers\n2 tbsp salt"
+    );
+
+    // Blank text clears the suggestion
+    let (status, profile) = common::send_json(&app_state, update(json!("   "))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(profile["default_ingredients"].is_null());
+}
+
+#[tokio::test]
+async fn test_update_profile_keeps_own_name() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = create_and_login_admin(&app_state).await;
+
+    let (status, profile) = common::send_json(
+        &app_state,
+        common::authed_request(
+            "PUT",
+            "/api/admin/profiles/2",
+            &cookie,
+            Some(json!({
+                "name": "Kombucha",
+                "type": "beverage",
+                "min_days": 7,
+                "max_days": 14,
+                "temp_min": 68.0,
+                "temp_max": 80.0
+            })),
+        ),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(profile["temp_max"], 80.0);
+}
+
+#[tokio::test]
+async fn test_update_profile_validation_errors() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = create_and_login_admin(&app_state).await;
+
+    let invalid_bodies = [
+        json!({ "name": "Pickles", "type": "vegetable", "min_days": 7, "max_days": 3, "temp_min": 65.0, "temp_max": 75.0 }),
+        json!({ "name": "Pickles", "type": "vegetable", "min_days": 3, "max_days": 7, "temp_min": 75.0, "temp_max": 75.0 }),
+        json!({ "name": " ", "type": "vegetable", "min_days": 3, "max_days": 7, "temp_min": 65.0, "temp_max": 75.0 }),
+    ];
+
+    for body in invalid_bodies {
+        let (status, _) = common::send_json(
+            &app_state,
+            common::authed_request("PUT", "/api/admin/profiles/1", &cookie, Some(body)),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+}
+
+#[tokio::test]
+async fn test_profile_type_validation() {
+    let mut app_state = common::create_test_app_state().await;
+    let cookie = create_and_login_admin(&app_state).await;
+
+    let create = |name: &str, profile_type: &str, cookie: &str| {
+        Request::builder()
+            .uri("/api/admin/profiles")
+            .method("POST")
+            .header("Content-Type", "application/json")
+            .header("Cookie", cookie)
+            .body(Body::from(
+                json!({
+                    "name": name,
+                    "type": profile_type,
+                    "min_days": 3,
+                    "max_days": 7,
+                    "temp_min": 65.0,
+                    "temp_max": 75.0
+                })
+                .to_string(),
+            ))
+            .unwrap()
+    };
+
+    // Known types are stored normalized
+    let (status, profile) =
+        common::send_json(&app_state, create("Carrot Sticks", "  Vegetable ", &cookie)).await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(profile["type"], "vegetable");
+
+    let (status, body) =
+        common::send_json(&app_state, create("Space Brew", "Spaceship", &cookie)).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(body["error"].as_str().unwrap().contains("vegetable"));
+
+    let (status, _) = common::send_json(
+        &app_state,
+        common::authed_request(
+            "PUT",
+            "/api/admin/profiles/1",
+            &cookie,
+            Some(json!({
+                "name": "Pickles",
+                "type": "pickle",
+                "min_days": 3,
+                "max_days": 7,
+                "temp_min": 65.0,
+                "temp_max": 75.0,
+            })),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    // The list filter ignores case as well
+    common::create_test_fermentation(&app_state, &cookie, "Garden Pickles").await;
+    let (status, fermentations) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri("/api/fermentations?profile_type=Vegetable")
+            .header("Cookie", &cookie)
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(fermentations.as_array().unwrap().len(), 1);
+    assert_eq!(fermentations[0]["name"], "Garden Pickles");
+
+    // Operators can opt into their own taxonomy
+    app_state.config = std::sync::Arc::new(raugupatis_log::config::AppConfig {
+        allow_custom_types: true,
+        ..(*app_state.config).clone()
+    });
+    let (status, profile) =
+        common::send_json(&app_state, create("Space Brew", "Spaceship", &cookie)).await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(profile["type"], "spaceship");
+}
+
+#[tokio::test]
+async fn test_update_profile_duplicate_name() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = create_and_login_admin(&app_state).await;
+
+    let (status, _) = common::send_json(
+        &app_state,
+        common::authed_request(
+            "PUT",
+            "/api/admin/profiles/1",
+            &cookie,
+            Some(json!({
+                "name": "Kombucha",
+                "type": "vegetable",
+                "min_days": 3,
+                "max_days": 7,
+                "temp_min": 65.0,
+                "temp_max": 75.0
+            })),
+        ),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::CONFLICT);
+}
+
+#[tokio::test]
+async fn test_update_profile_nonexistent() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = create_and_login_admin(&app_state).await;
+
+    let (status, _) = common::send_json(
+        &app_state,
+        common::authed_request(
+            "PUT",
+            "/api/admin/profiles/9999",
+            &cookie,
+            Some(json!({
+                "name": "Ghost",
+                "type": "vegetable",
+                "min_days": 3,
+                "max_days": 7,
+                "temp_min": 65.0,
+                "temp_max": 75.0
+            })),
+        ),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_update_profile_requires_admin() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = create_and_login_user(&app_state).await;
+
+    let (status, _) = common::send_json(
+        &app_state,
+        common::authed_request(
+            "PUT",
+            "/api/admin/profiles/1",
+            &cookie,
+            Some(json!({
+                "name": "Pickles",
+                "type": "vegetable",
+                "min_days": 3,
+                "max_days": 7,
+                "temp_min": 65.0,
+                "temp_max": 75.0
+            })),
+        ),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn test_delete_unused_profile() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = create_and_login_admin(&app_state).await;
+
+    let (status, profile) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri("/api/admin/profiles")
+            .method("POST")
+            .header("Content-Type", "application/json")
+            .header("Cookie", &cookie)
+            .body(Body::from(
+                json!({
+                    "name": "Setup Test Profile",
+                    "type": "vegetable",
+                    "min_days": 1,
+                    "max_days": 2,
+                    "temp_min": 60.0,
+                    "temp_max": 70.0
+                })
+                .to_string(),
+            ))
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let profile_id = profile["id"].as_i64().unwrap();
+
+    let (status, _) = common::send_json(
+        &app_state,
+        common::authed_request(
+            "DELETE",
+            &format!("/api/admin/profiles/{}", profile_id),
+            &cookie,
+            None,
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    let (_, profiles) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri("/api/admin/profiles")
+            .header("Cookie", &cookie)
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert!(!profiles
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|p| p["id"] == profile_id));
+
+    // Deleting again reports the profile as missing
+    let (status, _) = common::send_json(
+        &app_state,
+        common::authed_request(
+            "DELETE",
+            &format!("/api/admin/profiles/{}", profile_id),
+            &cookie,
+            None,
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_delete_profile_in_use_conflicts() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = create_and_login_admin(&app_state).await;
+
+    common::create_test_fermentation(&app_state, &cookie, "Pickles in progress").await;
+
+    let (status, _) = common::send_json(
+        &app_state,
+        common::authed_request("DELETE", "/api/admin/profiles/1", &cookie, None),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+}
+
+#[tokio::test]
+async fn test_delete_profile_requires_admin() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = create_and_login_user(&app_state).await;
+
+    let (status, _) = common::send_json(
+        &app_state,
+        common::authed_request("DELETE", "/api/admin/profiles/7", &cookie, None),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn test_profile_usage_counts_fermentations() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = create_and_login_admin(&app_state).await;
+
+    common::create_test_fermentation(&app_state, &cookie, "Pickles one").await;
+    common::create_test_fermentation(&app_state, &cookie, "Pickles two").await;
+
+    let (status, body) = common::send_json(
+        &app_state,
+        common::authed_request("GET", "/api/admin/profiles/usage", &cookie, None),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let usage = body.as_array().unwrap();
+    assert_eq!(usage.len(), 7);
+
+    // Most used profile comes first
+    assert_eq!(usage[0]["id"], 1);
+    assert_eq!(usage[0]["fermentation_count"], 2);
+    assert_eq!(usage[0]["active_fermentation_count"], 2);
+
+    // Unused profiles are still listed with zero counts
+    assert!(usage[1..]
+        .iter()
+        .all(|p| p["fermentation_count"] == 0 && p["active_fermentation_count"] == 0));
+}
+
+#[tokio::test]
+async fn test_profile_usage_requires_admin() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = create_and_login_user(&app_state).await;
+
+    let (status, _) = common::send_json(
+        &app_state,
+        common::authed_request("GET", "/api/admin/profiles/usage", &cookie, None),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn test_profile_created_by_and_reassign() {
+    use raugupatis_log::admin::AdminUserRepository;
+    use raugupatis_log::users::models::{ExperienceLevel, UserRole};
+    use raugupatis_log::users::UserRepository;
+
+    let app_state = common::create_test_app_state().await;
+    let cookie = create_and_login_admin(&app_state).await;
+
+    let admin = UserRepository::new(app_state.db.clone())
+        .find_by_email("admin@example.com")
+        .await
+        .unwrap()
+        .unwrap();
+
+    let create_request = Request::builder()
+        .uri("/api/admin/profiles")
+        .method("POST")
+        .header("Content-Type", "application/json")
+        .header("Cookie", &cookie)
+        .body(Body::from(
+            json!({
+                "name": "Miso",
+                "type": "grain",
+                "min_days": 90,
+                "max_days": 365,
+                "temp_min": 60.0,
+                "temp_max": 80.0
+            })
+            .to_string(),
+        ))
+        .unwrap();
+    let (status, profile) = common::send_json(&app_state, create_request).await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(profile["created_by"], admin.id);
+    let profile_id = profile["id"].as_i64().unwrap();
+
+    // Seeded profiles have no creator
+    let (_, profiles) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri("/api/admin/profiles")
+            .header("Cookie", &cookie)
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    let pickles = profiles
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|p| p["id"] == 1)
+        .unwrap();
+    assert!(pickles["created_by"].is_null());
+
+    let admin_repo = AdminUserRepository::new(app_state.db.clone());
+    let other_admin = admin_repo
+        .create_user_as_admin(
+            "other-admin@example.com".to_string(),
+            "adminpassword123".to_string(),
+            UserRole::Admin,
+            ExperienceLevel::Advanced,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+    let regular_user = admin_repo
+        .create_user_as_admin(
+            "regular@example.com".to_string(),
+            "userpassword123".to_string(),
+            UserRole::User,
+            ExperienceLevel::Beginner,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+
+    let (status, profile) = common::send_json(
+        &app_state,
+        common::authed_request(
+            "PUT",
+            &format!("/api/admin/profiles/{}/owner", profile_id),
+            &cookie,
+            Some(json!({ "created_by": other_admin.id })),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(profile["created_by"], other_admin.id);
+
+    // Only admins can own profiles
+    let (status, _) = common::send_json(
+        &app_state,
+        common::authed_request(
+            "PUT",
+            &format!("/api/admin/profiles/{}/owner", profile_id),
+            &cookie,
+            Some(json!({ "created_by": regular_user.id })),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, _) = common::send_json(
+        &app_state,
+        common::authed_request(
+            "PUT",
+            "/api/admin/profiles/9999/owner",
+            &cookie,
+            Some(json!({ "created_by": other_admin.id })),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_reassign_profile_requires_admin() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = create_and_login_user(&app_state).await;
+
+    let (status, _) = common::send_json(
+        &app_state,
+        common::authed_request(
+            "PUT",
+            "/api/admin/profiles/1/owner",
+            &cookie,
+            Some(json!({ "created_by": 1 })),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
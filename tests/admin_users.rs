@@ -19,6 +19,7 @@ async fn create_and_login_admin(app_state: raugupatis_log::AppState) -> String {
         experience_level: Some("intermediate".to_string()),
         first_name: Some("Admin".to_string()),
         last_name: Some("User".to_string()),
+        invite_code: None,
     };
 
     let admin_user = user_repo.create_user(admin_request).await.unwrap();
@@ -77,6 +78,7 @@ async fn create_and_login_user(app_state: raugupatis_log::AppState) -> String {
         experience_level: Some("beginner".to_string()),
         first_name: None,
         last_name: None,
+        invite_code: None,
     };
 
     user_repo.create_user(user_request).await.unwrap();
@@ -134,10 +136,13 @@ async fn test_admin_list_users() {
     let body = axum::body::to_bytes(response.into_body(), usize::MAX)
         .await
         .unwrap();
-    let users: Vec<serde_json::Value> = serde_json::from_slice(&body).unwrap();
+    let list: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let users = list["users"].as_array().unwrap();
 
     // Should have at least the admin user
     assert!(!users.is_empty());
+    assert_eq!(list["total"].as_i64().unwrap(), users.len() as i64);
+    assert_eq!(list["page"], 1);
 }
 
 #[tokio::test]
@@ -287,6 +292,7 @@ async fn test_admin_update_user() {
         experience_level: Some("beginner".to_string()),
         first_name: None,
         last_name: None,
+        invite_code: None,
     };
 
     let created_user = user_repo.create_user(user_request).await.unwrap();
@@ -343,6 +349,7 @@ async fn test_admin_lock_user() {
         experience_level: Some("beginner".to_string()),
         first_name: None,
         last_name: None,
+        invite_code: None,
     };
 
     let created_user = user_repo.create_user(user_request).await.unwrap();
@@ -391,6 +398,7 @@ async fn test_locked_user_cannot_login() {
         experience_level: Some("beginner".to_string()),
         first_name: None,
         last_name: None,
+        invite_code: None,
     };
 
     let created_user = user_repo.create_user(user_request).await.unwrap();
@@ -448,27 +456,32 @@ async fn test_admin_delete_user() {
     let app_state = common::create_test_app_state().await;
     let cookie = create_and_login_admin(app_state.clone()).await;
 
-    // Create a regular user first
-    use raugupatis_log::users::CreateUserRequest;
+    // The user has logged in, so they have sessions recorded against them
+    let user_cookie = create_and_login_user(app_state.clone()).await;
+
+    // ...and a fermentation with uploaded files
+    let fermentation_id =
+        common::create_test_fermentation(&app_state, &user_cookie, "Orphaned Photos").await;
+    let uploads =
+        std::path::Path::new(&app_state.config.uploads_dir).join(fermentation_id.to_string());
+    std::fs::create_dir_all(&uploads).unwrap();
+    std::fs::write(uploads.join("photo.jpg"), b"not really a jpeg").unwrap();
+
     use raugupatis_log::users::UserRepository;
 
     let user_repo = UserRepository::new(app_state.db.clone());
-    let user_request = CreateUserRequest {
-        email: "todelete@example.com".to_string(),
-        password: "password123".to_string(),
-        experience_level: Some("beginner".to_string()),
-        first_name: None,
-        last_name: None,
-    };
-
-    let created_user = user_repo.create_user(user_request).await.unwrap();
+    let user = user_repo
+        .find_by_email("user@example.com")
+        .await
+        .unwrap()
+        .unwrap();
 
-    let app = raugupatis_log::create_router(app_state).await;
+    let app = raugupatis_log::create_router(app_state.clone()).await;
 
     let response = app
         .oneshot(
             Request::builder()
-                .uri(format!("/api/admin/users/{}", created_user.id))
+                .uri(format!("/api/admin/users/{}", user.id))
                 .method("DELETE")
                 .header("Cookie", cookie)
                 .body(Body::empty())
@@ -478,6 +491,24 @@ async fn test_admin_delete_user() {
         .unwrap();
 
     assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    assert!(user_repo
+        .find_by_email("user@example.com")
+        .await
+        .unwrap()
+        .is_none());
+    assert!(!uploads.exists());
+
+    // The deleted user's session no longer works
+    let (status, _) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri("/api/users/me")
+            .header("Cookie", user_cookie)
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
 }
 
 #[tokio::test]
@@ -532,6 +563,7 @@ async fn test_admin_cannot_delete_themselves() {
         experience_level: Some("intermediate".to_string()),
         first_name: Some("Admin".to_string()),
         last_name: Some("User".to_string()),
+        invite_code: None,
     };
 
     let admin_user = user_repo.create_user(admin_request).await.unwrap();
@@ -588,6 +620,7 @@ async fn test_admin_cannot_delete_themselves() {
         experience_level: Some("intermediate".to_string()),
         first_name: Some("Admin".to_string()),
         last_name: Some("User".to_string()),
+        invite_code: None,
     };
 
     let admin_user2 = user_repo2.create_user(admin_request2).await.unwrap();
@@ -695,3 +728,242 @@ async fn test_admin_users_page_loads_for_admin() {
 
     assert_eq!(response.status(), StatusCode::OK);
 }
+
+#[tokio::test]
+async fn test_admin_list_users_pagination_and_filters() {
+    use raugupatis_log::admin::AdminUserRepository;
+    use raugupatis_log::users::{ExperienceLevel, UserRole};
+
+    let app_state = common::create_test_app_state().await;
+    let cookie = create_and_login_admin(app_state.clone()).await;
+
+    let repo = AdminUserRepository::new(app_state.db.clone());
+    let mut created = Vec::new();
+    for (email, first_name, last_name) in [
+        ("anna@example.com", "Anna", "Berg"),
+        ("bert@example.com", "Bert", "Kowalski"),
+        ("carla@example.com", "Carla", "Berger"),
+        ("dieter@example.com", "Dieter", "Schmidt"),
+    ] {
+        let user = repo
+            .create_user_as_admin(
+                email.to_string(),
+                "password123".to_string(),
+                UserRole::User,
+                ExperienceLevel::Beginner,
+                Some(first_name.to_string()),
+                Some(last_name.to_string()),
+            )
+            .await
+            .unwrap();
+        created.push(user);
+    }
+    repo.lock_user(created[3].id, true).await.unwrap();
+
+    let list = |query: &str| {
+        common::authed_request("GET", &format!("/api/admin/users?{}", query), &cookie, None)
+    };
+    let emails = |list: &serde_json::Value| -> Vec<String> {
+        list["users"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|u| u["email"].as_str().unwrap().to_string())
+            .collect()
+    };
+
+    // Pages split the full result set while reporting the overall total
+    let (status, first_page) = common::send_json(&app_state, list("page=1&per_page=2")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(first_page["total"], 5);
+    assert_eq!(first_page["per_page"], 2);
+    assert_eq!(emails(&first_page).len(), 2);
+
+    let (_, last_page) = common::send_json(&app_state, list("page=3&per_page=2")).await;
+    assert_eq!(last_page["total"], 5);
+    assert_eq!(emails(&last_page).len(), 1);
+
+    // Search matches email, first name and last name
+    let (_, berg) = common::send_json(&app_state, list("search=berg")).await;
+    assert_eq!(berg["total"], 2);
+    let mut found = emails(&berg);
+    found.sort();
+    assert_eq!(found, vec!["anna@example.com", "carla@example.com"]);
+
+    let (_, bert) = common::send_json(&app_state, list("search=bert@")).await;
+    assert_eq!(emails(&bert), vec!["bert@example.com"]);
+
+    // Role and lock filters
+    let (_, admins) = common::send_json(&app_state, list("role=admin")).await;
+    assert_eq!(emails(&admins), vec!["admin@example.com"]);
+
+    let (_, locked) = common::send_json(&app_state, list("is_locked=true")).await;
+    assert_eq!(emails(&locked), vec!["dieter@example.com"]);
+
+    let (_, unlocked_users) =
+        common::send_json(&app_state, list("role=user&is_locked=false")).await;
+    assert_eq!(unlocked_users["total"], 3);
+
+    // Invalid parameters are rejected
+    let (status, _) = common::send_json(&app_state, list("page=0")).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, _) = common::send_json(&app_state, list("role=superuser")).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_admin_get_user_detail() {
+    use raugupatis_log::users::UserRepository;
+
+    let app_state = common::create_test_app_state().await;
+    let admin_cookie = create_and_login_admin(app_state.clone()).await;
+    let user_cookie = create_and_login_user(app_state.clone()).await;
+
+    common::create_test_fermentation(&app_state, &user_cookie, "Sauerkraut").await;
+    common::create_test_fermentation(&app_state, &user_cookie, "Kimchi").await;
+
+    let user = UserRepository::new(app_state.db.clone())
+        .find_by_email("user@example.com")
+        .await
+        .unwrap()
+        .unwrap();
+
+    let detail = |id: i64, cookie: &str| {
+        common::authed_request("GET", &format!("/api/admin/users/{}", id), cookie, None)
+    };
+
+    let (status, body) = common::send_json(&app_state, detail(user.id, &admin_cookie)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["email"], "user@example.com");
+    assert_eq!(body["fermentation_count"], 2);
+    assert_eq!(body["active_fermentation_count"], 2);
+    assert!(body["last_login_at"].is_string());
+    assert_eq!(body["account_status"], "unverified");
+
+    // Unknown users are reported as missing
+    let (status, _) = common::send_json(&app_state, detail(99999, &admin_cookie)).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    // Regular users cannot view user details
+    let (status, _) = common::send_json(&app_state, detail(user.id, &user_cookie)).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn test_invite_only_registration() {
+    let mut app_state = common::create_test_app_state().await;
+    app_state.config = std::sync::Arc::new(raugupatis_log::config::AppConfig {
+        require_invite: true,
+        ..(*app_state.config).clone()
+    });
+    let admin_cookie = create_and_login_admin(app_state.clone()).await;
+    let user_cookie = create_and_login_user(app_state.clone()).await;
+
+    let mint = |cookie: &str, body: serde_json::Value| {
+        common::authed_request("POST", "/api/admin/invites", cookie, Some(body))
+    };
+    let register = |email: &str, invite_code: Option<&str>| {
+        Request::builder()
+            .uri("/api/users/register")
+            .method("POST")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                json!({
+                    "email": email,
+                    "password": "securepassword123",
+                    "invite_code": invite_code,
+                })
+                .to_string(),
+            ))
+            .unwrap()
+    };
+
+    // Only admins can mint codes
+    let (status, _) = common::send_json(&app_state, mint(&user_cookie, json!({}))).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, error) = common::send_json(
+        &app_state,
+        mint(&admin_cookie, json!({ "expires_in_days": 0 })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(error["error"].as_str().unwrap().contains("expires_in_days"));
+
+    let (status, invite) = common::send_json(&app_state, mint(&admin_cookie, json!({}))).await;
+    assert_eq!(status, StatusCode::CREATED);
+    let code = invite["code"].as_str().unwrap().to_string();
+    assert!(invite["used_by"].is_null());
+    assert!(invite["expires_at"].is_null());
+
+    let (status, error) =
+        common::send_json(&app_state, register("invited@example.com", None)).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(error["field"], "invite_code");
+
+    let (status, error) =
+        common::send_json(&app_state, register("invited@example.com", Some("made-up"))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(error["field"], "invite_code");
+
+    let (status, user) =
+        common::send_json(&app_state, register("invited@example.com", Some(&code))).await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    // Codes are single-use, and a rejected code creates no account
+    let (status, error) =
+        common::send_json(&app_state, register("second@example.com", Some(&code))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(error["field"], "invite_code");
+    let user_repo = raugupatis_log::users::UserRepository::new(app_state.db.clone());
+    assert!(user_repo
+        .find_by_email("second@example.com")
+        .await
+        .unwrap()
+        .is_none());
+
+    // Expired codes are rejected
+    let (status, expiring) = common::send_json(
+        &app_state,
+        mint(&admin_cookie, json!({ "expires_in_days": 7 })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert!(expiring["expires_at"].is_string());
+    app_state
+        .db
+        .get_connection()
+        .lock()
+        .unwrap()
+        .execute(
+            "UPDATE invite_codes SET expires_at = '2024-01-01 00:00:00' WHERE id = ?1",
+            [expiring["id"].as_i64().unwrap()],
+        )
+        .unwrap();
+    let (status, _) = common::send_json(
+        &app_state,
+        register("late@example.com", expiring["code"].as_str()),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, invites) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri("/api/admin/invites")
+            .header("Cookie", &admin_cookie)
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let used = invites
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|invite| invite["code"] == code.as_str())
+        .unwrap();
+    assert_eq!(used["used_by"], user["id"]);
+    assert!(used["used_at"].is_string());
+}
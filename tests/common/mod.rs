@@ -34,13 +34,174 @@ pub async fn create_test_app_state() -> AppState {
         environment: "test".to_string(),
         session_secret: "test-secret".to_string(),
         uploads_dir: test_uploads_dir,
+        session_ttl_hours: 24,
+        remember_me_days: 5,
+        require_email_verification: false,
+        max_request_body_bytes: 1024 * 1024,
+        allowed_origins: vec![],
+        session_same_site: "lax".to_string(),
+        password_min_length: 8,
+        password_require_digit: false,
+        password_require_non_alphanumeric: false,
+        // Many tests create fermentations against the default profiles
+        seed_default_profiles: true,
+        min_temperature_fahrenheit: 0.0,
+        max_temperature_fahrenheit: 150.0,
+        allow_custom_types: false,
+        max_photo_bytes_per_user: 500 * 1024 * 1024,
+        registration_open: true,
+        require_invite: false,
     });
 
+    config.prepare_uploads_dir().unwrap();
+
     let db = Arc::new(Database::new(&config.database_url).await.unwrap());
-    db.migrate().await.unwrap();
+    db.migrate(config.seed_default_profiles).await.unwrap();
 
     AppState {
         db,
         config: config.clone(),
+        started_at: std::time::Instant::now(),
+        metrics: Arc::new(Default::default()),
+    }
+}
+
+/// Registers a user with the given email and logs them in, returning the session cookie
+#[allow(dead_code)]
+pub async fn register_and_login(app_state: &AppState, email: &str) -> String {
+    use axum::{body::Body, http::Request};
+    use serde_json::json;
+    use tower::ServiceExt;
+
+    let register_body = json!({
+        "email": email,
+        "password": "securepassword123"
+    });
+
+    let app = raugupatis_log::create_router(app_state.clone()).await;
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/api/users/register")
+                .method("POST")
+                .header("Content-Type", "application/json")
+                .body(Body::from(serde_json::to_string(&register_body).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), axum::http::StatusCode::CREATED);
+
+    let login_body = json!({
+        "email": email,
+        "password": "securepassword123"
+    });
+
+    let app = raugupatis_log::create_router(app_state.clone()).await;
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/api/users/login")
+                .method("POST")
+                .header("Content-Type", "application/json")
+                .body(Body::from(serde_json::to_string(&login_body).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), axum::http::StatusCode::OK);
+
+    response
+        .headers()
+        .get("set-cookie")
+        .and_then(|v| v.to_str().ok())
+        .expect("Login should set a session cookie")
+        .split(';')
+        .next()
+        .unwrap()
+        .to_string()
+}
+
+/// Creates a fermentation for the logged in user and returns its ID
+#[allow(dead_code)]
+pub async fn create_test_fermentation(app_state: &AppState, cookie: &str, name: &str) -> i64 {
+    use axum::{body::Body, http::Request};
+    use serde_json::json;
+    use tower::ServiceExt;
+
+    let fermentation_body = json!({
+        "profile_id": 1,
+        "name": name,
+        "start_date": "2024-01-15T10:00:00Z",
+    });
+
+    let app = raugupatis_log::create_router(app_state.clone()).await;
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/api/fermentation")
+                .method("POST")
+                .header("Content-Type", "application/json")
+                .header("Cookie", cookie)
+                .body(Body::from(
+                    serde_json::to_string(&fermentation_body).unwrap(),
+                ))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), axum::http::StatusCode::CREATED);
+
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let fermentation: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    fermentation["id"].as_i64().unwrap()
+}
+
+/// Sends a single request through a fresh router and returns the status and parsed JSON body
+#[allow(dead_code)]
+pub async fn send_json(
+    app_state: &AppState,
+    request: axum::http::Request<axum::body::Body>,
+) -> (axum::http::StatusCode, serde_json::Value) {
+    use tower::ServiceExt;
+
+    let app = raugupatis_log::create_router(app_state.clone()).await;
+    let response = app.oneshot(request).await.unwrap();
+    let status = response.status();
+
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json = serde_json::from_slice(&body).unwrap_or(serde_json::Value::Null);
+
+    (status, json)
+}
+
+/// Builds a request carrying the session cookie; a JSON body is sent when given
+#[allow(dead_code)]
+pub fn authed_request(
+    method: &str,
+    uri: &str,
+    cookie: &str,
+    body: Option<serde_json::Value>,
+) -> axum::http::Request<axum::body::Body> {
+    use axum::{body::Body, http::Request};
+
+    let builder = Request::builder()
+        .uri(uri)
+        .method(method)
+        .header("Cookie", cookie);
+
+    match body {
+        Some(body) => builder
+            .header("Content-Type", "application/json")
+            .body(Body::from(body.to_string())),
+        None => builder.body(Body::empty()),
     }
+    .unwrap()
 }
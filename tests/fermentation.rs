@@ -66,6 +66,112 @@ async fn test_get_fermentation_profiles() {
 
     // Should have the default profiles from migration
     assert!(!profiles.as_array().unwrap().is_empty());
+
+    // Each profile carries its recommended conditions for display at selection time
+    let pickles = profiles
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|p| p["name"] == "Pickles")
+        .unwrap();
+    assert_eq!(pickles["temp_min"], 65.0);
+    assert_eq!(pickles["temp_max"], 75.0);
+    assert_eq!(pickles["min_days"], 3);
+    assert_eq!(pickles["max_days"], 7);
+    // Built-in profiles suggest typical ingredients
+    assert!(pickles["default_ingredients"]
+        .as_str()
+        .unwrap()
+        .contains("cucumbers"));
+    assert!(pickles["description"].is_string());
+}
+
+#[tokio::test]
+async fn test_get_fermentation_profiles_in_preferred_unit() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "profile-units@example.com").await;
+
+    let pickles = |profiles: serde_json::Value| {
+        profiles
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|p| p["name"] == "Pickles")
+            .unwrap()
+            .clone()
+    };
+    let profiles_request = |cookie: Option<&str>| {
+        let mut builder = Request::builder().uri("/api/fermentation/profiles");
+        if let Some(cookie) = cookie {
+            builder = builder.header("Cookie", cookie);
+        }
+        builder.body(Body::empty()).unwrap()
+    };
+
+    // Anonymous requests get Fahrenheit
+    let (status, profiles) = common::send_json(&app_state, profiles_request(None)).await;
+    assert_eq!(status, StatusCode::OK);
+    let profile = pickles(profiles);
+    assert_eq!(profile["temp_min_display"], 65.0);
+    assert_eq!(profile["temp_max_display"], 75.0);
+    assert_eq!(profile["temp_unit"], "fahrenheit");
+    assert_eq!(profile["unit_symbol"], "°F");
+
+    let (status, _) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri("/api/users/profile")
+            .method("POST")
+            .header("Content-Type", "application/json")
+            .header("Cookie", &cookie)
+            .body(Body::from(
+                json!({
+                    "experience_level": "beginner",
+                    "preferred_temp_unit": "celsius"
+                })
+                .to_string(),
+            ))
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    // Stored values stay in Fahrenheit next to the converted ones
+    let (status, profiles) = common::send_json(&app_state, profiles_request(Some(&cookie))).await;
+    assert_eq!(status, StatusCode::OK);
+    let profile = pickles(profiles);
+    assert_eq!(profile["temp_min"], 65.0);
+    assert_eq!(profile["temp_max"], 75.0);
+    assert!((profile["temp_min_display"].as_f64().unwrap() - 18.33).abs() < 0.01);
+    assert!((profile["temp_max_display"].as_f64().unwrap() - 23.89).abs() < 0.01);
+    assert_eq!(profile["temp_unit"], "celsius");
+    assert_eq!(profile["unit_symbol"], "°C");
+}
+
+#[tokio::test]
+async fn test_new_fermentation_page_shows_profile_guidance() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "beginner@example.com").await;
+
+    let app = raugupatis_log::create_router(app_state.clone()).await;
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/fermentation/new")
+                .header("Cookie", &cookie)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let html = String::from_utf8(body.to_vec()).unwrap();
+    assert!(html.contains("Keep between 65–75°F for 3–7 days"));
 }
 
 #[tokio::test]
@@ -832,6 +938,110 @@ async fn test_update_fermentation_success() {
     assert_eq!(updated["status"], "completed");
 }
 
+#[tokio::test]
+async fn test_patch_fermentation_absent_and_null_fields() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "patcher@example.com").await;
+    let fermentation_id = common::create_test_fermentation(&app_state, &cookie, "Patchy").await;
+
+    let patch = |body: serde_json::Value| {
+        common::authed_request(
+            "PATCH",
+            &format!("/api/fermentation/{}", fermentation_id),
+            &cookie,
+            Some(body),
+        )
+    };
+
+    let (status, fermentation) = common::send_json(
+        &app_state,
+        patch(json!({
+            "notes": "Keep an eye on the brine",
+            "target_end_date": "2024-02-01T10:00:00Z"
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(fermentation["notes"], "Keep an eye on the brine");
+
+    // Fields left out of the body are not touched
+    let (status, fermentation) =
+        common::send_json(&app_state, patch(json!({ "name": "Renamed" }))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(fermentation["name"], "Renamed");
+    assert_eq!(fermentation["notes"], "Keep an eye on the brine");
+    assert_eq!(fermentation["target_end_date"], "2024-02-01T10:00:00Z");
+
+    // An explicit null clears the field and leaves the others alone
+    let (status, fermentation) =
+        common::send_json(&app_state, patch(json!({ "notes": null }))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(fermentation["notes"].is_null());
+    assert_eq!(fermentation["name"], "Renamed");
+    assert_eq!(fermentation["target_end_date"], "2024-02-01T10:00:00Z");
+
+    let (status, fermentation) =
+        common::send_json(&app_state, patch(json!({ "target_end_date": null }))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(fermentation["target_end_date"].is_null());
+
+    // Values are still validated
+    let (status, _) = common::send_json(&app_state, patch(json!({ "success_rating": 9 }))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_update_fermentation_rejects_stale_version() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "two_tabs@example.com").await;
+    let fermentation_id = common::create_test_fermentation(&app_state, &cookie, "Contested").await;
+
+    let patch = |body: serde_json::Value| {
+        common::authed_request(
+            "PATCH",
+            &format!("/api/fermentation/{}", fermentation_id),
+            &cookie,
+            Some(body),
+        )
+    };
+
+    let (status, fermentation) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri(format!("/api/fermentation/{}", fermentation_id))
+            .header("Cookie", &cookie)
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let loaded_version = fermentation["version"].as_i64().unwrap();
+
+    // The first tab saves based on what it loaded
+    let (status, fermentation) = common::send_json(
+        &app_state,
+        patch(json!({ "name": "First Tab", "version": loaded_version })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(fermentation["version"], loaded_version + 1);
+
+    // The second tab still has the old version and must not overwrite the first
+    let (status, _) = common::send_json(
+        &app_state,
+        patch(json!({ "name": "Second Tab", "version": loaded_version })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    // Updates without a version still apply
+    let (status, fermentation) =
+        common::send_json(&app_state, patch(json!({ "notes": "No version sent" }))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(fermentation["name"], "First Tab");
+    assert_eq!(fermentation["version"], loaded_version + 2);
+}
+
 #[tokio::test]
 async fn test_update_fermentation_unauthorized() {
     let app = common::create_test_app().await;
@@ -1569,29 +1779,33 @@ async fn test_fermentation_list_includes_thumbnails() {
 
     // Add a "start" stage photo
     use chrono::Utc;
-    use raugupatis_log::photos::{PhotoRepository, PhotoStage};
+    use raugupatis_log::photos::{NewPhoto, PhotoRepository, PhotoStage};
 
     let photo_repo = PhotoRepository::new(app_state.db.clone());
     let _photo = photo_repo
-        .create_photo(
+        .create_photo(NewPhoto {
             fermentation_id,
-            "/uploads/test_start.jpg".to_string(),
-            Some("Start photo".to_string()),
-            Utc::now(),
-            PhotoStage::Start,
-        )
+            file_path: "/uploads/test_start.jpg".to_string(),
+            file_size: 0,
+            thumbnail_path: None,
+            caption: Some("Start photo".to_string()),
+            taken_at: Utc::now(),
+            stage: PhotoStage::Start,
+        })
         .await
         .unwrap();
 
     // Add a "progress" stage photo (should not be used as thumbnail)
     let _photo2 = photo_repo
-        .create_photo(
+        .create_photo(NewPhoto {
             fermentation_id,
-            "/uploads/test_progress.jpg".to_string(),
-            Some("Progress photo".to_string()),
-            Utc::now(),
-            PhotoStage::Progress,
-        )
+            file_path: "/uploads/test_progress.jpg".to_string(),
+            file_size: 0,
+            thumbnail_path: None,
+            caption: Some("Progress photo".to_string()),
+            taken_at: Utc::now(),
+            stage: PhotoStage::Progress,
+        })
         .await
         .unwrap();
 
@@ -1654,24 +1868,28 @@ async fn test_fermentation_list_includes_thumbnails() {
 
     // Add "start" and "end" photos to completed fermentation
     let _photo3 = photo_repo
-        .create_photo(
-            fermentation_id2,
-            "/uploads/test_completed_start.jpg".to_string(),
-            Some("Completed start photo".to_string()),
-            Utc::now(),
-            PhotoStage::Start,
-        )
+        .create_photo(NewPhoto {
+            fermentation_id: fermentation_id2,
+            file_path: "/uploads/test_completed_start.jpg".to_string(),
+            file_size: 0,
+            thumbnail_path: None,
+            caption: Some("Completed start photo".to_string()),
+            taken_at: Utc::now(),
+            stage: PhotoStage::Start,
+        })
         .await
         .unwrap();
 
     let _photo4 = photo_repo
-        .create_photo(
-            fermentation_id2,
-            "/uploads/test_completed_end.jpg".to_string(),
-            Some("Completed end photo".to_string()),
-            Utc::now(),
-            PhotoStage::End,
-        )
+        .create_photo(NewPhoto {
+            fermentation_id: fermentation_id2,
+            file_path: "/uploads/test_completed_end.jpg".to_string(),
+            file_size: 0,
+            thumbnail_path: None,
+            caption: Some("Completed end photo".to_string()),
+            taken_at: Utc::now(),
+            stage: PhotoStage::End,
+        })
         .await
         .unwrap();
 
@@ -2032,6 +2250,58 @@ async fn test_fermentation_list_filter_by_status() {
     assert_eq!(fermentations[0]["id"], ferm2_id);
 }
 
+#[tokio::test]
+async fn test_fermentation_list_sort_by_success_rating() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "ranker@example.com").await;
+    common::create_test_fermentation(&app_state, &cookie, "Unrated").await;
+    let best_id = common::create_test_fermentation(&app_state, &cookie, "Best").await;
+    let okay_id = common::create_test_fermentation(&app_state, &cookie, "Okay").await;
+
+    for (id, rating) in [(best_id, 5), (okay_id, 2)] {
+        let (status, _) = common::send_json(
+            &app_state,
+            Request::builder()
+                .uri(format!("/api/fermentation/{}/finish", id))
+                .method("POST")
+                .header("Content-Type", "application/json")
+                .header("Cookie", &cookie)
+                .body(Body::from(json!({ "success_rating": rating }).to_string()))
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    // Unrated fermentations sort last in both directions
+    for (order, expected) in [
+        ("desc", ["Best", "Okay", "Unrated"]),
+        ("asc", ["Okay", "Best", "Unrated"]),
+    ] {
+        let (status, fermentations) = common::send_json(
+            &app_state,
+            Request::builder()
+                .uri(format!(
+                    "/api/fermentations?sort_by=success_rating&sort_order={}",
+                    order
+                ))
+                .header("Cookie", &cookie)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+
+        let names: Vec<&str> = fermentations
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|f| f["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(names, expected);
+    }
+}
+
 #[tokio::test]
 async fn test_fermentation_list_sort_by_name() {
     let app_state = common::create_test_app_state().await;
@@ -3102,6 +3372,60 @@ async fn test_finish_fermentation_success() {
     assert_eq!(finished_fermentation["status"], "completed");
 }
 
+#[tokio::test]
+async fn test_finish_fermentation_twice_conflicts() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "finisher@example.com").await;
+    let fermentation_id = common::create_test_fermentation(&app_state, &cookie, "Once Only").await;
+
+    let finish = |rating: i32, taste_profile: &str| {
+        Request::builder()
+            .uri(format!("/api/fermentation/{}/finish", fermentation_id))
+            .method("POST")
+            .header("Content-Type", "application/json")
+            .header("Cookie", &cookie)
+            .body(Body::from(
+                json!({ "success_rating": rating, "taste_profile": taste_profile }).to_string(),
+            ))
+            .unwrap()
+    };
+
+    let (status, fermentation) = common::send_json(&app_state, finish(4, "Crisp and sour")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(fermentation["status"], "completed");
+
+    // A retried or repeated finish leaves the first result alone
+    let (status, _) = common::send_json(&app_state, finish(1, "Second opinion")).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    let (_, fermentations) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri("/api/fermentations")
+            .header("Cookie", &cookie)
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(fermentations[0]["success_rating"], 4);
+
+    let (_, taste_profiles) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri(format!(
+                "/api/fermentation/{}/taste-profiles",
+                fermentation_id
+            ))
+            .header("Cookie", &cookie)
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    let taste_profiles = taste_profiles.as_array().unwrap();
+    assert_eq!(taste_profiles.len(), 1);
+    assert_eq!(taste_profiles[0]["profile_text"], "Crisp and sour");
+}
+
 #[tokio::test]
 async fn test_finish_fermentation_unauthorized() {
     let app = common::create_test_app().await;
@@ -3521,3 +3845,4227 @@ async fn test_taste_profile_unauthorized() {
 
     assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
 }
+
+#[tokio::test]
+async fn test_update_temperature_log_success() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "temp_editor@example.com").await;
+    let fermentation_id =
+        common::create_test_fermentation(&app_state, &cookie, "Editable Temps").await;
+
+    let (status, temp_log) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri(format!("/api/fermentation/{}/temperature", fermentation_id))
+            .method("POST")
+            .header("Content-Type", "application/json")
+            .header("Cookie", &cookie)
+            .body(Body::from(json!({ "temperature": 27.0 }).to_string()))
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let log_id = temp_log["id"].as_i64().unwrap();
+
+    // Correct the fat-fingered reading
+    let (status, updated) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri(format!(
+                "/api/fermentation/{}/temperature/{}",
+                fermentation_id, log_id
+            ))
+            .method("PUT")
+            .header("Content-Type", "application/json")
+            .header("Cookie", &cookie)
+            .body(Body::from(
+                json!({ "temperature": 72.0, "notes": "Corrected" }).to_string(),
+            ))
+            .unwrap(),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(updated["id"], log_id);
+    assert_eq!(updated["temperature"], 72.0);
+    assert_eq!(updated["notes"], "Corrected");
+
+    // Out of range values are rejected just like on create
+    let (status, _) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri(format!(
+                "/api/fermentation/{}/temperature/{}",
+                fermentation_id, log_id
+            ))
+            .method("PUT")
+            .header("Content-Type", "application/json")
+            .header("Cookie", &cookie)
+            .body(Body::from(json!({ "temperature": 200.0 }).to_string()))
+            .unwrap(),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_delete_temperature_log_success() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "temp_deleter@example.com").await;
+    let fermentation_id =
+        common::create_test_fermentation(&app_state, &cookie, "Deletable Temps").await;
+
+    let (_, temp_log) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri(format!("/api/fermentation/{}/temperature", fermentation_id))
+            .method("POST")
+            .header("Content-Type", "application/json")
+            .header("Cookie", &cookie)
+            .body(Body::from(json!({ "temperature": 70.0 }).to_string()))
+            .unwrap(),
+    )
+    .await;
+    let log_id = temp_log["id"].as_i64().unwrap();
+
+    let (status, _) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri(format!(
+                "/api/fermentation/{}/temperature/{}",
+                fermentation_id, log_id
+            ))
+            .method("DELETE")
+            .header("Cookie", &cookie)
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    // Deleting again should report the log as missing
+    let (status, _) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri(format!(
+                "/api/fermentation/{}/temperature/{}",
+                fermentation_id, log_id
+            ))
+            .method("DELETE")
+            .header("Cookie", &cookie)
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_delete_all_temperature_logs() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "probe_tester@example.com").await;
+    let other_cookie = common::register_and_login(&app_state, "bystander@example.com").await;
+    let fermentation_id = common::create_test_fermentation(&app_state, &cookie, "Probe Test").await;
+    let kept_id = common::create_test_fermentation(&app_state, &cookie, "Keep Readings").await;
+
+    let log = |id: i64, temperature: f64| {
+        Request::builder()
+            .uri(format!("/api/fermentation/{}/temperature", id))
+            .method("POST")
+            .header("Content-Type", "application/json")
+            .header("Cookie", &cookie)
+            .body(Body::from(
+                json!({ "temperature": temperature }).to_string(),
+            ))
+            .unwrap()
+    };
+    for temperature in [68.0, 69.0, 70.0] {
+        common::send_json(&app_state, log(fermentation_id, temperature)).await;
+    }
+    common::send_json(&app_state, log(kept_id, 71.0)).await;
+
+    let delete_all = |id: i64, query: &str, cookie: &str| {
+        common::authed_request(
+            "DELETE",
+            &format!("/api/fermentation/{}/temperature{}", id, query),
+            cookie,
+            None,
+        )
+    };
+
+    // Without confirmation nothing is deleted
+    let (status, body) =
+        common::send_json(&app_state, delete_all(fermentation_id, "", &cookie)).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["field"], "confirm");
+
+    let (status, _) = common::send_json(
+        &app_state,
+        delete_all(fermentation_id, "?confirm=true", &other_cookie),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, body) = common::send_json(
+        &app_state,
+        delete_all(fermentation_id, "?confirm=true", &cookie),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["deleted"], 3);
+
+    let list = |id: i64| {
+        common::authed_request(
+            "GET",
+            &format!("/api/fermentation/{}/temperature", id),
+            &cookie,
+            None,
+        )
+    };
+    let (_, logs) = common::send_json(&app_state, list(fermentation_id)).await;
+    assert_eq!(logs.as_array().unwrap().len(), 0);
+    let (_, logs) = common::send_json(&app_state, list(kept_id)).await;
+    assert_eq!(logs.as_array().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_cannot_modify_other_users_temperature_log() {
+    let app_state = common::create_test_app_state().await;
+    let owner_cookie = common::register_and_login(&app_state, "temp_owner@example.com").await;
+    let other_cookie = common::register_and_login(&app_state, "temp_intruder@example.com").await;
+    let fermentation_id =
+        common::create_test_fermentation(&app_state, &owner_cookie, "Owned Temps").await;
+
+    let (_, temp_log) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri(format!("/api/fermentation/{}/temperature", fermentation_id))
+            .method("POST")
+            .header("Content-Type", "application/json")
+            .header("Cookie", &owner_cookie)
+            .body(Body::from(json!({ "temperature": 70.0 }).to_string()))
+            .unwrap(),
+    )
+    .await;
+    let log_id = temp_log["id"].as_i64().unwrap();
+
+    let (status, _) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri(format!(
+                "/api/fermentation/{}/temperature/{}",
+                fermentation_id, log_id
+            ))
+            .method("PUT")
+            .header("Content-Type", "application/json")
+            .header("Cookie", &other_cookie)
+            .body(Body::from(json!({ "temperature": 71.0 }).to_string()))
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, _) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri(format!(
+                "/api/fermentation/{}/temperature/{}",
+                fermentation_id, log_id
+            ))
+            .method("DELETE")
+            .header("Cookie", &other_cookie)
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_export_temperature_logs_csv() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "csv_exporter@example.com").await;
+    let fermentation_id =
+        common::create_test_fermentation(&app_state, &cookie, "Spicy Kimchi").await;
+
+    // Empty history still yields a header-only CSV
+    let app = raugupatis_log::create_router(app_state.clone()).await;
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri(format!(
+                    "/api/fermentation/{}/temperature.csv",
+                    fermentation_id
+                ))
+                .header("Cookie", &cookie)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response.headers()["content-type"]
+        .to_str()
+        .unwrap()
+        .starts_with("text/csv"));
+    assert!(response.headers()["content-disposition"]
+        .to_str()
+        .unwrap()
+        .contains("spicy_kimchi-temperatures.csv"));
+
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    assert_eq!(
+        String::from_utf8(body.to_vec()).unwrap(),
+        "recorded_at,temperature_f,temperature_display,notes\n"
+    );
+
+    let (status, _) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri(format!("/api/fermentation/{}/temperature", fermentation_id))
+            .method("POST")
+            .header("Content-Type", "application/json")
+            .header("Cookie", &cookie)
+            .body(Body::from(
+                json!({ "temperature": 68.0, "notes": "Cool, steady" }).to_string(),
+            ))
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let app = raugupatis_log::create_router(app_state.clone()).await;
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri(format!(
+                    "/api/fermentation/{}/temperature.csv",
+                    fermentation_id
+                ))
+                .header("Cookie", &cookie)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let csv = String::from_utf8(body.to_vec()).unwrap();
+    let lines: Vec<&str> = csv.lines().collect();
+
+    assert_eq!(lines.len(), 2);
+    assert!(lines[1].ends_with(",68.0,68.0,\"Cool, steady\""));
+}
+
+#[tokio::test]
+async fn test_export_temperature_logs_csv_escapes_formulas() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "csv_formulas@example.com").await;
+    let fermentation_id = common::create_test_fermentation(&app_state, &cookie, "Mead").await;
+
+    let (status, _) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri(format!("/api/fermentation/{}/temperature", fermentation_id))
+            .method("POST")
+            .header("Content-Type", "application/json")
+            .header("Cookie", &cookie)
+            .body(Body::from(
+                json!({ "temperature": 68.0, "notes": "=HYPERLINK(\"http://example.com\")" })
+                    .to_string(),
+            ))
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let app = raugupatis_log::create_router(app_state.clone()).await;
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri(format!(
+                    "/api/fermentation/{}/temperature.csv",
+                    fermentation_id
+                ))
+                .header("Cookie", &cookie)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let csv = String::from_utf8(body.to_vec()).unwrap();
+    let lines: Vec<&str> = csv.lines().collect();
+
+    // Spreadsheets treat the leading quote as a text marker rather than evaluating the cell
+    assert!(lines[1].ends_with(",\"'=HYPERLINK(\"\"http://example.com\"\")\""));
+}
+
+#[tokio::test]
+async fn test_export_temperature_logs_csv_other_user() {
+    let app_state = common::create_test_app_state().await;
+    let owner_cookie = common::register_and_login(&app_state, "csv_owner@example.com").await;
+    let other_cookie = common::register_and_login(&app_state, "csv_other@example.com").await;
+    let fermentation_id =
+        common::create_test_fermentation(&app_state, &owner_cookie, "Private Ferment").await;
+
+    let app = raugupatis_log::create_router(app_state).await;
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri(format!(
+                    "/api/fermentation/{}/temperature.csv",
+                    fermentation_id
+                ))
+                .header("Cookie", &other_cookie)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_import_temperature_logs_csv_success() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "csv_importer@example.com").await;
+    let fermentation_id =
+        common::create_test_fermentation(&app_state, &cookie, "Logged Kombucha").await;
+
+    let csv = "recorded_at,temperature,temp_unit,notes\n\
+               2024-01-15T10:00:00Z,70.5,fahrenheit,Morning\n\
+               2024-01-15T22:00:00Z,20,celsius,\"Cooler, at night\"\n\
+               2024-01-16T10:00:00Z,71,,\n";
+
+    let (status, logs) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri(format!(
+                "/api/fermentation/{}/temperature/import",
+                fermentation_id
+            ))
+            .method("POST")
+            .header("Content-Type", "text/csv")
+            .header("Cookie", &cookie)
+            .body(Body::from(csv))
+            .unwrap(),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::CREATED);
+    let logs = logs.as_array().unwrap();
+    assert_eq!(logs.len(), 3);
+    // Celsius readings are stored in Fahrenheit
+    assert_eq!(logs[1]["temperature"], 68.0);
+    assert_eq!(logs[1]["notes"], "Cooler, at night");
+}
+
+#[tokio::test]
+async fn test_import_temperature_logs_csv_multiline_notes() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "csv_multiline@example.com").await;
+    let fermentation_id =
+        common::create_test_fermentation(&app_state, &cookie, "Multiline Import").await;
+
+    // Quoted notes may span lines, as the CSV export writes them
+    let csv = "recorded_at,temperature,temp_unit,notes\r\n\
+               2024-01-15T10:00:00Z,70.5,fahrenheit,\"Sour smell\nskimmed kahm yeast\"\r\n\
+               2024-01-16T10:00:00Z,71,fahrenheit,Fine\r\n";
+
+    let (status, logs) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri(format!(
+                "/api/fermentation/{}/temperature/import",
+                fermentation_id
+            ))
+            .method("POST")
+            .header("Content-Type", "text/csv")
+            .header("Cookie", &cookie)
+            .body(Body::from(csv))
+            .unwrap(),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::CREATED);
+    let logs = logs.as_array().unwrap();
+    assert_eq!(logs.len(), 2);
+    assert_eq!(logs[0]["notes"], "Sour smell\nskimmed kahm yeast");
+    assert_eq!(logs[1]["notes"], "Fine");
+
+    // Rows are reported by the line they start on
+    let csv = "2024-01-15T10:00:00Z,70.5,fahrenheit,\"Two\nlines\"\n\
+               not-a-date,70.5,fahrenheit,Broken\n";
+
+    let (status, body) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri(format!(
+                "/api/fermentation/{}/temperature/import",
+                fermentation_id
+            ))
+            .method("POST")
+            .header("Content-Type", "text/csv")
+            .header("Cookie", &cookie)
+            .body(Body::from(csv))
+            .unwrap(),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let rows = body["rows"].as_array().unwrap();
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0]["row"], 3);
+}
+
+#[tokio::test]
+async fn test_import_temperature_logs_csv_rejects_whole_batch() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "csv_bad_import@example.com").await;
+    let fermentation_id = common::create_test_fermentation(&app_state, &cookie, "Bad Import").await;
+
+    let csv = "2024-01-15T10:00:00Z,70.5,fahrenheit,Fine\n\
+               not-a-date,70.5,fahrenheit,Broken\n\
+               2024-01-16T10:00:00Z,500,fahrenheit,Too hot\n";
+
+    let (status, body) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri(format!(
+                "/api/fermentation/{}/temperature/import",
+                fermentation_id
+            ))
+            .method("POST")
+            .header("Content-Type", "text/csv")
+            .header("Cookie", &cookie)
+            .body(Body::from(csv))
+            .unwrap(),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let rows = body["rows"].as_array().unwrap();
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0]["row"], 2);
+    assert_eq!(rows[1]["row"], 3);
+
+    // Nothing from the batch should have been stored
+    let (status, logs) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri(format!("/api/fermentation/{}/temperature", fermentation_id))
+            .header("Cookie", &cookie)
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert!(logs.as_array().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_temperature_webhook() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "prober@example.com").await;
+    let other_cookie = common::register_and_login(&app_state, "other@example.com").await;
+    let fermentation_id =
+        common::create_test_fermentation(&app_state, &cookie, "Probed Kraut").await;
+
+    let token_request = |method: &str, cookie: &str| {
+        common::authed_request(
+            method,
+            &format!(
+                "/api/fermentation/{}/temperature/webhook-token",
+                fermentation_id
+            ),
+            cookie,
+            None,
+        )
+    };
+    let webhook = |token: Option<&str>, body: serde_json::Value| {
+        let mut builder = Request::builder()
+            .uri(format!(
+                "/api/fermentation/{}/temperature/webhook",
+                fermentation_id
+            ))
+            .method("POST")
+            .header("Content-Type", "application/json");
+        if let Some(token) = token {
+            builder = builder.header("Authorization", format!("Bearer {}", token));
+        }
+        builder.body(Body::from(body.to_string())).unwrap()
+    };
+
+    // Before a token is generated the webhook is disabled
+    let (status, _) = common::send_json(
+        &app_state,
+        webhook(Some("guess"), json!({ "temperature": 68.0 })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    // Only the owner can generate a token
+    let (status, _) = common::send_json(&app_state, token_request("POST", &other_cookie)).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, body) = common::send_json(&app_state, token_request("POST", &cookie)).await;
+    assert_eq!(status, StatusCode::CREATED);
+    let token = body["token"].as_str().unwrap().to_string();
+
+    let (status, log) = common::send_json(
+        &app_state,
+        webhook(
+            Some(&token),
+            json!({ "temperature": 20.0, "temp_unit": "celsius" }),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(log["fermentation_id"], fermentation_id);
+    assert_eq!(log["temperature"], 68.0);
+
+    // Readings are validated like manual ones
+    let (status, _) = common::send_json(
+        &app_state,
+        webhook(Some(&token), json!({ "temperature": 500.0 })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, _) =
+        common::send_json(&app_state, webhook(None, json!({ "temperature": 68.0 }))).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    let (status, _) = common::send_json(
+        &app_state,
+        webhook(Some("wrong"), json!({ "temperature": 68.0 })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    // A new token replaces the old one
+    let (status, body) = common::send_json(&app_state, token_request("POST", &cookie)).await;
+    assert_eq!(status, StatusCode::CREATED);
+    let new_token = body["token"].as_str().unwrap().to_string();
+    assert_ne!(new_token, token);
+
+    let (status, _) = common::send_json(
+        &app_state,
+        webhook(Some(&token), json!({ "temperature": 68.0 })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    // Revoking disables the webhook
+    let (status, _) = common::send_json(&app_state, token_request("DELETE", &cookie)).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    let (status, _) = common::send_json(
+        &app_state,
+        webhook(Some(&new_token), json!({ "temperature": 68.0 })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    // Only the reading sent with a valid token was stored
+    let (status, logs) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri(format!("/api/fermentation/{}/temperature", fermentation_id))
+            .header("Cookie", &cookie)
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(logs.as_array().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_configured_temperature_bounds() {
+    let mut app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "tempeh_maker@example.com").await;
+    let fermentation_id = common::create_test_fermentation(&app_state, &cookie, "Tempeh").await;
+
+    let reading = |body: serde_json::Value| {
+        common::authed_request(
+            "POST",
+            &format!("/api/fermentation/{}/temperature", fermentation_id),
+            &cookie,
+            Some(body),
+        )
+    };
+
+    // 170°F is above the default upper bound
+    let (status, _) = common::send_json(&app_state, reading(json!({ "temperature": 170.0 }))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    app_state.config = std::sync::Arc::new(raugupatis_log::config::AppConfig {
+        max_temperature_fahrenheit: 200.0,
+        ..(*app_state.config).clone()
+    });
+
+    let (status, log) =
+        common::send_json(&app_state, reading(json!({ "temperature": 170.0 }))).await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(log["temperature"], 170.0);
+
+    // The bounds are in Fahrenheit, so 90°C (194°F) fits and 95°C (203°F) doesn't
+    let (status, _) = common::send_json(
+        &app_state,
+        reading(json!({ "temperature": 90.0, "temp_unit": "celsius" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let (status, _) = common::send_json(
+        &app_state,
+        reading(json!({ "temperature": 95.0, "temp_unit": "celsius" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    // CSV imports use the same bounds
+    let (status, _) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri(format!(
+                "/api/fermentation/{}/temperature/import",
+                fermentation_id
+            ))
+            .method("POST")
+            .header("Content-Type", "text/csv")
+            .header("Cookie", &cookie)
+            .body(Body::from(
+                "2024-01-16T10:00:00Z,185,fahrenheit,Incubator\n",
+            ))
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+}
+
+#[tokio::test]
+async fn test_temperature_stats() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "temp_stats@example.com").await;
+    let fermentation_id =
+        common::create_test_fermentation(&app_state, &cookie, "Stats Ferment").await;
+
+    // No readings yet: counts are zero and aggregates are null
+    let (status, stats) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri(format!(
+                "/api/fermentation/{}/temperature/stats",
+                fermentation_id
+            ))
+            .header("Cookie", &cookie)
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(stats["count"], 0);
+    assert!(stats["mean"].is_null());
+    assert!(stats["std_dev"].is_null());
+
+    for (temperature, recorded_at) in [
+        (66.0, "2024-01-15T10:00:00Z"),
+        (70.0, "2024-01-15T16:00:00Z"),
+        (74.0, "2024-01-15T22:00:00Z"),
+    ] {
+        let (status, _) = common::send_json(
+            &app_state,
+            Request::builder()
+                .uri(format!("/api/fermentation/{}/temperature", fermentation_id))
+                .method("POST")
+                .header("Content-Type", "application/json")
+                .header("Cookie", &cookie)
+                .body(Body::from(
+                    json!({ "temperature": temperature, "recorded_at": recorded_at }).to_string(),
+                ))
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+    }
+
+    let (status, stats) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri(format!(
+                "/api/fermentation/{}/temperature/stats",
+                fermentation_id
+            ))
+            .header("Cookie", &cookie)
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(stats["count"], 3);
+    assert_eq!(stats["min"], 66.0);
+    assert_eq!(stats["max"], 74.0);
+    assert_eq!(stats["mean"], 70.0);
+    assert!((stats["std_dev"].as_f64().unwrap() - 3.266).abs() < 0.01);
+    assert_eq!(stats["span_hours"], 12.0);
+    assert_eq!(stats["temp_unit"], "fahrenheit");
+}
+
+#[tokio::test]
+async fn test_temperature_stats_other_user() {
+    let app_state = common::create_test_app_state().await;
+    let owner_cookie = common::register_and_login(&app_state, "stats_owner@example.com").await;
+    let other_cookie = common::register_and_login(&app_state, "stats_other@example.com").await;
+    let fermentation_id =
+        common::create_test_fermentation(&app_state, &owner_cookie, "Private Stats").await;
+
+    let (status, _) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri(format!(
+                "/api/fermentation/{}/temperature/stats",
+                fermentation_id
+            ))
+            .header("Cookie", &other_cookie)
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_clone_fermentation() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "cloner@example.com").await;
+
+    let (status, source) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri("/api/fermentation")
+            .method("POST")
+            .header("Content-Type", "application/json")
+            .header("Cookie", &cookie)
+            .body(Body::from(
+                json!({
+                    "profile_id": 1,
+                    "name": "House Kraut",
+                    "start_date": "2024-01-15T10:00:00Z",
+                    "target_end_date": "2024-02-15T10:00:00Z",
+                    "notes": "2% salt",
+                    "ingredients": [
+                        { "name": "cabbage", "quantity": 1.5, "unit": "kg" },
+                        { "name": "salt", "quantity": 30, "unit": "g" }
+                    ]
+                })
+                .to_string(),
+            ))
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let source_id = source["id"].as_i64().unwrap();
+
+    let (status, clone) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri(format!("/api/fermentation/{}/clone", source_id))
+            .method("POST")
+            .header("Cookie", &cookie)
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_ne!(clone["id"].as_i64().unwrap(), source_id);
+    assert_eq!(clone["name"], "House Kraut (copy)");
+    assert_eq!(clone["profile_id"], 1);
+    assert_eq!(clone["notes"], "2% salt");
+    assert_eq!(clone["status"], "active");
+    assert!(clone["target_end_date"].is_null());
+
+    let user_repo = raugupatis_log::users::UserRepository::new(app_state.db.clone());
+    let user = user_repo
+        .find_by_email("cloner@example.com")
+        .await
+        .unwrap()
+        .unwrap();
+    let fermentation_repo =
+        raugupatis_log::fermentation::FermentationRepository::new(app_state.db.clone());
+    let cloned = fermentation_repo
+        .find_by_id(clone["id"].as_i64().unwrap(), user.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(cloned.ingredients_text(), "1.5 kg cabbage\n30 g salt");
+    assert!(cloned.actual_end_date.is_none());
+    assert!(cloned.success_rating.is_none());
+}
+
+#[tokio::test]
+async fn test_clone_fermentation_other_user() {
+    let app_state = common::create_test_app_state().await;
+    let owner_cookie = common::register_and_login(&app_state, "owner@example.com").await;
+    let other_cookie = common::register_and_login(&app_state, "other@example.com").await;
+    let fermentation_id =
+        common::create_test_fermentation(&app_state, &owner_cookie, "Private Batch").await;
+
+    let (status, _) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri(format!("/api/fermentation/{}/clone", fermentation_id))
+            .method("POST")
+            .header("Cookie", &other_cookie)
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_create_fermentation_with_structured_ingredients() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "ingredients@example.com").await;
+
+    let (status, fermentation) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri("/api/fermentation")
+            .method("POST")
+            .header("Content-Type", "application/json")
+            .header("Cookie", &cookie)
+            .body(Body::from(
+                json!({
+                    "profile_id": 1,
+                    "name": "Kimchi",
+                    "start_date": "2024-01-15T10:00:00Z",
+                    "ingredients": [
+                        { "name": "napa cabbage", "quantity": 1, "unit": "head" },
+                        { "name": "gochugaru", "quantity": 3, "unit": "tbsp" },
+                        { "name": "scallions" }
+                    ]
+                })
+                .to_string(),
+            ))
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let fermentation_id = fermentation["id"].as_i64().unwrap();
+
+    let app = raugupatis_log::create_router(app_state.clone()).await;
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri(format!("/fermentation/{}", fermentation_id))
+                .header("Cookie", &cookie)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let html = String::from_utf8(body.to_vec()).unwrap();
+    assert!(html.contains("<td style=\"padding: 0.75rem;\">napa cabbage</td>"));
+    assert!(html.contains("3 tbsp"));
+    assert!(html.contains("scallions"));
+}
+
+#[tokio::test]
+async fn test_update_fermentation_clears_ingredients() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "clearer@example.com").await;
+    let fermentation_id = common::create_test_fermentation(&app_state, &cookie, "Sauerkraut").await;
+
+    let user_repo = raugupatis_log::users::UserRepository::new(app_state.db.clone());
+    let user = user_repo
+        .find_by_email("clearer@example.com")
+        .await
+        .unwrap()
+        .unwrap();
+    let fermentation_repo =
+        raugupatis_log::fermentation::FermentationRepository::new(app_state.db.clone());
+
+    let update_request = |body: serde_json::Value| {
+        common::authed_request(
+            "PUT",
+            &format!("/api/fermentation/{}", fermentation_id),
+            &cookie,
+            Some(body),
+        )
+    };
+
+    for cleared in [json!([]), json!(""), json!(null)] {
+        let (status, _) = common::send_json(
+            &app_state,
+            update_request(json!({ "ingredients": "1 kg cabbage\n20 g salt" })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+
+        // Leaving the field out keeps the ingredients
+        let (status, _) = common::send_json(
+            &app_state,
+            update_request(json!({ "notes": "Burping daily" })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        let fermentation = fermentation_repo
+            .find_by_id(fermentation_id, user.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(fermentation.ingredients().len(), 2);
+
+        // An empty list, empty text or null clears them
+        let (status, _) = common::send_json(
+            &app_state,
+            update_request(json!({ "ingredients": cleared })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        let fermentation = fermentation_repo
+            .find_by_id(fermentation_id, user.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(fermentation.ingredients_json.is_none());
+        assert!(fermentation.ingredients().is_empty());
+    }
+}
+
+#[tokio::test]
+async fn test_add_and_remove_tags() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "tagger@example.com").await;
+    let fermentation_id = common::create_test_fermentation(&app_state, &cookie, "Hot Sauce").await;
+
+    let tag_request = |method: &str, tag: &str| {
+        common::authed_request(
+            method,
+            &format!("/api/fermentation/{}/tags", fermentation_id),
+            &cookie,
+            Some(json!({ "tag": tag })),
+        )
+    };
+
+    let (status, tags) = common::send_json(&app_state, tag_request("POST", "Spicy")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(tags.as_array().unwrap().len(), 1);
+    assert_eq!(tags[0]["name"], "spicy");
+
+    // Adding the same tag again is a no-op
+    let (_, tags) = common::send_json(&app_state, tag_request("POST", "spicy ")).await;
+    assert_eq!(tags.as_array().unwrap().len(), 1);
+
+    let (_, tags) = common::send_json(&app_state, tag_request("POST", "gift")).await;
+    let names: Vec<&str> = tags
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|t| t["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(names, vec!["gift", "spicy"]);
+
+    let (status, tags) = common::send_json(&app_state, tag_request("DELETE", "spicy")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(tags.as_array().unwrap().len(), 1);
+    assert_eq!(tags[0]["name"], "gift");
+
+    let (status, _) = common::send_json(&app_state, tag_request("POST", "   ")).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_tags_other_user() {
+    let app_state = common::create_test_app_state().await;
+    let owner_cookie = common::register_and_login(&app_state, "owner@example.com").await;
+    let other_cookie = common::register_and_login(&app_state, "other@example.com").await;
+    let fermentation_id =
+        common::create_test_fermentation(&app_state, &owner_cookie, "Private Batch").await;
+
+    let (status, _) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri(format!("/api/fermentation/{}/tags", fermentation_id))
+            .method("POST")
+            .header("Content-Type", "application/json")
+            .header("Cookie", &other_cookie)
+            .body(Body::from(json!({ "tag": "stolen" }).to_string()))
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_list_fermentations_filtered_by_tag() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "filter@example.com").await;
+    let spicy_id = common::create_test_fermentation(&app_state, &cookie, "Habanero Mash").await;
+    common::create_test_fermentation(&app_state, &cookie, "Plain Kraut").await;
+
+    let (status, _) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri(format!("/api/fermentation/{}/tags", spicy_id))
+            .method("POST")
+            .header("Content-Type", "application/json")
+            .header("Cookie", &cookie)
+            .body(Body::from(json!({ "tag": "spicy" }).to_string()))
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, fermentations) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri("/api/fermentations?tag=spicy")
+            .header("Cookie", &cookie)
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let fermentations = fermentations.as_array().unwrap();
+    assert_eq!(fermentations.len(), 1);
+    assert_eq!(fermentations[0]["name"], "Habanero Mash");
+
+    let (_, fermentations) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri("/api/fermentations")
+            .header("Cookie", &cookie)
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(fermentations.as_array().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn test_archive_and_unarchive_fermentation() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "archivist@example.com").await;
+    let archived_id = common::create_test_fermentation(&app_state, &cookie, "Old Kraut").await;
+    common::create_test_fermentation(&app_state, &cookie, "Current Kraut").await;
+
+    // Complete the fermentation first so we can check its status is restored
+    let (status, _) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri(format!("/api/fermentation/{}/finish", archived_id))
+            .method("POST")
+            .header("Content-Type", "application/json")
+            .header("Cookie", &cookie)
+            .body(Body::from(
+                json!({ "success_rating": 4, "lessons_learned": "More salt" }).to_string(),
+            ))
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, fermentation) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri(format!("/api/fermentation/{}/archive", archived_id))
+            .method("POST")
+            .header("Cookie", &cookie)
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(fermentation["status"], "archived");
+
+    let list = |query: &str| {
+        common::authed_request(
+            "GET",
+            &format!("/api/fermentations{}", query),
+            &cookie,
+            None,
+        )
+    };
+
+    // Archived fermentations are hidden by default
+    let (_, fermentations) = common::send_json(&app_state, list("")).await;
+    let fermentations = fermentations.as_array().unwrap();
+    assert_eq!(fermentations.len(), 1);
+    assert_eq!(fermentations[0]["name"], "Current Kraut");
+
+    let (_, fermentations) = common::send_json(&app_state, list("?status=archived")).await;
+    let fermentations = fermentations.as_array().unwrap();
+    assert_eq!(fermentations.len(), 1);
+    assert_eq!(fermentations[0]["name"], "Old Kraut");
+    assert_eq!(fermentations[0]["lessons_learned"], "More salt");
+
+    let (_, fermentations) = common::send_json(&app_state, list("?include_archived=true")).await;
+    assert_eq!(fermentations.as_array().unwrap().len(), 2);
+
+    let (status, fermentation) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri(format!("/api/fermentation/{}/unarchive", archived_id))
+            .method("POST")
+            .header("Cookie", &cookie)
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(fermentation["status"], "completed");
+}
+
+#[tokio::test]
+async fn test_reopen_fermentation() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "hasty@example.com").await;
+    let other_cookie = common::register_and_login(&app_state, "other@example.com").await;
+    let fermentation_id =
+        common::create_test_fermentation(&app_state, &cookie, "Early Kraut").await;
+
+    let post = |path: &str, cookie: &str, body: serde_json::Value| {
+        common::authed_request(
+            "POST",
+            &format!("/api/fermentation/{}/{}", fermentation_id, path),
+            cookie,
+            Some(body),
+        )
+    };
+
+    // Only finished fermentations can be reopened
+    let (status, _) = common::send_json(&app_state, post("reopen", &cookie, json!({}))).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    let (status, _) = common::send_json(
+        &app_state,
+        post(
+            "finish",
+            &cookie,
+            json!({ "success_rating": 3, "taste_profile": "Not sour enough yet" }),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, _) = common::send_json(&app_state, post("reopen", &other_cookie, json!({}))).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, fermentation) =
+        common::send_json(&app_state, post("reopen", &cookie, json!({}))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(fermentation["status"], "active");
+
+    // The end date is cleared but the rating and taste profiles are kept
+    let (_, fermentations) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri("/api/fermentations")
+            .header("Cookie", &cookie)
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert!(fermentations[0]["actual_end_date"].is_null());
+    assert_eq!(fermentations[0]["success_rating"], 3);
+
+    let (_, taste_profiles) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri(format!(
+                "/api/fermentation/{}/taste-profiles",
+                fermentation_id
+            ))
+            .header("Cookie", &cookie)
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(taste_profiles.as_array().unwrap().len(), 1);
+
+    // Archived fermentations must be unarchived before they can be reopened
+    let (status, _) = common::send_json(&app_state, post("archive", &cookie, json!({}))).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = common::send_json(&app_state, post("reopen", &cookie, json!({}))).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+}
+
+#[tokio::test]
+async fn test_archive_fermentation_other_user() {
+    let app_state = common::create_test_app_state().await;
+    let owner_cookie = common::register_and_login(&app_state, "owner@example.com").await;
+    let other_cookie = common::register_and_login(&app_state, "other@example.com").await;
+    let fermentation_id =
+        common::create_test_fermentation(&app_state, &owner_cookie, "Private Batch").await;
+
+    let (status, _) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri(format!("/api/fermentation/{}/archive", fermentation_id))
+            .method("POST")
+            .header("Cookie", &other_cookie)
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_favorite_fermentations() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "starry@example.com").await;
+    let other_cookie = common::register_and_login(&app_state, "other@example.com").await;
+    let favorite_id = common::create_test_fermentation(&app_state, &cookie, "Best Kimchi").await;
+    common::create_test_fermentation(&app_state, &cookie, "Plain Kraut").await;
+
+    let star = |id: i64, action: &str, cookie: &str| {
+        common::authed_request(
+            "POST",
+            &format!("/api/fermentation/{}/{}", id, action),
+            cookie,
+            None,
+        )
+    };
+    let list = |query: &str| {
+        common::authed_request(
+            "GET",
+            &format!("/api/fermentations{}", query),
+            &cookie,
+            None,
+        )
+    };
+
+    let (status, fermentation) =
+        common::send_json(&app_state, star(favorite_id, "favorite", &cookie)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(fermentation["is_favorite"], true);
+
+    // Starring twice is harmless
+    let (status, _) = common::send_json(&app_state, star(favorite_id, "favorite", &cookie)).await;
+    assert_eq!(status, StatusCode::OK);
+
+    // Other users can't star someone else's fermentation
+    let (status, _) =
+        common::send_json(&app_state, star(favorite_id, "favorite", &other_cookie)).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (_, fermentations) = common::send_json(&app_state, list("?favorites_only=true")).await;
+    let fermentations = fermentations.as_array().unwrap();
+    assert_eq!(fermentations.len(), 1);
+    assert_eq!(fermentations[0]["name"], "Best Kimchi");
+
+    let (_, fermentations) = common::send_json(&app_state, list("")).await;
+    assert_eq!(fermentations.as_array().unwrap().len(), 2);
+
+    let (status, fermentation) =
+        common::send_json(&app_state, star(favorite_id, "unfavorite", &cookie)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(fermentation["is_favorite"], false);
+
+    let (_, fermentations) = common::send_json(&app_state, list("?favorites_only=true")).await;
+    assert!(fermentations.as_array().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_fermentation_response_reports_overdue() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "forgetful@example.com").await;
+
+    let (status, fermentation) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri("/api/fermentation")
+            .method("POST")
+            .header("Content-Type", "application/json")
+            .header("Cookie", &cookie)
+            .body(Body::from(
+                json!({
+                    "profile_id": 1,
+                    "name": "Forgotten Pickles",
+                    "start_date": "2024-01-15T10:00:00Z",
+                    "target_end_date": "2024-01-22T10:00:00Z"
+                })
+                .to_string(),
+            ))
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(fermentation["is_overdue"], true);
+    assert!(fermentation["days_remaining"].as_i64().unwrap() < 0);
+
+    // The API suggests a target date, so create an open-ended fermentation directly
+    let user = raugupatis_log::users::UserRepository::new(app_state.db.clone())
+        .find_by_email("forgetful@example.com")
+        .await
+        .unwrap()
+        .unwrap();
+    let fermentation_id =
+        raugupatis_log::fermentation::FermentationRepository::new(app_state.db.clone())
+            .create_fermentation(
+                user.id,
+                raugupatis_log::fermentation::models::CreateFermentationRequest {
+                    profile_id: 1,
+                    name: "Open Ended".to_string(),
+                    start_date: "2024-01-15T10:00:00Z".to_string(),
+                    target_end_date: None,
+                    notes: None,
+                    ingredients: None,
+                },
+            )
+            .await
+            .unwrap()
+            .id;
+    let (status, fermentation) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri(format!("/api/fermentation/{}", fermentation_id))
+            .method("PUT")
+            .header("Content-Type", "application/json")
+            .header("Cookie", &cookie)
+            .body(Body::from(json!({ "notes": "No target date" }).to_string()))
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(fermentation["is_overdue"], false);
+    assert!(fermentation["days_remaining"].is_null());
+}
+
+#[tokio::test]
+async fn test_list_all_taste_profiles() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "taster@example.com").await;
+    let other_cookie = common::register_and_login(&app_state, "other@example.com").await;
+    let kimchi_id = common::create_test_fermentation(&app_state, &cookie, "Kimchi").await;
+    let kraut_id = common::create_test_fermentation(&app_state, &cookie, "Sauerkraut").await;
+    let other_id = common::create_test_fermentation(&app_state, &other_cookie, "Not Mine").await;
+
+    for (cookie, fermentation_id, text, tasted_at) in [
+        (
+            &cookie,
+            kimchi_id,
+            "Strong garlic flavor",
+            "2024-01-20T10:00:00Z",
+        ),
+        (
+            &cookie,
+            kraut_id,
+            "Mild and crunchy",
+            "2024-01-25T10:00:00Z",
+        ),
+        (
+            &other_cookie,
+            other_id,
+            "Garlic everywhere",
+            "2024-01-22T10:00:00Z",
+        ),
+    ] {
+        let (status, _) = common::send_json(
+            &app_state,
+            Request::builder()
+                .uri(format!(
+                    "/api/fermentation/{}/taste-profiles",
+                    fermentation_id
+                ))
+                .method("POST")
+                .header("Content-Type", "application/json")
+                .header("Cookie", cookie)
+                .body(Body::from(
+                    json!({ "profile_text": text, "tasted_at": tasted_at }).to_string(),
+                ))
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+    }
+
+    let (status, profiles) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri("/api/taste-profiles")
+            .header("Cookie", &cookie)
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let profiles = profiles.as_array().unwrap();
+    assert_eq!(profiles.len(), 2);
+    assert_eq!(profiles[0]["fermentation_name"], "Sauerkraut");
+    assert_eq!(profiles[1]["fermentation_name"], "Kimchi");
+
+    let (_, profiles) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri("/api/taste-profiles?search=garlic")
+            .header("Cookie", &cookie)
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    let profiles = profiles.as_array().unwrap();
+    assert_eq!(profiles.len(), 1);
+    assert_eq!(profiles[0]["profile_text"], "Strong garlic flavor");
+    assert_eq!(profiles[0]["fermentation_id"], kimchi_id);
+}
+
+#[tokio::test]
+async fn test_global_search() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "searcher@example.com").await;
+    let other_cookie = common::register_and_login(&app_state, "other@example.com").await;
+
+    let (status, _) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri("/api/fermentation")
+            .method("POST")
+            .header("Content-Type", "application/json")
+            .header("Cookie", &cookie)
+            .body(Body::from(
+                json!({
+                    "profile_id": 1,
+                    "name": "Garlic Honey",
+                    "start_date": "2024-01-15T10:00:00Z",
+                    "notes": "Burp daily"
+                })
+                .to_string(),
+            ))
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let kraut_id = common::create_test_fermentation(&app_state, &cookie, "Sauerkraut").await;
+    let (status, _) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri(format!("/api/fermentation/{}/temperature", kraut_id))
+            .method("POST")
+            .header("Content-Type", "application/json")
+            .header("Cookie", &cookie)
+            .body(Body::from(
+                json!({ "temperature": 68.0, "notes": "Spotted mold on the surface" }).to_string(),
+            ))
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let (status, _) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri(format!("/api/fermentation/{}/taste-profiles", kraut_id))
+            .method("POST")
+            .header("Content-Type", "application/json")
+            .header("Cookie", &cookie)
+            .body(Body::from(
+                json!({ "profile_text": "Sour, no trace of mold flavor" }).to_string(),
+            ))
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    // Another user's data never shows up
+    common::create_test_fermentation(&app_state, &other_cookie, "Moldy Experiment").await;
+
+    let search =
+        |q: &str| common::authed_request("GET", &format!("/api/search?q={}", q), &cookie, None);
+
+    let (status, results) = common::send_json(&app_state, search("mold")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(results["query"], "mold");
+    assert_eq!(results["fermentations"].as_array().unwrap().len(), 0);
+    assert_eq!(results["temperature_logs"].as_array().unwrap().len(), 1);
+    assert_eq!(
+        results["temperature_logs"][0]["fermentation_name"],
+        "Sauerkraut"
+    );
+    assert_eq!(results["taste_profiles"].as_array().unwrap().len(), 1);
+
+    let (_, results) = common::send_json(&app_state, search("burp")).await;
+    let fermentations = results["fermentations"].as_array().unwrap();
+    assert_eq!(fermentations.len(), 1);
+    assert_eq!(fermentations[0]["name"], "Garlic Honey");
+    assert_eq!(fermentations[0]["matched_fields"], json!(["notes"]));
+
+    let (status, _) = common::send_json(&app_state, search("")).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_list_fermentations_by_start_date_range() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "calendar@example.com").await;
+
+    for (name, start_date) in [
+        ("February Kraut", "2024-02-10T10:00:00Z"),
+        ("March Kimchi", "2024-03-05T10:00:00Z"),
+        ("March Pickles", "2024-03-28T10:00:00Z"),
+        ("April Miso", "2024-04-02T10:00:00Z"),
+    ] {
+        let (status, _) = common::send_json(
+            &app_state,
+            Request::builder()
+                .uri("/api/fermentation")
+                .method("POST")
+                .header("Content-Type", "application/json")
+                .header("Cookie", &cookie)
+                .body(Body::from(
+                    json!({ "profile_id": 1, "name": name, "start_date": start_date }).to_string(),
+                ))
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+    }
+
+    let list = |query: &str| {
+        common::authed_request(
+            "GET",
+            &format!("/api/fermentations?{}", query),
+            &cookie,
+            None,
+        )
+    };
+
+    let (status, fermentations) = common::send_json(
+        &app_state,
+        list("started_after=2024-03-01T00:00:00Z&started_before=2024-03-31T23:59:59Z&sort_by=start_date&sort_order=asc"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let names: Vec<&str> = fermentations
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|f| f["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(names, vec!["March Kimchi", "March Pickles"]);
+
+    let (_, fermentations) =
+        common::send_json(&app_state, list("started_after=2024-03-20T00:00:00Z")).await;
+    assert_eq!(fermentations.as_array().unwrap().len(), 2);
+
+    let (status, _) = common::send_json(&app_state, list("started_after=last-march")).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, _) = common::send_json(
+        &app_state,
+        list("started_after=2024-04-01T00:00:00Z&started_before=2024-03-01T00:00:00Z"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_dashboard_stats() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "stats@example.com").await;
+
+    let stats_request = || common::authed_request("GET", "/api/dashboard/stats", &cookie, None);
+
+    // A new user starts with empty stats
+    let (status, stats) = common::send_json(&app_state, stats_request()).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(stats["active_fermentations"], 0);
+    assert_eq!(stats["completed_fermentations"], 0);
+    assert!(stats["average_success_rating"].is_null());
+    assert_eq!(stats["total_temperature_readings"], 0);
+    assert!(stats["most_used_profile_type"].is_null());
+
+    // Two vegetable fermentations (profile 1) and one beverage (profile 2)
+    let pickles = common::create_test_fermentation(&app_state, &cookie, "Pickles").await;
+    let kraut = common::create_test_fermentation(&app_state, &cookie, "Kraut").await;
+    let (status, _) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri("/api/fermentation")
+            .method("POST")
+            .header("Content-Type", "application/json")
+            .header("Cookie", &cookie)
+            .body(Body::from(
+                json!({
+                    "profile_id": 2,
+                    "name": "Kombucha",
+                    "start_date": "2024-01-15T10:00:00Z"
+                })
+                .to_string(),
+            ))
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    for temperature in [68.0, 70.0, 72.0] {
+        let (status, _) = common::send_json(
+            &app_state,
+            Request::builder()
+                .uri(format!("/api/fermentation/{}/temperature", pickles))
+                .method("POST")
+                .header("Content-Type", "application/json")
+                .header("Cookie", &cookie)
+                .body(Body::from(
+                    json!({ "temperature": temperature }).to_string(),
+                ))
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+    }
+
+    for (id, rating) in [(pickles, 5), (kraut, 2)] {
+        let (status, _) = common::send_json(
+            &app_state,
+            Request::builder()
+                .uri(format!("/api/fermentation/{}/finish", id))
+                .method("POST")
+                .header("Content-Type", "application/json")
+                .header("Cookie", &cookie)
+                .body(Body::from(json!({ "success_rating": rating }).to_string()))
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    // Another user's data must not be counted
+    let other_cookie = common::register_and_login(&app_state, "other-stats@example.com").await;
+    common::create_test_fermentation(&app_state, &other_cookie, "Other").await;
+
+    let (status, stats) = common::send_json(&app_state, stats_request()).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(stats["active_fermentations"], 1);
+    assert_eq!(stats["completed_fermentations"], 2);
+    assert_eq!(stats["average_success_rating"].as_f64(), Some(3.5));
+    assert_eq!(stats["total_temperature_readings"], 3);
+    assert_eq!(stats["most_used_profile_type"], "vegetable");
+}
+
+#[tokio::test]
+async fn test_dashboard_stats_requires_authentication() {
+    let app_state = common::create_test_app_state().await;
+
+    let (status, _) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri("/api/dashboard/stats")
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn test_dashboard_profile_breakdown() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "categorizer@example.com").await;
+    let other_cookie = common::register_and_login(&app_state, "other@example.com").await;
+    common::create_test_fermentation(&app_state, &cookie, "Dill Pickles").await;
+    common::create_test_fermentation(&app_state, &cookie, "Garlic Pickles").await;
+    common::create_test_fermentation(&app_state, &other_cookie, "Not Mine").await;
+
+    // Profile 2 is Kombucha, a beverage
+    let (status, _) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri("/api/fermentation")
+            .method("POST")
+            .header("Content-Type", "application/json")
+            .header("Cookie", &cookie)
+            .body(Body::from(
+                json!({
+                    "profile_id": 2,
+                    "name": "Ginger Kombucha",
+                    "start_date": "2024-01-15T10:00:00Z"
+                })
+                .to_string(),
+            ))
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let breakdown_request = |cookie: &str| {
+        common::authed_request("GET", "/api/dashboard/profile-breakdown", cookie, None)
+    };
+
+    let (status, breakdown) = common::send_json(&app_state, breakdown_request(&cookie)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(breakdown, json!({ "vegetable": 2, "beverage": 1 }));
+
+    let (status, _) = common::send_json(&app_state, breakdown_request("")).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn test_dashboard_rating_distribution() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "improver@example.com").await;
+    let other_cookie = common::register_and_login(&app_state, "other@example.com").await;
+
+    let finish = |id: i64, cookie: &str, body: serde_json::Value| {
+        common::authed_request(
+            "POST",
+            &format!("/api/fermentation/{}/finish", id),
+            cookie,
+            Some(body),
+        )
+    };
+
+    for (name, rating) in [("First Try", 2), ("Better", 4), ("Also Better", 4)] {
+        let id = common::create_test_fermentation(&app_state, &cookie, name).await;
+        let (status, _) = common::send_json(
+            &app_state,
+            finish(id, &cookie, json!({ "success_rating": rating })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    let id = common::create_test_fermentation(&app_state, &cookie, "No Opinion").await;
+    let (status, _) = common::send_json(&app_state, finish(id, &cookie, json!({}))).await;
+    assert_eq!(status, StatusCode::OK);
+
+    // Active fermentations and other users' batches aren't counted
+    common::create_test_fermentation(&app_state, &cookie, "Still Going").await;
+    let id = common::create_test_fermentation(&app_state, &other_cookie, "Not Mine").await;
+    let (status, _) = common::send_json(
+        &app_state,
+        finish(id, &other_cookie, json!({ "success_rating": 5 })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let ratings_request =
+        |cookie: &str| common::authed_request("GET", "/api/dashboard/ratings", cookie, None);
+
+    let (status, distribution) = common::send_json(&app_state, ratings_request(&cookie)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        distribution,
+        json!({
+            "ratings": { "1": 0, "2": 1, "3": 0, "4": 2, "5": 0 },
+            "unrated": 1
+        })
+    );
+
+    let (status, _) = common::send_json(&app_state, ratings_request("")).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn test_dashboard_profile_success() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "recipes@example.com").await;
+    let other_cookie = common::register_and_login(&app_state, "other@example.com").await;
+
+    let create = |profile_id: i64, name: &str, cookie: &str| {
+        Request::builder()
+            .uri("/api/fermentation")
+            .method("POST")
+            .header("Content-Type", "application/json")
+            .header("Cookie", cookie)
+            .body(Body::from(
+                json!({
+                    "profile_id": profile_id,
+                    "name": name,
+                    "start_date": "2024-01-15T10:00:00Z"
+                })
+                .to_string(),
+            ))
+            .unwrap()
+    };
+    let finish = |id: i64, cookie: &str, body: serde_json::Value| {
+        common::authed_request(
+            "POST",
+            &format!("/api/fermentation/{}/finish", id),
+            cookie,
+            Some(body),
+        )
+    };
+
+    // Profile 1 is Pickles, profile 2 is Kombucha
+    for (profile_id, name, rating, cookie) in [
+        (1, "Dill Pickles", json!(5), &cookie),
+        (1, "Garlic Pickles", json!(4), &cookie),
+        (1, "Unrated Pickles", json!(null), &cookie),
+        (2, "Flat Kombucha", json!(1), &cookie),
+        (2, "Someone Else's Kombucha", json!(5), &other_cookie),
+    ] {
+        let (status, fermentation) =
+            common::send_json(&app_state, create(profile_id, name, cookie)).await;
+        assert_eq!(status, StatusCode::CREATED);
+        let id = fermentation["id"].as_i64().unwrap();
+        let (status, _) = common::send_json(
+            &app_state,
+            finish(id, cookie, json!({ "success_rating": rating })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    // Still running, so not part of the averages
+    common::create_test_fermentation(&app_state, &cookie, "Fresh Pickles").await;
+
+    let success_request = |cookie: &str| {
+        common::authed_request("GET", "/api/dashboard/profile-success", cookie, None)
+    };
+
+    let (status, profiles) = common::send_json(&app_state, success_request(&cookie)).await;
+    assert_eq!(status, StatusCode::OK);
+    let profiles = profiles.as_array().unwrap();
+    assert_eq!(profiles.len(), 2);
+
+    let kombucha = profiles
+        .iter()
+        .find(|p| p["profile_id"] == 2)
+        .expect("kombucha is listed");
+    assert_eq!(kombucha["completed_fermentations"], 1);
+    assert_eq!(kombucha["average_success_rating"], 1.0);
+
+    // Unrated batches count as completed but don't drag the average down
+    let pickles = profiles
+        .iter()
+        .find(|p| p["profile_id"] == 1)
+        .expect("pickles are listed");
+    assert_eq!(pickles["completed_fermentations"], 3);
+    assert_eq!(pickles["average_success_rating"], 4.5);
+
+    let (status, _) = common::send_json(&app_state, success_request("")).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn test_temperatures_displayed_in_preferred_unit() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "metric@example.com").await;
+    let fermentation_id =
+        common::create_test_fermentation(&app_state, &cookie, "Metric Pickles").await;
+
+    let (status, _) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri(format!("/api/fermentation/{}/temperature", fermentation_id))
+            .method("POST")
+            .header("Content-Type", "application/json")
+            .header("Cookie", &cookie)
+            .body(Body::from(json!({ "temperature": 68.0 }).to_string()))
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let list_request = || {
+        common::authed_request(
+            "GET",
+            &format!("/api/fermentation/{}/temperature", fermentation_id),
+            &cookie,
+            None,
+        )
+    };
+
+    // Fahrenheit is the default preference
+    let (status, logs) = common::send_json(&app_state, list_request()).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(logs[0]["temperature"].as_f64().unwrap(), 68.0);
+    assert_eq!(logs[0]["temp_unit"], "fahrenheit");
+    assert_eq!(logs[0]["unit_symbol"], "°F");
+
+    // Switch the user to Celsius
+    let (status, _) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri("/api/users/profile")
+            .method("POST")
+            .header("Content-Type", "application/json")
+            .header("Cookie", &cookie)
+            .body(Body::from(
+                json!({
+                    "experience_level": "beginner",
+                    "preferred_temp_unit": "celsius"
+                })
+                .to_string(),
+            ))
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, logs) = common::send_json(&app_state, list_request()).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!((logs[0]["temperature"].as_f64().unwrap() - 20.0).abs() < 0.01);
+    assert_eq!(logs[0]["temp_unit"], "celsius");
+    assert_eq!(logs[0]["unit_symbol"], "°C");
+
+    // The detail page shows readings and the profile range in Celsius (Pickles: 65-75°F)
+    let app = raugupatis_log::create_router(app_state.clone()).await;
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri(format!("/fermentation/{}", fermentation_id))
+                .header("Cookie", &cookie)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let html = String::from_utf8(body.to_vec()).unwrap();
+    assert!(html.contains("20°C"));
+    assert!(html.contains("18.3°C – 23.9°C"));
+}
+
+#[tokio::test]
+async fn test_create_and_list_ph_logs() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "ph@example.com").await;
+    let fermentation_id = common::create_test_fermentation(&app_state, &cookie, "Sauerkraut").await;
+
+    let ph_request = |body: serde_json::Value| {
+        common::authed_request(
+            "POST",
+            &format!("/api/fermentation/{}/ph", fermentation_id),
+            &cookie,
+            Some(body),
+        )
+    };
+
+    let (status, log) = common::send_json(
+        &app_state,
+        ph_request(json!({
+            "ph": 5.8,
+            "recorded_at": "2024-01-15T12:00:00Z",
+            "notes": "Day one"
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(log["fermentation_id"], fermentation_id);
+    assert_eq!(log["ph"].as_f64().unwrap(), 5.8);
+    assert_eq!(log["notes"], "Day one");
+
+    let (status, _) = common::send_json(
+        &app_state,
+        ph_request(json!({ "ph": 3.4, "recorded_at": "2024-01-20T12:00:00Z" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let (status, logs) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri(format!("/api/fermentation/{}/ph", fermentation_id))
+            .header("Cookie", &cookie)
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let logs = logs.as_array().unwrap();
+    assert_eq!(logs.len(), 2);
+    // Most recent reading first
+    assert_eq!(logs[0]["ph"].as_f64().unwrap(), 3.4);
+    assert_eq!(logs[1]["ph"].as_f64().unwrap(), 5.8);
+}
+
+#[tokio::test]
+async fn test_create_ph_log_validation() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "ph-invalid@example.com").await;
+    let fermentation_id = common::create_test_fermentation(&app_state, &cookie, "Kimchi").await;
+
+    for body in [
+        json!({ "ph": -0.5 }),
+        json!({ "ph": 14.1 }),
+        json!({ "ph": 4.0, "recorded_at": "yesterday" }),
+    ] {
+        let (status, _) = common::send_json(
+            &app_state,
+            Request::builder()
+                .uri(format!("/api/fermentation/{}/ph", fermentation_id))
+                .method("POST")
+                .header("Content-Type", "application/json")
+                .header("Cookie", &cookie)
+                .body(Body::from(body.to_string()))
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+}
+
+#[tokio::test]
+async fn test_ph_logs_scoped_to_owner() {
+    let app_state = common::create_test_app_state().await;
+    let owner_cookie = common::register_and_login(&app_state, "ph-owner@example.com").await;
+    let other_cookie = common::register_and_login(&app_state, "ph-other@example.com").await;
+    let fermentation_id =
+        common::create_test_fermentation(&app_state, &owner_cookie, "Kombucha").await;
+
+    let (status, _) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri(format!("/api/fermentation/{}/ph", fermentation_id))
+            .method("POST")
+            .header("Content-Type", "application/json")
+            .header("Cookie", &other_cookie)
+            .body(Body::from(json!({ "ph": 3.0 }).to_string()))
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, _) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri(format!("/api/fermentation/{}/ph", fermentation_id))
+            .header("Cookie", &other_cookie)
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, _) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri(format!("/api/fermentation/{}/ph", fermentation_id))
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn test_gravity_logs_and_stats() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "brewer@example.com").await;
+    let fermentation_id = common::create_test_fermentation(&app_state, &cookie, "Kombucha").await;
+
+    let gravity_request = |body: serde_json::Value| {
+        common::authed_request(
+            "POST",
+            &format!("/api/fermentation/{}/gravity", fermentation_id),
+            &cookie,
+            Some(body),
+        )
+    };
+    let get_request = |path: &str| {
+        common::authed_request(
+            "GET",
+            &format!("/api/fermentation/{}/{}", fermentation_id, path),
+            &cookie,
+            None,
+        )
+    };
+
+    // No readings yet
+    let (status, stats) = common::send_json(&app_state, get_request("gravity/stats")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(stats["count"], 0);
+    assert!(stats["estimated_abv"].is_null());
+
+    for (gravity, recorded_at) in [
+        (1.050, "2024-01-15T10:00:00Z"),
+        (1.030, "2024-01-18T10:00:00Z"),
+        (1.010, "2024-01-22T10:00:00Z"),
+    ] {
+        let (status, log) = common::send_json(
+            &app_state,
+            gravity_request(json!({ "gravity": gravity, "recorded_at": recorded_at })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(log["gravity"].as_f64().unwrap(), gravity);
+    }
+
+    let (status, logs) = common::send_json(&app_state, get_request("gravity")).await;
+    assert_eq!(status, StatusCode::OK);
+    let logs = logs.as_array().unwrap();
+    assert_eq!(logs.len(), 3);
+    assert_eq!(logs[0]["gravity"].as_f64().unwrap(), 1.010);
+
+    let (status, stats) = common::send_json(&app_state, get_request("gravity/stats")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(stats["count"], 3);
+    assert_eq!(stats["original_gravity"].as_f64().unwrap(), 1.050);
+    assert_eq!(stats["latest_gravity"].as_f64().unwrap(), 1.010);
+    assert_eq!(stats["estimated_abv"].as_f64().unwrap(), 5.25);
+
+    // Implausible readings are rejected
+    for gravity in [0.95, 1.25] {
+        let (status, _) =
+            common::send_json(&app_state, gravity_request(json!({ "gravity": gravity }))).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+}
+
+#[tokio::test]
+async fn test_gravity_logs_scoped_to_owner() {
+    let app_state = common::create_test_app_state().await;
+    let owner_cookie = common::register_and_login(&app_state, "gravity-owner@example.com").await;
+    let other_cookie = common::register_and_login(&app_state, "gravity-other@example.com").await;
+    let fermentation_id = common::create_test_fermentation(&app_state, &owner_cookie, "Mead").await;
+
+    for (method, path, body) in [
+        (
+            "POST",
+            "gravity",
+            Body::from(json!({ "gravity": 1.1 }).to_string()),
+        ),
+        ("GET", "gravity", Body::empty()),
+        ("GET", "gravity/stats", Body::empty()),
+    ] {
+        let (status, _) = common::send_json(
+            &app_state,
+            Request::builder()
+                .uri(format!("/api/fermentation/{}/{}", fermentation_id, path))
+                .method(method)
+                .header("Content-Type", "application/json")
+                .header("Cookie", &other_cookie)
+                .body(body)
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+}
+
+#[tokio::test]
+async fn test_journal_entries_lifecycle() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "diarist@example.com").await;
+    let fermentation_id = common::create_test_fermentation(&app_state, &cookie, "Sauerkraut").await;
+
+    let journal_request = |body: serde_json::Value| {
+        common::authed_request(
+            "POST",
+            &format!("/api/fermentation/{}/journal", fermentation_id),
+            &cookie,
+            Some(body),
+        )
+    };
+    let list_request = || {
+        common::authed_request(
+            "GET",
+            &format!("/api/fermentation/{}/journal", fermentation_id),
+            &cookie,
+            None,
+        )
+    };
+
+    let (status, later) = common::send_json(
+        &app_state,
+        journal_request(json!({
+            "body": "Brine turned cloudy, lots of bubbles",
+            "entry_date": "2024-01-18T09:00:00Z"
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(later["body"], "Brine turned cloudy, lots of bubbles");
+
+    let (status, _) = common::send_json(
+        &app_state,
+        journal_request(json!({
+            "body": "  Packed the jar tightly  ",
+            "entry_date": "2024-01-15T11:00:00Z"
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    // Entries are returned chronologically and trimmed
+    let (status, entries) = common::send_json(&app_state, list_request()).await;
+    assert_eq!(status, StatusCode::OK);
+    let entries = entries.as_array().unwrap();
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0]["body"], "Packed the jar tightly");
+    assert_eq!(entries[1]["body"], "Brine turned cloudy, lots of bubbles");
+
+    // Entries appear in the detail page timeline
+    let app = raugupatis_log::create_router(app_state.clone()).await;
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri(format!("/fermentation/{}", fermentation_id))
+                .header("Cookie", &cookie)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let html = String::from_utf8(body.to_vec()).unwrap();
+    let first = html.find("Packed the jar tightly").unwrap();
+    let second = html.find("Brine turned cloudy, lots of bubbles").unwrap();
+    assert!(first < second);
+
+    // Delete one entry
+    let (status, _) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri(format!(
+                "/api/fermentation/{}/journal/{}",
+                fermentation_id, later["id"]
+            ))
+            .method("DELETE")
+            .header("Cookie", &cookie)
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    let (_, entries) = common::send_json(&app_state, list_request()).await;
+    assert_eq!(entries.as_array().unwrap().len(), 1);
+
+    let (status, _) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri(format!(
+                "/api/fermentation/{}/journal/{}",
+                fermentation_id, later["id"]
+            ))
+            .method("DELETE")
+            .header("Cookie", &cookie)
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_journal_entry_validation_and_ownership() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "journal-owner@example.com").await;
+    let other_cookie = common::register_and_login(&app_state, "journal-other@example.com").await;
+    let fermentation_id = common::create_test_fermentation(&app_state, &cookie, "Miso").await;
+
+    for body in [
+        json!({ "body": "   " }),
+        json!({ "body": "x".repeat(5001) }),
+        json!({ "body": "Looks good", "entry_date": "tuesday" }),
+    ] {
+        let (status, _) = common::send_json(
+            &app_state,
+            Request::builder()
+                .uri(format!("/api/fermentation/{}/journal", fermentation_id))
+                .method("POST")
+                .header("Content-Type", "application/json")
+                .header("Cookie", &cookie)
+                .body(Body::from(body.to_string()))
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    let (status, _) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri(format!("/api/fermentation/{}/journal", fermentation_id))
+            .method("POST")
+            .header("Content-Type", "application/json")
+            .header("Cookie", &other_cookie)
+            .body(Body::from(json!({ "body": "Not mine" }).to_string()))
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_fermentation_timeline_merges_events() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "timeline@example.com").await;
+    let fermentation_id = common::create_test_fermentation(&app_state, &cookie, "Kimchi").await;
+
+    let post = |path: &str, body: serde_json::Value| {
+        common::authed_request(
+            "POST",
+            &format!("/api/fermentation/{}/{}", fermentation_id, path),
+            &cookie,
+            Some(body),
+        )
+    };
+
+    let (status, _) = common::send_json(
+        &app_state,
+        post(
+            "temperature",
+            json!({ "temperature": 70.0, "recorded_at": "2024-01-16T08:00:00Z" }),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let (status, _) = common::send_json(
+        &app_state,
+        post(
+            "journal",
+            json!({ "body": "Started bubbling", "entry_date": "2024-01-17T08:00:00Z" }),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let (status, _) = common::send_json(
+        &app_state,
+        post(
+            "journal",
+            json!({ "body": "Packed into jars", "entry_date": "2024-01-15T12:00:00Z" }),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let (status, events) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri(format!("/api/fermentation/{}/timeline", fermentation_id))
+            .header("Cookie", &cookie)
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let events = events.as_array().unwrap();
+    let types: Vec<&str> = events.iter().map(|e| e["type"].as_str().unwrap()).collect();
+    assert_eq!(
+        types,
+        vec!["journal_entry", "temperature_log", "journal_entry"]
+    );
+    assert_eq!(events[0]["body"], "Packed into jars");
+    assert_eq!(events[1]["temperature"].as_f64().unwrap(), 70.0);
+    assert_eq!(events[1]["unit_symbol"], "°F");
+    assert_eq!(events[2]["body"], "Started bubbling");
+}
+
+#[tokio::test]
+async fn test_fermentation_timeline_scoped_to_owner() {
+    let app_state = common::create_test_app_state().await;
+    let owner_cookie = common::register_and_login(&app_state, "timeline-owner@example.com").await;
+    let other_cookie = common::register_and_login(&app_state, "timeline-other@example.com").await;
+    let fermentation_id =
+        common::create_test_fermentation(&app_state, &owner_cookie, "Kefir").await;
+
+    let (status, events) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri(format!("/api/fermentation/{}/timeline", fermentation_id))
+            .header("Cookie", &owner_cookie)
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(events.as_array().unwrap().is_empty());
+
+    let (status, _) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri(format!("/api/fermentation/{}/timeline", fermentation_id))
+            .header("Cookie", &other_cookie)
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_create_fermentation_suggests_target_end_date() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "planner@example.com").await;
+
+    let create_request = |body: serde_json::Value| {
+        common::authed_request("POST", "/api/fermentation", &cookie, Some(body))
+    };
+
+    // Pickles run for at most 7 days
+    let (status, fermentation) = common::send_json(
+        &app_state,
+        create_request(json!({
+            "profile_id": 1,
+            "name": "Suggested Pickles",
+            "start_date": "2024-01-15T10:00:00Z"
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(fermentation["target_end_date"], "2024-01-22T10:00:00Z");
+    assert_eq!(fermentation["target_end_date_suggested"], true);
+
+    let (status, fermentation) = common::send_json(
+        &app_state,
+        create_request(json!({
+            "profile_id": 1,
+            "name": "Planned Pickles",
+            "start_date": "2024-01-15T10:00:00Z",
+            "target_end_date": "2024-01-20T10:00:00Z"
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(fermentation["target_end_date"], "2024-01-20T10:00:00Z");
+    assert_eq!(fermentation["target_end_date_suggested"], false);
+}
+
+#[tokio::test]
+async fn test_create_fermentation_with_idempotency_key() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "flaky@example.com").await;
+    let other_cookie = common::register_and_login(&app_state, "other@example.com").await;
+
+    let create_request = |cookie: &str, key: &str| {
+        Request::builder()
+            .uri("/api/fermentation")
+            .method("POST")
+            .header("Content-Type", "application/json")
+            .header("Cookie", cookie)
+            .header("Idempotency-Key", key)
+            .body(Body::from(
+                json!({
+                    "profile_id": 1,
+                    "name": "Retried Pickles",
+                    "start_date": "2024-01-15T10:00:00Z"
+                })
+                .to_string(),
+            ))
+            .unwrap()
+    };
+
+    let (status, first) = common::send_json(&app_state, create_request(&cookie, "abc-123")).await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    // Replaying the key returns the original fermentation instead of creating another
+    let (status, replay) = common::send_json(&app_state, create_request(&cookie, "abc-123")).await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(replay["id"], first["id"]);
+    assert_eq!(first["target_end_date_suggested"], true);
+    assert_eq!(replay["target_end_date_suggested"], true);
+
+    // Keys are scoped per user
+    let (status, other) =
+        common::send_json(&app_state, create_request(&other_cookie, "abc-123")).await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_ne!(other["id"], first["id"]);
+
+    let (_, fermentations) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri("/api/fermentations")
+            .header("Cookie", &cookie)
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(fermentations.as_array().unwrap().len(), 1);
+
+    // Expired keys no longer match
+    let db = app_state.db.clone();
+    tokio::task::spawn_blocking(move || {
+        let conn = db.get_connection().lock().unwrap();
+        conn.execute(
+            "UPDATE idempotency_keys SET created_at = datetime('now', '-25 hours')",
+            [],
+        )
+        .unwrap();
+    })
+    .await
+    .unwrap();
+    let (status, fresh) = common::send_json(&app_state, create_request(&cookie, "abc-123")).await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_ne!(fresh["id"], first["id"]);
+
+    // Concurrent requests with the same key create a single fermentation
+    let ((status_a, a), (status_b, b)) = tokio::join!(
+        common::send_json(&app_state, create_request(&cookie, "race-1")),
+        common::send_json(&app_state, create_request(&cookie, "race-1")),
+    );
+    assert_eq!(status_a, StatusCode::CREATED);
+    assert_eq!(status_b, StatusCode::CREATED);
+    assert_eq!(a["id"], b["id"]);
+    assert_eq!(a["target_end_date_suggested"], true);
+    assert_eq!(b["target_end_date_suggested"], true);
+
+    let (status, _) = common::send_json(&app_state, create_request(&cookie, "   ")).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_replay_idempotency_key_after_delete() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "regretful@example.com").await;
+
+    let create_request = || {
+        let mut request = common::authed_request(
+            "POST",
+            "/api/fermentation",
+            &cookie,
+            Some(json!({
+                "profile_id": 1,
+                "name": "Retried Pickles",
+                "start_date": "2024-01-15T10:00:00Z"
+            })),
+        );
+        request
+            .headers_mut()
+            .insert("Idempotency-Key", "gone-1".parse().unwrap());
+        request
+    };
+
+    let (status, first) = common::send_json(&app_state, create_request()).await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let (status, _) = common::send_json(
+        &app_state,
+        common::authed_request(
+            "DELETE",
+            &format!("/api/fermentation/{}", first["id"]),
+            &cookie,
+            None,
+        ),
+    )
+    .await;
+    assert!(status.is_success());
+
+    // Deleting the fermentation releases its key
+    let (status, replay) = common::send_json(&app_state, create_request()).await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_ne!(replay["id"], first["id"]);
+}
+
+#[tokio::test]
+async fn test_get_fermentation_conditional_request() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "poller@example.com").await;
+    let other_cookie = common::register_and_login(&app_state, "other@example.com").await;
+    let fermentation_id =
+        common::create_test_fermentation(&app_state, &cookie, "Watched Pot").await;
+
+    let get_request = |cookie: &str, if_none_match: Option<&str>| {
+        let mut builder = Request::builder()
+            .uri(format!("/api/fermentation/{}", fermentation_id))
+            .header("Cookie", cookie);
+        if let Some(etag) = if_none_match {
+            builder = builder.header("If-None-Match", etag);
+        }
+        builder.body(Body::empty()).unwrap()
+    };
+
+    let app = raugupatis_log::create_router(app_state.clone()).await;
+    let response = app.oneshot(get_request(&cookie, None)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let etag = response
+        .headers()
+        .get("ETag")
+        .unwrap()
+        .to_str()
+        .unwrap()
+        .to_string();
+
+    let (status, body) = common::send_json(&app_state, get_request(&cookie, Some(&etag))).await;
+    assert_eq!(status, StatusCode::NOT_MODIFIED);
+    assert!(body.is_null());
+
+    // Other users can't see the fermentation at all
+    let (status, _) = common::send_json(&app_state, get_request(&other_cookie, None)).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, _) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri(format!("/api/fermentation/{}", fermentation_id))
+            .method("PUT")
+            .header("Content-Type", "application/json")
+            .header("Cookie", &cookie)
+            .body(Body::from(json!({ "notes": "Stirred" }).to_string()))
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, body) = common::send_json(&app_state, get_request(&cookie, Some(&etag))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["notes"], "Stirred");
+}
+
+#[tokio::test]
+async fn test_get_fermentation_etag_tracks_quick_edits_and_unit() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "quick@example.com").await;
+    let fermentation_id = common::create_test_fermentation(&app_state, &cookie, "Busy Crock").await;
+    let uri = format!("/api/fermentation/{}", fermentation_id);
+
+    let fetch_etag = |if_none_match: Option<String>| {
+        let app_state = app_state.clone();
+        let cookie = cookie.clone();
+        let uri = uri.clone();
+        async move {
+            let mut builder = Request::builder().uri(uri).header("Cookie", cookie);
+            if let Some(etag) = if_none_match {
+                builder = builder.header("If-None-Match", etag);
+            }
+            let app = raugupatis_log::create_router(app_state).await;
+            let response = app
+                .oneshot(builder.body(Body::empty()).unwrap())
+                .await
+                .unwrap();
+            let etag = response
+                .headers()
+                .get("ETag")
+                .unwrap()
+                .to_str()
+                .unwrap()
+                .to_string();
+            (response.status(), etag)
+        }
+    };
+
+    // Two edits within the same second must still yield distinct tags
+    let mut etags = Vec::new();
+    for notes in ["First stir", "Second stir"] {
+        let (status, _) = common::send_json(
+            &app_state,
+            common::authed_request("PATCH", &uri, &cookie, Some(json!({ "notes": notes }))),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        let (status, etag) = fetch_etag(None).await;
+        assert_eq!(status, StatusCode::OK);
+        etags.push(etag);
+    }
+    assert_ne!(etags[0], etags[1]);
+
+    let (status, _) = fetch_etag(Some(etags[0].clone())).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = fetch_etag(Some(etags[1].clone())).await;
+    assert_eq!(status, StatusCode::NOT_MODIFIED);
+
+    // Switching the preferred unit invalidates cached copies as well
+    let (status, _) = common::send_json(
+        &app_state,
+        common::authed_request(
+            "POST",
+            "/api/users/profile",
+            &cookie,
+            Some(json!({
+                "experience_level": "beginner",
+                "preferred_temp_unit": "celsius"
+            })),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, etag) = fetch_etag(Some(etags[1].clone())).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_ne!(etag, etags[1]);
+}
+
+#[tokio::test]
+async fn test_export_fermentation() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "backup@example.com").await;
+    let other_cookie = common::register_and_login(&app_state, "other@example.com").await;
+    let fermentation_id = common::create_test_fermentation(&app_state, &cookie, "Sauerkraut").await;
+
+    let post = |path: &str, body: serde_json::Value| {
+        common::authed_request(
+            "POST",
+            &format!("/api/fermentation/{}/{}", fermentation_id, path),
+            &cookie,
+            Some(body),
+        )
+    };
+
+    for (path, body) in [
+        (
+            "temperature",
+            json!({ "temperature": 68.0, "recorded_at": "2024-01-16T08:00:00Z" }),
+        ),
+        (
+            "taste-profiles",
+            json!({ "profile_text": "Crunchy and mildly sour" }),
+        ),
+        (
+            "journal",
+            json!({ "body": "Weighted down the cabbage", "entry_date": "2024-01-15T12:00:00Z" }),
+        ),
+        ("tags", json!({ "tag": "cabbage" })),
+    ] {
+        let (status, _) = common::send_json(&app_state, post(path, body)).await;
+        assert!(status.is_success());
+    }
+
+    let export_request = |cookie: &str| {
+        common::authed_request(
+            "GET",
+            &format!("/api/fermentation/{}/export", fermentation_id),
+            cookie,
+            None,
+        )
+    };
+
+    let (status, export) = common::send_json(&app_state, export_request(&cookie)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(export["fermentation"]["name"], "Sauerkraut");
+    assert_eq!(export["temperature_logs"][0]["temperature"], 68.0);
+    assert_eq!(
+        export["taste_profiles"][0]["profile_text"],
+        "Crunchy and mildly sour"
+    );
+    assert_eq!(
+        export["journal_entries"][0]["body"],
+        "Weighted down the cabbage"
+    );
+    assert_eq!(export["tags"][0]["name"], "cabbage");
+    assert!(export["ph_logs"].as_array().unwrap().is_empty());
+    assert!(export["gravity_logs"].as_array().unwrap().is_empty());
+    assert!(export["photos"].as_array().unwrap().is_empty());
+
+    let (status, _) = common::send_json(&app_state, export_request(&other_cookie)).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_fermentation_list_filter_by_has_photos() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "photographer@example.com").await;
+    let pictured_id = common::create_test_fermentation(&app_state, &cookie, "Pictured").await;
+    common::create_test_fermentation(&app_state, &cookie, "Unpictured").await;
+
+    let photo_repo = raugupatis_log::photos::PhotoRepository::new(app_state.db.clone());
+    for stage in [
+        raugupatis_log::photos::PhotoStage::Start,
+        raugupatis_log::photos::PhotoStage::Progress,
+    ] {
+        photo_repo
+            .create_photo(raugupatis_log::photos::NewPhoto {
+                fermentation_id: pictured_id,
+                file_path: format!("/uploads/{}.jpg", stage.as_str()),
+                file_size: 0,
+                thumbnail_path: None,
+                caption: None,
+                taken_at: chrono::Utc::now(),
+                stage,
+            })
+            .await
+            .unwrap();
+    }
+
+    let list = |query: &str| {
+        common::authed_request(
+            "GET",
+            &format!("/api/fermentations?{}", query),
+            &cookie,
+            None,
+        )
+    };
+
+    // Several photos still yield a single row
+    let (status, fermentations) = common::send_json(&app_state, list("has_photos=true")).await;
+    assert_eq!(status, StatusCode::OK);
+    let fermentations = fermentations.as_array().unwrap();
+    assert_eq!(fermentations.len(), 1);
+    assert_eq!(fermentations[0]["name"], "Pictured");
+
+    let (_, fermentations) = common::send_json(&app_state, list("has_photos=false")).await;
+    let fermentations = fermentations.as_array().unwrap();
+    assert_eq!(fermentations.len(), 1);
+    assert_eq!(fermentations[0]["name"], "Unpictured");
+
+    let (_, fermentations) = common::send_json(&app_state, list("")).await;
+    assert_eq!(fermentations.as_array().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn test_free_text_fields_trimmed_and_length_limited() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "verbose@example.com").await;
+    let fermentation_id = common::create_test_fermentation(&app_state, &cookie, "Wordy").await;
+    let too_long = "a".repeat(5001);
+
+    let request = |method: &str, uri: String, body: serde_json::Value| {
+        common::authed_request(method, &uri, &cookie, Some(body))
+    };
+
+    let (status, _) = common::send_json(
+        &app_state,
+        request(
+            "POST",
+            "/api/fermentation".to_string(),
+            json!({
+                "profile_id": 1,
+                "name": "Essay",
+                "start_date": "2024-01-15T10:00:00Z",
+                "notes": too_long
+            }),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, _) = common::send_json(
+        &app_state,
+        request(
+            "POST",
+            format!("/api/fermentation/{}/taste-profiles", fermentation_id),
+            json!({ "profile_text": too_long }),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, _) = common::send_json(
+        &app_state,
+        request(
+            "POST",
+            format!("/api/fermentation/{}/finish", fermentation_id),
+            json!({ "lessons_learned": too_long }),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    // Surrounding whitespace is stripped before storing
+    let (status, fermentation) = common::send_json(
+        &app_state,
+        request(
+            "PUT",
+            format!("/api/fermentation/{}", fermentation_id),
+            json!({ "notes": "  Needs more salt \n" }),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(fermentation["notes"], "Needs more salt");
+
+    let (status, _) = common::send_json(
+        &app_state,
+        request(
+            "PUT",
+            format!("/api/fermentation/{}", fermentation_id),
+            json!({ "notes": too_long }),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    // Exactly at the limit is accepted
+    let (status, taste_profile) = common::send_json(
+        &app_state,
+        request(
+            "POST",
+            format!("/api/fermentation/{}/taste-profiles", fermentation_id),
+            json!({ "profile_text": format!(" {} ", "b".repeat(5000)) }),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(taste_profile["profile_text"].as_str().unwrap().len(), 5000);
+}
+
+#[tokio::test]
+async fn test_delete_and_restore_fermentation() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "tidy@example.com").await;
+    let other_cookie = common::register_and_login(&app_state, "other@example.com").await;
+    let fermentation_id =
+        common::create_test_fermentation(&app_state, &cookie, "Oops Pickles").await;
+
+    let request = |method: &str, uri: String, cookie: &str| {
+        common::authed_request(method, &uri, cookie, None)
+    };
+    let detail_uri = format!("/api/fermentation/{}", fermentation_id);
+    let restore_uri = format!("/api/fermentation/{}/restore", fermentation_id);
+
+    let (status, _) = common::send_json(
+        &app_state,
+        request("DELETE", detail_uri.clone(), &other_cookie),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, _) =
+        common::send_json(&app_state, request("DELETE", detail_uri.clone(), &cookie)).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    // Deleted fermentations are hidden from the list and detail endpoints
+    let (status, _) =
+        common::send_json(&app_state, request("GET", detail_uri.clone(), &cookie)).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (_, fermentations) = common::send_json(
+        &app_state,
+        request("GET", "/api/fermentations".to_string(), &cookie),
+    )
+    .await;
+    assert_eq!(fermentations.as_array().unwrap().len(), 0);
+
+    let (status, _) =
+        common::send_json(&app_state, request("DELETE", detail_uri.clone(), &cookie)).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, _) = common::send_json(
+        &app_state,
+        request("POST", restore_uri.clone(), &other_cookie),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, fermentation) =
+        common::send_json(&app_state, request("POST", restore_uri.clone(), &cookie)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(fermentation["name"], "Oops Pickles");
+
+    let (status, _) =
+        common::send_json(&app_state, request("GET", detail_uri.clone(), &cookie)).await;
+    assert_eq!(status, StatusCode::OK);
+
+    // Restoring a fermentation that is not deleted is a no-op
+    let (status, _) =
+        common::send_json(&app_state, request("POST", restore_uri.clone(), &cookie)).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_purge_deleted_fermentations() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "purge@example.com").await;
+    let old_id = common::create_test_fermentation(&app_state, &cookie, "Long Gone").await;
+    let recent_id = common::create_test_fermentation(&app_state, &cookie, "Just Binned").await;
+    let kept_id = common::create_test_fermentation(&app_state, &cookie, "Still Here").await;
+
+    for id in [old_id, recent_id] {
+        let (status, _) = common::send_json(
+            &app_state,
+            Request::builder()
+                .uri(format!("/api/fermentation/{}", id))
+                .method("DELETE")
+                .header("Cookie", &cookie)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+    }
+
+    // Push one deletion outside the restore window
+    let db = app_state.db.clone();
+    tokio::task::spawn_blocking(move || {
+        let conn = db.get_connection().lock().unwrap();
+        conn.execute(
+            "UPDATE fermentations SET deleted_at = datetime('now', '-45 days') WHERE id = ?1",
+            [old_id],
+        )
+        .unwrap();
+    })
+    .await
+    .unwrap();
+
+    let fermentation_repo =
+        raugupatis_log::fermentation::FermentationRepository::new(app_state.db.clone());
+    let cutoff = chrono::Utc::now() - chrono::Duration::days(30);
+    let purged = fermentation_repo
+        .purge_deleted_older_than(cutoff)
+        .await
+        .unwrap();
+    assert_eq!(purged, vec![old_id]);
+
+    let restore = |id: i64| {
+        common::authed_request(
+            "POST",
+            &format!("/api/fermentation/{}/restore", id),
+            &cookie,
+            None,
+        )
+    };
+
+    let (status, _) = common::send_json(&app_state, restore(old_id)).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, _) = common::send_json(&app_state, restore(recent_id)).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (_, fermentations) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri("/api/fermentations")
+            .header("Cookie", &cookie)
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    let mut ids: Vec<i64> = fermentations
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|f| f["id"].as_i64().unwrap())
+        .collect();
+    ids.sort();
+    assert_eq!(ids, vec![recent_id, kept_id]);
+}
+
+#[tokio::test]
+async fn test_create_fermentation_rejects_oversized_body() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "verbose@example.com").await;
+
+    let body = json!({
+        "profile_id": 1,
+        "name": "Wordy Pickles",
+        "start_date": "2024-01-15T10:00:00Z",
+        "notes": "a".repeat(app_state.config.max_request_body_bytes + 1),
+    });
+
+    let (status, _) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri("/api/fermentation")
+            .method("POST")
+            .header("Content-Type", "application/json")
+            .header("Cookie", &cookie)
+            .body(Body::from(body.to_string()))
+            .unwrap(),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+}
+
+#[tokio::test]
+async fn test_fermentation_errors_have_json_body() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "curious@example.com").await;
+
+    let create = |body: serde_json::Value| {
+        common::authed_request("POST", "/api/fermentation", &cookie, Some(body))
+    };
+
+    let (status, body) = common::send_json(
+        &app_state,
+        create(json!({
+            "profile_id": 1,
+            "name": "   ",
+            "start_date": "2024-01-15T10:00:00Z",
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["field"], "name");
+    assert_eq!(body["error"], "Name is required");
+
+    let (status, body) = common::send_json(
+        &app_state,
+        create(json!({
+            "profile_id": 1,
+            "name": "Dill Pickles",
+            "start_date": "last tuesday",
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["field"], "start_date");
+
+    // Errors that aren't tied to a field omit it
+    let (status, body) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri("/api/fermentation/9999")
+            .header("Cookie", &cookie)
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["error"], "Fermentation not found");
+    assert!(body.get("field").is_none());
+
+    let (status, body) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri("/api/fermentations")
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    assert_eq!(body["error"], "Unauthorized");
+}
+
+#[tokio::test]
+async fn test_create_and_list_measurement_logs() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "measure@example.com").await;
+    let fermentation_id = common::create_test_fermentation(&app_state, &cookie, "Sauerkraut").await;
+
+    let measurement_request = |body: serde_json::Value| {
+        common::authed_request(
+            "POST",
+            &format!("/api/fermentation/{}/measurements", fermentation_id),
+            &cookie,
+            Some(body),
+        )
+    };
+
+    let (status, log) = common::send_json(
+        &app_state,
+        measurement_request(json!({
+            "kind": "weight",
+            "value": 1250.0,
+            "recorded_at": "2024-01-15T12:00:00Z",
+            "notes": "After pressing"
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(log["fermentation_id"], fermentation_id);
+    assert_eq!(log["kind"], "weight");
+    assert_eq!(log["value"].as_f64().unwrap(), 1250.0);
+    // The first unit of the kind is used when none is given
+    assert_eq!(log["unit"], "g");
+
+    let (status, _) = common::send_json(
+        &app_state,
+        measurement_request(json!({
+            "kind": "weight",
+            "value": 1.1,
+            "unit": "KG",
+            "recorded_at": "2024-01-20T12:00:00Z"
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let (status, _) = common::send_json(
+        &app_state,
+        measurement_request(json!({ "kind": "volume", "value": 2.0, "unit": "l" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let list = |query: &str| {
+        common::authed_request(
+            "GET",
+            &format!(
+                "/api/fermentation/{}/measurements{}",
+                fermentation_id, query
+            ),
+            &cookie,
+            None,
+        )
+    };
+
+    let (status, all) = common::send_json(&app_state, list("")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(all.as_array().unwrap().len(), 3);
+
+    let (status, weights) = common::send_json(&app_state, list("?kind=weight")).await;
+    assert_eq!(status, StatusCode::OK);
+    let weights = weights.as_array().unwrap();
+    assert_eq!(weights.len(), 2);
+    // Most recent reading first
+    assert_eq!(weights[0]["unit"], "kg");
+    assert_eq!(weights[1]["notes"], "After pressing");
+
+    let (status, _) = common::send_json(&app_state, list("?kind=salinity")).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_create_measurement_log_validation() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "measure-invalid@example.com").await;
+    let fermentation_id = common::create_test_fermentation(&app_state, &cookie, "Kimchi").await;
+    let other_cookie = common::register_and_login(&app_state, "measure-other@example.com").await;
+
+    for (body, field) in [
+        (json!({ "kind": "weight", "value": 0.0 }), "value"),
+        (json!({ "kind": "volume", "value": -1.0 }), "value"),
+        (json!({ "kind": "ph", "value": 15.0 }), "value"),
+        (json!({ "kind": "gravity", "value": 2.5 }), "value"),
+        (json!({ "kind": "salinity", "value": 3.0 }), "kind"),
+        (
+            json!({ "kind": "weight", "value": 3.0, "unit": "ml" }),
+            "unit",
+        ),
+        (
+            json!({ "kind": "weight", "value": 3.0, "recorded_at": "yesterday" }),
+            "recorded_at",
+        ),
+    ] {
+        let (status, error) = common::send_json(
+            &app_state,
+            Request::builder()
+                .uri(format!(
+                    "/api/fermentation/{}/measurements",
+                    fermentation_id
+                ))
+                .method("POST")
+                .header("Content-Type", "application/json")
+                .header("Cookie", &cookie)
+                .body(Body::from(body.to_string()))
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{}", body);
+        assert_eq!(error["field"], field);
+    }
+
+    // Other users cannot log against the fermentation
+    let (status, _) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri(format!(
+                "/api/fermentation/{}/measurements",
+                fermentation_id
+            ))
+            .method("POST")
+            .header("Content-Type", "application/json")
+            .header("Cookie", &other_cookie)
+            .body(Body::from(
+                json!({ "kind": "weight", "value": 3.0 }).to_string(),
+            ))
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_batch_numbers_are_sequential_per_user() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "batches@example.com").await;
+    let other_cookie = common::register_and_login(&app_state, "other-batches@example.com").await;
+
+    let first_id = common::create_test_fermentation(&app_state, &cookie, "First").await;
+    common::create_test_fermentation(&app_state, &other_cookie, "Elsewhere").await;
+    let second_id = common::create_test_fermentation(&app_state, &cookie, "Second").await;
+
+    let (status, clone) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri(format!("/api/fermentation/{}/clone", first_id))
+            .method("POST")
+            .header("Cookie", &cookie)
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(clone["batch_number"], 3);
+
+    // Deleted fermentations keep their number, so it is not handed out again
+    let (status, _) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri(format!("/api/fermentation/{}", second_id))
+            .method("DELETE")
+            .header("Cookie", &cookie)
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    common::create_test_fermentation(&app_state, &cookie, "Fourth").await;
+
+    let list = |cookie: &str, order: &str| {
+        common::authed_request(
+            "GET",
+            &format!(
+                "/api/fermentations?sort_by=batch_number&sort_order={}",
+                order
+            ),
+            cookie,
+            None,
+        )
+    };
+    let batches = |fermentations: &serde_json::Value| -> Vec<(i64, String)> {
+        fermentations
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|f| {
+                (
+                    f["batch_number"].as_i64().unwrap(),
+                    f["name"].as_str().unwrap().to_string(),
+                )
+            })
+            .collect()
+    };
+
+    let (status, fermentations) = common::send_json(&app_state, list(&cookie, "asc")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        batches(&fermentations),
+        vec![
+            (1, "First".to_string()),
+            (3, "First (copy)".to_string()),
+            (4, "Fourth".to_string()),
+        ]
+    );
+
+    let (_, fermentations) = common::send_json(&app_state, list(&cookie, "desc")).await;
+    assert_eq!(batches(&fermentations)[0].0, 4);
+
+    let (_, fermentations) = common::send_json(&app_state, list(&other_cookie, "asc")).await;
+    assert_eq!(batches(&fermentations), vec![(1, "Elsewhere".to_string())]);
+}
+
+#[tokio::test]
+async fn test_fermentation_search_matches_every_term() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "multi-search@example.com").await;
+    common::create_test_fermentation(&app_state, &cookie, "Pickles, spicy").await;
+    common::create_test_fermentation(&app_state, &cookie, "Sweet Pickles").await;
+    common::create_test_fermentation(&app_state, &cookie, "Spicy Kimchi").await;
+
+    let search = |query: &str| {
+        common::authed_request(
+            "GET",
+            &format!("/api/fermentations?search={}", query),
+            &cookie,
+            None,
+        )
+    };
+    let names = |fermentations: &serde_json::Value| -> Vec<String> {
+        let mut names: Vec<String> = fermentations
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|f| f["name"].as_str().unwrap().to_string())
+            .collect();
+        names.sort();
+        names
+    };
+
+    // Terms can appear in any order and case
+    let (status, fermentations) = common::send_json(&app_state, search("spicy%20PICKLE")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(names(&fermentations), vec!["Pickles, spicy"]);
+
+    let (_, fermentations) = common::send_json(&app_state, search("%20spicy%20%20")).await;
+    assert_eq!(
+        names(&fermentations),
+        vec!["Pickles, spicy", "Spicy Kimchi"]
+    );
+
+    let (_, fermentations) = common::send_json(&app_state, search("sweet%20kimchi")).await;
+    assert!(names(&fermentations).is_empty());
+}
+
+#[tokio::test]
+async fn test_list_due_soon() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "due-soon@example.com").await;
+    let now = chrono::Utc::now();
+
+    let create = |name: &str, target_in_days: i64| {
+        Request::builder()
+            .uri("/api/fermentation")
+            .method("POST")
+            .header("Content-Type", "application/json")
+            .header("Cookie", &cookie)
+            .body(Body::from(
+                json!({
+                    "profile_id": 1,
+                    "name": name,
+                    "start_date": (now - chrono::Duration::days(10)).to_rfc3339(),
+                    "target_end_date": (now + chrono::Duration::days(target_in_days)).to_rfc3339(),
+                })
+                .to_string(),
+            ))
+            .unwrap()
+    };
+
+    for (name, target_in_days) in [("Later", 10), ("Soon", 2), ("Overdue", -1), ("Done", 1)] {
+        let (status, _) = common::send_json(&app_state, create(name, target_in_days)).await;
+        assert_eq!(status, StatusCode::CREATED);
+    }
+
+    // Finished fermentations no longer need attention
+    let (_, fermentations) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri("/api/fermentations?search=Done")
+            .header("Cookie", &cookie)
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    let done_id = fermentations[0]["id"].as_i64().unwrap();
+    let (status, _) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri(format!("/api/fermentation/{}/finish", done_id))
+            .method("POST")
+            .header("Content-Type", "application/json")
+            .header("Cookie", &cookie)
+            .body(Body::from(json!({ "success_rating": 4 }).to_string()))
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let due_soon = |query: &str| {
+        common::authed_request(
+            "GET",
+            &format!("/api/fermentations/due-soon{}", query),
+            &cookie,
+            None,
+        )
+    };
+    let names = |fermentations: &serde_json::Value| -> Vec<String> {
+        fermentations
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|f| f["name"].as_str().unwrap().to_string())
+            .collect()
+    };
+
+    // Defaults to three days, soonest (overdue) first
+    let (status, due) = common::send_json(&app_state, due_soon("")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(names(&due), vec!["Overdue", "Soon"]);
+    assert_eq!(due[0]["is_overdue"], true);
+    assert_eq!(due[1]["is_overdue"], false);
+
+    let (_, due) = common::send_json(&app_state, due_soon("?days=14")).await;
+    assert_eq!(names(&due), vec!["Overdue", "Soon", "Later"]);
+
+    let (status, error) = common::send_json(&app_state, due_soon("?days=-1")).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(error["field"], "days");
+}
+
+#[tokio::test]
+async fn test_list_by_ingredient() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "dill-lover@example.com").await;
+    let other_cookie = common::register_and_login(&app_state, "dill-too@example.com").await;
+
+    let create = |cookie: &str, name: &str, start_date: &str, notes: &str, ingredients| {
+        Request::builder()
+            .uri("/api/fermentation")
+            .method("POST")
+            .header("Content-Type", "application/json")
+            .header("Cookie", cookie)
+            .body(Body::from(
+                json!({
+                    "profile_id": 1,
+                    "name": name,
+                    "start_date": start_date,
+                    "notes": notes,
+                    "ingredients": ingredients,
+                })
+                .to_string(),
+            ))
+            .unwrap()
+    };
+
+    for (cookie, name, start_date, notes, ingredients) in [
+        (
+            &cookie,
+            "Dill Pickles",
+            "2024-03-01T10:00:00Z",
+            "",
+            json!([{ "name": "cucumbers" }, { "name": "Fresh Dill" }]),
+        ),
+        (
+            &cookie,
+            "Kraut",
+            "2024-02-15T10:00:00Z",
+            "No dill this time",
+            json!([{ "name": "cabbage" }, { "name": "salt" }]),
+        ),
+        (
+            &other_cookie,
+            "Their Pickles",
+            "2024-01-15T10:00:00Z",
+            "",
+            json!([{ "name": "dill" }]),
+        ),
+    ] {
+        let (status, _) = common::send_json(
+            &app_state,
+            create(cookie.as_str(), name, start_date, notes, ingredients),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+    }
+
+    // Older fermentations hold their ingredients as free text
+    let legacy_id = common::create_test_fermentation(&app_state, &cookie, "Legacy Batch").await;
+    app_state
+        .db
+        .get_connection()
+        .lock()
+        .unwrap()
+        .execute(
+            "UPDATE fermentations SET ingredients_json = 'carrots\ndill seeds' WHERE id = ?1",
+            [legacy_id],
+        )
+        .unwrap();
+
+    let by_ingredient = |query: &str| {
+        common::authed_request(
+            "GET",
+            &format!("/api/fermentations/by-ingredient{}", query),
+            &cookie,
+            None,
+        )
+    };
+    let names = |fermentations: &serde_json::Value| -> Vec<String> {
+        fermentations
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|f| f["name"].as_str().unwrap().to_string())
+            .collect()
+    };
+
+    // Matches ingredient names only, case-insensitively; mentions in notes don't count
+    let (status, fermentations) = common::send_json(&app_state, by_ingredient("?name=DILL")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(names(&fermentations), vec!["Dill Pickles", "Legacy Batch"]);
+
+    // A fermentation's name isn't an ingredient
+    let (_, fermentations) = common::send_json(&app_state, by_ingredient("?name=kraut")).await;
+    assert!(fermentations.as_array().unwrap().is_empty());
+
+    let (_, fermentations) = common::send_json(&app_state, by_ingredient("?name=%25")).await;
+    assert!(fermentations.as_array().unwrap().is_empty());
+
+    let (status, error) = common::send_json(&app_state, by_ingredient("?name=%20")).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(error["field"], "name");
+}
+
+#[tokio::test]
+async fn test_list_recent() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "recent@example.com").await;
+    let other_cookie = common::register_and_login(&app_state, "not-recent@example.com").await;
+
+    let mut ids = Vec::new();
+    for name in ["Oldest", "Middle", "Archived"] {
+        ids.push(common::create_test_fermentation(&app_state, &cookie, name).await);
+    }
+    common::create_test_fermentation(&app_state, &other_cookie, "Someone Else's").await;
+
+    let (status, _) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri(format!("/api/fermentation/{}/archive", ids[2]))
+            .method("POST")
+            .header("Cookie", &cookie)
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    // Timestamps have second precision, so spread the updates out explicitly
+    {
+        let conn = app_state.db.get_connection().lock().unwrap();
+        for (id, updated_at) in ids.iter().zip([
+            "2024-03-03 12:00:00",
+            "2024-03-01 12:00:00",
+            "2024-03-02 12:00:00",
+        ]) {
+            conn.execute(
+                "UPDATE fermentations SET updated_at = ?1 WHERE id = ?2",
+                rusqlite::params![updated_at, id],
+            )
+            .unwrap();
+        }
+    }
+
+    let recent = |query: &str| {
+        common::authed_request(
+            "GET",
+            &format!("/api/fermentations/recent{}", query),
+            &cookie,
+            None,
+        )
+    };
+    let names = |fermentations: &serde_json::Value| -> Vec<String> {
+        fermentations
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|f| f["name"].as_str().unwrap().to_string())
+            .collect()
+    };
+
+    // Archived fermentations are included; other users' are not
+    let (status, fermentations) = common::send_json(&app_state, recent("")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(names(&fermentations), vec!["Oldest", "Archived", "Middle"]);
+
+    let (_, fermentations) = common::send_json(&app_state, recent("?limit=2")).await;
+    assert_eq!(names(&fermentations), vec!["Oldest", "Archived"]);
+
+    let (status, error) = common::send_json(&app_state, recent("?limit=0")).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(error["field"], "limit");
+}
+
+#[tokio::test]
+async fn test_temperature_gaps() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "temp_gaps@example.com").await;
+    let fermentation_id = common::create_test_fermentation(&app_state, &cookie, "Neglected").await;
+
+    let gaps_request = |cookie: &str| {
+        common::authed_request(
+            "GET",
+            &format!("/api/fermentation/{}/temperature/gaps", fermentation_id),
+            cookie,
+            None,
+        )
+    };
+
+    let (status, gaps) = common::send_json(&app_state, gaps_request(&cookie)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(gaps["count"], 0);
+    assert!(gaps["longest_gap_hours"].is_null());
+    assert!(gaps["hours_since_last_reading"].is_null());
+
+    // Logged out of order to make sure readings are sorted before diffing
+    for recorded_at in [
+        "2024-01-16T10:00:00Z",
+        "2024-01-15T10:00:00Z",
+        "2024-01-19T22:00:00Z",
+        "2024-01-16T16:00:00Z",
+    ] {
+        let (status, _) = common::send_json(
+            &app_state,
+            Request::builder()
+                .uri(format!("/api/fermentation/{}/temperature", fermentation_id))
+                .method("POST")
+                .header("Content-Type", "application/json")
+                .header("Cookie", &cookie)
+                .body(Body::from(
+                    json!({ "temperature": 68.0, "recorded_at": recorded_at }).to_string(),
+                ))
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+    }
+
+    let (status, gaps) = common::send_json(&app_state, gaps_request(&cookie)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(gaps["count"], 4);
+    assert_eq!(gaps["longest_gap_hours"].as_f64().unwrap(), 78.0);
+    assert!(gaps["longest_gap_started_at"]
+        .as_str()
+        .unwrap()
+        .starts_with("2024-01-16T16:00:00"));
+    assert!(gaps["longest_gap_ended_at"]
+        .as_str()
+        .unwrap()
+        .starts_with("2024-01-19T22:00:00"));
+    assert!(gaps["hours_since_last_reading"].as_f64().unwrap() > 24.0);
+
+    // Other users cannot inspect the readings
+    let other_cookie = common::register_and_login(&app_state, "temp_gaps_other@example.com").await;
+    let (status, _) = common::send_json(&app_state, gaps_request(&other_cookie)).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_get_fermentation_includes_profile() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "nested-profile@example.com").await;
+    let fermentation_id =
+        common::create_test_fermentation(&app_state, &cookie, "Dill Pickles").await;
+
+    let (status, fermentation) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri(format!("/api/fermentation/{}", fermentation_id))
+            .header("Cookie", &cookie)
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+    let profile = &fermentation["profile"];
+    assert_eq!(profile["id"], fermentation["profile_id"]);
+    assert_eq!(profile["name"], fermentation["profile_name"]);
+    assert_eq!(profile["type"], "vegetable");
+    assert_eq!(profile["min_days"], 3);
+    assert_eq!(profile["max_days"], 7);
+    assert_eq!(profile["temp_min"].as_f64().unwrap(), 65.0);
+    assert_eq!(profile["temp_max"].as_f64().unwrap(), 75.0);
+    assert!(profile["description"].is_string());
+}
+
+#[tokio::test]
+async fn test_create_fermentation_rejects_implausible_dates() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "timeline@example.com").await;
+    let now = chrono::Utc::now();
+
+    let create = |start_date: String, target_end_date: Option<String>| {
+        Request::builder()
+            .uri("/api/fermentation")
+            .method("POST")
+            .header("Content-Type", "application/json")
+            .header("Cookie", &cookie)
+            .body(Body::from(
+                json!({
+                    "profile_id": 1,
+                    "name": "Time Travel Pickles",
+                    "start_date": start_date,
+                    "target_end_date": target_end_date,
+                })
+                .to_string(),
+            ))
+            .unwrap()
+    };
+
+    let (status, body) = common::send_json(
+        &app_state,
+        create((now + chrono::Duration::days(30)).to_rfc3339(), None),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["field"], "start_date");
+
+    let (status, body) = common::send_json(
+        &app_state,
+        create(
+            "2024-01-15T10:00:00Z".to_string(),
+            Some("2024-01-10T10:00:00Z".to_string()),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["field"], "target_end_date");
+
+    // Starting later today is fine, e.g. from a time zone ahead of the server
+    let (status, _) = common::send_json(
+        &app_state,
+        create((now + chrono::Duration::hours(6)).to_rfc3339(), None),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+}
+
+#[tokio::test]
+async fn test_bulk_status_update() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "bulk-owner@example.com").await;
+    let other_cookie = common::register_and_login(&app_state, "bulk-other@example.com").await;
+
+    let first = common::create_test_fermentation(&app_state, &cookie, "Batch one").await;
+    let second = common::create_test_fermentation(&app_state, &cookie, "Batch two").await;
+    let foreign = common::create_test_fermentation(&app_state, &other_cookie, "Not mine").await;
+
+    // Other users' fermentations and unknown ids are skipped without failing the request
+    let (status, body) = common::send_json(
+        &app_state,
+        common::authed_request(
+            "POST",
+            "/api/fermentations/bulk-status",
+            &cookie,
+            Some(json!({ "ids": [first, second, foreign, 99999], "status": "archived" })),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["updated"], 2);
+    assert_eq!(body["skipped"], 2);
+
+    let get_status = |id: i64, cookie: String| {
+        let state = &app_state;
+        async move {
+            let (_, fermentation) = common::send_json(
+                state,
+                Request::builder()
+                    .uri(format!("/api/fermentation/{}", id))
+                    .header("Cookie", cookie)
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await;
+            fermentation["status"].as_str().unwrap().to_string()
+        }
+    };
+    assert_eq!(get_status(first, cookie.clone()).await, "archived");
+    assert_eq!(get_status(second, cookie.clone()).await, "archived");
+    assert_eq!(get_status(foreign, other_cookie.clone()).await, "active");
+
+    // Bulk archiving keeps the previous status for unarchiving
+    let (status, fermentation) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri(format!("/api/fermentation/{}/unarchive", first))
+            .method("POST")
+            .header("Cookie", &cookie)
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(fermentation["status"], "active");
+
+    // Fermentations already in the target status are not counted as updated
+    let (_, body) = common::send_json(
+        &app_state,
+        common::authed_request(
+            "POST",
+            "/api/fermentations/bulk-status",
+            &cookie,
+            Some(json!({ "ids": [first, second], "status": "archived" })),
+        ),
+    )
+    .await;
+    assert_eq!(body["updated"], 1);
+    assert_eq!(body["skipped"], 1);
+}
+
+#[tokio::test]
+async fn test_bulk_status_update_validation() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "bulk-invalid@example.com").await;
+    let id = common::create_test_fermentation(&app_state, &cookie, "Batch").await;
+
+    let (status, body) = common::send_json(
+        &app_state,
+        common::authed_request(
+            "POST",
+            "/api/fermentations/bulk-status",
+            &cookie,
+            Some(json!({ "ids": [id], "status": "done" })),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["field"], "status");
+
+    let (status, body) = common::send_json(
+        &app_state,
+        common::authed_request(
+            "POST",
+            "/api/fermentations/bulk-status",
+            &cookie,
+            Some(json!({ "ids": [], "status": "completed" })),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["field"], "ids");
+
+    let unauthenticated = Request::builder()
+        .uri("/api/fermentations/bulk-status")
+        .method("POST")
+        .header("Content-Type", "application/json")
+        .body(Body::from(
+            json!({ "ids": [id], "status": "completed" }).to_string(),
+        ))
+        .unwrap();
+    let (status, _) = common::send_json(&app_state, unauthenticated).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn test_detail_page_dates_follow_accept_language() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "locale@example.com").await;
+    let fermentation_id = common::create_test_fermentation(&app_state, &cookie, "Dates").await;
+
+    let render = |accept_language: Option<&'static str>| {
+        let app_state = app_state.clone();
+        let cookie = cookie.clone();
+        async move {
+            let mut builder = Request::builder()
+                .uri(format!("/fermentation/{}", fermentation_id))
+                .header("Cookie", cookie);
+            if let Some(language) = accept_language {
+                builder = builder.header("Accept-Language", language);
+            }
+
+            let app = raugupatis_log::create_router(app_state).await;
+            let response = app
+                .oneshot(builder.body(Body::empty()).unwrap())
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            String::from_utf8(body.to_vec()).unwrap()
+        }
+    };
+
+    // The test fermentation starts on 2024-01-15
+    let us = render(Some("en-US,en;q=0.9")).await;
+    assert!(us.contains("January 15, 2024"));
+
+    let default = render(None).await;
+    assert!(default.contains("January 15, 2024"));
+
+    let german = render(Some("de-DE,de;q=0.9,en;q=0.8")).await;
+    assert!(german.contains("2024-01-15"));
+    assert!(!german.contains("January 15, 2024"));
+}
+
+#[tokio::test]
+async fn test_share_fermentation_link() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "sharer@example.com").await;
+    let other_cookie = common::register_and_login(&app_state, "other@example.com").await;
+    let fermentation_id =
+        common::create_test_fermentation(&app_state, &cookie, "Show-off Kimchi").await;
+    common::create_test_fermentation(&app_state, &cookie, "Secret Batch").await;
+
+    let share_request = |method: &str, cookie: &str| {
+        common::authed_request(
+            method,
+            &format!("/api/fermentation/{}/share", fermentation_id),
+            cookie,
+            None,
+        )
+    };
+    let view = |url: String| {
+        let app_state = app_state.clone();
+        async move {
+            // No cookie: the page is public
+            let app = raugupatis_log::create_router(app_state).await;
+            let response = app
+                .oneshot(Request::builder().uri(url).body(Body::empty()).unwrap())
+                .await
+                .unwrap();
+            let status = response.status();
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            (status, String::from_utf8(body.to_vec()).unwrap())
+        }
+    };
+
+    // Only the owner can share
+    let (status, _) = common::send_json(&app_state, share_request("POST", &other_cookie)).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, share) = common::send_json(&app_state, share_request("POST", &cookie)).await;
+    assert_eq!(status, StatusCode::OK);
+    let url = share["url"].as_str().unwrap().to_string();
+    assert_eq!(url, format!("/share/{}", share["slug"].as_str().unwrap()));
+
+    // Sharing again keeps the same link
+    let (_, again) = common::send_json(&app_state, share_request("POST", &cookie)).await;
+    assert_eq!(again["slug"], share["slug"]);
+
+    let (status, html) = view(url.clone()).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(html.contains("Show-off Kimchi"));
+    assert!(!html.contains("Secret Batch"));
+    assert!(!html.contains("/edit"));
+
+    let (status, _) = view("/share/not-a-real-slug".to_string()).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    // Other users can't revoke the link
+    let (status, _) = common::send_json(&app_state, share_request("DELETE", &other_cookie)).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, _) = common::send_json(&app_state, share_request("DELETE", &cookie)).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    let (status, _) = view(url).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_calendar_feed() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "planner@example.com").await;
+    let other_cookie = common::register_and_login(&app_state, "neighbour@example.com").await;
+
+    let patch = |id: i64, cookie: &str, body: serde_json::Value| {
+        common::authed_request(
+            "PATCH",
+            &format!("/api/fermentation/{}", id),
+            cookie,
+            Some(body),
+        )
+    };
+    let token_request = |method: &str, cookie: &str| {
+        common::authed_request(method, "/api/users/calendar-token", cookie, None)
+    };
+    let feed = |url: String| {
+        let app_state = app_state.clone();
+        async move {
+            // Calendar apps don't send a session cookie
+            let app = raugupatis_log::create_router(app_state).await;
+            let response = app
+                .oneshot(Request::builder().uri(url).body(Body::empty()).unwrap())
+                .await
+                .unwrap();
+            let status = response.status();
+            let content_type = response
+                .headers()
+                .get("content-type")
+                .map(|v| v.to_str().unwrap().to_string());
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            (
+                status,
+                content_type,
+                String::from_utf8(body.to_vec()).unwrap(),
+            )
+        }
+    };
+
+    let scheduled = common::create_test_fermentation(&app_state, &cookie, "Brine Pickles").await;
+    common::send_json(
+        &app_state,
+        patch(
+            scheduled,
+            &cookie,
+            json!({ "target_end_date": "2024-02-01T10:00:00Z" }),
+        ),
+    )
+    .await;
+    // New fermentations get a suggested target end date, so clear it to leave one open-ended
+    let open_ended =
+        common::create_test_fermentation(&app_state, &cookie, "Open-ended Vinegar").await;
+    common::send_json(
+        &app_state,
+        patch(open_ended, &cookie, json!({ "target_end_date": null })),
+    )
+    .await;
+    let finished = common::create_test_fermentation(&app_state, &cookie, "Done Kraut").await;
+    common::send_json(
+        &app_state,
+        patch(
+            finished,
+            &cookie,
+            json!({ "target_end_date": "2024-01-25T10:00:00Z", "status": "completed" }),
+        ),
+    )
+    .await;
+    let foreign = common::create_test_fermentation(&app_state, &other_cookie, "Their Kimchi").await;
+    common::send_json(
+        &app_state,
+        patch(
+            foreign,
+            &other_cookie,
+            json!({ "target_end_date": "2024-02-03T10:00:00Z" }),
+        ),
+    )
+    .await;
+
+    let (status, _) = common::send_json(&app_state, token_request("POST", "")).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    let (status, token) = common::send_json(&app_state, token_request("POST", &cookie)).await;
+    assert_eq!(status, StatusCode::CREATED);
+    let url = token["url"].as_str().unwrap().to_string();
+
+    let (status, content_type, ics) = feed(url.clone()).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        content_type.as_deref(),
+        Some("text/calendar; charset=utf-8")
+    );
+    assert!(ics.starts_with("BEGIN:VCALENDAR\r\n"));
+    assert!(ics.contains("SUMMARY:Check Brine Pickles\r\n"));
+    assert!(ics.contains("DTSTART:20240201T100000Z\r\n"));
+    assert!(ics.contains(&format!("UID:fermentation-{}@raugupatis-log", scheduled)));
+    assert_eq!(ics.matches("BEGIN:VEVENT").count(), 1);
+    assert!(!ics.contains("Done Kraut"));
+    assert!(!ics.contains("Their Kimchi"));
+
+    let (status, _, _) = feed("/api/calendar.ics".to_string()).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let (status, _, _) = feed(format!("{}x", url)).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    let (status, _) = common::send_json(&app_state, token_request("DELETE", &cookie)).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let (status, _, _) = feed(url).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn test_fermentation_durations() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "timer@example.com").await;
+    let fermentation_id = common::create_test_fermentation(&app_state, &cookie, "Timed").await;
+
+    let patch = |body: serde_json::Value| {
+        common::authed_request(
+            "PATCH",
+            &format!("/api/fermentation/{}", fermentation_id),
+            &cookie,
+            Some(body),
+        )
+    };
+
+    let (status, fermentation) = common::send_json(
+        &app_state,
+        patch(json!({ "target_end_date": "2024-01-22T10:00:00Z" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(fermentation["target_duration_days"], 7);
+    assert!(fermentation["actual_duration_days"].is_null());
+
+    let (status, fermentation) = common::send_json(
+        &app_state,
+        patch(json!({ "status": "completed", "actual_end_date": "2024-01-25T18:00:00Z" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(fermentation["actual_duration_days"], 10);
+    assert_eq!(fermentation["target_duration_days"], 7);
+}
@@ -23,6 +23,30 @@ async fn test_health_endpoint() {
     assert_eq!(response.status(), StatusCode::OK);
 }
 
+#[tokio::test]
+async fn test_detailed_health_endpoint() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "health@example.com").await;
+    common::create_test_fermentation(&app_state, &cookie, "Monitored Batch").await;
+
+    let (status, body) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri("/health/detailed")
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["status"], "ok");
+    assert_eq!(body["database"]["connected"], true);
+    assert!(body["database"].get("error").is_none());
+    assert!(body["database"]["migration_version"].as_i64().unwrap() > 0);
+    assert!(body["uptime_seconds"].is_u64());
+    assert_eq!(body["active_fermentations"], 1);
+}
+
 #[tokio::test]
 async fn test_home_endpoint() {
     let app = common::create_test_app().await;
@@ -238,3 +262,555 @@ async fn test_dashboard_without_user_names() {
         "Dashboard should not display Last Name label when not provided"
     );
 }
+
+#[tokio::test]
+async fn test_cors_restricted_to_allowed_origins_in_production() {
+    let app_state = common::create_test_app_state().await;
+    let mut config = (*app_state.config).clone();
+    config.environment = "production".to_string();
+    config.allowed_origins = vec!["https://ferments.example.com".to_string()];
+    let app_state = raugupatis_log::AppState {
+        config: std::sync::Arc::new(config),
+        ..app_state
+    };
+
+    let preflight = |origin: &str| {
+        Request::builder()
+            .uri("/api/fermentations")
+            .method("OPTIONS")
+            .header("Origin", origin)
+            .header("Access-Control-Request-Method", "GET")
+            .body(Body::empty())
+            .unwrap()
+    };
+
+    let app = raugupatis_log::create_router(app_state.clone()).await;
+    let response = app
+        .oneshot(preflight("https://ferments.example.com"))
+        .await
+        .unwrap();
+    let headers = response.headers();
+    assert_eq!(
+        headers.get("access-control-allow-origin").unwrap(),
+        "https://ferments.example.com"
+    );
+    assert_eq!(
+        headers.get("access-control-allow-credentials").unwrap(),
+        "true"
+    );
+
+    let app = raugupatis_log::create_router(app_state).await;
+    let response = app
+        .oneshot(preflight("https://evil.example.com"))
+        .await
+        .unwrap();
+    assert!(response
+        .headers()
+        .get("access-control-allow-origin")
+        .is_none());
+}
+
+#[tokio::test]
+async fn test_cors_permissive_in_test_environment() {
+    let app = common::create_test_app().await;
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/api/fermentations")
+                .method("OPTIONS")
+                .header("Origin", "http://localhost:5173")
+                .header("Access-Control-Request-Method", "GET")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        response
+            .headers()
+            .get("access-control-allow-origin")
+            .unwrap(),
+        "*"
+    );
+}
+
+#[tokio::test]
+async fn test_metrics_endpoint() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "metrics@example.com").await;
+    let fermentation_id = common::create_test_fermentation(&app_state, &cookie, "Counted").await;
+
+    let failed_login = Request::builder()
+        .uri("/api/users/login")
+        .method("POST")
+        .header("Content-Type", "application/json")
+        .body(Body::from(
+            serde_json::json!({ "email": "metrics@example.com", "password": "wrongpassword" })
+                .to_string(),
+        ))
+        .unwrap();
+    common::send_json(&app_state, failed_login).await;
+
+    let get_fermentation = Request::builder()
+        .uri(format!("/api/fermentation/{}", fermentation_id))
+        .header("Cookie", &cookie)
+        .body(Body::empty())
+        .unwrap();
+    common::send_json(&app_state, get_fermentation).await;
+
+    let app = raugupatis_log::create_router(app_state).await;
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/metrics")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response.headers()["content-type"]
+        .to_str()
+        .unwrap()
+        .starts_with("text/plain"));
+
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let metrics = String::from_utf8(body.to_vec()).unwrap();
+
+    assert!(metrics.contains("raugupatis_logins_total{result=\"success\"} 1\n"));
+    assert!(metrics.contains("raugupatis_logins_total{result=\"failure\"} 1\n"));
+    assert!(metrics.contains("raugupatis_fermentations_created_total 1\n"));
+    // Requests are grouped by route pattern, not by concrete id
+    assert!(metrics.contains(
+        "raugupatis_http_requests_by_endpoint_total{method=\"GET\",path=\"/api/fermentation/:id\"} 1\n"
+    ));
+    assert!(metrics.contains(
+        "raugupatis_http_requests_by_endpoint_total{method=\"POST\",path=\"/api/users/login\"} 2\n"
+    ));
+}
+
+#[tokio::test]
+async fn test_request_id_generated_and_echoed() {
+    let app_state = common::create_test_app_state().await;
+
+    let request_id = |header: Option<&str>| {
+        let app_state = app_state.clone();
+        let header = header.map(str::to_string);
+        async move {
+            let mut builder = Request::builder().uri("/health");
+            if let Some(value) = header {
+                builder = builder.header("X-Request-Id", value);
+            }
+
+            let app = raugupatis_log::create_router(app_state).await;
+            let response = app
+                .oneshot(builder.body(Body::empty()).unwrap())
+                .await
+                .unwrap();
+            response.headers()["x-request-id"]
+                .to_str()
+                .unwrap()
+                .to_string()
+        }
+    };
+
+    // A missing id is generated, and each request gets its own
+    let generated = request_id(None).await;
+    assert_eq!(generated.len(), 32);
+    assert_ne!(generated, request_id(None).await);
+
+    // A caller's id is passed through so logs can be correlated across services
+    assert_eq!(request_id(Some("client-abc.123")).await, "client-abc.123");
+
+    // Ids with unexpected characters are replaced
+    let replaced = request_id(Some("bad id with spaces")).await;
+    assert_ne!(replaced, "bad id with spaces");
+    assert_eq!(replaced.len(), 32);
+}
+
+#[tokio::test]
+async fn test_database_checkpoint_on_shutdown() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "shutdown@example.com").await;
+    common::create_test_fermentation(&app_state, &cookie, "Before shutdown").await;
+
+    app_state.db.checkpoint().await.unwrap();
+    app_state.db.health_check().await.unwrap();
+}
+
+#[test]
+fn test_prepare_uploads_dir() {
+    let base = std::env::temp_dir().join(format!(
+        "test_uploads_check_{}",
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos()
+    ));
+
+    // Missing directories are created, including parents
+    let config = raugupatis_log::config::AppConfig {
+        uploads_dir: base.join("nested/uploads").to_string_lossy().to_string(),
+        ..Default::default()
+    };
+    config.prepare_uploads_dir().unwrap();
+    assert!(base.join("nested/uploads").is_dir());
+    assert!(!base.join("nested/uploads/.write-check").exists());
+
+    // A path that can't be a directory is reported clearly
+    std::fs::write(base.join("not-a-dir"), b"").unwrap();
+    let config = raugupatis_log::config::AppConfig {
+        uploads_dir: base.join("not-a-dir/uploads").to_string_lossy().to_string(),
+        ..Default::default()
+    };
+    let error = config.prepare_uploads_dir().unwrap_err();
+    assert!(error.to_string().contains("uploads_dir"));
+
+    std::fs::remove_dir_all(&base).unwrap();
+}
+
+#[tokio::test]
+async fn test_database_uses_wal_and_busy_timeout() {
+    let app_state = common::create_test_app_state().await;
+    let conn = app_state.db.get_connection().lock().unwrap();
+
+    let journal_mode: String = conn
+        .query_row("PRAGMA journal_mode", [], |row| row.get(0))
+        .unwrap();
+    assert_eq!(journal_mode, "wal");
+
+    let busy_timeout: i64 = conn
+        .query_row("PRAGMA busy_timeout", [], |row| row.get(0))
+        .unwrap();
+    assert_eq!(
+        busy_timeout,
+        raugupatis_log::database::BUSY_TIMEOUT.as_millis() as i64
+    );
+}
+
+#[tokio::test]
+async fn test_database_hands_out_concurrent_connections() {
+    let app_state = common::create_test_app_state().await;
+    let pool = app_state.db.get_connection();
+
+    // Holding one connection no longer blocks others from being checked out
+    let first = pool.lock().unwrap();
+    let second = pool.lock().unwrap();
+
+    let count: i64 = first
+        .query_row("SELECT COUNT(*) FROM users", [], |row| row.get(0))
+        .unwrap();
+    let same_count: i64 = second
+        .query_row("SELECT COUNT(*) FROM users", [], |row| row.get(0))
+        .unwrap();
+    assert_eq!(count, same_count);
+}
+
+#[tokio::test]
+async fn test_migrate_records_applied_migrations_and_is_idempotent() {
+    let app_state = common::create_test_app_state().await;
+
+    let applied = app_state.db.applied_migrations().await.unwrap();
+    assert!(!applied.is_empty());
+    for (index, migration) in applied.iter().enumerate() {
+        assert_eq!(migration.version, index as i64 + 1);
+        assert!(migration
+            .name
+            .starts_with(&format!("{:03}_", migration.version)));
+    }
+    assert_eq!(applied[0].name, "001_initial_schema");
+
+    let version = app_state.db.migration_version().await.unwrap();
+    assert_eq!(version, applied.len() as i64);
+
+    // Running again on every startup changes nothing
+    app_state.db.migrate(true).await.unwrap();
+    let reapplied = app_state.db.applied_migrations().await.unwrap();
+    assert_eq!(reapplied.len(), applied.len());
+    assert_eq!(app_state.db.migration_version().await.unwrap(), version);
+}
+
+#[tokio::test]
+async fn test_migrate_rejects_unknown_recorded_migration() {
+    let app_state = common::create_test_app_state().await;
+    {
+        let conn = app_state.db.get_connection().lock().unwrap();
+        conn.execute(
+            "UPDATE schema_migrations SET name = '003_from_another_branch' WHERE version = 3",
+            [],
+        )
+        .unwrap();
+    }
+
+    let error = app_state.db.migrate(true).await.unwrap_err();
+    assert!(error.to_string().contains("003_from_another_branch"));
+}
+
+#[tokio::test]
+async fn test_seed_default_profiles_only_when_enabled() {
+    let temp_dir = std::env::temp_dir();
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_nanos();
+    let count_profiles = |db: &raugupatis_log::database::Database| -> i64 {
+        let conn = db.get_connection().lock().unwrap();
+        conn.query_row("SELECT COUNT(*) FROM fermentation_profiles", [], |row| {
+            row.get(0)
+        })
+        .unwrap()
+    };
+
+    let empty_path = temp_dir.join(format!("test_unseeded_{}.db", timestamp));
+    let empty_db = raugupatis_log::database::Database::new(&empty_path.to_string_lossy())
+        .await
+        .unwrap();
+    empty_db.migrate(false).await.unwrap();
+    assert_eq!(count_profiles(&empty_db), 0);
+
+    let seeded_path = temp_dir.join(format!("test_seeded_{}.db", timestamp));
+    let seeded_db = raugupatis_log::database::Database::new(&seeded_path.to_string_lossy())
+        .await
+        .unwrap();
+    seeded_db.migrate(true).await.unwrap();
+    assert_eq!(count_profiles(&seeded_db), 7);
+    let pickles_ingredients: Option<String> = seeded_db
+        .get_connection()
+        .lock()
+        .unwrap()
+        .query_row(
+            "SELECT default_ingredients FROM fermentation_profiles WHERE name = 'Pickles'",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert!(pickles_ingredients.is_some());
+
+    // An existing database is never re-seeded
+    seeded_db.migrate(true).await.unwrap();
+    assert_eq!(count_profiles(&seeded_db), 7);
+}
+
+#[tokio::test]
+async fn test_upgrade_keeps_rows_referencing_rebuilt_fermentations() {
+    use rusqlite_migration::{Migrations, M};
+
+    let db_path = std::env::temp_dir().join(format!(
+        "test_upgrade_{}.db",
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos()
+    ));
+
+    // A database as it was before 011 rebuilt the fermentations table
+    {
+        let mut conn = rusqlite::Connection::open(&db_path).unwrap();
+        let sql: Vec<String> = [
+            "001_initial_schema",
+            "002_add_sessions_table",
+            "003_add_user_names",
+            "004_add_user_locked_field",
+            "005_add_profile_active_field",
+            "006_add_preferred_temp_unit",
+            "007_add_taste_profiles_and_lessons",
+            "008_add_password_reset_tokens",
+            "009_add_login_throttling",
+            "010_add_tags",
+        ]
+        .iter()
+        .map(|name| {
+            std::fs::read_to_string(format!(
+                "{}/migrations/{}.sql",
+                env!("CARGO_MANIFEST_DIR"),
+                name
+            ))
+            .unwrap()
+        })
+        .collect();
+        Migrations::new(sql.iter().map(|sql| M::up(sql)).collect())
+            .to_latest(&mut conn)
+            .unwrap();
+
+        conn.execute_batch(
+            "INSERT INTO users (id, email, password_hash) VALUES (1, 'upgrade@example.com', 'hash');
+             INSERT INTO fermentation_profiles (id, name, type, min_days, max_days, temp_min, temp_max)
+                 VALUES (100, 'Upgrade Kraut', 'sauerkraut', 14, 28, 65.0, 72.0);
+             INSERT INTO fermentations (id, user_id, profile_id, name, start_date)
+                 VALUES (1, 1, 100, 'Before the upgrade', '2024-01-01 00:00:00');
+             INSERT INTO temperature_logs (fermentation_id, recorded_at, temperature)
+                 VALUES (1, '2024-01-02 00:00:00', 68.0);
+             INSERT INTO fermentation_photos (fermentation_id, file_path, taken_at)
+                 VALUES (1, 'before.jpg', '2024-01-02 00:00:00');
+             INSERT INTO taste_profiles (fermentation_id, profile_text, tasted_at)
+                 VALUES (1, 'Tangy', '2024-01-03 00:00:00');
+             INSERT INTO tags (id, user_id, name) VALUES (1, 1, 'kept');
+             INSERT INTO fermentation_tags (fermentation_id, tag_id) VALUES (1, 1);",
+        )
+        .unwrap();
+    }
+
+    let db = raugupatis_log::database::Database::new(&db_path.to_string_lossy())
+        .await
+        .unwrap();
+    db.migrate(true).await.unwrap();
+
+    let conn = db.get_connection().lock().unwrap();
+    for table in [
+        "temperature_logs",
+        "fermentation_photos",
+        "taste_profiles",
+        "fermentation_tags",
+    ] {
+        let count: i64 = conn
+            .query_row(
+                &format!("SELECT COUNT(*) FROM {} WHERE fermentation_id = 1", table),
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(count, 1, "{} rows were lost in the upgrade", table);
+    }
+
+    // Foreign keys are enforced again once migrating is done
+    let foreign_keys: i64 = conn
+        .query_row("PRAGMA foreign_keys", [], |row| row.get(0))
+        .unwrap();
+    assert_eq!(foreign_keys, 1);
+}
+
+#[tokio::test]
+async fn test_openapi_document() {
+    let app_state = common::create_test_app_state().await;
+
+    let (status, body) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri("/api/openapi.json")
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert!(body["openapi"].as_str().unwrap().starts_with("3."));
+    assert_eq!(body["info"]["title"], "Raugupatis Log API");
+
+    let paths = &body["paths"];
+    assert!(paths["/api/users/register"]["post"].is_object());
+    assert!(paths["/api/fermentation/{id}"]["get"].is_object());
+    assert!(paths["/api/fermentation/{id}/temperature"]["post"].is_object());
+    assert!(paths["/api/taste-profiles"]["get"].is_object());
+    assert!(paths["/api/admin/profiles/{id}"]["put"].is_object());
+
+    let schemas = &body["components"]["schemas"];
+    assert!(schemas["CreateFermentationRequest"]["properties"]["profile_id"].is_object());
+    assert!(schemas["UserResponse"]["properties"]["email"].is_object());
+    assert!(body["components"]["securitySchemes"]["session_cookie"].is_object());
+    assert!(body["components"]["securitySchemes"]["webhook_token"].is_object());
+}
+
+/// Every `/api` route registered in `create_router` as `(path, method)`, with axum's `:param`
+/// segments written the OpenAPI way as `{param}`
+fn api_routes() -> std::collections::BTreeSet<(String, String)> {
+    let source = std::fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/src/lib.rs"))
+        .expect("Failed to read src/lib.rs");
+
+    let mut routes = std::collections::BTreeSet::new();
+    for call in source.split(".route(").skip(1) {
+        // The path is the call's first string literal, the handlers follow up to the closing paren
+        let path = call.split('"').nth(1).unwrap();
+        let mut depth = 1;
+        let end = call
+            .char_indices()
+            .find(|&(_, c)| {
+                match c {
+                    '(' => depth += 1,
+                    ')' => depth -= 1,
+                    _ => {}
+                }
+                depth == 0
+            })
+            .map(|(i, _)| i)
+            .unwrap();
+        if !path.starts_with("/api/") {
+            continue;
+        }
+        let path = path
+            .split('/')
+            .map(|segment| match segment.strip_prefix(':') {
+                Some(param) => format!("{{{}}}", param),
+                None => segment.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join("/");
+        for method in route_methods(&call[..end]) {
+            routes.insert((path.clone(), method.to_string()));
+        }
+    }
+    routes
+}
+
+/// HTTP methods of the axum method routers (`get(`, `.post(`, `axum::routing::delete(`, ...) in
+/// the handler part of a route call
+fn route_methods(handlers: &str) -> Vec<&'static str> {
+    ["get", "post", "put", "patch", "delete"]
+        .into_iter()
+        .filter(|method| {
+            handlers
+                .match_indices(&format!("{}(", method))
+                .any(|(i, _)| !handlers[..i].ends_with(|c: char| c.is_alphanumeric() || c == '_'))
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_openapi_document_covers_every_api_route() {
+    let app_state = common::create_test_app_state().await;
+
+    let (status, body) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri("/api/openapi.json")
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let documented: std::collections::BTreeSet<(String, String)> = body["paths"]
+        .as_object()
+        .unwrap()
+        .iter()
+        .flat_map(|(path, operations)| {
+            operations
+                .as_object()
+                .unwrap()
+                .keys()
+                .map(move |method| (path.clone(), method.clone()))
+        })
+        .collect();
+
+    // The document itself and the iCalendar feed aren't part of the JSON API
+    let mut routes = api_routes();
+    routes.remove(&("/api/openapi.json".to_string(), "get".to_string()));
+    routes.remove(&("/api/calendar.ics".to_string(), "get".to_string()));
+
+    let undocumented: Vec<_> = routes.difference(&documented).collect();
+    assert!(
+        undocumented.is_empty(),
+        "Not in the OpenAPI document: {:?}",
+        undocumented
+    );
+    let unrouted: Vec<_> = documented.difference(&routes).collect();
+    assert!(
+        unrouted.is_empty(),
+        "Documented but not routed: {:?}",
+        unrouted
+    );
+}
@@ -387,3 +387,734 @@ async fn test_cannot_access_other_users_fermentation_photos() {
     // Should return 404 when trying to access another user's fermentation
     assert_eq!(response.status(), StatusCode::NOT_FOUND);
 }
+
+/// Builds a multipart upload request for a photo
+fn photo_upload_request(
+    fermentation_id: i64,
+    cookie: &str,
+    filename: &str,
+    content_type: &str,
+    data: &[u8],
+) -> Request<Body> {
+    let boundary = "----boundary";
+    let mut body = format!(
+        "--{}\r\nContent-Disposition: form-data; name=\"stage\"\r\n\r\nstart\r\n--{}\r\nContent-Disposition: form-data; name=\"caption\"\r\n\r\nDay one\r\n--{}\r\nContent-Disposition: form-data; name=\"photo\"; filename=\"{}\"\r\nContent-Type: {}\r\n\r\n",
+        boundary, boundary, boundary, filename, content_type
+    )
+    .into_bytes();
+    body.extend_from_slice(data);
+    body.extend_from_slice(format!("\r\n--{}--\r\n", boundary).as_bytes());
+
+    let mut request = common::authed_request(
+        "POST",
+        &format!("/api/fermentation/{}/photos", fermentation_id),
+        cookie,
+        None,
+    );
+    request.headers_mut().insert(
+        "Content-Type",
+        format!("multipart/form-data; boundary={}", boundary)
+            .parse()
+            .unwrap(),
+    );
+    *request.body_mut() = Body::from(body);
+    request
+}
+
+#[tokio::test]
+async fn test_upload_photo_success() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "photographer@example.com").await;
+    let fermentation_id =
+        common::create_test_fermentation(&app_state, &cookie, "Photo Batch").await;
+
+    let (status, photo) = common::send_json(
+        &app_state,
+        photo_upload_request(
+            fermentation_id,
+            &cookie,
+            "jar.jpg",
+            "image/jpeg",
+            b"fake-image-data",
+        ),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(photo["stage"], "start");
+    assert_eq!(photo["caption"], "Day one");
+
+    let file_path = photo["file_path"].as_str().unwrap();
+    assert!(file_path.starts_with(&format!("{}/", fermentation_id)));
+    let stored = std::fs::read(format!("{}/{}", app_state.config.uploads_dir, file_path)).unwrap();
+    assert_eq!(stored, b"fake-image-data");
+}
+
+#[tokio::test]
+async fn test_upload_photo_rejects_non_image_content_type() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "sneaky@example.com").await;
+    let fermentation_id =
+        common::create_test_fermentation(&app_state, &cookie, "Photo Batch").await;
+
+    let (status, _) = common::send_json(
+        &app_state,
+        photo_upload_request(
+            fermentation_id,
+            &cookie,
+            "script.jpg",
+            "application/javascript",
+            b"alert(1)",
+        ),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_upload_photo_too_large() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "bigphoto@example.com").await;
+    let fermentation_id =
+        common::create_test_fermentation(&app_state, &cookie, "Photo Batch").await;
+
+    let data = vec![0u8; raugupatis_log::photos::MAX_PHOTO_SIZE + 1];
+    let (status, _) = common::send_json(
+        &app_state,
+        photo_upload_request(fermentation_id, &cookie, "huge.png", "image/png", &data),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+}
+
+#[tokio::test]
+async fn test_upload_photo_storage_quota() {
+    let mut app_state = common::create_test_app_state().await;
+    app_state.config = std::sync::Arc::new(raugupatis_log::config::AppConfig {
+        max_photo_bytes_per_user: 250,
+        ..(*app_state.config).clone()
+    });
+    let cookie = common::register_and_login(&app_state, "hoarder@example.com").await;
+    let other_cookie = common::register_and_login(&app_state, "frugal@example.com").await;
+    let fermentation_id =
+        common::create_test_fermentation(&app_state, &cookie, "Photo Batch").await;
+    let other_fermentation_id =
+        common::create_test_fermentation(&app_state, &other_cookie, "Other Batch").await;
+
+    let data = vec![0u8; 100];
+    let mut photo_ids = Vec::new();
+    for _ in 0..2 {
+        let (status, photo) = common::send_json(
+            &app_state,
+            photo_upload_request(fermentation_id, &cookie, "jar.jpg", "image/jpeg", &data),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        photo_ids.push(photo["id"].as_i64().unwrap());
+    }
+
+    let photo_repo = raugupatis_log::photos::PhotoRepository::new(app_state.db.clone());
+    let user_id: i64 = app_state
+        .db
+        .get_connection()
+        .lock()
+        .unwrap()
+        .query_row(
+            "SELECT id FROM users WHERE email = 'hoarder@example.com'",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(photo_repo.total_bytes_for_user(user_id).await.unwrap(), 200);
+
+    // A third photo would take the user to 300 bytes
+    let (status, _) = common::send_json(
+        &app_state,
+        photo_upload_request(fermentation_id, &cookie, "jar.jpg", "image/jpeg", &data),
+    )
+    .await;
+    assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+
+    // The quota is per user
+    let (status, _) = common::send_json(
+        &app_state,
+        photo_upload_request(
+            other_fermentation_id,
+            &other_cookie,
+            "jar.jpg",
+            "image/jpeg",
+            &data,
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    // Deleting a photo frees its space
+    let (status, _) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri(format!(
+                "/api/fermentation/{}/photos/{}",
+                fermentation_id, photo_ids[0]
+            ))
+            .method("DELETE")
+            .header("Cookie", &cookie)
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert!(status.is_success());
+
+    let (status, _) = common::send_json(
+        &app_state,
+        photo_upload_request(fermentation_id, &cookie, "jar.jpg", "image/jpeg", &data),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+}
+
+#[tokio::test]
+async fn test_backfill_photo_file_sizes() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "legacy@example.com").await;
+    let fermentation_id =
+        common::create_test_fermentation(&app_state, &cookie, "Photo Batch").await;
+
+    // Photos recorded before sizes were tracked have a file_size of 0
+    let dir = format!("{}/{}", app_state.config.uploads_dir, fermentation_id);
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(format!("{}/old.jpg", dir), vec![0u8; 120]).unwrap();
+    std::fs::write(format!("{}/old_thumb.jpg", dir), vec![0u8; 30]).unwrap();
+
+    let photo_repo = raugupatis_log::photos::PhotoRepository::new(app_state.db.clone());
+    for (file_path, thumbnail_path) in [("old.jpg", Some("old_thumb.jpg")), ("missing.jpg", None)] {
+        photo_repo
+            .create_photo(raugupatis_log::photos::NewPhoto {
+                fermentation_id,
+                file_path: format!("{}/{}", fermentation_id, file_path),
+                file_size: 0,
+                thumbnail_path: thumbnail_path.map(|path| format!("{}/{}", fermentation_id, path)),
+                caption: None,
+                taken_at: chrono::Utc::now(),
+                stage: raugupatis_log::photos::PhotoStage::Progress,
+            })
+            .await
+            .unwrap();
+    }
+
+    let updated = photo_repo
+        .backfill_file_sizes(&app_state.config.uploads_dir)
+        .await
+        .unwrap();
+    assert_eq!(updated, 1);
+
+    let user_id: i64 = app_state
+        .db
+        .get_connection()
+        .lock()
+        .unwrap()
+        .query_row(
+            "SELECT id FROM users WHERE email = 'legacy@example.com'",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(photo_repo.total_bytes_for_user(user_id).await.unwrap(), 150);
+}
+
+#[tokio::test]
+async fn test_upload_photo_above_general_body_limit() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "hires@example.com").await;
+    let fermentation_id =
+        common::create_test_fermentation(&app_state, &cookie, "Photo Batch").await;
+
+    // Photo uploads are exempt from the general request body limit
+    let data = vec![0u8; app_state.config.max_request_body_bytes * 2];
+    let (status, _) = common::send_json(
+        &app_state,
+        photo_upload_request(fermentation_id, &cookie, "big.jpg", "image/jpeg", &data),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::CREATED);
+}
+
+#[tokio::test]
+async fn test_list_and_delete_photo() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "curator@example.com").await;
+    let fermentation_id =
+        common::create_test_fermentation(&app_state, &cookie, "Photo Batch").await;
+
+    let (status, photo) = common::send_json(
+        &app_state,
+        photo_upload_request(
+            fermentation_id,
+            &cookie,
+            "jar.png",
+            "image/png",
+            b"fake-image-data",
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let photo_id = photo["id"].as_i64().unwrap();
+    let stored_path = format!(
+        "{}/{}",
+        app_state.config.uploads_dir,
+        photo["file_path"].as_str().unwrap()
+    );
+
+    let (status, photos) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri(format!("/api/fermentation/{}/photos", fermentation_id))
+            .header("Cookie", &cookie)
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(photos.as_array().unwrap().len(), 1);
+    assert_eq!(photos[0]["id"], photo_id);
+
+    let delete_request = || {
+        common::authed_request(
+            "DELETE",
+            &format!("/api/fermentation/{}/photos/{}", fermentation_id, photo_id),
+            &cookie,
+            None,
+        )
+    };
+
+    let (status, _) = common::send_json(&app_state, delete_request()).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    assert!(!std::path::Path::new(&stored_path).exists());
+
+    // Already deleted
+    let (status, _) = common::send_json(&app_state, delete_request()).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_delete_photo_with_missing_file() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "tidy@example.com").await;
+    let fermentation_id =
+        common::create_test_fermentation(&app_state, &cookie, "Photo Batch").await;
+
+    let photo_repo = raugupatis_log::photos::PhotoRepository::new(app_state.db.clone());
+    let photo = photo_repo
+        .create_photo(raugupatis_log::photos::NewPhoto {
+            fermentation_id,
+            file_path: format!("{}/missing.jpg", fermentation_id),
+            file_size: 0,
+            thumbnail_path: None,
+            caption: None,
+            taken_at: chrono::Utc::now(),
+            stage: raugupatis_log::photos::PhotoStage::Progress,
+        })
+        .await
+        .unwrap();
+
+    let (status, _) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri(format!(
+                "/api/fermentation/{}/photos/{}",
+                fermentation_id, photo.id
+            ))
+            .method("DELETE")
+            .header("Cookie", &cookie)
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    assert!(photo_repo.find_by_id(photo.id).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_delete_photo_of_deleted_fermentation() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "binned@example.com").await;
+    let fermentation_id =
+        common::create_test_fermentation(&app_state, &cookie, "Binned Batch").await;
+
+    let photo_repo = raugupatis_log::photos::PhotoRepository::new(app_state.db.clone());
+    let photo = photo_repo
+        .create_photo(raugupatis_log::photos::NewPhoto {
+            fermentation_id,
+            file_path: format!("{}/binned.jpg", fermentation_id),
+            file_size: 0,
+            thumbnail_path: None,
+            caption: None,
+            taken_at: chrono::Utc::now(),
+            stage: raugupatis_log::photos::PhotoStage::Progress,
+        })
+        .await
+        .unwrap();
+
+    let (status, _) = common::send_json(
+        &app_state,
+        common::authed_request(
+            "DELETE",
+            &format!("/api/fermentation/{}", fermentation_id),
+            &cookie,
+            None,
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    // Photos of a fermentation in the trash stay untouched until it is restored or purged
+    let (status, _) = common::send_json(
+        &app_state,
+        common::authed_request(
+            "DELETE",
+            &format!("/api/fermentation/{}/photos/{}", fermentation_id, photo.id),
+            &cookie,
+            None,
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert!(photo_repo.find_by_id(photo.id).await.unwrap().is_some());
+
+    let user = raugupatis_log::users::UserRepository::new(app_state.db.clone())
+        .find_by_email("binned@example.com")
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(photo_repo
+        .list_by_fermentation(fermentation_id, user.id)
+        .await
+        .unwrap()
+        .is_empty());
+}
+
+#[tokio::test]
+async fn test_delete_other_users_photo() {
+    let app_state = common::create_test_app_state().await;
+    let owner_cookie = common::register_and_login(&app_state, "owner@example.com").await;
+    let other_cookie = common::register_and_login(&app_state, "other@example.com").await;
+    let fermentation_id =
+        common::create_test_fermentation(&app_state, &owner_cookie, "Private Batch").await;
+
+    let (status, photo) = common::send_json(
+        &app_state,
+        photo_upload_request(
+            fermentation_id,
+            &owner_cookie,
+            "jar.jpg",
+            "image/jpeg",
+            b"fake-image-data",
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let (status, _) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri(format!(
+                "/api/fermentation/{}/photos/{}",
+                fermentation_id, photo["id"]
+            ))
+            .method("DELETE")
+            .header("Cookie", &other_cookie)
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_upload_photo_generates_thumbnail() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "thumbs@example.com").await;
+    let fermentation_id =
+        common::create_test_fermentation(&app_state, &cookie, "Photo Batch").await;
+
+    let mut png = std::io::Cursor::new(Vec::new());
+    image::RgbImage::new(1200, 800)
+        .write_to(&mut png, image::ImageFormat::Png)
+        .unwrap();
+
+    let (status, photo) = common::send_json(
+        &app_state,
+        photo_upload_request(
+            fermentation_id,
+            &cookie,
+            "large.png",
+            "image/png",
+            png.get_ref(),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let thumbnail_path = photo["thumbnail_path"]
+        .as_str()
+        .expect("Thumbnail should be generated for a decodable image");
+    let thumbnail = image::open(format!(
+        "{}/{}",
+        app_state.config.uploads_dir, thumbnail_path
+    ))
+    .unwrap();
+    assert_eq!(thumbnail.width(), 300);
+    assert_eq!(thumbnail.height(), 200);
+
+    // Both files count towards the storage quota
+    let on_disk: u64 = [photo["file_path"].as_str().unwrap(), thumbnail_path]
+        .iter()
+        .map(|path| {
+            std::fs::metadata(format!("{}/{}", app_state.config.uploads_dir, path))
+                .unwrap()
+                .len()
+        })
+        .sum();
+    let file_size: i64 = app_state
+        .db
+        .get_connection()
+        .lock()
+        .unwrap()
+        .query_row(
+            "SELECT file_size FROM fermentation_photos WHERE id = ?1",
+            [photo["id"].as_i64().unwrap()],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(file_size as u64, on_disk);
+
+    // Undecodable images are still stored, just without a thumbnail
+    let (status, photo) = common::send_json(
+        &app_state,
+        photo_upload_request(
+            fermentation_id,
+            &cookie,
+            "broken.jpg",
+            "image/jpeg",
+            b"fake-image-data",
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert!(photo["thumbnail_path"].is_null());
+}
+
+fn encode_jpeg(width: u32, height: u32) -> Vec<u8> {
+    let mut jpeg = std::io::Cursor::new(Vec::new());
+    image::RgbImage::from_fn(width, height, |x, y| {
+        image::Rgb([(x % 256) as u8, (y % 256) as u8, 128])
+    })
+    .write_to(&mut jpeg, image::ImageFormat::Jpeg)
+    .unwrap();
+    jpeg.into_inner()
+}
+
+/// Insert an APP1 EXIF segment holding big-endian TIFF `ifds` (starting at offset 8) into a JPEG
+fn with_exif(jpeg: &[u8], ifds: &[u8]) -> Vec<u8> {
+    let mut exif = b"Exif\0\0MM\0\x2a\0\0\0\x08".to_vec();
+    exif.extend_from_slice(ifds);
+
+    let mut data = jpeg[..2].to_vec();
+    data.extend_from_slice(&[0xFF, 0xE1]);
+    data.extend_from_slice(&((exif.len() + 2) as u16).to_be_bytes());
+    data.extend_from_slice(&exif);
+    data.extend_from_slice(&jpeg[2..]);
+    data
+}
+
+/// A JPEG whose EXIF block says it must be rotated 90° clockwise to display upright
+fn sideways_jpeg(width: u32, height: u32) -> Vec<u8> {
+    let mut ifd = vec![0x00, 0x01]; // one IFD entry
+    ifd.extend_from_slice(&[0x01, 0x12, 0x00, 0x03, 0x00, 0x00, 0x00, 0x01]); // Orientation, SHORT
+    ifd.extend_from_slice(&[0x00, 0x06, 0x00, 0x00]); // 6 = rotate 90° clockwise
+    ifd.extend_from_slice(&[0x00, 0x00, 0x00, 0x00]); // no next IFD
+    with_exif(&encode_jpeg(width, height), &ifd)
+}
+
+/// An upright JPEG whose EXIF block records where it was taken
+fn geotagged_jpeg(jpeg: &[u8]) -> Vec<u8> {
+    let mut ifd = vec![0x00, 0x01]; // one IFD entry
+    ifd.extend_from_slice(&[0x88, 0x25, 0x00, 0x04, 0x00, 0x00, 0x00, 0x01]); // GPS IFD pointer, LONG
+    ifd.extend_from_slice(&[0x00, 0x00, 0x00, 0x1a]); // GPS IFD at offset 26
+    ifd.extend_from_slice(&[0x00, 0x00, 0x00, 0x00]); // no next IFD
+    ifd.extend_from_slice(&[0x00, 0x01]); // one GPS entry
+    ifd.extend_from_slice(&[0x00, 0x01, 0x00, 0x02, 0x00, 0x00, 0x00, 0x02]); // GPSLatitudeRef, ASCII
+    ifd.extend_from_slice(b"N\0\0\0");
+    ifd.extend_from_slice(&[0x00, 0x00, 0x00, 0x00]); // no next IFD
+    with_exif(jpeg, &ifd)
+}
+
+#[tokio::test]
+async fn test_upload_photo_strips_gps_metadata() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "geotagged@example.com").await;
+    let fermentation_id =
+        common::create_test_fermentation(&app_state, &cookie, "Photo Batch").await;
+
+    let jpeg = encode_jpeg(640, 480);
+    let upload = geotagged_jpeg(&jpeg);
+    assert!(upload.windows(6).any(|w| w == b"Exif\0\0"));
+
+    let (status, photo) = common::send_json(
+        &app_state,
+        photo_upload_request(
+            fermentation_id,
+            &cookie,
+            "geotagged.jpg",
+            "image/jpeg",
+            &upload,
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let stored = std::fs::read(format!(
+        "{}/{}",
+        app_state.config.uploads_dir,
+        photo["file_path"].as_str().unwrap()
+    ))
+    .unwrap();
+    assert!(
+        !stored.windows(6).any(|w| w == b"Exif\0\0"),
+        "EXIF block with GPS tags should be stripped"
+    );
+    // An upright JPEG is stored without re-encoding: only the EXIF segment is gone
+    assert_eq!(stored, jpeg);
+}
+
+#[tokio::test]
+async fn test_upload_photo_applies_exif_orientation() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "sideways@example.com").await;
+    let fermentation_id =
+        common::create_test_fermentation(&app_state, &cookie, "Photo Batch").await;
+
+    let (status, photo) = common::send_json(
+        &app_state,
+        photo_upload_request(
+            fermentation_id,
+            &cookie,
+            "sideways.jpg",
+            "image/jpeg",
+            &sideways_jpeg(1200, 800),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let stored = std::fs::read(format!(
+        "{}/{}",
+        app_state.config.uploads_dir,
+        photo["file_path"].as_str().unwrap()
+    ))
+    .unwrap();
+    assert!(
+        !stored.windows(6).any(|w| w == b"Exif\0\0"),
+        "EXIF block should be stripped from the rotated photo"
+    );
+    let stored = image::load_from_memory(&stored).unwrap();
+    assert_eq!(stored.width(), 800);
+    assert_eq!(stored.height(), 1200);
+
+    let thumbnail = image::open(format!(
+        "{}/{}",
+        app_state.config.uploads_dir,
+        photo["thumbnail_path"].as_str().unwrap()
+    ))
+    .unwrap();
+    assert_eq!(thumbnail.width(), 200);
+    assert_eq!(thumbnail.height(), 300);
+}
+
+#[tokio::test]
+async fn test_feature_photo_overrides_thumbnail() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "featured@example.com").await;
+    let other_cookie = common::register_and_login(&app_state, "not-featured@example.com").await;
+    let fermentation_id =
+        common::create_test_fermentation(&app_state, &cookie, "Photo Batch").await;
+
+    let mut photos = Vec::new();
+    for filename in ["first.jpg", "second.jpg"] {
+        let (status, photo) = common::send_json(
+            &app_state,
+            photo_upload_request(
+                fermentation_id,
+                &cookie,
+                filename,
+                "image/jpeg",
+                b"fake-image-data",
+            ),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(photo["is_featured"], false);
+        photos.push(photo);
+    }
+
+    let (state, list_cookie) = (&app_state, cookie.as_str());
+    let thumbnail = || async move {
+        let (_, fermentations) = common::send_json(
+            state,
+            Request::builder()
+                .uri("/api/fermentations")
+                .header("Cookie", list_cookie)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+        fermentations[0]["thumbnail_path"].clone()
+    };
+    let feature_request = |photo: &serde_json::Value, cookie: &str| {
+        common::authed_request(
+            "POST",
+            &format!(
+                "/api/fermentation/{}/photos/{}/feature",
+                fermentation_id, photo["id"]
+            ),
+            cookie,
+            None,
+        )
+    };
+
+    // Without a featured photo the first start-stage photo is used
+    assert_eq!(thumbnail().await, photos[0]["file_path"]);
+
+    let (status, featured) =
+        common::send_json(&app_state, feature_request(&photos[1], &cookie)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(featured["is_featured"], true);
+    assert_eq!(thumbnail().await, photos[1]["file_path"]);
+
+    // Featuring another photo clears the previous one
+    let (status, _) = common::send_json(&app_state, feature_request(&photos[0], &cookie)).await;
+    assert_eq!(status, StatusCode::OK);
+    let (_, listed) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri(format!("/api/fermentation/{}/photos", fermentation_id))
+            .header("Cookie", &cookie)
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    let flags: Vec<bool> = listed
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|p| p["is_featured"].as_bool().unwrap())
+        .collect();
+    assert_eq!(flags, vec![true, false]);
+
+    let (status, _) =
+        common::send_json(&app_state, feature_request(&photos[1], &other_cookie)).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
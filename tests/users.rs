@@ -128,6 +128,17 @@ async fn test_register_user_short_password() {
         .unwrap();
 
     assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let body_json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(body_json["field"], "password");
+    assert_eq!(
+        body_json["error"],
+        "Password must be at least 8 characters long"
+    );
 }
 
 #[tokio::test]
@@ -290,6 +301,73 @@ async fn test_register_page_endpoint() {
     assert_eq!(response.status(), StatusCode::OK);
 }
 
+#[tokio::test]
+async fn test_registration_closed() {
+    let mut app_state = common::create_test_app_state().await;
+    common::register_and_login(&app_state, "owner@example.com").await;
+    app_state.config = std::sync::Arc::new(raugupatis_log::config::AppConfig {
+        registration_open: false,
+        ..(*app_state.config).clone()
+    });
+
+    let (status, error) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri("/api/users/register")
+            .method("POST")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                json!({
+                    "email": "latecomer@example.com",
+                    "password": "securepassword123",
+                    "experience_level": "beginner"
+                })
+                .to_string(),
+            ))
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert!(error["error"].as_str().unwrap().contains("closed"));
+
+    // Existing users can still log in
+    let (status, _) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri("/api/users/login")
+            .method("POST")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                json!({
+                    "email": "owner@example.com",
+                    "password": "securepassword123"
+                })
+                .to_string(),
+            ))
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let app = raugupatis_log::create_router(app_state.clone()).await;
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/register")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let html = String::from_utf8(body.to_vec()).unwrap();
+    assert!(html.contains("Registration is closed"));
+    assert!(!html.contains("registrationForm"));
+}
+
 #[tokio::test]
 async fn test_login_invalid_email() {
     let app = common::create_test_app().await;
@@ -1411,6 +1489,7 @@ async fn test_change_password_wrong_current_password() {
     let body_json: serde_json::Value = serde_json::from_slice(&body).unwrap();
 
     assert_eq!(body_json["error"], "Current password is incorrect");
+    assert_eq!(body_json["field"], "current_password");
 }
 
 #[tokio::test]
@@ -1708,3 +1787,1014 @@ async fn test_update_profile_invalid_temp_unit() {
     // Should return bad request
     assert_eq!(response.status(), StatusCode::BAD_REQUEST);
 }
+
+#[tokio::test]
+async fn test_delete_account_success() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "leaving@example.com").await;
+    let fermentation_id = common::create_test_fermentation(&app_state, &cookie, "Last Batch").await;
+
+    let (status, _) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri(format!("/api/fermentation/{}/temperature", fermentation_id))
+            .method("POST")
+            .header("Content-Type", "application/json")
+            .header("Cookie", &cookie)
+            .body(Body::from(json!({ "temperature": 70.0 }).to_string()))
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    // An invite code the user handed out and the one they registered with
+    let user_repo = raugupatis_log::users::UserRepository::new(app_state.db.clone());
+    let user = user_repo
+        .find_by_email("leaving@example.com")
+        .await
+        .unwrap()
+        .unwrap();
+    {
+        let conn = app_state.db.get_connection().lock().unwrap();
+        conn.execute(
+            "INSERT INTO invite_codes (code, created_by) VALUES ('handed-out', ?1)",
+            [user.id],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO invite_codes (code, used_by, used_at) VALUES ('redeemed', ?1, CURRENT_TIMESTAMP)",
+            [user.id],
+        )
+        .unwrap();
+    }
+
+    let (status, _) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri("/api/users/account")
+            .method("DELETE")
+            .header("Content-Type", "application/json")
+            .header("Cookie", &cookie)
+            .body(Body::from(
+                json!({ "password": "securepassword123" }).to_string(),
+            ))
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    // The old session no longer works
+    let (status, _) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri("/api/fermentations")
+            .header("Cookie", &cookie)
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    // The account and its data are gone
+    assert!(user_repo
+        .find_by_email("leaving@example.com")
+        .await
+        .unwrap()
+        .is_none());
+
+    let conn = app_state.db.get_connection().lock().unwrap();
+    let remaining_logs: i64 = conn
+        .query_row(
+            "SELECT COUNT(*) FROM temperature_logs WHERE fermentation_id = ?1",
+            [fermentation_id],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(remaining_logs, 0);
+
+    // Invite codes no longer point at the deleted account, but stay redeemed
+    let (dangling, redeemed): (i64, i64) = conn
+        .query_row(
+            "SELECT
+                (SELECT COUNT(*) FROM invite_codes WHERE created_by = ?1 OR used_by = ?1),
+                (SELECT COUNT(*) FROM invite_codes WHERE code = 'redeemed' AND used_at IS NOT NULL)",
+            [user.id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .unwrap();
+    assert_eq!(dangling, 0);
+    assert_eq!(redeemed, 1);
+}
+
+#[tokio::test]
+async fn test_delete_account_wrong_password() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "staying@example.com").await;
+
+    let (status, _) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri("/api/users/account")
+            .method("DELETE")
+            .header("Content-Type", "application/json")
+            .header("Cookie", &cookie)
+            .body(Body::from(
+                json!({ "password": "wrongpassword" }).to_string(),
+            ))
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    let user_repo = raugupatis_log::users::UserRepository::new(app_state.db.clone());
+    assert!(user_repo
+        .find_by_email("staying@example.com")
+        .await
+        .unwrap()
+        .is_some());
+}
+
+#[tokio::test]
+async fn test_password_reset_request_does_not_reveal_accounts() {
+    let app_state = common::create_test_app_state().await;
+    common::register_and_login(&app_state, "known@example.com").await;
+
+    for email in ["known@example.com", "unknown@example.com"] {
+        let (status, body) = common::send_json(
+            &app_state,
+            Request::builder()
+                .uri("/api/users/password-reset/request")
+                .method("POST")
+                .header("Content-Type", "application/json")
+                .body(Body::from(json!({ "email": email }).to_string()))
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["success"], true);
+    }
+}
+
+#[tokio::test]
+async fn test_password_reset_confirm_success() {
+    let app_state = common::create_test_app_state().await;
+    common::register_and_login(&app_state, "forgetful@example.com").await;
+
+    let user_repo = raugupatis_log::users::UserRepository::new(app_state.db.clone());
+    let user = user_repo
+        .find_by_email("forgetful@example.com")
+        .await
+        .unwrap()
+        .unwrap();
+    let token = user_repo
+        .create_password_reset_token(user.id)
+        .await
+        .unwrap();
+
+    let confirm_request = |token: &str| {
+        Request::builder()
+            .uri("/api/users/password-reset/confirm")
+            .method("POST")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                json!({ "token": token, "new_password": "brandnewpassword456" }).to_string(),
+            ))
+            .unwrap()
+    };
+
+    let (status, body) = common::send_json(&app_state, confirm_request(&token)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["success"], true);
+
+    // The new password works
+    let (status, _) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri("/api/users/login")
+            .method("POST")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                json!({
+                    "email": "forgetful@example.com",
+                    "password": "brandnewpassword456"
+                })
+                .to_string(),
+            ))
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    // Tokens are single-use
+    let (status, _) = common::send_json(&app_state, confirm_request(&token)).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_password_reset_confirm_invalid_token() {
+    let app_state = common::create_test_app_state().await;
+
+    let (status, _) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri("/api/users/password-reset/confirm")
+            .method("POST")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                json!({ "token": "1.not-a-real-token", "new_password": "brandnewpassword456" })
+                    .to_string(),
+            ))
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_login_throttled_after_repeated_failures() {
+    let app_state = common::create_test_app_state().await;
+    common::register_and_login(&app_state, "guessed@example.com").await;
+
+    let login_request = |password: &str| {
+        Request::builder()
+            .uri("/api/users/login")
+            .method("POST")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                json!({ "email": "guessed@example.com", "password": password }).to_string(),
+            ))
+            .unwrap()
+    };
+
+    for _ in 0..4 {
+        let (_, body) = common::send_json(&app_state, login_request("wrongpassword")).await;
+        assert_eq!(body["success"], false);
+        assert_eq!(body["message"], "Invalid email or password");
+    }
+
+    let (_, body) = common::send_json(&app_state, login_request("wrongpassword")).await;
+    assert_eq!(body["success"], false);
+    assert!(body["message"]
+        .as_str()
+        .unwrap()
+        .starts_with("Too many failed login attempts"));
+
+    // Even the correct password is rejected while throttled
+    let (_, body) = common::send_json(&app_state, login_request("securepassword123")).await;
+    assert_eq!(body["success"], false);
+    assert!(body["message"]
+        .as_str()
+        .unwrap()
+        .starts_with("Too many failed login attempts"));
+
+    // The admin lock flag is left untouched
+    let user_repo = raugupatis_log::users::UserRepository::new(app_state.db.clone());
+    let user = user_repo
+        .find_by_email("guessed@example.com")
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(!user.is_locked);
+}
+
+#[tokio::test]
+async fn test_successful_login_resets_failed_attempts() {
+    let app_state = common::create_test_app_state().await;
+    common::register_and_login(&app_state, "typo@example.com").await;
+
+    let login_request = |password: &str| {
+        Request::builder()
+            .uri("/api/users/login")
+            .method("POST")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                json!({ "email": "typo@example.com", "password": password }).to_string(),
+            ))
+            .unwrap()
+    };
+
+    for _ in 0..4 {
+        common::send_json(&app_state, login_request("wrongpassword")).await;
+    }
+
+    let (_, body) = common::send_json(&app_state, login_request("securepassword123")).await;
+    assert_eq!(body["success"], true);
+
+    // The counter starts over, so another four failures do not throttle
+    for _ in 0..4 {
+        let (_, body) = common::send_json(&app_state, login_request("wrongpassword")).await;
+        assert_eq!(body["message"], "Invalid email or password");
+    }
+}
+
+#[tokio::test]
+async fn test_verify_email_success() {
+    let app_state = common::create_test_app_state().await;
+    common::register_and_login(&app_state, "newcomer@example.com").await;
+
+    let login_request = || {
+        Request::builder()
+            .uri("/api/users/login")
+            .method("POST")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                json!({ "email": "newcomer@example.com", "password": "securepassword123" })
+                    .to_string(),
+            ))
+            .unwrap()
+    };
+
+    // Login still works before verification but reports the address as unverified
+    let (status, body) = common::send_json(&app_state, login_request()).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["success"], true);
+    assert_eq!(body["user"]["email_verified"], false);
+
+    let user_repo = raugupatis_log::users::UserRepository::new(app_state.db.clone());
+    let user = user_repo
+        .find_by_email("newcomer@example.com")
+        .await
+        .unwrap()
+        .unwrap();
+    let token = user_repo
+        .create_email_verification_token(user.id, &user.email)
+        .await
+        .unwrap();
+
+    let verify_request = |token: &str| {
+        Request::builder()
+            .uri("/api/users/verify-email")
+            .method("POST")
+            .header("Content-Type", "application/json")
+            .body(Body::from(json!({ "token": token }).to_string()))
+            .unwrap()
+    };
+
+    let (status, body) = common::send_json(&app_state, verify_request(&token)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["success"], true);
+
+    let (_, body) = common::send_json(&app_state, login_request()).await;
+    assert_eq!(body["user"]["email_verified"], true);
+
+    // Tokens are single-use
+    let (status, _) = common::send_json(&app_state, verify_request(&token)).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_verify_email_invalid_token() {
+    let app_state = common::create_test_app_state().await;
+
+    let (status, _) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri("/api/users/verify-email")
+            .method("POST")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                json!({ "token": "1.not-a-real-token" }).to_string(),
+            ))
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_require_email_verification_blocks_unverified_users() {
+    let mut app_state = common::create_test_app_state().await;
+    app_state.config = std::sync::Arc::new(raugupatis_log::config::AppConfig {
+        require_email_verification: true,
+        ..(*app_state.config).clone()
+    });
+    let cookie = common::register_and_login(&app_state, "unverified@example.com").await;
+
+    let create_request = || {
+        Request::builder()
+            .uri("/api/fermentation")
+            .method("POST")
+            .header("Content-Type", "application/json")
+            .header("Cookie", &cookie)
+            .body(Body::from(
+                json!({
+                    "profile_id": 1,
+                    "name": "Gated Pickles",
+                    "start_date": "2024-01-15T10:00:00Z"
+                })
+                .to_string(),
+            ))
+            .unwrap()
+    };
+
+    let (status, _) = common::send_json(&app_state, create_request()).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let user_repo = raugupatis_log::users::UserRepository::new(app_state.db.clone());
+    let user = user_repo
+        .find_by_email("unverified@example.com")
+        .await
+        .unwrap()
+        .unwrap();
+    let token = user_repo
+        .create_email_verification_token(user.id, &user.email)
+        .await
+        .unwrap();
+    assert!(user_repo
+        .consume_email_verification_token(&token)
+        .await
+        .unwrap()
+        .is_some());
+
+    let (status, _) = common::send_json(&app_state, create_request()).await;
+    assert_eq!(status, StatusCode::CREATED);
+}
+
+#[tokio::test]
+async fn test_change_email_success() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "old@example.com").await;
+
+    let (status, body) = common::send_json(
+        &app_state,
+        common::authed_request(
+            "POST",
+            "/api/users/change-email",
+            &cookie,
+            Some(json!({
+                "current_password": "securepassword123",
+                "new_email": "new@example.com",
+            })),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["success"], true);
+    assert_eq!(body["user"]["email"], "new@example.com");
+    assert_eq!(body["user"]["email_verified"], false);
+
+    // The new address is now the login identity
+    let login_request = |email: &str| {
+        Request::builder()
+            .uri("/api/users/login")
+            .method("POST")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                json!({ "email": email, "password": "securepassword123" }).to_string(),
+            ))
+            .unwrap()
+    };
+    let (_, body) = common::send_json(&app_state, login_request("new@example.com")).await;
+    assert_eq!(body["success"], true);
+    let (_, body) = common::send_json(&app_state, login_request("old@example.com")).await;
+    assert_eq!(body["success"], false);
+}
+
+#[tokio::test]
+async fn test_change_email_duplicate() {
+    let app_state = common::create_test_app_state().await;
+    common::register_and_login(&app_state, "taken@example.com").await;
+    let cookie = common::register_and_login(&app_state, "mover@example.com").await;
+
+    let (status, _) = common::send_json(
+        &app_state,
+        common::authed_request(
+            "POST",
+            "/api/users/change-email",
+            &cookie,
+            Some(json!({
+                "current_password": "securepassword123",
+                "new_email": "taken@example.com",
+            })),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+}
+
+#[tokio::test]
+async fn test_change_email_validation() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "careful@example.com").await;
+
+    let (status, _) = common::send_json(
+        &app_state,
+        common::authed_request(
+            "POST",
+            "/api/users/change-email",
+            &cookie,
+            Some(json!({ "current_password": "wrongpassword", "new_email": "other@example.com" })),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, _) = common::send_json(
+        &app_state,
+        common::authed_request(
+            "POST",
+            "/api/users/change-email",
+            &cookie,
+            Some(json!({ "current_password": "securepassword123", "new_email": "not-an-email" })),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, _) = common::send_json(
+        &app_state,
+        common::authed_request(
+            "POST",
+            "/api/users/change-email",
+            "",
+            Some(json!({
+                "current_password": "securepassword123",
+                "new_email": "other@example.com",
+            })),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn test_change_email_invalidates_old_verification_token() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "first@example.com").await;
+
+    let user_repo = raugupatis_log::users::UserRepository::new(app_state.db.clone());
+    let user = user_repo
+        .find_by_email("first@example.com")
+        .await
+        .unwrap()
+        .unwrap();
+    let old_token = user_repo
+        .create_email_verification_token(user.id, &user.email)
+        .await
+        .unwrap();
+
+    let (status, _) = common::send_json(
+        &app_state,
+        common::authed_request(
+            "POST",
+            "/api/users/change-email",
+            &cookie,
+            Some(json!({
+                "current_password": "securepassword123",
+                "new_email": "second@example.com",
+            })),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    assert!(user_repo
+        .consume_email_verification_token(&old_token)
+        .await
+        .unwrap()
+        .is_none());
+    let user = user_repo.find_by_id(user.id).await.unwrap();
+    assert!(!user.email_verified);
+}
+
+#[tokio::test]
+async fn test_export_account() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "owner@example.com").await;
+    let other_cookie = common::register_and_login(&app_state, "other@example.com").await;
+    let kimchi_id = common::create_test_fermentation(&app_state, &cookie, "Kimchi").await;
+    common::create_test_fermentation(&app_state, &cookie, "Kombucha").await;
+    common::create_test_fermentation(&app_state, &other_cookie, "Not Mine").await;
+
+    let (status, _) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri(format!("/api/fermentation/{}/temperature", kimchi_id))
+            .method("POST")
+            .header("Content-Type", "application/json")
+            .header("Cookie", &cookie)
+            .body(Body::from(
+                json!({ "temperature": 66.0, "recorded_at": "2024-01-16T08:00:00Z" }).to_string(),
+            ))
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let export_request =
+        |cookie: &str| common::authed_request("GET", "/api/users/export", cookie, None);
+
+    let (status, export) = common::send_json(&app_state, export_request(&cookie)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(export["user"]["email"], "owner@example.com");
+    assert!(export["user"].get("password_hash").is_none());
+
+    let fermentations = export["fermentations"].as_array().unwrap();
+    assert_eq!(fermentations.len(), 2);
+    assert_eq!(fermentations[0]["fermentation"]["name"], "Kimchi");
+    assert_eq!(fermentations[0]["temperature_logs"][0]["temperature"], 66.0);
+    assert_eq!(fermentations[1]["fermentation"]["name"], "Kombucha");
+
+    let (status, _) = common::send_json(&app_state, export_request("")).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
+
+/// Registers a user and returns the full `Set-Cookie` header of the login response
+async fn login_set_cookie(app_state: &raugupatis_log::AppState, email: &str) -> String {
+    let body = json!({
+        "email": email,
+        "password": "securepassword123"
+    });
+
+    let app = raugupatis_log::create_router(app_state.clone()).await;
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/api/users/register")
+                .method("POST")
+                .header("Content-Type", "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    let app = raugupatis_log::create_router(app_state.clone()).await;
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/api/users/login")
+                .method("POST")
+                .header("Content-Type", "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    response
+        .headers()
+        .get("set-cookie")
+        .unwrap()
+        .to_str()
+        .unwrap()
+        .to_string()
+}
+
+#[tokio::test]
+async fn test_session_cookie_attributes_in_test_environment() {
+    let app_state = common::create_test_app_state().await;
+
+    let set_cookie = login_set_cookie(&app_state, "local@example.com").await;
+
+    assert!(set_cookie.contains("HttpOnly"));
+    assert!(set_cookie.contains("SameSite=Lax"));
+    assert!(!set_cookie.contains("Secure"));
+}
+
+#[tokio::test]
+async fn test_session_cookie_attributes_in_production() {
+    let app_state = common::create_test_app_state().await;
+    let mut config = (*app_state.config).clone();
+    config.environment = "production".to_string();
+    config.session_same_site = "strict".to_string();
+    let app_state = raugupatis_log::AppState {
+        config: std::sync::Arc::new(config),
+        ..app_state
+    };
+
+    let set_cookie = login_set_cookie(&app_state, "deployed@example.com").await;
+
+    assert!(set_cookie.contains("HttpOnly"));
+    assert!(set_cookie.contains("SameSite=Strict"));
+    assert!(set_cookie.contains("Secure"));
+}
+
+#[tokio::test]
+async fn test_logout_all_sessions() {
+    let app_state = common::create_test_app_state().await;
+    let first_cookie = common::register_and_login(&app_state, "worried@example.com").await;
+
+    // Log in a second time, e.g. from another device
+    let app = raugupatis_log::create_router(app_state.clone()).await;
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/api/users/login")
+                .method("POST")
+                .header("Content-Type", "application/json")
+                .body(Body::from(
+                    json!({
+                        "email": "worried@example.com",
+                        "password": "securepassword123"
+                    })
+                    .to_string(),
+                ))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let second_cookie = response
+        .headers()
+        .get("set-cookie")
+        .unwrap()
+        .to_str()
+        .unwrap()
+        .split(';')
+        .next()
+        .unwrap()
+        .to_string();
+
+    // Another user's session is left alone
+    let bystander_cookie = common::register_and_login(&app_state, "calm@example.com").await;
+
+    let list_fermentations =
+        |cookie: &str| common::authed_request("GET", "/api/fermentations", cookie, None);
+
+    let (status, _) = common::send_json(&app_state, list_fermentations(&second_cookie)).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, body) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri("/api/users/logout-all")
+            .method("POST")
+            .header("Cookie", &first_cookie)
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["success"], true);
+    assert_eq!(body["sessions_terminated"], 2);
+
+    for cookie in [&first_cookie, &second_cookie] {
+        let (status, _) = common::send_json(&app_state, list_fermentations(cookie)).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+    }
+
+    let (status, _) = common::send_json(&app_state, list_fermentations(&bystander_cookie)).await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_user_response_includes_account_timestamps() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "veteran@example.com").await;
+
+    // Pretend the account was created and last touched a while ago
+    let db = app_state.db.clone();
+    tokio::task::spawn_blocking(move || {
+        let conn = db.get_connection().lock().unwrap();
+        conn.execute(
+            "UPDATE users SET created_at = '2020-03-01 12:00:00', updated_at = '2020-03-01 12:00:00' WHERE email = ?1",
+            ["veteran@example.com"],
+        )
+        .unwrap();
+    })
+    .await
+    .unwrap();
+
+    let (status, user) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri("/api/users/profile")
+            .method("POST")
+            .header("Content-Type", "application/json")
+            .header("Cookie", &cookie)
+            .body(Body::from(
+                json!({
+                    "experience_level": "advanced",
+                    "preferred_temp_unit": "celsius"
+                })
+                .to_string(),
+            ))
+            .unwrap(),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert!(user["created_at"]
+        .as_str()
+        .unwrap()
+        .starts_with("2020-03-01"));
+    assert!(!user["updated_at"]
+        .as_str()
+        .unwrap()
+        .starts_with("2020-03-01"));
+
+    let app = raugupatis_log::create_router(app_state).await;
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/profile")
+                .header("Cookie", &cookie)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let html = String::from_utf8(body.to_vec()).unwrap();
+    assert!(html.contains("Last Updated"));
+    assert!(html.contains("2020-03-01"));
+}
+
+#[tokio::test]
+async fn test_last_login_recorded_only_on_success() {
+    use raugupatis_log::users::{CreateUserRequest, UserRepository};
+
+    let app_state = common::create_test_app_state().await;
+    let user_repo = UserRepository::new(app_state.db.clone());
+    let user = user_repo
+        .create_user(CreateUserRequest {
+            email: "dormant@example.com".to_string(),
+            password: "securepassword123".to_string(),
+            experience_level: None,
+            first_name: None,
+            last_name: None,
+            invite_code: None,
+        })
+        .await
+        .unwrap();
+    assert!(user.last_login_at.is_none());
+
+    let login_request = |password: &str| {
+        Request::builder()
+            .uri("/api/users/login")
+            .method("POST")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                json!({ "email": "dormant@example.com", "password": password }).to_string(),
+            ))
+            .unwrap()
+    };
+
+    let (_, body) = common::send_json(&app_state, login_request("wrongpassword")).await;
+    assert_eq!(body["success"], false);
+    let user = user_repo.find_by_id(user.id).await.unwrap();
+    assert!(user.last_login_at.is_none());
+
+    let (_, body) = common::send_json(&app_state, login_request("securepassword123")).await;
+    assert_eq!(body["success"], true);
+    assert!(body["user"]["last_login_at"].is_string());
+    let user = user_repo.find_by_id(user.id).await.unwrap();
+    assert!(user.last_login_at.is_some());
+}
+
+#[tokio::test]
+async fn test_configured_password_policy() {
+    let mut app_state = common::create_test_app_state().await;
+    app_state.config = std::sync::Arc::new(raugupatis_log::config::AppConfig {
+        password_min_length: 12,
+        password_require_digit: true,
+        ..(*app_state.config).clone()
+    });
+
+    let register = |password: &str| {
+        Request::builder()
+            .uri("/api/users/register")
+            .method("POST")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                json!({ "email": "policy@example.com", "password": password }).to_string(),
+            ))
+            .unwrap()
+    };
+
+    let (status, body) = common::send_json(&app_state, register("short1")).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["field"], "password");
+    assert_eq!(
+        body["error"],
+        "Password must be at least 12 characters long"
+    );
+
+    let (status, body) = common::send_json(&app_state, register("no digits at all")).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"], "Password must contain at least one digit");
+
+    // The policy also applies when changing the password
+    let cookie = common::register_and_login(&app_state, "policy-change@example.com").await;
+    let (status, body) = common::send_json(
+        &app_state,
+        Request::builder()
+            .uri("/api/users/password")
+            .method("POST")
+            .header("Content-Type", "application/json")
+            .header("Cookie", &cookie)
+            .body(Body::from(
+                json!({
+                    "current_password": "securepassword123",
+                    "new_password": "anotherlongpassword"
+                })
+                .to_string(),
+            ))
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["field"], "new_password");
+    assert_eq!(
+        body["error"],
+        "New password must contain at least one digit"
+    );
+}
+
+#[tokio::test]
+async fn test_current_user() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "me@example.com").await;
+
+    let me_request = |cookie: Option<&str>| {
+        let mut builder = Request::builder().uri("/api/users/me");
+        if let Some(cookie) = cookie {
+            builder = builder.header("Cookie", cookie);
+        }
+        builder.body(Body::empty()).unwrap()
+    };
+
+    let (status, body) = common::send_json(&app_state, me_request(Some(&cookie))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["email"], "me@example.com");
+    assert!(body.get("password_hash").is_none());
+    assert!(!body["last_login_at"].is_null());
+
+    let (status, _) = common::send_json(&app_state, me_request(None)).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn test_default_profile_preference() {
+    let app_state = common::create_test_app_state().await;
+    let cookie = common::register_and_login(&app_state, "default-profile@example.com").await;
+
+    let update = |default_profile_id: serde_json::Value| {
+        Request::builder()
+            .uri("/api/users/profile")
+            .method("POST")
+            .header("Content-Type", "application/json")
+            .header("Cookie", &cookie)
+            .body(Body::from(
+                json!({
+                    "experience_level": "beginner",
+                    "preferred_temp_unit": "fahrenheit",
+                    "default_profile_id": default_profile_id
+                })
+                .to_string(),
+            ))
+            .unwrap()
+    };
+
+    let (status, body) = common::send_json(&app_state, update(json!(2))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["default_profile_id"], 2);
+
+    // The new-fermentation form pre-selects the default profile
+    let app = raugupatis_log::create_router(app_state.clone()).await;
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/fermentation/new")
+                .header("Cookie", &cookie)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let html = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let html = String::from_utf8(html.to_vec()).unwrap();
+    let option_attributes = |id: &str| {
+        html.split(&format!("value=\"{}\"", id))
+            .nth(1)
+            .and_then(|rest| rest.split('>').next())
+            .unwrap()
+            .to_string()
+    };
+    assert!(option_attributes("2").contains("selected"));
+    assert!(!option_attributes("1").contains("selected"));
+
+    // Unknown and inactive profiles are rejected
+    let (status, body) = common::send_json(&app_state, update(json!(9999))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["field"], "default_profile_id");
+
+    raugupatis_log::admin::AdminProfileRepository::new(app_state.db.clone())
+        .set_profile_active_status(3, false)
+        .await
+        .unwrap();
+    let (status, _) = common::send_json(&app_state, update(json!(3))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    // Null clears the preference
+    let (status, body) = common::send_json(&app_state, update(serde_json::Value::Null)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body["default_profile_id"].is_null());
+}